@@ -0,0 +1,349 @@
+use crate::models::{Affordance, Breadboard, Place, Status};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+// Questions about how a board's places connect, as opposed to `lint`'s
+// checks for mistakes.
+
+// Stop enumerating after this many cycles; densely linked boards have
+// exponentially many and nobody reads past the first screenful
+const MAX_CYCLES: usize = 200;
+
+// A closed path through the board: each step is an affordance, starting at
+// the place it belongs to and leading to the next step's place
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cycle {
+    pub steps: Vec<(u32, u32)>, // (place, affordance)
+}
+
+impl Cycle {
+    pub fn places(&self) -> impl Iterator<Item = u32> + '_ {
+        self.steps.iter().map(|(place_id, _)| *place_id)
+    }
+
+    // Two places linking to each other, like a page and its "Back" button,
+    // are usually intentional
+    pub fn is_back_and_forth(&self) -> bool {
+        self.steps.len() == 2
+    }
+}
+
+// Every simple cycle (no place visited twice), shortest first. Each cycle is
+// reported once, starting from its earliest place on the board.
+pub fn cycles(breadboard: &Breadboard) -> Vec<Cycle> {
+    let order: HashMap<u32, usize> = breadboard.places.iter().enumerate().map(|(i, p)| (p.id, i)).collect();
+    let mut found = Vec::new();
+
+    for start in &breadboard.places {
+        let mut path = Vec::new();
+        walk(breadboard, &order, start.id, start.id, &mut path, &mut found);
+        if found.len() >= MAX_CYCLES {
+            break;
+        }
+    }
+
+    found.sort_by_key(|cycle: &Cycle| cycle.steps.len());
+    found
+}
+
+// Depth-first search for paths back to `start` that only pass through
+// places after it on the board, so each cycle is found from one place only
+fn walk(breadboard: &Breadboard, order: &HashMap<u32, usize>, start: u32, at: u32, path: &mut Vec<(u32, u32)>, found: &mut Vec<Cycle>) {
+    let Some(place) = breadboard.find_place(&at) else {
+        return;
+    };
+
+    for affordance in &place.affordances {
+        if found.len() >= MAX_CYCLES {
+            return;
+        }
+        for dest in affordance.destinations() {
+            if found.len() >= MAX_CYCLES {
+                return;
+            }
+            if dest == start {
+                path.push((at, affordance.id));
+                found.push(Cycle { steps: path.clone() });
+                path.pop();
+            } else if order.get(&dest) > order.get(&start) && !path.iter().any(|(p, _)| *p == dest) {
+                path.push((at, affordance.id));
+                walk(breadboard, order, start, dest, path, found);
+                path.pop();
+            }
+        }
+    }
+}
+
+// One place the target can be reached from, through `affordance`, which
+// starts a shortest path of `steps` connections to it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Source {
+    pub place_id: u32,
+    pub affordance_id: u32,
+    pub steps: usize,
+}
+
+// Every place from which `target` can be reached by following connections,
+// not just the ones linking straight to it, nearest first
+pub fn reaching(breadboard: &Breadboard, target: u32) -> Vec<Source> {
+    let mut links_into: HashMap<u32, Vec<(u32, u32)>> = HashMap::new();
+    for place in &breadboard.places {
+        for affordance in &place.affordances {
+            for dest in affordance.destinations() {
+                links_into.entry(dest).or_default().push((place.id, affordance.id));
+            }
+        }
+    }
+
+    let mut sources = Vec::new();
+    let mut queue = VecDeque::from([(target, 0)]);
+    while let Some((at, steps)) = queue.pop_front() {
+        for &(place_id, affordance_id) in links_into.get(&at).into_iter().flatten() {
+            if place_id == target || sources.iter().any(|s: &Source| s.place_id == place_id) {
+                continue;
+            }
+            sources.push(Source { place_id, affordance_id, steps: steps + 1 });
+            queue.push_back((place_id, steps + 1));
+        }
+    }
+    sources
+}
+
+// How often random clicking from the entry place got to each place and
+// affordance: the number of walks that visited it at least once
+#[derive(Debug, Default)]
+pub struct Walks {
+    pub places: HashMap<u32, usize>,
+    pub affordances: HashMap<u32, usize>,
+}
+
+impl Walks {
+    // Places and affordances visited by fewer than `walks` walks. Cut
+    // affordances aren't built, so they're neither walked nor reported.
+    pub fn fewer_than<'a>(&self, breadboard: &'a Breadboard, walks: usize) -> (Vec<&'a Place>, Vec<(&'a Place, &'a Affordance)>) {
+        let count = |visits: &HashMap<u32, usize>, id| visits.get(&id).copied().unwrap_or(0);
+        let places = breadboard.places.iter().filter(|p| count(&self.places, p.id) < walks).collect();
+        let affordances = breadboard.places.iter()
+            .flat_map(|p| p.affordances.iter().filter(|a| !a.cut).map(move |a| (p, a)))
+            .filter(|(_, a)| count(&self.affordances, a.id) < walks)
+            .collect();
+        (places, affordances)
+    }
+}
+
+// A "monkey" clicking at random: each of `walks` walks starts at the entry
+// (first) place and, for up to `steps` steps, picks one of the place's
+// affordances and goes where it leads (one outcome, picked at random). A
+// walk ends early at an affordance leading nowhere or a place without any.
+pub fn random_walks(breadboard: &Breadboard, walks: usize, steps: usize, seed: u64) -> Walks {
+    let mut rng = Rng(seed.max(1));
+    let mut report = Walks::default();
+    let Some(entry) = breadboard.places.first() else {
+        return report;
+    };
+
+    for _ in 0..walks {
+        let mut places = HashSet::from([entry.id]);
+        let mut affordances = HashSet::new();
+        let mut at = entry;
+        for _ in 0..steps {
+            let choices: Vec<&Affordance> = at.affordances.iter().filter(|a| !a.cut).collect();
+            if choices.is_empty() {
+                break;
+            }
+            let affordance = choices[rng.below(choices.len())];
+            affordances.insert(affordance.id);
+            let destinations = affordance.destinations();
+            let Some(next) = (!destinations.is_empty())
+                .then(|| destinations[rng.below(destinations.len())])
+                .and_then(|id| breadboard.find_place(&id)) else {
+                break;
+            };
+            places.insert(next.id);
+            at = next;
+        }
+        for id in places {
+            *report.places.entry(id).or_default() += 1;
+        }
+        for id in affordances {
+            *report.affordances.entry(id).or_default() += 1;
+        }
+    }
+    report
+}
+
+// xorshift64*: plenty for picking affordances, and repeatable from a seed
+struct Rng(u64);
+
+impl Rng {
+    fn below(&mut self, n: usize) -> usize {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        (self.0.wrapping_mul(0x2545_F491_4F6C_DD1D) >> 32) as usize % n
+    }
+}
+
+// Affordance counts by status. Cut affordances are counted on their own
+// and not in the total, since they won't be built.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct Progress {
+    pub planned: usize,
+    pub building: usize,
+    pub built: usize,
+    pub cut: usize,
+    pub points: f64, // Estimated points of the affordances not cut
+}
+
+impl Progress {
+    pub fn total(&self) -> usize {
+        self.planned + self.building + self.built
+    }
+
+    fn count(&mut self, affordance: &Affordance) {
+        match (affordance.cut, affordance.status) {
+            (true, _) => self.cut += 1,
+            (false, Status::Planned) => self.planned += 1,
+            (false, Status::Building) => self.building += 1,
+            (false, Status::Built) => self.built += 1,
+        }
+        if !affordance.cut {
+            self.points += affordance.estimate.map_or(0.0, |estimate| estimate.points());
+        }
+    }
+
+    fn add(&mut self, other: &Progress) {
+        self.planned += other.planned;
+        self.building += other.building;
+        self.built += other.built;
+        self.cut += other.cut;
+        self.points += other.points;
+    }
+}
+
+// Progress of each place, in board order, and of the whole board
+pub fn progress(breadboard: &Breadboard) -> (Vec<(u32, Progress)>, Progress) {
+    let mut total = Progress::default();
+    let places = breadboard.places.iter()
+        .map(|place| {
+            let mut progress = Progress::default();
+            for affordance in &place.affordances {
+                progress.count(affordance);
+                total.count(affordance);
+            }
+            (place.id, progress)
+        })
+        .collect();
+    (places, total)
+}
+
+// Progress of each group of places, the scopes of the work, in the order
+// the groups first appear. Empty when no place is grouped.
+pub fn group_progress(breadboard: &Breadboard) -> Vec<(String, Progress)> {
+    let mut groups: Vec<(String, Progress)> = Vec::new();
+    for (place_id, progress) in progress(breadboard).0 {
+        let Some(group) = breadboard.find_place(&place_id).and_then(|p| p.group.as_ref()) else {
+            continue;
+        };
+        match groups.iter_mut().find(|(name, _)| name == group) {
+            Some((_, sum)) => sum.add(&progress),
+            None => groups.push((group.clone(), progress)),
+        }
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::testing::board;
+    use crate::models::{Affordance, Estimate, Size};
+
+    fn place_lists(cycles: &[Cycle]) -> Vec<Vec<u32>> {
+        cycles.iter().map(|c| c.places().collect()).collect()
+    }
+
+    #[test]
+    fn test_acyclic_board_has_no_cycles() {
+        assert!(cycles(&board(&["P0", "P1", "P2"], &[(0, 1), (1, 2), (0, 2)])).is_empty());
+    }
+
+    #[test]
+    fn test_each_cycle_found_once() {
+        // 1 ⇄ 2 back-and-forth, plus the loop 1 → 2 → 3 → 1
+        let found = cycles(&board(&["P0", "P1", "P2"], &[(0, 1), (1, 0), (1, 2), (2, 0)]));
+        assert_eq!(place_lists(&found), vec![vec![1, 2], vec![1, 2, 3]]);
+        assert!(found[0].is_back_and_forth());
+        assert!(!found[1].is_back_and_forth());
+        assert_eq!(found[1].steps[2], (3, 4));
+    }
+
+    #[test]
+    fn test_progress_counts_cut_separately() {
+        let mut breadboard = board(&["P0", "P1"], &[(0, 1), (0, 1), (1, 0)]);
+        breadboard.places[0].affordances[0].status = Status::Built;
+        breadboard.places[0].affordances[1].status = Status::Built;
+        breadboard.places[0].affordances[1].cut = true;
+        breadboard.places[1].affordances[0].status = Status::Building;
+
+        let (places, total) = progress(&breadboard);
+        assert_eq!(places[0], (1, Progress { built: 1, cut: 1, ..Default::default() }));
+        assert_eq!(total, Progress { planned: 0, building: 1, built: 1, cut: 1, points: 0.0 });
+        assert_eq!(total.total(), 2);
+    }
+
+    #[test]
+    fn test_estimates_sum_per_place_and_group() {
+        let mut breadboard = board(&["P0", "P1", "P2"], &[(0, 1), (0, 1), (1, 2), (2, 0)]);
+        breadboard.places[0].affordances[0].estimate = Some(Estimate::Size(Size::Medium));
+        breadboard.places[0].affordances[1].estimate = Some(Estimate::Points(1.5));
+        breadboard.places[1].affordances[0].estimate = Some(Estimate::Points(3.0));
+        breadboard.places[1].affordances[0].cut = true;
+        breadboard.places[2].affordances[0].estimate = Some(Estimate::Size(Size::Small));
+        breadboard.places[0].group = Some("Checkout".to_string());
+        breadboard.places[1].group = Some("Checkout".to_string());
+
+        let (places, total) = progress(&breadboard);
+        assert_eq!(places[0].1.points, 3.5);
+        assert_eq!(places[1].1.points, 0.0);
+        assert_eq!(total.points, 4.5);
+        let groups = group_progress(&breadboard);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].0, "Checkout");
+        assert_eq!(groups[0].1.points, 3.5);
+        assert_eq!(groups[0].1.total(), 2);
+    }
+
+    #[test]
+    fn test_reaching_goes_past_direct_links() {
+        // P0 → P1 → P2 → P3, and P4 → P3 directly; P5 is cut off
+        let breadboard = board(&["P0", "P1", "P2", "P3", "P4", "P5"], &[(0, 1), (1, 2), (2, 3), (4, 3), (3, 0)]);
+        let sources: Vec<(u32, usize)> = reaching(&breadboard, 4).iter().map(|s| (s.place_id, s.steps)).collect();
+        assert_eq!(sources, vec![(3, 1), (5, 1), (2, 2), (1, 3)]);
+        assert_eq!(reaching(&breadboard, 4)[2].affordance_id, 2);
+        assert!(reaching(&breadboard, 6).is_empty());
+    }
+
+    #[test]
+    fn test_random_walks_find_unvisited_corners() {
+        // P2 hangs off P1 but P3 is only linked from the unreachable P4
+        let mut breadboard = board(&["P0", "P1", "P2", "P3", "P4"], &[(0, 1), (1, 0), (1, 2), (4, 3)]);
+        breadboard.places[2].add_affordance(Affordance::new(9, "Nowhere".to_string()));
+        let walks = random_walks(&breadboard, 200, 20, 7);
+        assert_eq!(walks.places.get(&1), Some(&200));
+
+        let (places, affordances) = walks.fewer_than(&breadboard, 1);
+        let places: Vec<&str> = places.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(places, vec!["P3", "P4"]);
+        let affordances: Vec<&str> = affordances.iter().map(|(_, a)| a.name.as_str()).collect();
+        assert_eq!(affordances, vec!["to 3"]);
+
+        // The same seed walks the same way
+        assert_eq!(random_walks(&breadboard, 50, 5, 3).affordances, random_walks(&breadboard, 50, 5, 3).affordances);
+    }
+
+    #[test]
+    fn test_self_link_is_a_cycle() {
+        assert_eq!(place_lists(&cycles(&board(&["P0", "P1"], &[(1, 1)]))), vec![vec![2]]);
+    }
+}
@@ -0,0 +1,260 @@
+use crate::app::App;
+use crate::file::FileManager;
+use crate::models::Breadboard;
+use anyhow::Result;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+// REST API over one board file. The file stays the source of truth: every
+// request reads it fresh, so edits made in the TUI show up, and every change
+// is written straight back. Writes use optimistic concurrency: responses
+// carry the board's version as an ETag and changes must send it back in
+// `If-Match`, so two tools can't overwrite each other's edits unnoticed.
+//
+//   GET  /board                                  Whole board
+//   GET  /places                                 All places
+//   POST /places                   {name}        New place
+//   GET  /places/{id}                            One place
+//   GET  /places/{id}/affordances                A place's affordances
+//   POST /places/{id}/affordances  {name, connects_to?}
+//   GET  /connections                            All connections
+//   POST /connections              {place_id, affordance_id, dest_id, add_back?}
+
+pub const DEFAULT_ADDRESS: &str = "127.0.0.1:7878";
+
+#[derive(Debug, PartialEq)]
+pub struct Response {
+    pub status: u16,
+    pub body: Value,
+    pub version: Option<String>, // Board version after the request, sent as ETag
+}
+
+impl Response {
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self { status, body: json!({ "error": message.into() }), version: None }
+    }
+}
+
+#[derive(Deserialize)]
+struct NewPlace {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct NewAffordance {
+    name: String,
+    connects_to: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct NewConnection {
+    place_id: u32,
+    affordance_id: u32,
+    dest_id: Option<u32>, // null removes the connection
+    #[serde(default)]
+    add_back: bool,
+}
+
+pub struct Api {
+    path: String,
+    file_manager: FileManager,
+}
+
+impl Api {
+    pub fn new(path: String) -> Self {
+        Self { path, file_manager: FileManager::new() }
+    }
+
+    pub fn serve(&self, address: &str) -> Result<()> {
+        let server = tiny_http::Server::http(address).map_err(|e| anyhow::anyhow!("can't listen on {}: {}", address, e))?;
+        eprintln!("Serving {} on http://{}", self.path, address);
+
+        for mut request in server.incoming_requests() {
+            let mut body = String::new();
+            let response = match request.as_reader().read_to_string(&mut body) {
+                Ok(_) => {
+                    let if_match = request.headers().iter()
+                        .find(|h| h.field.equiv("If-Match"))
+                        .map(|h| h.value.as_str().to_string());
+                    self.handle(request.method().as_str(), request.url(), if_match.as_deref(), &body)
+                }
+                Err(e) => Response::error(400, e.to_string()),
+            };
+
+            let mut reply = tiny_http::Response::from_string(response.body.to_string())
+                .with_status_code(response.status)
+                .with_header(header("Content-Type", "application/json"));
+            if let Some(version) = &response.version {
+                reply.add_header(header("ETag", &format!("\"{}\"", version)));
+            }
+            let _ = request.respond(reply);
+        }
+
+        Ok(())
+    }
+
+    pub fn handle(&self, method: &str, url: &str, if_match: Option<&str>, body: &str) -> Response {
+        let mut app = App::new();
+        app.breadboard = match self.file_manager.load_from_file(&self.path) {
+            Ok(mut breadboard) => {
+                breadboard.sync_id_counters();
+                breadboard
+            }
+            Err(e) => return Response::error(500, format!("{:#}", e)),
+        };
+        let version = version_of(&app.breadboard);
+
+        let path = url.split('?').next().unwrap_or("");
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+        if method == "GET" {
+            let body = match segments.as_slice() {
+                ["board"] => json!(app.breadboard),
+                ["places"] => json!(app.breadboard.places),
+                ["places", id] => match parse_id(id).and_then(|id| app.breadboard.find_place(&id)) {
+                    Some(place) => json!(place),
+                    None => return Response::error(404, format!("no place {}", id)),
+                },
+                ["places", id, "affordances"] => match parse_id(id).and_then(|id| app.breadboard.find_place(&id)) {
+                    Some(place) => json!(place.affordances),
+                    None => return Response::error(404, format!("no place {}", id)),
+                },
+                ["connections"] => json!(connections(&app.breadboard)),
+                _ => return Response::error(404, format!("no route {}", path)),
+            };
+            return Response { status: 200, body, version: Some(version) };
+        }
+
+        if method != "POST" {
+            return Response::error(405, format!("method {} not allowed", method));
+        }
+
+        // Changes only apply to the version the client last saw
+        match if_match.map(|v| v.trim().trim_matches('"')) {
+            None => return Response::error(428, "send the board's ETag in If-Match"),
+            Some(seen) if seen != version && seen != "*" => {
+                return Response { version: Some(version), ..Response::error(412, "board changed since it was read") };
+            }
+            Some(_) => {}
+        }
+
+        let result = match segments.as_slice() {
+            ["places"] => parse_body::<NewPlace>(body).map(|new| Ok(app.new_place(new.name))),
+            ["places", id, "affordances"] => match parse_id(id) {
+                Some(place_id) => parse_body::<NewAffordance>(body)
+                    .map(|new| app.add_affordance(place_id, new.name, new.connects_to)),
+                None => return Response::error(404, format!("no place {}", id)),
+            },
+            ["connections"] => parse_body::<NewConnection>(body).map(|new| {
+                app.set_connection(new.place_id, new.affordance_id, new.dest_id, new.add_back).map(|()| new.affordance_id)
+            }),
+            _ => return Response::error(404, format!("no route {}", path)),
+        };
+
+        let id = match result {
+            Ok(Ok(id)) => id,
+            Ok(Err(e)) => return Response::error(404, format!("{:#}", e)),
+            Err(response) => return response,
+        };
+        if let Err(e) = self.file_manager.save_to_file(&app.breadboard, &self.path) {
+            return Response::error(500, format!("{:#}", e));
+        }
+
+        Response { status: 201, body: json!({ "id": id }), version: Some(version_of(&app.breadboard)) }
+    }
+}
+
+// Version tag for optimistic concurrency: a hash of the board's contents
+fn version_of(breadboard: &Breadboard) -> String {
+    let mut hasher = DefaultHasher::new();
+    toml::to_string(breadboard).unwrap_or_default().hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+fn connections(breadboard: &Breadboard) -> Vec<Value> {
+    breadboard.places.iter()
+        .flat_map(|place| place.affordances.iter().flat_map(move |a| {
+            let direct = a.connects_to.map(|dest_id| json!({ "place_id": place.id, "affordance_id": a.id, "dest_id": dest_id }));
+            let outcomes = a.branches.iter().filter_map(move |branch| branch.connects_to.map(|dest_id| {
+                json!({ "place_id": place.id, "affordance_id": a.id, "dest_id": dest_id, "outcome": branch.label })
+            }));
+            direct.into_iter().chain(outcomes)
+        }))
+        .collect()
+}
+
+fn parse_id(segment: &str) -> Option<u32> {
+    segment.parse().ok()
+}
+
+fn parse_body<T: serde::de::DeserializeOwned>(body: &str) -> Result<T, Response> {
+    serde_json::from_str(body).map_err(|e| Response::error(400, e.to_string()))
+}
+
+fn header(name: &str, value: &str) -> tiny_http::Header {
+    tiny_http::Header::from_bytes(name.as_bytes(), value.as_bytes()).expect("valid header")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn api_with_board() -> (tempfile::TempDir, Api) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("board.toml");
+        let mut app = App::new();
+        app.new_place("Invoice".to_string());
+        FileManager::new().save_to_file(&app.breadboard, &path).unwrap();
+        (dir, Api::new(path.to_string_lossy().to_string()))
+    }
+
+    #[test]
+    fn test_read_endpoints() {
+        let (_dir, api) = api_with_board();
+        let board = api.handle("GET", "/board", None, "");
+        assert_eq!(board.status, 200);
+        assert_eq!(board.body["places"][0]["name"], "Invoice");
+        assert!(board.version.is_some());
+
+        assert_eq!(api.handle("GET", "/places/1", None, "").body["name"], "Invoice");
+        assert_eq!(api.handle("GET", "/places/9", None, "").status, 404);
+        assert_eq!(api.handle("GET", "/nowhere", None, "").status, 404);
+    }
+
+    #[test]
+    fn test_writes_need_current_version() {
+        let (_dir, api) = api_with_board();
+        let version = api.handle("GET", "/board", None, "").version.unwrap();
+
+        assert_eq!(api.handle("POST", "/places", None, r#"{"name":"Setup"}"#).status, 428);
+
+        let created = api.handle("POST", "/places", Some(&format!("\"{}\"", version)), r#"{"name":"Setup"}"#);
+        assert_eq!(created.status, 201);
+        assert_eq!(created.body["id"], 2);
+        assert_ne!(created.version.as_ref(), Some(&version));
+
+        // The old version is now stale
+        let stale = api.handle("POST", "/places", Some(&version), r#"{"name":"Confirm"}"#);
+        assert_eq!(stale.status, 412);
+        assert_eq!(stale.version, created.version);
+    }
+
+    #[test]
+    fn test_add_affordance_and_connect() {
+        let (_dir, api) = api_with_board();
+        api.handle("POST", "/places", Some("*"), r#"{"name":"Setup"}"#);
+
+        let affordance = api.handle("POST", "/places/1/affordances", Some("*"), r#"{"name":"Turn on Autopay"}"#);
+        assert_eq!(affordance.status, 201);
+        let body = format!(r#"{{"place_id":1,"affordance_id":{},"dest_id":2}}"#, affordance.body["id"]);
+        assert_eq!(api.handle("POST", "/connections", Some("*"), &body).status, 201);
+
+        let connections = api.handle("GET", "/connections", None, "").body;
+        assert_eq!(connections[0]["dest_id"], 2);
+
+        assert_eq!(api.handle("POST", "/places/7/affordances", Some("*"), r#"{"name":"x"}"#).status, 404);
+        assert_eq!(api.handle("POST", "/places", Some("*"), "{}").status, 400);
+    }
+}
@@ -1,5 +1,32 @@
-use crate::models::{Breadboard, Place, Affordance};
+use crate::analysis::{self, Cycle};
+use crate::models::{annotation, Attribution, Breadboard, BoardLink, Branch, Comment, Detail, Estimate, NamedView, Place, PlaceKind, Affordance, Status, ViewMode};
 use crate::input::Mode;
+use crate::config::{Config, OwnPlace, Snippet};
+use crate::diff::Comparison;
+use crate::examples;
+use crate::export::Document;
+use crate::tutorial;
+use crate::file::{BoardSummary, FileEntry};
+use crate::scan::Scan;
+use crate::workspace::{ExternalPlace, Workspace};
+use crate::lock::BoardLock;
+use crate::spelling::{Dictionary, Fix};
+use crate::icons::{self, Icon};
+use crate::keys::Pending;
+use crate::history::History;
+use crate::hooks;
+use crate::lint;
+use crate::locale::{self, Strings};
+use crate::trash::{self, TrashEntry};
+use std::sync::Arc;
+
+// What pasting a copied place did to its affordances' connections
+#[derive(Debug, Default, PartialEq)]
+pub struct PasteReport {
+    pub place_id: u32,
+    pub remapped: Vec<String>, // "Pay → Confirmation", now leading to this board's place
+    pub dropped: Vec<String>,  // Affordances whose destination isn't on this board
+}
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Selection {
@@ -7,26 +34,143 @@ pub enum Selection {
     Affordance { place_id: u32, affordance_id: u32 },
 }
 
-#[derive(Debug)]
+// What the save prompt writes to the entered filename
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SaveTarget {
+    Board,        // The breadboard as TOML
+    ViewSnapshot, // Plain text of the current view
+    Document(Document), // A walkthrough, pitch or other document written from the board
+    Extract { replace: bool }, // The selected place and what it reaches, as a new board
+    Bundle, // The board and its attachments as a .bboard zip
+    Attachment, // Not a save: the file to attach to the selected place
+    Compare, // Not a save: the board file to compare the board with
+}
+
+const MAX_SEARCH_HISTORY: usize = 20;
+const MAX_COUNT: usize = 999; // Largest count typed before a navigation key
+const LIST_PAGE: usize = 10; // Entries PageUp and PageDown move through a list
+
+// The order of the open dialog's files
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum FileSort {
+    #[default]
+    Name,
+    Modified, // Most recently changed first
+}
+
+// How much of the board an export covers
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum ExportScope {
+    #[default]
+    Board,     // Everything the view shows
+    Selection, // The selected place, or just the selected affordance
+    Subtree,   // The selected place and every place reachable from it
+    Filter,    // What the current filter leaves
+    Flattened, // Everything, with the places nested inside others inlined
+}
+
+impl ExportScope {
+    pub fn next(self) -> Self {
+        match self {
+            ExportScope::Board => ExportScope::Selection,
+            ExportScope::Selection => ExportScope::Subtree,
+            ExportScope::Subtree => ExportScope::Filter,
+            ExportScope::Filter => ExportScope::Flattened,
+            ExportScope::Flattened => ExportScope::Board,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ExportScope::Board => "board",
+            ExportScope::Selection => "selection",
+            ExportScope::Subtree => "subtree",
+            ExportScope::Filter => "filter",
+            ExportScope::Flattened => "flattened",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct AppState {
     pub mode: Mode,
     pub selection: Option<Selection>,
+    pub view: ViewMode,
     pub collapsed: bool,
-    pub filter: Option<String>,
+    pub filter: Option<String>, // Live board filter query (None when no filter is active)
     pub navigation_trail: Vec<u32>,
     pub edit_buffer: String,
     pub connection_search_buffer: String,
+    pub connection_search_prefilled: bool, // Buffer holds the current destination, replaced by the first key typed
     pub connection_search_results: Vec<u32>,
+    pub connection_search_external: Vec<usize>, // Matching `external_places`, listed after the local results
     pub selected_connection_result: Option<usize>,
-    pub file_list: Vec<String>,
-    pub selected_file_index: Option<usize>,
+    pub self_connection_warned: bool, // Enter on the affordance's own place warned once; the next one connects
+    pub file_list: Vec<FileEntry>,
+    pub file_sort: FileSort,
+    pub file_scan: Option<Scan>, // Still listing the open dialog's directory
+    pub running_commands: Vec<hooks::Running>, // Hooks and commands yet to finish
+    pub file_filter: String, // Typed in the open dialog to narrow the file list
+    pub selected_file_index: Option<usize>, // Of the files the filter leaves
+    pub library: Vec<BoardSummary>, // Boards in the boards directory or workspace
+    pub selected_library_index: Option<usize>,
     pub place_search_buffer: String,
     pub place_search_results: Vec<u32>,
     pub selected_place_result: Option<usize>,
     pub is_searching_places: bool, // True when actively searching for places in Navigate mode
+    pub search_history: Vec<String>, // Jump search queries used this session, newest last
+    pub search_history_index: Option<usize>, // Query recalled into the search, while stepping back through them
     pub pending_deletion: Option<Selection>, // Track what's pending deletion for confirmation
-    pub save_filename: String, // Filename for saving (temporary buffer)
+    pub reopen_candidate: Option<String>, // Last board, offered on startup
+    pub recovery_candidate: Option<crate::recovery::Recovered>, // Unsaved edits from an unclean exit, offered on startup
+    pub external_places: Vec<ExternalPlace>, // Places in the workspace's other boards
+    pub save_filename: String, // Filename typed at the save or import prompt
+    pub save_target: SaveTarget,
+    pub export_scope: ExportScope, // Part of the board the export prompt writes
+    pub scope: Option<Vec<Selection>>, // Places and affordances an export copy is narrowed to
+    pub capture_place: Option<u32>, // Place capture lines without one add to
+    pub import_path: Option<String>, // File being imported, once chosen, while the group is typed
+    pub pending_notes: Option<Breadboard>, // Structure read from pasted notes, awaiting confirmation
+    pub copied_place: Option<Breadboard>, // Place copied for pasting, here or into another board
+    pub marked: Vec<Selection>, // Places and affordances marked for connecting in one go
+    pub folded: Vec<u32>, // Places whose affordances are hidden in the outline
     pub current_filename: Option<String>, // Currently loaded/saved file
+    pub saved_board: Option<String>, // The board as last loaded or saved, to tell unsaved edits
+    pub status_message: Option<String>, // One-shot feedback shown in the status bar
+    pub show_preview: bool, // Floating preview of the selected affordance's destination
+    pub show_sketch: bool, // Preview pane of the selected place's sketch
+    pub show_minimap: bool, // Overview of the whole diagram in the graph view
+    pub show_progress: bool, // Panel of built affordances per place
+    pub graph_offset: (usize, usize), // Scroll position of the graph view (columns, rows)
+    pub graph_zoom: Detail,
+    pub swimlanes: bool, // Graph view split into one horizontal lane per group
+    pub incoming_results: Vec<(u32, u32)>, // (source place, affordance) pairs linking into the selected place
+    pub selected_incoming_result: Option<usize>,
+    pub incoming_steps: Option<Vec<usize>>, // Distances when the list shows every place that can reach here
+    pub cycles: Vec<Cycle>, // Connection loops listed by the cycles view
+    pub selected_cycle: Option<usize>,
+    pub cycle_origin: Option<Selection>, // Selection to go back to when the cycles view is cancelled
+    pub selected_branch: Option<usize>, // Highlighted outcome of the selected affordance
+    pub editing_branch: bool, // Edit and connect mode work on the highlighted outcome, not the affordance
+    pub selected_command: Option<usize>, // Highlighted entry of the custom command list
+    pub selected_snippet: Option<usize>, // Highlighted entry of the snippet list
+    pub spelling_fixes: Vec<Fix>, // Ways to fix the typos in the selection, while picking one
+    pub selected_icon: usize, // Highlighted entry of the icons offered for a `:name` being typed
+    pub selected_fix: Option<usize>,
+    pub leader: Option<Pending>, // Leader sequence being typed, and what can come next
+    pub count: Option<String>, // Digits typed before a navigation key, vim-style, to repeat it
+    pub start_items: Vec<StartItem>, // What the start screen offers
+    pub tutorial: Option<usize>, // Step of the tutorial being taken
+    pub drill_stack: Vec<(Breadboard, u32)>, // Boards around the one drilled into, and the place drilled into in each
+    pub read_only: bool, // Opened with --read-only: look around, but don't change the board
+    pub locked_by: Option<String>, // Who else has the board open, when we chose not to edit it
+    pub selected_start_index: Option<usize>,
+    pub output_panel: Option<(String, String)>, // (title, text) of the last command's output
+    pub comparison: Option<Comparison>, // The board beside another version of it
+    pub snapshots: History, // Taken before destructive operations this session
+    pub trash: Vec<TrashEntry>, // Deleted this session, newest last
+    pub selected_trash: Option<usize>, // Highlighted entry of the trash panel, which lists the newest first
+    pub selected_view: Option<usize>, // Highlighted entry of the named views menu
 }
 
 impl Default for AppState {
@@ -34,32 +178,228 @@ impl Default for AppState {
         Self {
             mode: Mode::Navigate,
             selection: None,
+            view: ViewMode::Outline,
             collapsed: false,
             filter: None,
             navigation_trail: Vec::new(),
             edit_buffer: String::new(),
             connection_search_buffer: String::new(),
+            connection_search_prefilled: false,
             connection_search_results: Vec::new(),
+            connection_search_external: Vec::new(),
             selected_connection_result: None,
+            self_connection_warned: false,
             file_list: Vec::new(),
+            file_sort: FileSort::default(),
+            file_scan: None,
+            running_commands: Vec::new(),
+            file_filter: String::new(),
             selected_file_index: None,
+            library: Vec::new(),
+            selected_library_index: None,
             place_search_buffer: String::new(),
             place_search_results: Vec::new(),
             selected_place_result: None,
             is_searching_places: false,
+            search_history: Vec::new(),
+            search_history_index: None,
             pending_deletion: None,
+            reopen_candidate: None,
+            recovery_candidate: None,
+            external_places: Vec::new(),
             save_filename: String::from("breadboard.toml"),
+            save_target: SaveTarget::Board,
+            export_scope: ExportScope::Board,
+            scope: None,
+            capture_place: None,
+            import_path: None,
+            pending_notes: None,
+            copied_place: None,
+            marked: Vec::new(),
+            folded: Vec::new(),
             current_filename: None,
+            saved_board: None,
+            status_message: None,
+            show_preview: false,
+            show_sketch: false,
+            show_minimap: true,
+            show_progress: false,
+            graph_offset: (0, 0),
+            graph_zoom: Detail::Full,
+            swimlanes: false,
+            incoming_results: Vec::new(),
+            selected_incoming_result: None,
+            incoming_steps: None,
+            cycles: Vec::new(),
+            selected_cycle: None,
+            cycle_origin: None,
+            selected_branch: None,
+            editing_branch: false,
+            selected_command: None,
+            selected_snippet: None,
+            spelling_fixes: Vec::new(),
+            selected_icon: 0,
+            leader: None,
+            count: None,
+            selected_fix: None,
+            start_items: Vec::new(),
+            tutorial: None,
+            drill_stack: Vec::new(),
+            read_only: false,
+            locked_by: None,
+            selected_start_index: None,
+            output_panel: None,
+            comparison: None,
+            snapshots: History::default(),
+            trash: Vec::new(),
+            selected_trash: None,
+            selected_view: None,
         }
     }
 }
 
+#[derive(Clone)]
 pub struct App {
     pub breadboard: Breadboard,
     pub state: AppState,
+    pub config: Config,
+    pub workspace: Option<Workspace>,
+    pub lock: Option<Arc<BoardLock>>, // Shared so copies of the app don't release it
+    pub dictionary: Option<Arc<Dictionary>>, // For spell-checking, when there's one to use
     pub should_quit: bool,
 }
 
+// Entries of the start screen, shown when bboard starts without a board
+#[derive(Debug, Clone, PartialEq)]
+pub enum StartItem {
+    NewBoard,
+    Tutorial,
+    Recent(String),
+    Open,
+    Example(usize),
+}
+
+impl StartItem {
+    pub fn label(&self, s: &Strings) -> String {
+        match self {
+            StartItem::NewBoard => s.start_new_board.to_string(),
+            StartItem::Tutorial => s.start_tutorial.to_string(),
+            StartItem::Recent(file) => locale::fill(s.start_recent, &[file]),
+            StartItem::Open => s.start_open.to_string(),
+            StartItem::Example(index) => locale::fill(s.start_example, &[examples::EXAMPLES[*index].0]),
+        }
+    }
+}
+
+// One segment of the breadcrumb: what it reads and where choosing it goes
+#[derive(Debug, Clone, PartialEq)]
+pub struct Crumb {
+    pub label: String,
+    pub target: Option<Selection>,
+    pub depth: usize, // How many boards deep the target is, for crumbs of the boards around a nested one
+}
+
+// "Pay now -> Confirm" split into the affordance's name and the name of the
+// place it leads to. "→" works too, as the outline writes connections that way.
+pub fn split_arrow(text: &str) -> (&str, Option<&str>) {
+    let arrow = ["->", "→"].iter()
+        .filter_map(|arrow| text.find(arrow).map(|index| (index, arrow.len())))
+        .min();
+    match arrow {
+        Some((index, len)) => (text[..index].trim(), Some(text[index + len..].trim())),
+        None => (text, None),
+    }
+}
+
+// How well `query` matches `name`, ignoring case: None unless its
+// characters all appear in order. Matches in one run, at the start and at
+// the starts of words score higher, and gaps cost.
+pub fn fuzzy_score(query: &str, name: &str) -> Option<i32> {
+    let query: Vec<char> = query.to_lowercase().chars().filter(|c| !c.is_whitespace()).collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+    let name: Vec<char> = name.to_lowercase().chars().collect();
+    let text: String = name.iter().collect();
+    let mut score = 0;
+    let mut next = 0;
+    let mut previous: Option<usize> = None;
+    for c in &query {
+        let at = next + name[next..].iter().position(|n| n == c)?;
+        score += match previous {
+            Some(p) if at == p + 1 => 5, // Continues a run
+            _ => 1,
+        };
+        if at == 0 || !name[at - 1].is_alphanumeric() {
+            score += 3; // Starts a word
+        }
+        if let Some(p) = previous {
+            score -= (at - p - 1).min(3) as i32;
+        }
+        previous = Some(at);
+        next = at + 1;
+    }
+    let typed: String = query.iter().collect();
+    if text.starts_with(&typed) {
+        score += 10;
+    } else if text.contains(&typed) {
+        score += 5;
+    }
+    Some(score)
+}
+
+// A board filter query: words to find in names, plus "by:bob" for items
+// bob added or last changed and "since:7d" or "since:2026-10-01" for ones
+// added or changed since then. Both must hold for the same stamp.
+// "is:annotated" keeps names ending in a note in brackets.
+#[derive(Debug, Default, PartialEq)]
+struct FilterQuery {
+    text: String,
+    by: Option<String>,
+    since: Option<chrono::NaiveDate>,
+    annotated: bool,
+}
+
+impl FilterQuery {
+    fn parse(query: &str, today: chrono::NaiveDate) -> Self {
+        let mut filter = FilterQuery::default();
+        let mut words = Vec::new();
+        for word in query.split_whitespace() {
+            if let Some(name) = word.strip_prefix("by:") {
+                filter.by = Some(name.to_lowercase()).filter(|n| !n.is_empty());
+            } else if let Some(date) = word.strip_prefix("since:") {
+                filter.since = date.strip_suffix('d')
+                    .and_then(|days| days.parse().ok())
+                    .and_then(|days| today.checked_sub_days(chrono::Days::new(days)))
+                    .or_else(|| chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").ok());
+            } else if word.eq_ignore_ascii_case("is:annotated") {
+                filter.annotated = true;
+            } else {
+                words.push(word);
+            }
+        }
+        filter.text = words.join(" ").to_lowercase();
+        filter
+    }
+
+    fn matches(&self, name: &str, attribution: &Attribution) -> bool {
+        if !name.to_lowercase().contains(&self.text) || (self.annotated && annotation(name).is_none()) {
+            return false;
+        }
+        if self.by.is_none() && self.since.is_none() {
+            return true;
+        }
+        attribution.stamps().any(|(by, at)| {
+            let by_matches = self.by.as_ref().is_none_or(|name| by.to_lowercase().contains(name));
+            let since_matches = self.since.is_none_or(|since| {
+                chrono::DateTime::parse_from_rfc3339(at)
+                    .is_ok_and(|at| at.with_timezone(&chrono::Local).date_naive() >= since)
+            });
+            by_matches && since_matches
+        })
+    }
+}
+
 impl App {
     pub fn new() -> Self {
         let breadboard = Breadboard::new("New Breadboard".to_string());
@@ -68,15 +408,178 @@ impl App {
         Self {
             breadboard,
             state,
+            config: Config::default(),
+            workspace: None,
+            lock: None,
+            dictionary: None,
             should_quit: false,
         }
     }
 
-    #[cfg(test)]
-    pub fn new_place(&mut self, name: String) {
+    pub fn new_place(&mut self, name: String) -> u32 {
         let id = self.breadboard.generate_place_id();
         let place = Place::new(id, name);
         self.breadboard.add_place(place);
+        id
+    }
+
+    // The place named `name`, ignoring case, or a new one if there's none
+    pub fn place_named(&mut self, name: &str) -> u32 {
+        match self.breadboard.places.iter().find(|p| p.name.trim().eq_ignore_ascii_case(name.trim())) {
+            Some(place) => place.id,
+            None => self.new_place(name.trim().to_string()),
+        }
+    }
+
+    // One line of capture mode, adding whatever it names that's missing:
+    //
+    //   Invoice                                     the place
+    //   Invoice: Turn on Autopay                    an affordance on it
+    //   Invoice: Turn on Autopay -> Setup Autopay   connected to that place
+    //   Download PDF -> Receipt                     on the last line's place
+    //
+    // Returns the line as captured, for the status bar.
+    pub fn capture_line(&mut self, line: &str) -> anyhow::Result<String> {
+        let (left, dest) = split_arrow(line.trim());
+        let (place_name, affordance_name) = match left.split_once(':') {
+            Some((place, affordance)) => (Some(place.trim()), Some(affordance.trim())),
+            None if dest.is_some() => (None, Some(left)),
+            None => (Some(left), None),
+        };
+        if affordance_name == Some("") {
+            anyhow::bail!("an affordance needs a name");
+        }
+
+        let place_id = match place_name {
+            Some("") => anyhow::bail!("a place needs a name"),
+            Some(name) => self.place_named(name),
+            None => self.state.capture_place
+                .filter(|id| self.breadboard.find_place(id).is_some())
+                .ok_or_else(|| anyhow::anyhow!("start with \"Place: {}\"", left))?,
+        };
+        self.state.capture_place = Some(place_id);
+        self.state.selection = Some(Selection::Place(place_id));
+
+        let Some(affordance_name) = affordance_name else {
+            return Ok(self.breadboard.find_place(&place_id).unwrap().name.clone());
+        };
+        let existing = self.breadboard.find_place(&place_id).unwrap().affordances.iter()
+            .find(|a| a.name.trim().eq_ignore_ascii_case(affordance_name))
+            .map(|a| a.id);
+        let affordance_id = existing.unwrap_or_else(|| {
+            let id = self.breadboard.generate_affordance_id();
+            self.add_affordance_to_place(&place_id, Affordance::new(id, affordance_name.to_string()));
+            id
+        });
+        if let Some(dest) = dest.filter(|d| !d.is_empty()) {
+            let dest_id = self.place_named(dest);
+            self.connect_affordance(place_id, affordance_id, dest_id, false);
+        }
+        self.state.selection = Some(Selection::Affordance { place_id, affordance_id });
+
+        let place = self.breadboard.find_place(&place_id).unwrap();
+        let affordance = place.affordances.iter().find(|a| a.id == affordance_id).unwrap();
+        Ok(match affordance.connects_to.and_then(|id| self.breadboard.find_place(&id)) {
+            Some(dest) => format!("{}: {} → {}", place.name, affordance.name, dest.name),
+            None => format!("{}: {}", place.name, affordance.name),
+        })
+    }
+
+    // A copy of a place to paste later: the place first, then the places its
+    // affordances lead to, whose names are all pasting needs from them
+    pub fn copy_place(&self, place_id: u32) -> Option<Breadboard> {
+        let place = self.breadboard.find_place(&place_id)?;
+        let mut copied = Breadboard::new(self.breadboard.name.clone());
+        copied.add_place(place.clone());
+        let destinations = place.affordances.iter().flat_map(|a| a.destinations());
+        for dest in destinations {
+            if copied.find_place(&dest).is_some() {
+                continue;
+            }
+            if let Some(target) = self.breadboard.find_place(&dest) {
+                copied.add_place(Place::new(target.id, target.name.clone()));
+            }
+        }
+        Some(copied)
+    }
+
+    // Paste a place copied with `copy_place` as a new place, with its
+    // connections rewired to the places of the same name on this board.
+    // Ones whose destination isn't here are left unconnected rather than
+    // pointing at whatever place happens to have the old ID.
+    pub fn paste_place(&mut self, copied: &Breadboard) -> Option<PasteReport> {
+        let mut place = copied.places.first()?.clone();
+        let copied_id = place.id;
+        let pasted_id = self.breadboard.generate_place_id();
+        let mut report = PasteReport { place_id: pasted_id, ..Default::default() };
+
+        let local = |dest: u32| -> Option<u32> {
+            if dest == copied_id {
+                return Some(pasted_id);
+            }
+            let name = &copied.find_place(&dest)?.name;
+            self.breadboard.places.iter()
+                .find(|p| p.name.trim().eq_ignore_ascii_case(name.trim()))
+                .map(|p| p.id)
+        };
+        for affordance in &mut place.affordances {
+            if let Some(dest) = affordance.connects_to {
+                affordance.connects_to = local(dest);
+                match copied.find_place(&dest) {
+                    _ if affordance.connects_to.is_none() => report.dropped.push(affordance.name.clone()),
+                    Some(target) if dest != copied_id => {
+                        report.remapped.push(format!("{} → {}", affordance.name, target.name));
+                    }
+                    _ => {}
+                }
+            }
+            for branch in &mut affordance.branches {
+                if let Some(dest) = branch.connects_to {
+                    branch.connects_to = local(dest);
+                    if branch.connects_to.is_none() {
+                        report.dropped.push(format!("{} ({})", affordance.name, branch.label));
+                    }
+                }
+            }
+        }
+
+        place.id = pasted_id;
+        for affordance in &mut place.affordances {
+            affordance.id = self.breadboard.generate_affordance_id();
+        }
+        self.breadboard.add_place(place);
+        Some(report)
+    }
+
+    // Add a snippet's affordances to a place, leaving out ones it already has
+    // by name. "back" as a destination is the first place leading here.
+    // Returns how many were added and how many left out.
+    pub fn insert_snippet(&mut self, place_id: u32, snippet: &Snippet) -> (usize, usize) {
+        let back = self.breadboard.get_incoming_connections(&place_id).first()
+            .map(|(place, _)| place.id)
+            .filter(|id| *id != place_id);
+        let (mut added, mut skipped) = (0, 0);
+        for entry in &snippet.affordances {
+            let (name, dest) = split_arrow(entry);
+            let Some(place) = self.breadboard.find_place(&place_id) else {
+                break;
+            };
+            if name.is_empty() || place.affordances.iter().any(|a| a.name.trim().eq_ignore_ascii_case(name)) {
+                skipped += 1;
+                continue;
+            }
+
+            let id = self.breadboard.generate_affordance_id();
+            let mut affordance = Affordance::new(id, name.to_string());
+            affordance.connects_to = match dest {
+                Some(dest) if dest.eq_ignore_ascii_case("back") => back,
+                Some(dest) if !dest.is_empty() => Some(self.place_named(dest)),
+                _ => None,
+            };
+            self.add_affordance_to_place(&place_id, affordance);
+            added += 1;
+        }
+        (added, skipped)
     }
 
     pub fn add_affordance_to_place(&mut self, place_id: &u32, affordance: Affordance) {
@@ -85,6 +588,46 @@ impl App {
         }
     }
 
+    // Checked edits for the headless front ends (RPC, HTTP API), which take
+    // IDs from outside and must not silently ignore unknown ones
+    pub fn add_affordance(&mut self, place_id: u32, name: String, connects_to: Option<u32>) -> anyhow::Result<u32> {
+        self.require_place(place_id)?;
+        if let Some(dest_id) = connects_to {
+            self.require_place(dest_id)?;
+        }
+        let id = self.breadboard.generate_affordance_id();
+        let mut affordance = Affordance::new(id, name);
+        affordance.connects_to = connects_to;
+        self.add_affordance_to_place(&place_id, affordance);
+        Ok(id)
+    }
+
+    // Connect an affordance to `dest_id`, or remove its connection with None
+    pub fn set_connection(&mut self, place_id: u32, affordance_id: u32, dest_id: Option<u32>, add_back: bool) -> anyhow::Result<()> {
+        if !self.require_place(place_id)?.affordances.iter().any(|a| a.id == affordance_id) {
+            anyhow::bail!("no affordance {} in place {}", affordance_id, place_id);
+        }
+        match dest_id {
+            Some(dest_id) => {
+                self.require_place(dest_id)?;
+                self.connect_affordance(place_id, affordance_id, dest_id, add_back);
+            }
+            None => {
+                if let Some(affordance) = self.breadboard.find_place_mut(&place_id)
+                    .and_then(|p| p.affordances.iter_mut().find(|a| a.id == affordance_id))
+                {
+                    affordance.connects_to = None;
+                    affordance.link = None;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn require_place(&self, id: u32) -> anyhow::Result<&Place> {
+        self.breadboard.find_place(&id).ok_or_else(|| anyhow::anyhow!("no place {}", id))
+    }
+
     pub fn get_selected_place(&self) -> Option<&Place> {
         match &self.state.selection {
             Some(Selection::Place(id)) => self.breadboard.find_place(id),
@@ -93,7 +636,6 @@ impl App {
         }
     }
 
-    #[allow(dead_code)]
     pub fn get_selected_place_mut(&mut self) -> Option<&mut Place> {
         let id = match &self.state.selection {
             Some(Selection::Place(id)) | Some(Selection::Affordance { place_id: id, .. }) => *id,
@@ -102,146 +644,1633 @@ impl App {
         self.breadboard.find_place_mut(&id)
     }
 
-    pub fn navigate_to_place(&mut self, place_id: u32) {
-        if let Some(current_place) = self.get_selected_place() {
-            self.state.navigation_trail.push(current_place.id);
+    // Remember the board as it now is on disk, so later edits show as unsaved
+    pub fn mark_saved(&mut self) {
+        self.state.saved_board = toml::to_string(&self.root_board()).ok();
+    }
+
+    // Whether the board can't be changed: opened with --read-only, or open
+    // elsewhere and left to them
+    pub fn is_read_only(&self) -> bool {
+        self.state.read_only || self.state.locked_by.is_some()
+    }
+
+    // The board as it was last loaded or saved
+    pub fn saved_version(&self) -> Option<Breadboard> {
+        toml::from_str(self.state.saved_board.as_deref()?).ok()
+    }
+
+    // Whether the board has edits that aren't on disk. One that was never
+    // saved counts as soon as it has places.
+    pub fn is_dirty(&self) -> bool {
+        match &self.state.saved_board {
+            Some(saved) => toml::to_string(&self.root_board()).ok().as_ref() != Some(saved),
+            None => !self.root_board().places.is_empty(),
         }
-        self.state.selection = Some(Selection::Place(place_id));
     }
 
-    pub fn navigate_back(&mut self) {
-        if let Some(previous_id) = self.state.navigation_trail.pop() {
-            self.state.selection = Some(Selection::Place(previous_id));
+    // Where the selection is, as "Place › Affordance"
+    pub fn selection_path(&self) -> Option<String> {
+        let place = self.get_selected_place()?;
+        Some(match self.get_selected_affordance() {
+            Some(affordance) => format!("{} › {}", place.name, affordance.name),
+            None => place.name.clone(),
+        })
+    }
+
+    // Board ▸ Group ▸ Place ▸ Affordance down to the selection. The board
+    // and group segments lead to their first place.
+    pub fn breadcrumb(&self) -> Vec<Crumb> {
+        let first_place = |group: Option<&str>| self.breadboard.places.iter()
+            .find(|p| group.is_none() || p.group.as_deref() == group)
+            .map(|p| Selection::Place(p.id));
+        // Drilled into a place, the boards around it come first: the top
+        // one by name, then each place drilled into
+        let depth = self.state.drill_stack.len();
+        let top = self.state.drill_stack.first().map_or(&self.breadboard, |(board, _)| board);
+        let mut crumbs = vec![Crumb {
+            label: top.name.clone(),
+            target: top.places.first().map(|p| Selection::Place(p.id)),
+            depth: 0,
+        }];
+        for (level, (board, id)) in self.state.drill_stack.iter().enumerate() {
+            let label = board.find_place(id).map_or_else(String::new, |p| p.name.clone());
+            crumbs.push(Crumb { label, target: Some(Selection::Place(*id)), depth: level });
+        }
+        let Some(place) = self.get_selected_place() else {
+            return crumbs;
+        };
+        if let Some(group) = place.group.as_deref() {
+            crumbs.push(Crumb { label: group.to_string(), target: first_place(Some(group)), depth });
+        }
+        crumbs.push(Crumb { label: place.name.clone(), target: Some(Selection::Place(place.id)), depth });
+        if let Some(affordance) = self.get_selected_affordance() {
+            crumbs.push(Crumb {
+                label: affordance.name.clone(),
+                target: Some(Selection::Affordance { place_id: place.id, affordance_id: affordance.id }),
+                depth,
+            });
         }
+        crumbs
     }
 
-    pub fn toggle_collapsed(&mut self) {
-        self.state.collapsed = !self.state.collapsed;
+    // Go to where a breadcrumb segment leads. Moving to another place is
+    // remembered in the trail, as following a connection is.
+    pub fn choose_crumb(&mut self, crumb: &Crumb) {
+        let Some(target) = crumb.target.clone() else {
+            return;
+        };
+        while self.state.drill_stack.len() > crumb.depth {
+            self.drill_out();
+        }
+        match target {
+            Selection::Place(id) if self.get_selected_place().is_some_and(|p| p.id != id) => self.navigate_to_place(id),
+            target => self.state.selection = Some(target),
+        }
+    }
+
+    // Open the board inside the selected place, creating it if the place has
+    // none yet. Returns the place's name.
+    pub fn drill_in(&mut self) -> Option<String> {
+        let place = self.get_selected_place_mut()?;
+        let (id, name) = (place.id, place.name.clone());
+        let mut inner = place.board.take().map_or_else(|| Breadboard::new(name.clone()), |board| *board);
+        inner.sync_id_counters();
+        let outer = std::mem::replace(&mut self.breadboard, inner);
+        self.state.drill_stack.push((outer, id));
+        self.state.selection = self.breadboard.places.first().map(|p| Selection::Place(p.id));
+        self.leave_board_state();
+        Some(name)
     }
 
-    // Connection search methods
-    const REMOVE_CONNECTION_ID: u32 = 0; // Special ID for remove connection option
+    // Back to the board around the one drilled into, with the place it's
+    // inside selected. False at the top board.
+    pub fn drill_out(&mut self) -> bool {
+        let Some((outer, id)) = self.state.drill_stack.pop() else {
+            return false;
+        };
+        let inner = std::mem::replace(&mut self.breadboard, outer);
+        if let Some(place) = self.breadboard.find_place_mut(&id) {
+            place.board = Some(Box::new(inner)).filter(|board| !board.places.is_empty());
+        }
+        self.state.selection = Some(Selection::Place(id));
+        self.leave_board_state();
+        true
+    }
 
-    pub fn update_connection_search(&mut self) {
-        // Start with the remove connection option
-        let mut results = vec![Self::REMOVE_CONNECTION_ID];
+    // Selections and marks belong to the board being left
+    fn leave_board_state(&mut self) {
+        self.state.navigation_trail.clear();
+        self.state.marked.clear();
+        self.state.scope = None;
+        self.clear_filter();
+    }
 
-        if self.state.connection_search_buffer.is_empty() {
-            // Add all places
-            results.extend(self.breadboard.places.iter().map(|p| p.id));
-        } else {
-            let search_lower = self.state.connection_search_buffer.to_lowercase();
-            // Add matching places
-            results.extend(self.breadboard.places.iter()
-                .filter(|p| p.name.to_lowercase().contains(&search_lower))
-                .map(|p| p.id));
+    // The whole board as saved, with the board drilled into put back in place
+    pub fn root_board(&self) -> Breadboard {
+        let mut board = self.breadboard.clone();
+        for (outer, id) in self.state.drill_stack.iter().rev() {
+            let mut outer = outer.clone();
+            if let Some(place) = outer.find_place_mut(id) {
+                place.board = Some(Box::new(board)).filter(|board| !board.places.is_empty());
+            }
+            board = outer;
         }
+        board
+    }
 
-        self.state.connection_search_results = results;
+    // Replace the whole board, staying drilled into the same places
+    pub fn set_root_board(&mut self, root: Breadboard) {
+        let mut board = root;
+        for (outer, id) in &mut self.state.drill_stack {
+            let place = board.find_place_mut(id);
+            let name = place.as_ref().map_or_else(String::new, |p| p.name.clone());
+            let inner = place.and_then(|p| p.board.take()).map_or_else(|| Breadboard::new(name), |inner| *inner);
+            *outer = board;
+            board = inner;
+        }
+        self.breadboard = board;
+    }
 
-        // Reset selection to first result (remove connection)
-        self.state.selected_connection_result = Some(0);
+    // Keep the whole board as it is now, before something hard to undo by
+    // hand. `label` says what: "deleting Invoice".
+    pub fn take_snapshot(&mut self, label: String) {
+        let board = self.root_board();
+        self.state.snapshots.push(label, &board, self.state.selection.clone(), &self.config.history);
     }
 
-    pub fn start_connection_search(&mut self) {
-        self.state.connection_search_buffer.clear();
-        self.state.connection_search_results.clear();
-        self.state.selected_connection_result = None;
-        self.update_connection_search();
+    // Put back the board from the last snapshot, returning what it was taken
+    // before. The selection goes back too, if it's still there.
+    pub fn restore_snapshot(&mut self) -> Option<String> {
+        let (label, board, selection) = self.state.snapshots.pop()?;
+        self.set_root_board(board);
+        self.state.marked.clear();
+        self.state.selection = selection.filter(|selection| match selection {
+            Selection::Place(id) => self.breadboard.find_place(id).is_some(),
+            Selection::Affordance { place_id, affordance_id } => self.breadboard.find_place(place_id)
+                .is_some_and(|p| p.affordances.iter().any(|a| a.id == *affordance_id)),
+        });
+        if self.state.selection.is_none() {
+            self.state.selection = self.breadboard.places.first().map(|p| Selection::Place(p.id));
+        }
+        Some(label)
     }
 
-    pub fn clear_connection_search(&mut self) {
-        self.state.connection_search_buffer.clear();
-        self.state.connection_search_results.clear();
-        self.state.selected_connection_result = None;
+    // Delete a place or affordance, keeping it in the trash. Returns its name.
+    pub fn delete(&mut self, item: &Selection) -> Option<String> {
+        let trashed = match *item {
+            Selection::Place(id) => trash::delete_place(&mut self.breadboard, id)?,
+            Selection::Affordance { place_id, affordance_id } => {
+                trash::delete_affordance(&mut self.breadboard, place_id, affordance_id)?
+            }
+        };
+        let name = trashed.name().to_string();
+        let drill_path = self.state.drill_stack.iter().map(|(_, id)| *id).collect();
+        self.state.trash.push(TrashEntry { item: trashed, drill_path, deleted_at: chrono::Local::now() });
+        Some(name)
     }
 
-    pub fn get_selected_connection_place(&self) -> Option<&Place> {
-        if let Some(selected_index) = self.state.selected_connection_result {
-            if selected_index < self.state.connection_search_results.len() {
-                let place_id = &self.state.connection_search_results[selected_index];
-                // Check if this is the remove connection option
-                if *place_id == Self::REMOVE_CONNECTION_ID {
-                    return None; // This is the remove option, not a real place
-                }
-                self.breadboard.find_place(place_id)
-            } else {
-                None
+    // The trash newest first, as the panel lists it
+    pub fn trash_entries(&self) -> impl Iterator<Item = &TrashEntry> {
+        self.state.trash.iter().rev()
+    }
+
+    // Put the highlighted trash entry back and select it
+    pub fn restore_selected_trash(&mut self) -> anyhow::Result<String> {
+        let Some(index) = self.state.selected_trash.and_then(|i| self.state.trash.len().checked_sub(i + 1)) else {
+            anyhow::bail!("Nothing to restore");
+        };
+        let here: Vec<u32> = self.state.drill_stack.iter().map(|(_, id)| *id).collect();
+        if self.state.trash[index].drill_path != here {
+            anyhow::bail!("{} was deleted inside another place: drill into it to restore it", self.state.trash[index].item.name());
+        }
+        let entry = self.state.trash[index].clone();
+        let (place_id, affordance_id) = trash::restore(&mut self.breadboard, entry.item.clone())?;
+        self.state.trash.remove(index);
+        self.state.selected_trash = self.state.selected_trash.filter(|_| !self.state.trash.is_empty())
+            .map(|i| i.min(self.state.trash.len() - 1));
+        self.state.selection = Some(match affordance_id {
+            Some(affordance_id) => Selection::Affordance { place_id, affordance_id },
+            None => Selection::Place(place_id),
+        });
+        Ok(entry.item.name().to_string())
+    }
+
+    // Up one breadcrumb level: from an affordance to its place, from a place
+    // to the first of its group, and from there to the board's first place
+    pub fn jump_up(&mut self) -> bool {
+        let crumbs = self.breadcrumb();
+        let depth = self.state.drill_stack.len();
+        let up = crumbs.iter().rev()
+            .find(|crumb| crumb.target.is_some() && (crumb.target != self.state.selection || crumb.depth != depth));
+        match up {
+            Some(crumb) => {
+                self.choose_crumb(crumb);
+                true
             }
-        } else {
-            None
+            None => false,
         }
     }
 
-    pub fn is_remove_connection_selected(&self) -> bool {
-        if let Some(selected_index) = self.state.selected_connection_result {
-            if selected_index < self.state.connection_search_results.len() {
-                let place_id = &self.state.connection_search_results[selected_index];
-                *place_id == Self::REMOVE_CONNECTION_ID
-            } else {
-                false
+    // Add a comment to the selected place or affordance, returning its name
+    pub fn add_comment(&mut self, text: &str) -> Option<String> {
+        let text = text.trim();
+        if text.is_empty() {
+            return None;
+        }
+        let comment = Comment::new(self.config.author(), text.to_string());
+        match self.state.selection.clone()? {
+            Selection::Place(id) => {
+                let place = self.breadboard.find_place_mut(&id)?;
+                place.comments.push(comment);
+                Some(place.name.clone())
+            }
+            Selection::Affordance { .. } => {
+                let affordance = self.get_selected_affordance_mut()?;
+                affordance.comments.push(comment);
+                Some(affordance.name.clone())
             }
-        } else {
-            false
         }
     }
 
-    // File opening methods
-    pub fn start_file_opening(&mut self, file_manager: &crate::file::FileManager) -> anyhow::Result<()> {
-        self.state.file_list = file_manager.list_toml_files()?;
-        self.state.selected_file_index = if self.state.file_list.is_empty() {
-            None
-        } else {
-            Some(0)
+    // Set the selected affordance's estimate from what was typed, or clear
+    // it when nothing was, returning what to tell the user
+    pub fn set_estimate(&mut self, text: &str) -> anyhow::Result<String> {
+        let s = self.config.language.strings();
+        let estimate = match text.trim() {
+            "" => None,
+            text => Some(Estimate::parse(text).ok_or_else(|| anyhow::anyhow!(s.estimate_invalid))?),
         };
-        Ok(())
+        let affordance = self.get_selected_affordance_mut().ok_or_else(|| anyhow::anyhow!(s.select_affordance_to_estimate))?;
+        affordance.estimate = estimate;
+        Ok(match estimate {
+            Some(estimate) => locale::fill(s.estimated, &[&affordance.name, &estimate.to_string()]),
+            None => locale::fill(s.estimate_cleared, &[&affordance.name]),
+        })
     }
 
-    pub fn get_selected_file(&self) -> Option<&String> {
-        if let Some(selected_index) = self.state.selected_file_index {
-            if selected_index < self.state.file_list.len() {
-                Some(&self.state.file_list[selected_index])
-            } else {
-                None
+    // Comments on the selected place or affordance
+    pub fn selected_comments(&self) -> &[Comment] {
+        match &self.state.selection {
+            Some(Selection::Affordance { .. }) => self.get_selected_affordance().map_or(&[], |a| &a.comments),
+            _ => self.get_selected_place().map_or(&[], |p| &p.comments),
+        }
+    }
+
+    // The folder of the board file, which attachments are relative to
+    pub fn board_dir(&self) -> std::path::PathBuf {
+        match self.state.current_filename.as_deref().and_then(|f| std::path::Path::new(f).parent()) {
+            Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+            _ => std::path::PathBuf::from("."),
+        }
+    }
+
+    // Attach the file at `path` to the selected place, returning the place's
+    // name. Files under the board's folder are kept relative to it.
+    pub fn attach_file(&mut self, path: &str) -> anyhow::Result<String> {
+        let path = std::path::Path::new(path.trim());
+        if !path.is_file() {
+            anyhow::bail!("{} is not a file", path.display());
+        }
+        let absolute = std::path::absolute(path)?;
+        let attachment = match std::path::absolute(self.board_dir()).map(|dir| absolute.strip_prefix(dir).map(|p| p.to_path_buf())) {
+            Ok(Ok(relative)) => relative,
+            _ => absolute,
+        };
+        let place = self.get_selected_place_mut().ok_or_else(|| anyhow::anyhow!("select a place to attach to"))?;
+        let attachment = attachment.to_string_lossy().into_owned();
+        if !place.attachments.contains(&attachment) {
+            place.attachments.push(attachment);
+        }
+        Ok(place.name.clone())
+    }
+
+    // Who added and last changed the selected place or affordance
+    pub fn selected_attribution(&self) -> Option<&Attribution> {
+        match &self.state.selection {
+            Some(Selection::Affordance { .. }) => self.get_selected_affordance().map(|a| &a.attribution),
+            _ => self.get_selected_place().map(|p| &p.attribution),
+        }
+    }
+
+    // Notes of the selected place or affordance
+    pub fn selected_notes(&self) -> Option<&str> {
+        match &self.state.selection {
+            Some(Selection::Place(id)) => self.breadboard.find_place(id)?.notes.as_deref(),
+            Some(Selection::Affordance { place_id, affordance_id }) => self.breadboard.find_place(place_id)?
+                .affordances.iter()
+                .find(|a| &a.id == affordance_id)?
+                .notes.as_deref(),
+            None => None,
+        }
+    }
+
+    // Replace the selection's notes; blank text removes them
+    pub fn set_selected_notes(&mut self, text: &str) -> bool {
+        let notes = Some(text.trim_end().to_string()).filter(|t| !t.trim().is_empty());
+        let target = match self.state.selection.clone() {
+            Some(Selection::Place(id)) => self.breadboard.find_place_mut(&id).map(|p| &mut p.notes),
+            Some(Selection::Affordance { place_id, affordance_id }) => self.breadboard.find_place_mut(&place_id)
+                .and_then(|p| p.affordances.iter_mut().find(|a| a.id == affordance_id))
+                .map(|a| &mut a.notes),
+            None => None,
+        };
+        match target {
+            Some(target) => {
+                *target = notes;
+                true
             }
-        } else {
-            None
+            None => false,
         }
     }
 
-    pub fn clear_file_selection(&mut self) {
-        self.state.file_list.clear();
-        self.state.selected_file_index = None;
+    // Icons matching the `:name` at the end of the name being edited
+    pub fn icon_matches(&self) -> Vec<&'static Icon> {
+        if self.state.mode != Mode::Edit {
+            return Vec::new();
+        }
+        icons::query(&self.state.edit_buffer)
+            .map(|(_, typed)| icons::matching(typed, self.config.nerd_font))
+            .unwrap_or_default()
     }
 
-    // Calculate the index of the currently selected item in the rendered list
-    // This is used for scrolling to keep the selected item visible
-    pub fn get_selected_item_index(&self) -> Option<usize> {
-        let mut index = 0;
+    // Put the highlighted icon in place of the `:name` typed for it. Returns
+    // whether there was one.
+    pub fn pick_icon(&mut self) -> bool {
+        let Some(icon) = self.icon_matches().get(self.state.selected_icon).copied() else {
+            return false;
+        };
+        if let Some((colon, _)) = icons::query(&self.state.edit_buffer) {
+            self.state.edit_buffer.truncate(colon);
+            self.state.edit_buffer.push_str(icon.glyph);
+        }
+        self.state.selected_icon = 0;
+        true
+    }
+
+    // Ways to fix the typos in the selection's name and notes
+    pub fn selection_fixes(&self) -> Vec<Fix> {
+        let Some(dictionary) = &self.dictionary else {
+            return Vec::new();
+        };
+        let (name, notes) = match &self.state.selection {
+            Some(Selection::Place(_)) => self.get_selected_place().map(|p| (p.name.as_str(), p.notes.as_deref())),
+            Some(Selection::Affordance { .. }) => self.get_selected_affordance().map(|a| (a.name.as_str(), a.notes.as_deref())),
+            None => None,
+        }
+        .unwrap_or_default();
+        dictionary.fixes(name, notes, &self.breadboard.glossary)
+    }
+
+    // Put a fix's replacement in the selection's name or notes
+    pub fn apply_fix(&mut self, fix: &Fix) {
+        let text = match self.state.selection.clone() {
+            Some(Selection::Place(id)) => self.breadboard.find_place_mut(&id)
+                .and_then(|p| if fix.in_notes { p.notes.as_mut() } else { Some(&mut p.name) }),
+            Some(Selection::Affordance { .. }) => self.get_selected_affordance_mut()
+                .and_then(|a| if fix.in_notes { a.notes.as_mut() } else { Some(&mut a.name) }),
+            None => None,
+        };
+        if let Some(text) = text.filter(|text| text.get(fix.range.clone()) == Some(fix.word.as_str())) {
+            text.replace_range(fix.range.clone(), &fix.replacement);
+        }
+    }
+
+    // Cut or restore the selected affordance. Returns its name and whether
+    // it is now cut.
+    pub fn toggle_selected_cut(&mut self) -> Option<(String, bool)> {
+        let Some(Selection::Affordance { place_id, affordance_id }) = self.state.selection.clone() else {
+            return None;
+        };
+        let affordance = self.breadboard.find_place_mut(&place_id)?
+            .affordances.iter_mut()
+            .find(|a| a.id == affordance_id)?;
+        affordance.cut = !affordance.cut;
+        Some((affordance.name.clone(), affordance.cut))
+    }
+
+    // Flag the selected place or affordance as a rabbit hole, or unflag it.
+    // Returns its name and whether it is now flagged.
+    pub fn toggle_selected_rabbit_hole(&mut self) -> Option<(String, bool)> {
+        if let Some(affordance) = self.get_selected_affordance_mut() {
+            affordance.rabbit_hole = !affordance.rabbit_hole;
+            return Some((affordance.name.clone(), affordance.rabbit_hole));
+        }
+        let place = self.get_selected_place_mut()?;
+        place.rabbit_hole = !place.rabbit_hole;
+        Some((place.name.clone(), place.rabbit_hole))
+    }
+
+    // Give the selected place the next kind of icon. Returns its name and
+    // the new kind.
+    pub fn cycle_selected_place_kind(&mut self) -> Option<(String, Option<PlaceKind>)> {
+        let place = self.get_selected_place_mut()?;
+        place.kind = PlaceKind::cycle(place.kind);
+        Some((place.name.clone(), place.kind))
+    }
+
+    // Move the selected affordance on to its next status. Returns its name
+    // and the new status.
+    pub fn advance_selected_status(&mut self) -> Option<(String, Status)> {
+        let Some(Selection::Affordance { place_id, affordance_id }) = self.state.selection.clone() else {
+            return None;
+        };
+        let affordance = self.breadboard.find_place_mut(&place_id)?
+            .affordances.iter_mut()
+            .find(|a| a.id == affordance_id)?;
+        affordance.status = affordance.status.next();
+        Some((affordance.name.clone(), affordance.status))
+    }
+
+    // Copy of the app with cut affordances taken off the board, for exports
+    pub fn without_cut(&self) -> App {
+        let mut breadboard = self.breadboard.clone();
+        for place in &mut breadboard.places {
+            place.affordances.retain(|a| !a.cut);
+        }
+        App {
+            breadboard,
+            state: self.state.clone(),
+            config: self.config.clone(),
+            workspace: self.workspace.clone(),
+            lock: None,
+            dictionary: self.dictionary.clone(),
+            should_quit: false,
+        }
+    }
+
+    // Copy of the app narrowed to part of the board, for exports. The board
+    // itself is kept whole so connections leaving the part still show where
+    // they lead; `visible_places` and `visible_board` do the narrowing, on
+    // top of any filter.
+    pub fn scoped(&self, scope: ExportScope) -> anyhow::Result<App> {
+        let selected_place = || self.get_selected_place().map(|p| p.id).ok_or_else(|| anyhow::anyhow!("nothing is selected"));
+        let items = match scope {
+            ExportScope::Board => return Ok(self.clone()),
+            ExportScope::Flattened => {
+                let mut app = self.clone();
+                app.breadboard = self.breadboard.flattened();
+                return Ok(app);
+            }
+            ExportScope::Selection => {
+                selected_place()?;
+                vec![self.state.selection.clone().unwrap()]
+            }
+            ExportScope::Subtree => {
+                let reachable = self.breadboard.reachable_from(selected_place()?);
+                self.breadboard.places.iter()
+                    .filter(|p| reachable.contains(&p.id))
+                    .map(|p| Selection::Place(p.id))
+                    .collect()
+            }
+            ExportScope::Filter => {
+                if !self.is_filter_active() {
+                    anyhow::bail!("no filter is active");
+                }
+                self.visible_places().into_iter()
+                    .flat_map(|(place, affordances)| {
+                        if affordances.len() == place.affordances.len() {
+                            vec![Selection::Place(place.id)]
+                        } else {
+                            affordances.iter()
+                                .map(|a| Selection::Affordance { place_id: place.id, affordance_id: a.id })
+                                .collect()
+                        }
+                    })
+                    .collect()
+            }
+        };
+
+        let mut app = self.clone();
+        app.state.scope = Some(items);
+        Ok(app)
+    }
+
+    pub fn navigate_to_place(&mut self, place_id: u32) {
+        if let Some(current_place) = self.get_selected_place() {
+            self.state.navigation_trail.push(current_place.id);
+        }
+        self.state.selection = Some(Selection::Place(place_id));
+    }
+
+    pub fn navigate_back(&mut self) {
+        if let Some(previous_id) = self.state.navigation_trail.pop() {
+            self.state.selection = Some(Selection::Place(previous_id));
+        }
+    }
+
+    // Connect an affordance to a destination place. With `add_back` a "Back"
+    // affordance is also added on the destination pointing to the source,
+    // unless the destination already links back or this is a self-connection.
+    pub fn connect_affordance(&mut self, place_id: u32, affordance_id: u32, dest_id: u32, add_back: bool) {
+        let Some(place) = self.breadboard.find_place_mut(&place_id) else {
+            return;
+        };
+        let Some(affordance) = place.affordances.iter_mut().find(|a| a.id == affordance_id) else {
+            return;
+        };
+        affordance.connects_to = Some(dest_id);
+        affordance.link = None;
+
+        if !add_back || dest_id == place_id {
+            return;
+        }
+
+        let already_links_back = self.breadboard.find_place(&dest_id)
+            .map(|dest| dest.affordances.iter().any(|a| a.connects_to == Some(place_id)))
+            .unwrap_or(true);
+        if !already_links_back {
+            let back_id = self.breadboard.generate_affordance_id();
+            self.add_affordance_to_place(&dest_id, Affordance::new(back_id, "Back".to_string()).with_connection(place_id));
+        }
+    }
+
+    // Mark the selected place or affordance for a bulk connect, or unmark it
+    pub fn toggle_mark(&mut self) {
+        let Some(item) = self.state.selection.clone() else {
+            return;
+        };
+        match self.state.marked.iter().position(|marked| *marked == item) {
+            Some(index) => {
+                self.state.marked.remove(index);
+            }
+            None => self.state.marked.push(item),
+        }
+    }
+
+    // Connect every marked affordance to `dest`, and give every marked place
+    // a "Go to" affordance leading there unless it already has a way there.
+    // `None` removes the marked affordances' connections instead. Returns
+    // how many affordances were connected and how many places were given one.
+    pub fn connect_marked(&mut self, dest: Option<u32>) -> (usize, usize) {
+        let dest_name = dest.and_then(|id| self.breadboard.find_place(&id)).map(|p| p.name.clone());
+        let (mut connected, mut linked) = (0, 0);
+        // Affordances first, so a place whose marked affordance now leads
+        // there isn't given a second way
+        let mut marked = self.state.marked.clone();
+        marked.sort_by_key(|item| matches!(item, Selection::Place(_)));
+        for item in marked {
+            match item {
+                Selection::Affordance { place_id, affordance_id } => {
+                    let Some(affordance) = self.breadboard.find_place_mut(&place_id)
+                        .and_then(|p| p.affordances.iter_mut().find(|a| a.id == affordance_id)) else {
+                        continue;
+                    };
+                    affordance.connects_to = dest;
+                    affordance.link = None;
+                    connected += 1;
+                }
+                Selection::Place(place_id) => {
+                    let (Some(dest), Some(name)) = (dest, &dest_name) else {
+                        continue;
+                    };
+                    let leads_there = self.breadboard.find_place(&place_id)
+                        .map(|p| p.affordances.iter().any(|a| a.connects_to == Some(dest)))
+                        .unwrap_or(true);
+                    if place_id == dest || leads_there {
+                        continue;
+                    }
+                    let id = self.breadboard.generate_affordance_id();
+                    self.add_affordance_to_place(&place_id, Affordance::new(id, format!("Go to {}", name)).with_connection(dest));
+                    linked += 1;
+                }
+            }
+        }
+        (connected, linked)
+    }
+
+    // Connect an affordance to a place in another board of the workspace
+    pub fn link_affordance(&mut self, place_id: u32, affordance_id: u32, link: BoardLink) {
+        if let Some(affordance) = self.breadboard.find_place_mut(&place_id)
+            .and_then(|p| p.affordances.iter_mut().find(|a| a.id == affordance_id))
+        {
+            affordance.connects_to = None;
+            affordance.link = Some(link);
+        }
+    }
+
+    // "Board: Place" for a link's destination, or the raw reference when the
+    // workspace doesn't know it
+    pub fn link_label(&self, link: &BoardLink) -> String {
+        match self.state.external_places.iter().find(|p| p.board == link.board && p.place_id == link.place_id) {
+            Some(place) => format!("{}: {}", place.title, place.name),
+            None => format!("{} #{}", link.board, link.place_id),
+        }
+    }
+
+    // Reverse the connection of an affordance: add an affordance on the
+    // destination place that points back to the source. With `remove_original`
+    // the link is swapped instead, moving the affordance to the other side.
+    // Returns the ID of the newly created affordance.
+    pub fn reverse_connection(&mut self, place_id: u32, affordance_id: u32, remove_original: bool) -> Option<u32> {
+        let source = self.breadboard.find_place(&place_id)?;
+        let affordance = source.affordances.iter().find(|a| a.id == affordance_id)?;
+        let dest_id = affordance.connects_to?;
+        self.breadboard.find_place(&dest_id)?;
+
+        let name = if remove_original {
+            affordance.name.clone()
+        } else {
+            format!("Back to {}", source.name)
+        };
+
+        if remove_original {
+            if let Some(source) = self.breadboard.find_place_mut(&place_id) {
+                source.affordances.retain(|a| a.id != affordance_id);
+            }
+        }
+
+        let new_id = self.breadboard.generate_affordance_id();
+        self.add_affordance_to_place(&dest_id, Affordance::new(new_id, name).with_connection(place_id));
+        Some(new_id)
+    }
+
+    // Destination of the selected affordance, if it has a valid connection
+    pub fn get_preview_place(&self) -> Option<&Place> {
+        match &self.state.selection {
+            Some(Selection::Affordance { place_id, affordance_id }) => {
+                let dest_id = self.breadboard.find_place(place_id)?
+                    .affordances.iter()
+                    .find(|a| &a.id == affordance_id)?
+                    .connects_to?;
+                self.breadboard.find_place(&dest_id)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn toggle_preview(&mut self) {
+        self.state.show_preview = !self.state.show_preview;
+    }
+
+    pub fn toggle_minimap(&mut self) {
+        self.state.show_minimap = !self.state.show_minimap;
+    }
+
+    pub fn toggle_progress(&mut self) {
+        self.state.show_progress = !self.state.show_progress;
+    }
+
+    // Scroll the graph view by (columns, rows); the renderer clamps the
+    // offset to the diagram's size
+    pub fn pan_graph(&mut self, dx: i32, dy: i32) {
+        let (x, y) = self.state.graph_offset;
+        self.state.graph_offset = (x.saturating_add_signed(dx as isize), y.saturating_add_signed(dy as isize));
+    }
+
+    pub fn zoom_graph(&mut self, zoom_in: bool) {
+        self.state.graph_zoom = if zoom_in {
+            self.state.graph_zoom.zoom_in()
+        } else {
+            self.state.graph_zoom.zoom_out()
+        };
+    }
+
+    // Swimlanes are a way of drawing the graph, so turning them on also
+    // switches to the graph view
+    pub fn toggle_swimlanes(&mut self) {
+        self.state.swimlanes = !self.state.swimlanes;
+        if self.state.swimlanes {
+            self.state.view = ViewMode::Graph;
+        }
+    }
+
+    pub fn toggle_collapsed(&mut self) {
+        self.state.collapsed = !self.state.collapsed;
+    }
+
+    pub fn toggle_graph_view(&mut self) {
+        self.state.view = match self.state.view {
+            ViewMode::Graph => ViewMode::Outline,
+            _ => ViewMode::Graph,
+        };
+    }
+
+    // Connection search: places matching what's typed, best first. Ties,
+    // and everything before anything is typed, go to the places nearest
+    // the one being connected from.
+    pub fn update_connection_search(&mut self) {
+        let query = self.state.connection_search_buffer.clone();
+        // The place being connected from is usually a misclick: it goes last
+        let own = self.connecting_from();
+        let hide_own = self.config.own_place_in_connect == OwnPlace::Hide;
+        let mut ranked: Vec<(bool, i32, i32, usize, u32)> = self.breadboard.places.iter()
+            .enumerate()
+            .filter(|(_, p)| !(hide_own && own == Some(p.id)))
+            .filter_map(|(order, p)| {
+                let score = fuzzy_score(&query, &p.name)?;
+                Some((own == Some(p.id), score, self.connection_proximity(p.id), order, p.id))
+            })
+            .collect();
+        ranked.sort_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)).then(b.2.cmp(&a.2)).then(a.3.cmp(&b.3)));
+        self.state.connection_search_results = ranked.into_iter().map(|(.., id)| id).collect();
+
+        // Places in the workspace's other boards match on board title too
+        let mut external: Vec<(i32, usize)> = self.state.external_places.iter()
+            .enumerate()
+            .filter_map(|(index, p)| {
+                let score = fuzzy_score(&query, &p.name).max(fuzzy_score(&query, &p.title))?;
+                Some((score, index))
+            })
+            .collect();
+        external.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        self.state.connection_search_external = external.into_iter().map(|(_, index)| index).collect();
+
+        self.state.selected_connection_result = (self.connection_result_count() > 0).then_some(0);
+        self.state.self_connection_warned = false;
+    }
+
+    // The place whose affordance or outcome connection mode is wiring up;
+    // None when connecting marked items
+    pub fn connecting_from(&self) -> Option<u32> {
+        match self.state.selection {
+            Some(Selection::Affordance { place_id, .. }) if self.state.marked.is_empty() => Some(place_id),
+            _ => None,
+        }
+    }
+
+    // How close `place_id` is to the place being connected from: in the
+    // same group, recently visited, or already connected either way
+    fn connection_proximity(&self, place_id: u32) -> i32 {
+        let Some(from) = self.get_selected_place() else {
+            return 0;
+        };
+        let Some(place) = self.breadboard.find_place(&place_id) else {
+            return 0;
+        };
+        let mut proximity = 0;
+        if from.group.is_some() && from.group == place.group {
+            proximity += 2;
+        }
+        let trail = &self.state.navigation_trail;
+        if let Some(visited) = trail.iter().rposition(|id| *id == place_id) {
+            // The more recent the visit, the nearer
+            proximity += 3 - (trail.len() - 1 - visited).min(2) as i32;
+        }
+        let leads = |a: &Place, b: u32| a.affordances.iter().any(|aff| aff.destinations().contains(&b));
+        if leads(from, place_id) || leads(place, from.id) {
+            proximity += 2;
+        }
+        proximity
+    }
+
+    pub fn start_connection_search(&mut self) {
+        self.state.connection_search_buffer.clear();
+        self.state.connection_search_results.clear();
+        self.state.selected_connection_result = None;
+        self.update_connection_search();
+    }
+
+    // Connection search for changing where the selected affordance leads:
+    // its current destination is typed in and highlighted, so Enter keeps
+    // it and typing searches afresh. False if it isn't connected.
+    pub fn start_rewire(&mut self) -> bool {
+        let Some(affordance) = self.get_selected_affordance() else {
+            return false;
+        };
+        let (name, target) = if let Some(link) = &affordance.link {
+            let Some(index) = self.state.external_places.iter()
+                .position(|p| p.board == link.board && p.place_id == link.place_id) else {
+                return false;
+            };
+            (self.state.external_places[index].name.clone(), Err(index))
+        } else if let Some(place) = affordance.connects_to.and_then(|id| self.breadboard.find_place(&id)) {
+            (place.name.clone(), Ok(place.id))
+        } else {
+            return false;
+        };
+
+        self.state.connection_search_buffer = name;
+        self.update_connection_search();
+        let selected = match target {
+            Ok(place_id) => self.state.connection_search_results.iter().position(|id| *id == place_id),
+            Err(index) => self.state.connection_search_external.iter().position(|i| *i == index)
+                .map(|position| self.state.connection_search_results.len() + position),
+        };
+        self.state.selected_connection_result = selected.or(Some(0));
+        self.state.connection_search_prefilled = true;
+        true
+    }
+
+    pub fn clear_connection_search(&mut self) {
+        self.state.connection_search_prefilled = false;
+        self.state.connection_search_buffer.clear();
+        self.state.connection_search_results.clear();
+        self.state.connection_search_external.clear();
+        self.state.selected_connection_result = None;
+        self.state.self_connection_warned = false;
+    }
+
+    pub fn connection_result_count(&self) -> usize {
+        self.state.connection_search_results.len() + self.state.connection_search_external.len()
+    }
+
+    // Move the selection of the list the mode shows a step down (or up),
+    // stopping at either end. False in modes without such a list.
+    pub fn step_list(&mut self, down: bool) -> bool {
+        if self.state.mode == Mode::Cycles {
+            // Selecting a cycle also selects its first affordance
+            let selected = self.state.selected_cycle;
+            if let Some(index) = step(selected, down, self.state.cycles.len()).filter(|index| Some(*index) != selected) {
+                self.select_cycle(index);
+            }
+            return true;
+        }
+        let (len, selected) = match self.state.mode {
+            Mode::Connect => {
+                self.state.self_connection_warned = false;
+                (self.connection_result_count(), &mut self.state.selected_connection_result)
+            }
+            Mode::OpenFile => (self.visible_files().len(), &mut self.state.selected_file_index),
+            Mode::Incoming => (self.state.incoming_results.len(), &mut self.state.selected_incoming_result),
+            Mode::Branches => (self.get_selected_affordance().map_or(0, |a| a.branches.len()), &mut self.state.selected_branch),
+            Mode::Commands => (self.config.commands.len(), &mut self.state.selected_command),
+            Mode::Snippets => (self.config.snippets.len(), &mut self.state.selected_snippet),
+            Mode::Spelling => (self.state.spelling_fixes.len(), &mut self.state.selected_fix),
+            Mode::Trash => (self.state.trash.len(), &mut self.state.selected_trash),
+            Mode::Views => (self.breadboard.views.len(), &mut self.state.selected_view),
+            Mode::Start => (self.state.start_items.len(), &mut self.state.selected_start_index),
+            Mode::Library => (self.state.library.len(), &mut self.state.selected_library_index),
+            _ => return false,
+        };
+        *selected = step(*selected, down, len);
+        true
+    }
+
+    pub fn get_selected_external_place(&self) -> Option<&ExternalPlace> {
+        let index = self.state.selected_connection_result?.checked_sub(self.state.connection_search_results.len())?;
+        self.state.external_places.get(*self.state.connection_search_external.get(index)?)
+    }
+
+    pub fn get_selected_connection_place(&self) -> Option<&Place> {
+        let place_id = self.state.connection_search_results.get(self.state.selected_connection_result?)?;
+        self.breadboard.find_place(place_id)
+    }
+
+    // File opening methods
+    pub fn start_file_opening(&mut self) -> anyhow::Result<()> {
+        // Paths into the boards directory, or bare names in the current one
+        let recursive = self.config.search_subdirectories;
+        let scan = match self.config.boards_dir() {
+            Some(dir) => Scan::start(dir, true, recursive),
+            None => Scan::start(std::env::current_dir()?, false, recursive),
+        };
+        self.state.file_scan = Some(scan);
+        self.state.file_list.clear();
+        self.state.file_filter.clear();
+        self.state.selected_file_index = None;
+        Ok(())
+    }
+
+    // Add the files the scan found since the last frame, keeping the
+    // highlighted one
+    pub fn poll_file_scan(&mut self) {
+        let Some(scan) = &self.state.file_scan else {
+            return;
+        };
+        let found = scan.take();
+        if found.done {
+            self.state.file_scan = None;
+        }
+        if let Some(error) = found.error {
+            self.state.status_message = Some(locale::fill(self.config.language.strings().list_files_failed, &[&error.to_string()]));
+        }
+        if !found.files.is_empty() {
+            self.state.file_list.extend(found.files);
+            self.sort_files();
+        }
+    }
+
+    // Report the hooks and commands that finished since the last frame:
+    // quiet successes in the status line, anything with output or a failure
+    // in the output panel
+    pub fn poll_commands(&mut self) {
+        let s = self.config.language.strings();
+        let mut still_running = Vec::new();
+        for running in std::mem::take(&mut self.state.running_commands) {
+            match running.finished() {
+                None => still_running.push(running),
+                Some(Ok(output)) if output.success && output.text.is_empty() => {
+                    self.state.status_message = Some(locale::fill(s.command_finished, &[&running.title]));
+                }
+                Some(Ok(output)) => {
+                    let title = if output.success { running.title } else { locale::fill(s.command_failed, &[&running.title]) };
+                    self.state.output_panel = Some((title, output.text));
+                }
+                Some(Err(e)) => self.state.status_message = Some(e),
+            }
+        }
+        self.state.running_commands = still_running;
+    }
+
+    pub fn is_scanning_files(&self) -> bool {
+        self.state.file_scan.is_some()
+    }
+
+    // Put the files in the chosen order, keeping the highlighted file, or
+    // highlighting the first if there wasn't one
+    fn sort_files(&mut self) {
+        let selected = self.get_selected_file().cloned();
+        match self.state.file_sort {
+            FileSort::Name => self.state.file_list.sort_by(|a, b| a.path.cmp(&b.path)),
+            FileSort::Modified => self.state.file_list.sort_by_key(|file| std::cmp::Reverse(file.modified)),
+        }
+        let visible = self.visible_files();
+        self.state.selected_file_index = visible.iter()
+            .position(|file| Some(&file.path) == selected.as_ref())
+            .or((!visible.is_empty()).then_some(0));
+    }
+
+    // Switch the open dialog between name and modification order
+    pub fn toggle_file_sort(&mut self) {
+        self.state.file_sort = match self.state.file_sort {
+            FileSort::Name => FileSort::Modified,
+            FileSort::Modified => FileSort::Name,
+        };
+        self.sort_files();
+    }
+
+    // How the open dialog shows a file: its path inside the boards
+    // directory, or as listed from the current one
+    pub fn file_label(&self, file: &FileEntry) -> String {
+        let path = std::path::Path::new(&file.path);
+        self.config.boards_dir()
+            .and_then(|dir| path.strip_prefix(dir).ok())
+            .unwrap_or(path)
+            .to_string_lossy()
+            .to_string()
+    }
+
+    // The files whose labels have the typed filter in them
+    pub fn visible_files(&self) -> Vec<&FileEntry> {
+        let filter = self.state.file_filter.to_lowercase();
+        self.state.file_list.iter()
+            .filter(|file| self.file_label(file).to_lowercase().contains(&filter))
+            .collect()
+    }
+
+    // Narrow the file list to paths with `filter` in them, highlighting the first
+    pub fn set_file_filter(&mut self, filter: String) {
+        self.state.file_filter = filter;
+        self.state.selected_file_index = if self.visible_files().is_empty() { None } else { Some(0) };
+    }
+
+    pub fn get_selected_file(&self) -> Option<&String> {
+        self.visible_files().get(self.state.selected_file_index?).map(|file| &file.path)
+    }
+
+    pub fn clear_file_selection(&mut self) {
+        self.state.file_scan = None;
+        self.state.file_list.clear();
+        self.state.file_filter.clear();
+        self.state.selected_file_index = None;
+    }
+
+    // Where the highlight is in the list the mode shows, and how long the
+    // list is
+    pub fn list_position(&self) -> Option<(usize, usize)> {
+        let state = &self.state;
+        match state.mode {
+            Mode::Navigate if state.is_searching_places => Some((state.selected_place_result?, state.place_search_results.len())),
+            Mode::Connect => Some((state.selected_connection_result?, self.connection_result_count())),
+            Mode::OpenFile => Some((state.selected_file_index?, self.visible_files().len())),
+            Mode::Incoming => Some((state.selected_incoming_result?, state.incoming_results.len())),
+            Mode::Branches => Some((state.selected_branch?, self.get_selected_affordance().map_or(0, |a| a.branches.len()))),
+            Mode::Cycles => Some((state.selected_cycle?, state.cycles.len())),
+            Mode::Commands => Some((state.selected_command?, self.config.commands.len())),
+            Mode::Snippets => Some((state.selected_snippet?, self.config.snippets.len())),
+            Mode::Spelling => Some((state.selected_fix?, state.spelling_fixes.len())),
+            Mode::Trash => Some((state.selected_trash?, state.trash.len())),
+            Mode::Views => Some((state.selected_view?, self.breadboard.views.len())),
+            Mode::Start => Some((state.selected_start_index?, state.start_items.len())),
+            Mode::Library => Some((state.selected_library_index?, state.library.len())),
+            _ => None,
+        }
+    }
+
+    // Highlight entry `index` of the list the mode shows
+    pub fn select_in_list(&mut self, index: usize) {
+        match self.state.mode {
+            Mode::Navigate => self.state.selected_place_result = Some(index),
+            Mode::Connect => {
+                self.state.selected_connection_result = Some(index);
+                self.state.self_connection_warned = false;
+            }
+            Mode::OpenFile => self.state.selected_file_index = Some(index),
+            Mode::Incoming => self.state.selected_incoming_result = Some(index),
+            Mode::Branches => self.state.selected_branch = Some(index),
+            Mode::Cycles => self.select_cycle(index),
+            Mode::Commands => self.state.selected_command = Some(index),
+            Mode::Snippets => self.state.selected_snippet = Some(index),
+            Mode::Spelling => self.state.selected_fix = Some(index),
+            Mode::Trash => self.state.selected_trash = Some(index),
+            Mode::Views => self.state.selected_view = Some(index),
+            Mode::Start => self.state.selected_start_index = Some(index),
+            Mode::Library => self.state.selected_library_index = Some(index),
+            _ => {}
+        }
+    }
+
+    // Move the highlight a page up or down the list
+    pub fn page_list(&mut self, down: bool) {
+        if let Some((at, len)) = self.list_position().filter(|(_, len)| *len > 0) {
+            let index = if down { (at + LIST_PAGE).min(len - 1) } else { at.saturating_sub(LIST_PAGE) };
+            self.select_in_list(index);
+        }
+    }
+
+    // Highlight the first or last entry of the list
+    pub fn list_end(&mut self, last: bool) {
+        if let Some((_, len)) = self.list_position().filter(|(_, len)| *len > 0) {
+            self.select_in_list(if last { len - 1 } else { 0 });
+        }
+    }
+
+    // Fill the library from the boards directory, selecting `preferred` if
+    // it's there. Returns false when there's nothing to show.
+    pub fn start_library(&mut self, file_manager: &crate::file::FileManager, preferred: Option<&str>) -> bool {
+        let Some(dir) = self.config.boards_dir() else {
+            return false;
+        };
+        self.state.library = file_manager.library(&dir).unwrap_or_default();
+        let preferred = preferred.and_then(|file| std::fs::canonicalize(file).ok());
+        self.state.selected_library_index = self.state.library.iter()
+            .position(|board| preferred.is_some() && std::fs::canonicalize(&board.path).ok() == preferred)
+            .or((!self.state.library.is_empty()).then_some(0));
+        !self.state.library.is_empty()
+    }
+
+    // Fill the library with the workspace's boards, selecting the open one
+    pub fn start_workspace_switcher(&mut self, file_manager: &crate::file::FileManager) -> bool {
+        let Some(workspace) = &self.workspace else {
+            return false;
+        };
+        let current = self.state.current_filename.as_deref().and_then(|path| std::fs::canonicalize(path).ok());
+
+        self.state.library = workspace.boards.iter()
+            .filter_map(|board| {
+                let summary = file_manager.summarize(&workspace.path_of(board))?;
+                Some(BoardSummary { name: board.title.clone(), ..summary })
+            })
+            .collect();
+        self.state.selected_library_index = self.state.library.iter()
+            .position(|board| current.is_some() && std::fs::canonicalize(&board.path).ok() == current)
+            .or((!self.state.library.is_empty()).then_some(0));
+        !self.state.library.is_empty()
+    }
+
+    // Fill the start screen, offering `recent` if there's a board to reopen
+    pub fn start_screen(&mut self, recent: Option<String>) {
+        self.state.start_items = [StartItem::NewBoard, StartItem::Tutorial].into_iter()
+            .chain(recent.map(StartItem::Recent))
+            .chain(std::iter::once(StartItem::Open))
+            .chain((0..examples::EXAMPLES.len()).map(StartItem::Example))
+            .collect();
+        self.state.selected_start_index = Some(0);
+    }
+
+    pub fn get_selected_start_item(&self) -> Option<&StartItem> {
+        self.state.selected_start_index.and_then(|i| self.state.start_items.get(i))
+    }
+
+    // Replace the board with a bundled example. It isn't tied to a file, so
+    // saving asks where to put the copy.
+    pub fn open_example(&mut self, index: usize) -> anyhow::Result<()> {
+        self.breadboard = examples::load(index)?;
+        self.state.drill_stack.clear();
+        self.state.snapshots.clear();
+        self.state.trash.clear();
+        self.state.current_filename = None;
+        self.state.saved_board = None;
+        self.state.marked.clear();
+        self.state.navigation_trail.clear();
+        self.state.selection = self.breadboard.places.first().map(|p| Selection::Place(p.id));
+        Ok(())
+    }
+
+    // Begin the tutorial on a blank scratch board
+    pub fn start_tutorial(&mut self) {
+        self.breadboard = Breadboard::new("Tutorial".to_string());
+        self.state.drill_stack.clear();
+        self.state.snapshots.clear();
+        self.state.trash.clear();
+        self.state.current_filename = None;
+        self.state.saved_board = None;
+        self.state.marked.clear();
+        self.state.navigation_trail.clear();
+        self.state.selection = None;
+        self.state.tutorial = Some(0);
+    }
+
+    pub fn tutorial_step(&self) -> Option<(usize, tutorial::Step)> {
+        let index = self.state.tutorial?;
+        tutorial::STEPS.get(index).map(|step| (index, *step))
+    }
+
+    // Move past every step the board shows is done
+    pub fn advance_tutorial(&mut self) {
+        while let Some((index, step)) = self.tutorial_step() {
+            if !step.is_done(self) {
+                break;
+            }
+            self.state.tutorial = Some(index + 1);
+        }
+    }
+
+    pub fn get_selected_library_board(&self) -> Option<&BoardSummary> {
+        self.state.selected_library_index.and_then(|i| self.state.library.get(i))
+    }
+
+    pub fn clear_library(&mut self) {
+        self.state.library.clear();
+        self.state.selected_library_index = None;
+    }
+
+    // Calculate the index of the currently selected item in the rendered list
+    // This is used for scrolling to keep the selected item visible
+    pub fn get_selected_item_index(&self) -> Option<usize> {
+        let mut index = 0;
+
+        for (place, affordances) in self.visible_places() {
+            // Count place header
+            if self.state.selection == Some(Selection::Place(place.id)) {
+                return Some(index);
+            }
+            index += 1;
+
+            // Count affordances
+            for affordance in affordances {
+                if self.state.selection == Some(Selection::Affordance {
+                    place_id: place.id,
+                    affordance_id: affordance.id
+                }) {
+                    return Some(index);
+                }
+                index += 1;
+            }
+
+            // Count spacing
+            index += 1; // spacing between places
+        }
+
+        None
+    }
+
+    // Board filter methods (live filter-as-you-type)
+    pub fn start_filter(&mut self) {
+        if self.state.filter.is_none() {
+            self.state.filter = Some(String::new());
+        }
+    }
+
+    // Keep the view mode, collapsing, filter and zoom under `name` in the
+    // board, replacing a view of the same name
+    pub fn save_view(&mut self, name: &str) -> anyhow::Result<String> {
+        let s = self.config.language.strings();
+        let name = name.trim();
+        if name.is_empty() {
+            anyhow::bail!(s.view_needs_name);
+        }
+        let view = NamedView {
+            name: name.to_string(),
+            view: self.state.view,
+            collapsed: self.state.collapsed,
+            filter: self.state.filter.clone().filter(|f| !f.is_empty()),
+            zoom: self.state.graph_zoom,
+            swimlanes: self.state.swimlanes,
+        };
+        let views = &mut self.breadboard.views;
+        let message = match views.iter().position(|v| v.name.eq_ignore_ascii_case(name)) {
+            Some(index) => {
+                views[index] = view;
+                self.state.selected_view = Some(index);
+                locale::fill(s.view_updated, &[name])
+            }
+            None => {
+                views.push(view);
+                self.state.selected_view = Some(views.len() - 1);
+                locale::fill(s.view_saved, &[name])
+            }
+        };
+        Ok(message)
+    }
+
+    // Look at the board the way the highlighted view does, returning its name
+    pub fn apply_selected_view(&mut self) -> Option<String> {
+        let view = self.breadboard.views.get(self.state.selected_view?)?.clone();
+        self.state.view = view.view;
+        self.state.collapsed = view.collapsed;
+        self.state.filter = view.filter;
+        self.state.graph_zoom = view.zoom;
+        self.state.swimlanes = view.swimlanes;
+        self.ensure_selection_visible();
+        Some(view.name)
+    }
+
+    pub fn remove_selected_view(&mut self) -> Option<String> {
+        let index = self.state.selected_view.filter(|i| *i < self.breadboard.views.len())?;
+        let view = self.breadboard.views.remove(index);
+        let count = self.breadboard.views.len();
+        self.state.selected_view = (count > 0).then(|| index.min(count - 1));
+        Some(view.name)
+    }
+
+    pub fn is_filter_active(&self) -> bool {
+        self.state.filter.as_deref().is_some_and(|q| !q.is_empty())
+    }
+
+    pub fn clear_filter(&mut self) {
+        self.state.filter = None;
+    }
+
+    // Places and affordances that survive the current filter, in board order.
+    // A place whose name matches keeps all of its affordances; otherwise only
+    // its matching affordances are kept and the place is shown as their parent.
+    pub fn visible_places(&self) -> Vec<(&Place, Vec<&Affordance>)> {
+        let mut visible = self.filtered_places();
+        if let Some(scope) = &self.state.scope {
+            visible.retain_mut(|(place, affordances)| {
+                if !scope.contains(&Selection::Place(place.id)) {
+                    affordances.retain(|a| scope.contains(&Selection::Affordance { place_id: place.id, affordance_id: a.id }));
+                    if affordances.is_empty() {
+                        return false;
+                    }
+                }
+                true
+            });
+        }
+        for (place, affordances) in &mut visible {
+            if self.state.folded.contains(&place.id) {
+                affordances.clear();
+            }
+        }
+        visible
+    }
+
+    // Hide the place's affordances in the outline, or show them again.
+    // Whether they're hidden now.
+    pub fn toggle_fold(&mut self, place_id: u32) -> bool {
+        if let Some(index) = self.state.folded.iter().position(|id| *id == place_id) {
+            self.state.folded.remove(index);
+            false
+        } else {
+            self.state.folded.push(place_id);
+            true
+        }
+    }
+
+    // Where the place's first connected affordance leads
+    pub fn first_destination(&self, place_id: &u32) -> Option<u32> {
+        self.breadboard.find_place(place_id)?.affordances.iter().find_map(|a| a.connects_to)
+    }
+
+    fn filtered_places(&self) -> Vec<(&Place, Vec<&Affordance>)> {
+        let query = match self.state.filter.as_deref() {
+            Some(q) if !q.is_empty() => FilterQuery::parse(q, chrono::Local::now().date_naive()),
+            _ => {
+                return self.breadboard.places.iter()
+                    .map(|p| (p, p.affordances.iter().collect()))
+                    .collect();
+            }
+        };
+
+        self.breadboard.places.iter()
+            .filter_map(|place| {
+                if query.matches(&place.name, &place.attribution) {
+                    return Some((place, place.affordances.iter().collect()));
+                }
+                let matching: Vec<_> = place.affordances.iter()
+                    .filter(|a| query.matches(&a.name, &a.attribution))
+                    .collect();
+                if matching.is_empty() {
+                    None
+                } else {
+                    Some((place, matching))
+                }
+            })
+            .collect()
+    }
+
+    // Copy of the board reduced to what the filter leaves visible
+    pub fn visible_board(&self) -> Breadboard {
+        let mut board = self.breadboard.clone();
+        board.places = self.visible_places().into_iter()
+            .map(|(place, affordances)| Place {
+                affordances: affordances.into_iter().cloned().collect(),
+                ..place.clone()
+            })
+            .collect();
+        board
+    }
+
+    pub fn visible_place_ids(&self) -> Vec<u32> {
+        self.visible_places().iter().map(|(p, _)| p.id).collect()
+    }
+
+    // Select the first or last place shown
+    pub fn select_end_place(&mut self, last: bool) {
+        let places = self.visible_place_ids();
+        let place = if last { places.last() } else { places.first() };
+        if let Some(id) = place {
+            self.state.selection = Some(Selection::Place(*id));
+        }
+    }
+
+    // Select the first or last affordance of the selected place
+    pub fn select_end_affordance(&mut self, last: bool) {
+        let Some(place_id) = self.get_selected_place().map(|p| p.id) else {
+            return;
+        };
+        let affordances = self.visible_affordance_ids(&place_id);
+        let affordance = if last { affordances.last() } else { affordances.first() };
+        if let Some(affordance_id) = affordance {
+            self.state.selection = Some(Selection::Affordance { place_id, affordance_id: *affordance_id });
+        }
+    }
+
+    // Down past a place's last affordance: on to the next place's header
+    pub fn select_next_place(&mut self, place_id: &u32) {
+        let places = self.visible_place_ids();
+        if let Some(next) = places.iter().position(|id| id == place_id).and_then(|index| places.get(index + 1)) {
+            self.state.selection = Some(Selection::Place(*next));
+        }
+    }
+
+    // Up from a place's header: on to the last affordance of the place
+    // before it, or its header when it shows none
+    pub fn select_end_of_previous_place(&mut self, place_id: &u32) {
+        let places = self.visible_place_ids();
+        let Some(index) = places.iter().position(|id| id == place_id).filter(|index| *index > 0) else {
+            return;
+        };
+        let previous = places[index - 1];
+        let last = if self.state.collapsed { None } else { self.visible_affordance_ids(&previous).last().copied() };
+        self.state.selection = Some(match last {
+            Some(affordance_id) => Selection::Affordance { place_id: previous, affordance_id },
+            None => Selection::Place(previous),
+        });
+    }
+
+    pub fn visible_affordance_ids(&self, place_id: &u32) -> Vec<u32> {
+        self.visible_places().iter()
+            .find(|(p, _)| &p.id == place_id)
+            .map(|(_, affordances)| affordances.iter().map(|a| a.id).collect())
+            .unwrap_or_default()
+    }
+
+    // Move the selection onto the first visible place if the filter hid it
+    pub fn ensure_selection_visible(&mut self) {
+        let visible = self.visible_places();
+        let is_visible = match &self.state.selection {
+            Some(Selection::Place(id)) => visible.iter().any(|(p, _)| &p.id == id),
+            Some(Selection::Affordance { place_id, affordance_id }) => visible.iter()
+                .any(|(p, affs)| &p.id == place_id && affs.iter().any(|a| &a.id == affordance_id)),
+            None => false,
+        };
+
+        if !is_visible {
+            self.state.selection = visible.first().map(|(p, _)| Selection::Place(p.id));
+        }
+    }
+
+    // Incoming connection methods (jump to places that link here)
+    pub fn start_incoming_list(&mut self) -> bool {
+        let place_id = match self.get_selected_place() {
+            Some(place) => place.id,
+            None => return false,
+        };
+
+        self.state.incoming_results = self.breadboard.get_incoming_connections(&place_id)
+            .into_iter()
+            .map(|(place, affordance)| (place.id, affordance.id))
+            .collect();
+        self.state.incoming_steps = None;
+        self.state.selected_incoming_result = if self.state.incoming_results.is_empty() {
+            None
+        } else {
+            Some(0)
+        };
+        !self.state.incoming_results.is_empty()
+    }
+
+    // Switch the incoming list between the places linking straight here and
+    // every place from which here can be reached, each listed with the
+    // affordance that sets off towards it
+    pub fn toggle_incoming_reach(&mut self) {
+        let Some(place_id) = self.get_selected_place().map(|p| p.id) else {
+            return;
+        };
+        if self.state.incoming_steps.is_some() {
+            self.start_incoming_list();
+            return;
+        }
+        let sources = analysis::reaching(&self.breadboard, place_id);
+        self.state.incoming_results = sources.iter().map(|s| (s.place_id, s.affordance_id)).collect();
+        self.state.incoming_steps = Some(sources.iter().map(|s| s.steps).collect());
+        self.state.selected_incoming_result = if sources.is_empty() { None } else { Some(0) };
+    }
+
+    pub fn clear_incoming_list(&mut self) {
+        self.state.incoming_steps = None;
+        self.state.incoming_results.clear();
+        self.state.selected_incoming_result = None;
+    }
+
+    // Jump to the affordance that links into the current place, leaving the
+    // current place on the navigation trail so Backspace returns here
+    pub fn jump_to_selected_incoming(&mut self) {
+        let selected = self.state.selected_incoming_result
+            .and_then(|index| self.state.incoming_results.get(index))
+            .copied();
+
+        if let Some((place_id, affordance_id)) = selected {
+            self.navigate_to_place(place_id);
+            self.state.selection = Some(Selection::Affordance { place_id, affordance_id });
+        }
+        self.clear_incoming_list();
+    }
+
+    // Cycle methods (browse the loops formed by connections)
+    pub fn start_cycle_list(&mut self) -> bool {
+        self.state.cycles = analysis::cycles(&self.breadboard);
+        if self.state.cycles.is_empty() {
+            return false;
+        }
+        self.state.cycle_origin = self.state.selection.clone();
+        self.select_cycle(0);
+        true
+    }
+
+    // Highlight a cycle and move the selection onto its first step so the
+    // outline scrolls to it
+    pub fn select_cycle(&mut self, index: usize) {
+        if let Some(cycle) = self.state.cycles.get(index) {
+            let (place_id, affordance_id) = cycle.steps[0];
+            self.state.selection = Some(Selection::Affordance { place_id, affordance_id });
+            self.state.selected_cycle = Some(index);
+        }
+    }
+
+    pub fn get_selected_cycle(&self) -> Option<&Cycle> {
+        self.state.selected_cycle.and_then(|index| self.state.cycles.get(index))
+    }
+
+    // Whether a place or affordance is part of the highlighted cycle
+    pub fn in_selected_cycle(&self, item: &Selection) -> bool {
+        let Some(cycle) = self.get_selected_cycle() else {
+            return false;
+        };
+        match item {
+            Selection::Place(place_id) => cycle.places().any(|id| id == *place_id),
+            Selection::Affordance { place_id, affordance_id } => cycle.steps.contains(&(*place_id, *affordance_id)),
+        }
+    }
+
+    // Add the selected place to the end of the happy path, or take it off.
+    // Returns whether it's now on the path.
+    pub fn toggle_happy_path(&mut self) -> Option<bool> {
+        let place_id = self.get_selected_place()?.id;
+        let path = &mut self.breadboard.happy_path;
+        match path.iter().position(|id| *id == place_id) {
+            Some(index) => {
+                path.remove(index);
+                Some(false)
+            }
+            None => {
+                path.push(place_id);
+                Some(true)
+            }
+        }
+    }
+
+    // Whether a place is on the happy path, or an affordance takes it from
+    // one step to the next
+    pub fn on_happy_path(&self, item: &Selection) -> bool {
+        let path = &self.breadboard.happy_path;
+        match item {
+            Selection::Place(place_id) => path.contains(place_id),
+            Selection::Affordance { place_id, affordance_id } => {
+                let Some(next) = path.iter().position(|id| id == place_id).and_then(|index| path.get(index + 1)) else {
+                    return false;
+                };
+                self.breadboard.find_place(place_id)
+                    .and_then(|p| p.affordances.iter().find(|a| a.id == *affordance_id))
+                    .is_some_and(|a| a.destinations().contains(next))
+            }
+        }
+    }
+
+    // Leave the cycles view, either staying on the cycle (with the previous
+    // place on the navigation trail) or putting back the earlier selection
+    pub fn clear_cycle_list(&mut self, keep_selection: bool) {
+        let origin = self.state.cycle_origin.take();
+        if !keep_selection {
+            self.state.selection = origin;
+        } else if let Some(Selection::Place(place_id) | Selection::Affordance { place_id, .. }) = origin {
+            self.state.navigation_trail.push(place_id);
+        }
+        self.state.cycles.clear();
+        self.state.selected_cycle = None;
+    }
+
+    // Outcome methods (labeled branches of the selected affordance)
+    pub fn get_selected_affordance(&self) -> Option<&Affordance> {
+        let Some(Selection::Affordance { place_id, affordance_id }) = &self.state.selection else {
+            return None;
+        };
+        self.breadboard.find_place(place_id)?.affordances.iter().find(|a| &a.id == affordance_id)
+    }
+
+    fn get_selected_affordance_mut(&mut self) -> Option<&mut Affordance> {
+        let Some(Selection::Affordance { place_id, affordance_id }) = self.state.selection.clone() else {
+            return None;
+        };
+        self.breadboard.find_place_mut(&place_id)?.affordances.iter_mut().find(|a| a.id == affordance_id)
+    }
+
+    pub fn start_branch_list(&mut self) -> bool {
+        let Some(affordance) = self.get_selected_affordance() else {
+            return false;
+        };
+        self.state.selected_branch = if affordance.branches.is_empty() { None } else { Some(0) };
+        true
+    }
+
+    pub fn get_selected_branch(&self) -> Option<&Branch> {
+        self.state.selected_branch.and_then(|index| self.get_selected_affordance()?.branches.get(index))
+    }
+
+    // Add an outcome with a default label and highlight it. Returns the label.
+    pub fn add_branch(&mut self) -> Option<String> {
+        let affordance = self.get_selected_affordance_mut()?;
+        let label = format!("Outcome {}", affordance.branches.len() + 1);
+        affordance.branches.push(Branch { label: label.clone(), connects_to: None });
+        self.state.selected_branch = Some(affordance.branches.len() - 1);
+        Some(label)
+    }
+
+    pub fn rename_selected_branch(&mut self, label: String) {
+        let index = self.state.selected_branch;
+        if let Some(branch) = index.and_then(|i| self.get_selected_affordance_mut()?.branches.get_mut(i)) {
+            branch.label = label;
+        }
+    }
+
+    pub fn connect_selected_branch(&mut self, dest_id: Option<u32>) {
+        let index = self.state.selected_branch;
+        if let Some(branch) = index.and_then(|i| self.get_selected_affordance_mut()?.branches.get_mut(i)) {
+            branch.connects_to = dest_id;
+        }
+    }
+
+    pub fn remove_selected_branch(&mut self) {
+        let Some(index) = self.state.selected_branch else {
+            return;
+        };
+        let Some(affordance) = self.get_selected_affordance_mut() else {
+            return;
+        };
+        if index < affordance.branches.len() {
+            affordance.branches.remove(index);
+        }
+        let remaining = affordance.branches.len();
+        self.state.selected_branch = if remaining == 0 { None } else { Some(index.min(remaining - 1)) };
+    }
+
+    pub fn clear_branch_list(&mut self) {
+        self.state.selected_branch = None;
+        self.state.editing_branch = false;
+    }
 
-        for place in &self.breadboard.places {
-            // Count place header
-            if self.state.selection == Some(Selection::Place(place.id)) {
-                return Some(index);
+    // Take `typed` as a digit of a count, when it is one: a count can't
+    // start with 0. Typing on after a count searches for what was typed,
+    // digits and all.
+    pub fn type_count(&mut self, typed: &str) -> bool {
+        let digit = typed.len() == 1 && typed.chars().all(|c| c.is_ascii_digit());
+        match self.state.count.take() {
+            Some(mut count) if digit => {
+                count.push_str(typed);
+                self.state.count = Some(count);
+                true
             }
-            index += 1;
-
-            // Count affordances
-            for affordance in &place.affordances {
-                if self.state.selection == Some(Selection::Affordance {
-                    place_id: place.id,
-                    affordance_id: affordance.id
-                }) {
-                    return Some(index);
-                }
-                index += 1;
+            None if digit && typed != "0" => {
+                self.state.count = Some(typed.to_string());
+                true
             }
-
-            // Count spacing
-            index += 1; // spacing between places
+            Some(count) => {
+                self.start_place_search();
+                self.state.place_search_buffer = format!("{}{}", count, typed);
+                self.update_place_search();
+                true
+            }
+            None => false,
         }
+    }
 
-        None
+    // The count typed so far as a number of repeats, at most MAX_COUNT, and
+    // forget it
+    pub fn take_count(&mut self) -> Option<usize> {
+        self.state.count.take().map(|digits| digits.parse().map_or(MAX_COUNT, |count: usize| count.min(MAX_COUNT)))
     }
 
     // Place search methods (for quick navigation)
@@ -250,24 +2279,22 @@ impl App {
         self.state.place_search_results.clear();
         self.state.selected_place_result = None;
         self.state.is_searching_places = true;
+        self.state.search_history_index = None;
         self.update_place_search();
     }
 
-    pub fn update_place_search(&mut self) {
-        let mut results = Vec::new();
-
-        if self.state.place_search_buffer.is_empty() {
-            // Show all places
-            results.extend(self.breadboard.places.iter().map(|p| p.id));
-        } else {
-            let search_lower = self.state.place_search_buffer.to_lowercase();
-            // Add matching places
-            results.extend(self.breadboard.places.iter()
-                .filter(|p| p.name.to_lowercase().contains(&search_lower))
-                .map(|p| p.id));
-        }
+    // Places whose names contain `query`, in board order; all of them for
+    // an empty one
+    fn places_matching(&self, query: &str) -> Vec<u32> {
+        let query = query.to_lowercase();
+        self.breadboard.places.iter()
+            .filter(|p| p.name.to_lowercase().contains(&query))
+            .map(|p| p.id)
+            .collect()
+    }
 
-        self.state.place_search_results = results;
+    pub fn update_place_search(&mut self) {
+        self.state.place_search_results = self.places_matching(&self.state.place_search_buffer);
 
         // Auto-select first result
         self.state.selected_place_result = if self.state.place_search_results.is_empty() {
@@ -277,6 +2304,105 @@ impl App {
         };
     }
 
+    // Keep the search's query for recalling and repeating. A query used
+    // again moves to the newest.
+    pub fn remember_search(&mut self) {
+        let query = self.state.place_search_buffer.trim().to_string();
+        if query.is_empty() {
+            return;
+        }
+        self.state.search_history.retain(|q| *q != query);
+        if self.state.search_history.len() >= MAX_SEARCH_HISTORY {
+            self.state.search_history.remove(0);
+        }
+        self.state.search_history.push(query);
+        self.state.search_history_index = None;
+    }
+
+    // Replace the search with the query before the one recalled (`older`),
+    // or after it. Past the newest, the search is empty again. False when
+    // there's nothing further to step to.
+    pub fn recall_search(&mut self, older: bool) -> bool {
+        let count = self.state.search_history.len();
+        let index = match (self.state.search_history_index, older) {
+            (None, true) if count > 0 => Some(count - 1),
+            (Some(index), true) if index > 0 => Some(index - 1),
+            (Some(index), false) if index + 1 < count => Some(index + 1),
+            (Some(_), false) => None,
+            _ => return false,
+        };
+        self.state.search_history_index = index;
+        self.state.place_search_buffer = index.map(|i| self.state.search_history[i].clone()).unwrap_or_default();
+        self.update_place_search();
+        true
+    }
+
+    // Select the next place matching the last search after the selected
+    // one, or the one before it, going round the board. Returns its name
+    // and where it is among the matches.
+    pub fn repeat_search(&mut self, forward: bool) -> Option<(String, usize, usize)> {
+        let query = self.state.search_history.last()?;
+        let matches = self.places_matching(query);
+        let current = self.get_selected_place().and_then(|p| matches.iter().position(|id| *id == p.id));
+        let index = match current {
+            Some(index) if forward => (index + 1) % matches.len(),
+            Some(index) => (index + matches.len() - 1) % matches.len(),
+            None if matches.is_empty() => return None,
+            None => {
+                // From outside the matches, the next one down the board
+                let at = self.get_selected_place()
+                    .and_then(|p| self.breadboard.places.iter().position(|q| q.id == p.id))
+                    .unwrap_or(0);
+                let after = |id: &u32| self.breadboard.places.iter().position(|p| p.id == *id) > Some(at);
+                match matches.iter().position(after) {
+                    Some(index) if forward => index,
+                    Some(index) => (index + matches.len() - 1) % matches.len(),
+                    None if forward => 0,
+                    None => matches.len() - 1,
+                }
+            }
+        };
+        self.navigate_to_place(matches[index]);
+        let name = self.get_selected_place()?.name.clone();
+        Some((name, index + 1, matches.len()))
+    }
+
+    // `name` the way the board's naming convention writes it, when it's set
+    // to fix names as their edit is finished
+    pub fn conventional_name(&self, name: &str, place: bool) -> String {
+        self.breadboard.naming.as_ref()
+            .filter(|naming| naming.fix)
+            .and_then(|naming| crate::naming::suggestion(naming, name, place, &self.breadboard.glossary))
+            .unwrap_or_else(|| name.to_string())
+    }
+
+    // The places and affordances going against the board's rules, each with
+    // what the rule says about it
+    pub fn rule_breaks(&self) -> Vec<(Selection, String)> {
+        lint::rule_issues(&self.breadboard).into_iter()
+            .map(|issue| {
+                let item = match issue.affordance_id {
+                    Some(affordance_id) => Selection::Affordance { place_id: issue.place_id, affordance_id },
+                    None => Selection::Place(issue.place_id),
+                };
+                (item, issue.message)
+            })
+            .collect()
+    }
+
+    // Whether `item` is a jump search result, and if so whether it's the
+    // highlighted one. Nothing matches until something is typed.
+    pub fn search_match(&self, item: &Selection) -> Option<bool> {
+        let Selection::Place(id) = item else {
+            return None;
+        };
+        if !self.state.is_searching_places || self.state.place_search_buffer.is_empty() {
+            return None;
+        }
+        let index = self.state.place_search_results.iter().position(|result| result == id)?;
+        Some(self.state.selected_place_result == Some(index))
+    }
+
     pub fn clear_place_search(&mut self) {
         self.state.place_search_buffer.clear();
         self.state.place_search_results.clear();
@@ -298,6 +2424,16 @@ impl App {
     }
 }
 
+// `selected` moved a step down (or up) a list of `len` items, staying put
+// at either end
+fn step(selected: Option<usize>, down: bool, len: usize) -> Option<usize> {
+    selected.map(|index| match down {
+        true if index + 1 < len => index + 1,
+        true => index,
+        false => index.saturating_sub(1),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -412,6 +2548,157 @@ mod tests {
         assert_eq!(app.state.navigation_trail.len(), 0);
     }
 
+    fn connected_test_app() -> (App, u32, u32, u32) {
+        let mut app = App::new();
+        app.new_place("Invoice".to_string());
+        app.new_place("Setup Autopay".to_string());
+
+        let invoice_id = app.breadboard.places[0].id;
+        let setup_id = app.breadboard.places[1].id;
+        let affordance_id = app.breadboard.generate_affordance_id();
+        let affordance = crate::models::Affordance::new(affordance_id, "Turn on Autopay".to_string())
+            .with_connection(setup_id);
+        app.add_affordance_to_place(&invoice_id, affordance);
+        (app, invoice_id, setup_id, affordance_id)
+    }
+
+    #[test]
+    fn test_reverse_connection_keeps_original() {
+        let (mut app, invoice_id, setup_id, affordance_id) = connected_test_app();
+
+        let new_id = app.reverse_connection(invoice_id, affordance_id, false).unwrap();
+
+        let setup = app.breadboard.find_place(&setup_id).unwrap();
+        assert_eq!(setup.affordances.len(), 1);
+        assert_eq!(setup.affordances[0].id, new_id);
+        assert_eq!(setup.affordances[0].name, "Back to Invoice");
+        assert_eq!(setup.affordances[0].connects_to, Some(invoice_id));
+        assert_eq!(app.breadboard.find_place(&invoice_id).unwrap().affordances.len(), 1);
+    }
+
+    #[test]
+    fn test_reverse_connection_swaps() {
+        let (mut app, invoice_id, setup_id, affordance_id) = connected_test_app();
+
+        app.reverse_connection(invoice_id, affordance_id, true).unwrap();
+
+        assert!(app.breadboard.find_place(&invoice_id).unwrap().affordances.is_empty());
+        let setup = app.breadboard.find_place(&setup_id).unwrap();
+        assert_eq!(setup.affordances[0].name, "Turn on Autopay");
+        assert_eq!(setup.affordances[0].connects_to, Some(invoice_id));
+    }
+
+    #[test]
+    fn test_reverse_unconnected_affordance_does_nothing() {
+        let (mut app, invoice_id, _, _) = connected_test_app();
+        let affordance_id = app.breadboard.generate_affordance_id();
+        app.add_affordance_to_place(&invoice_id, crate::models::Affordance::new(affordance_id, "View".to_string()));
+
+        assert!(app.reverse_connection(invoice_id, affordance_id, false).is_none());
+    }
+
+    #[test]
+    fn test_incoming_list_jumps_to_source() {
+        let (mut app, invoice_id, setup_id, affordance_id) = connected_test_app();
+        app.state.selection = Some(Selection::Place(setup_id));
+
+        assert!(app.start_incoming_list());
+        assert_eq!(app.state.incoming_results, vec![(invoice_id, affordance_id)]);
+
+        app.jump_to_selected_incoming();
+        assert_eq!(app.state.selection, Some(Selection::Affordance { place_id: invoice_id, affordance_id }));
+        assert_eq!(app.state.navigation_trail, vec![setup_id]);
+        assert!(app.state.incoming_results.is_empty());
+    }
+
+    #[test]
+    fn test_incoming_list_empty() {
+        let (mut app, invoice_id, _, _) = connected_test_app();
+        app.state.selection = Some(Selection::Place(invoice_id));
+        assert!(!app.start_incoming_list());
+    }
+
+    #[test]
+    fn test_cycle_list_highlights_and_restores() {
+        let (mut app, invoice_id, setup_id, affordance_id) = connected_test_app();
+        assert!(!app.start_cycle_list());
+
+        app.reverse_connection(invoice_id, affordance_id, false).unwrap();
+        app.state.selection = Some(Selection::Place(setup_id));
+        assert!(app.start_cycle_list());
+        assert_eq!(app.state.selection, Some(Selection::Affordance { place_id: invoice_id, affordance_id }));
+        assert!(app.in_selected_cycle(&Selection::Place(setup_id)));
+
+        app.clear_cycle_list(false);
+        assert_eq!(app.state.selection, Some(Selection::Place(setup_id)));
+        assert!(!app.in_selected_cycle(&Selection::Place(setup_id)));
+    }
+
+    #[test]
+    fn test_branches_add_connect_and_remove() {
+        let (mut app, invoice_id, setup_id, affordance_id) = connected_test_app();
+        app.state.selection = Some(Selection::Affordance { place_id: invoice_id, affordance_id });
+        assert!(app.start_branch_list());
+        assert_eq!(app.state.selected_branch, None);
+
+        assert_eq!(app.add_branch(), Some("Outcome 1".to_string()));
+        app.rename_selected_branch("declined".to_string());
+        app.connect_selected_branch(Some(invoice_id));
+        app.add_branch();
+        assert_eq!(app.state.selected_branch, Some(1));
+
+        let affordance = app.get_selected_affordance().unwrap();
+        assert_eq!(affordance.branches[0], Branch { label: "declined".to_string(), connects_to: Some(invoice_id) });
+        assert_eq!(affordance.destinations(), vec![setup_id, invoice_id]);
+        assert_eq!(app.breadboard.get_incoming_connections(&invoice_id).len(), 1);
+
+        app.remove_selected_branch();
+        assert_eq!(app.state.selected_branch, Some(0));
+        app.remove_selected_branch();
+        assert_eq!(app.state.selected_branch, None);
+        assert!(app.get_selected_affordance().unwrap().branches.is_empty());
+    }
+
+    #[test]
+    fn test_preview_place_follows_selection() {
+        let (mut app, invoice_id, setup_id, affordance_id) = connected_test_app();
+
+        app.state.selection = Some(Selection::Place(invoice_id));
+        assert!(app.get_preview_place().is_none());
+
+        app.state.selection = Some(Selection::Affordance { place_id: invoice_id, affordance_id });
+        assert_eq!(app.get_preview_place().map(|p| p.id), Some(setup_id));
+    }
+
+    #[test]
+    fn test_connect_affordance_with_back_link() {
+        let (mut app, invoice_id, setup_id, _) = connected_test_app();
+        let affordance_id = app.breadboard.generate_affordance_id();
+        app.add_affordance_to_place(&invoice_id, crate::models::Affordance::new(affordance_id, "Pay".to_string()));
+
+        app.connect_affordance(invoice_id, affordance_id, setup_id, true);
+
+        let setup = app.breadboard.find_place(&setup_id).unwrap();
+        assert_eq!(setup.affordances.len(), 1);
+        assert_eq!(setup.affordances[0].name, "Back");
+        assert_eq!(setup.affordances[0].connects_to, Some(invoice_id));
+
+        // A second connection doesn't add another back link
+        app.connect_affordance(invoice_id, affordance_id, setup_id, true);
+        assert_eq!(app.breadboard.find_place(&setup_id).unwrap().affordances.len(), 1);
+    }
+
+    #[test]
+    fn test_connect_affordance_without_back_link() {
+        let (mut app, invoice_id, setup_id, affordance_id) = connected_test_app();
+        app.connect_affordance(invoice_id, affordance_id, invoice_id, true);
+
+        let invoice = app.breadboard.find_place(&invoice_id).unwrap();
+        assert_eq!(invoice.affordances[0].connects_to, Some(invoice_id));
+        assert_eq!(invoice.affordances.len(), 1);
+        assert!(app.breadboard.find_place(&setup_id).unwrap().affordances.is_empty());
+    }
+
     #[test]
     fn test_toggle_collapsed() {
         let mut app = App::new();
@@ -424,6 +2711,578 @@ mod tests {
         assert!(!app.state.collapsed);
     }
 
+    fn filter_test_app() -> App {
+        let mut app = App::new();
+        app.new_place("Invoice".to_string());
+        app.new_place("Setup Autopay".to_string());
+
+        let invoice_id = app.breadboard.places[0].id;
+        let setup_id = app.breadboard.places[1].id;
+        for (place_id, name) in [(invoice_id, "Download PDF"), (invoice_id, "Pay now"), (setup_id, "CC Fields")] {
+            let affordance_id = app.breadboard.generate_affordance_id();
+            app.add_affordance_to_place(&place_id, crate::models::Affordance::new(affordance_id, name.to_string()));
+        }
+        app
+    }
+
+    #[test]
+    fn test_visible_places_without_filter() {
+        let app = filter_test_app();
+        let visible = app.visible_places();
+        assert_eq!(visible.len(), 2);
+        assert_eq!(visible[0].1.len(), 2);
+    }
+
+    #[test]
+    fn test_filter_keeps_tree_structure() {
+        let mut app = filter_test_app();
+        app.start_filter();
+        app.state.filter = Some("pdf".to_string());
+
+        let visible = app.visible_places();
+        assert_eq!(visible.len(), 1);
+        assert_eq!(visible[0].0.name, "Invoice");
+        assert_eq!(visible[0].1.len(), 1);
+        assert_eq!(visible[0].1[0].name, "Download PDF");
+
+        // A matching place name keeps all of its affordances
+        app.state.filter = Some("invoice".to_string());
+        assert_eq!(app.visible_places()[0].1.len(), 2);
+
+        app.clear_filter();
+        assert!(!app.is_filter_active());
+        assert_eq!(app.visible_places().len(), 2);
+    }
+
+    #[test]
+    fn test_filter_by_author_and_date() {
+        let mut app = filter_test_app();
+        app.breadboard.places[0].affordances[0].attribution = Attribution {
+            created_by: Some("Bob".to_string()),
+            created_at: Some("2026-10-14T09:00:00+00:00".to_string()),
+            ..Attribution::default()
+        };
+        app.state.filter = Some("by:bob".to_string());
+        let visible = app.visible_places();
+        assert_eq!(visible.len(), 1);
+        assert_eq!(visible[0].1.len(), 1);
+        assert_eq!(visible[0].1[0].name, "Download PDF");
+
+        app.state.filter = Some("by:alice".to_string());
+        assert!(app.visible_places().is_empty());
+
+        let today = chrono::NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        let query = FilterQuery::parse("pdf by:Bob since:7d", today);
+        assert_eq!(query.text, "pdf");
+        assert_eq!(query.since, chrono::NaiveDate::from_ymd_opt(2026, 10, 9));
+        assert!(query.matches("Download PDF", &app.breadboard.places[0].affordances[0].attribution));
+        let query = FilterQuery::parse("by:bob since:2026-10-15", today);
+        assert!(!query.matches("Download PDF", &app.breadboard.places[0].affordances[0].attribution));
+    }
+
+    #[test]
+    fn test_filter_annotated() {
+        let mut app = filter_test_app();
+        app.breadboard.places[0].affordances[1].name = "Pay now [needs API change]".to_string();
+        app.breadboard.places[1].name = "Setup Autopay [v2]".to_string();
+        app.state.filter = Some("is:annotated pay".to_string());
+        let visible = app.visible_places();
+        assert_eq!(visible.len(), 2);
+        assert_eq!(visible[0].1.len(), 1);
+        assert_eq!(visible[0].1[0].name, "Pay now [needs API change]");
+        assert_eq!(visible[1].1.len(), 1);
+
+        app.state.filter = Some("is:annotated pdf".to_string());
+        assert!(app.visible_places().is_empty());
+    }
+
+    #[test]
+    fn test_split_arrow() {
+        assert_eq!(split_arrow("Pay now -> Confirm"), ("Pay now", Some("Confirm")));
+        assert_eq!(split_arrow("Pay now→Confirm -> x"), ("Pay now", Some("Confirm -> x")));
+        assert_eq!(split_arrow("Pay now ->"), ("Pay now", Some("")));
+        assert_eq!(split_arrow("Pay now"), ("Pay now", None));
+    }
+
+    #[test]
+    fn test_place_named_finds_or_adds() {
+        let mut app = filter_test_app();
+        assert_eq!(app.place_named(" setup autopay "), app.breadboard.places[1].id);
+        let confirm = app.place_named("Confirm ");
+        assert_eq!(app.breadboard.find_place(&confirm).unwrap().name, "Confirm");
+        assert_eq!(app.breadboard.places.len(), 3);
+    }
+
+    #[test]
+    fn test_paste_place_rewires_by_name() {
+        let mut source = App::new();
+        let invoice = source.new_place("Invoice".to_string());
+        let confirm = source.new_place("Confirmation".to_string());
+        let help = source.new_place("Help".to_string());
+        source.add_affordance(invoice, "Pay".to_string(), Some(confirm)).unwrap();
+        source.add_affordance(invoice, "Get help".to_string(), Some(help)).unwrap();
+        source.add_affordance(invoice, "Refresh".to_string(), Some(invoice)).unwrap();
+        let copied = source.copy_place(invoice).unwrap();
+        assert_eq!(copied.places.len(), 3);
+
+        // Same names, different IDs, and Help is missing
+        let mut dest = App::new();
+        dest.new_place("Home".to_string());
+        let local_confirm = dest.new_place("confirmation".to_string());
+        let report = dest.paste_place(&copied).unwrap();
+
+        assert_eq!(report.remapped, vec!["Pay → Confirmation"]);
+        assert_eq!(report.dropped, vec!["Get help"]);
+        let pasted = dest.breadboard.find_place(&report.place_id).unwrap();
+        assert_eq!(pasted.name, "Invoice");
+        let connections: Vec<Option<u32>> = pasted.affordances.iter().map(|a| a.connects_to).collect();
+        assert_eq!(connections, vec![Some(local_confirm), None, Some(report.place_id)]);
+        assert_eq!(dest.breadboard.places.len(), 3);
+    }
+
+    #[test]
+    fn test_start_rewire_highlights_current_destination() {
+        let mut app = App::new();
+        let invoice = app.new_place("Invoice".to_string());
+        app.new_place("Confirmation page".to_string());
+        let confirm = app.new_place("Confirmation".to_string());
+        let pay = app.add_affordance(invoice, "Pay".to_string(), Some(confirm)).unwrap();
+        let help = app.add_affordance(invoice, "Help".to_string(), None).unwrap();
+
+        app.state.selection = Some(Selection::Affordance { place_id: invoice, affordance_id: pay });
+        assert!(app.start_rewire());
+        assert_eq!(app.state.connection_search_buffer, "Confirmation");
+        assert_eq!(app.get_selected_connection_place().map(|p| p.id), Some(confirm));
+        assert!(app.state.connection_search_prefilled);
+
+        // Typing a new destination highlights the first match
+        app.state.connection_search_buffer = "inv".to_string();
+        app.update_connection_search();
+        assert_eq!(app.get_selected_connection_place().map(|p| p.id), Some(invoice));
+
+        app.state.selection = Some(Selection::Affordance { place_id: invoice, affordance_id: help });
+        app.clear_connection_search();
+        assert!(!app.start_rewire());
+        assert!(!app.state.connection_search_prefilled);
+    }
+
+    #[test]
+    fn test_fuzzy_score() {
+        assert_eq!(fuzzy_score("", "Invoice"), Some(0));
+        assert_eq!(fuzzy_score("xyz", "Invoice"), None);
+        assert_eq!(fuzzy_score("ivc", "Invoice"), fuzzy_score("IVC", "invoice"));
+        // A prefix beats a word start, which beats letters scattered about
+        assert!(fuzzy_score("rec", "Receipt") > fuzzy_score("rec", "Email receipt"));
+        assert!(fuzzy_score("rec", "Email receipt") > fuzzy_score("rec", "Refund check"));
+        assert!(fuzzy_score("cp", "Confirmation page") > fuzzy_score("cp", "Checkup"));
+    }
+
+    #[test]
+    fn test_connection_search_ranking() {
+        let mut app = App::new();
+        let cart = app.new_place("Cart".to_string());
+        let help = app.new_place("Help".to_string());
+        let checkout = app.new_place("Checkout".to_string());
+        let settings = app.new_place("Settings".to_string());
+        let receipt = app.new_place("Receipt".to_string());
+        for id in [cart, checkout, receipt] {
+            app.breadboard.find_place_mut(&id).unwrap().group = Some("Buying".to_string());
+        }
+        let pay = app.add_affordance(checkout, "Pay".to_string(), Some(receipt)).unwrap();
+        app.state.navigation_trail = vec![settings, cart];
+        app.state.selection = Some(Selection::Affordance { place_id: checkout, affordance_id: pay });
+
+        // Before typing, nearest first: just visited and in the group, then
+        // connected, then visited before that, with Pay's own place last
+        app.start_connection_search();
+        assert_eq!(app.state.connection_search_results, vec![cart, receipt, settings, help, checkout]);
+        assert_eq!(app.connecting_from(), Some(checkout));
+        // Nothing destructive is picked by default
+        assert_eq!(app.get_selected_connection_place().map(|p| p.id), Some(cart));
+
+        app.state.connection_search_buffer = "he".to_string();
+        app.update_connection_search();
+        assert_eq!(app.state.connection_search_results, vec![help, checkout]);
+
+        app.config.own_place_in_connect = OwnPlace::Hide;
+        app.update_connection_search();
+        assert_eq!(app.state.connection_search_results, vec![help]);
+    }
+
+    #[test]
+    fn test_connect_marked() {
+        let mut app = App::new();
+        let home = app.new_place("Home".to_string());
+        let invoice = app.new_place("Invoice".to_string());
+        let settings = app.new_place("Settings".to_string());
+        let help = app.new_place("Help".to_string());
+        let back = app.add_affordance(invoice, "Back".to_string(), None).unwrap();
+        let cancel = app.add_affordance(settings, "Cancel".to_string(), Some(help)).unwrap();
+        app.add_affordance(home, "Support".to_string(), Some(help)).unwrap();
+
+        for item in [
+            Selection::Affordance { place_id: invoice, affordance_id: back },
+            Selection::Affordance { place_id: settings, affordance_id: cancel },
+        ] {
+            app.state.selection = Some(item);
+            app.toggle_mark();
+        }
+        assert_eq!(app.connect_marked(Some(home)), (2, 0));
+        assert_eq!(app.breadboard.get_incoming_connections(&home).len(), 2);
+
+        // Places get a "Go to" affordance unless they already lead there
+        app.state.marked.clear();
+        for place in [home, invoice, settings, help] {
+            app.state.selection = Some(Selection::Place(place));
+            app.toggle_mark();
+        }
+        app.state.selection = Some(Selection::Place(settings));
+        app.toggle_mark();
+        assert_eq!(app.state.marked.len(), 3);
+        assert_eq!(app.connect_marked(Some(help)), (0, 1));
+        let go_to = app.breadboard.find_place(&invoice).unwrap().affordances.last().unwrap();
+        assert_eq!((go_to.name.as_str(), go_to.connects_to), ("Go to Help", Some(help)));
+    }
+
+    #[test]
+    fn test_happy_path() {
+        let mut app = App::new();
+        let invoice = app.new_place("Invoice".to_string());
+        let setup = app.new_place("Setup".to_string());
+        let help = app.add_affordance(invoice, "Help".to_string(), None).unwrap();
+        let turn_on = app.add_affordance(invoice, "Turn on".to_string(), Some(setup)).unwrap();
+
+        for place in [invoice, setup] {
+            app.state.selection = Some(Selection::Place(place));
+            assert_eq!(app.toggle_happy_path(), Some(true));
+        }
+        assert_eq!(app.breadboard.happy_path, vec![invoice, setup]);
+        assert!(app.on_happy_path(&Selection::Place(setup)));
+        assert!(app.on_happy_path(&Selection::Affordance { place_id: invoice, affordance_id: turn_on }));
+        assert!(!app.on_happy_path(&Selection::Affordance { place_id: invoice, affordance_id: help }));
+
+        app.state.selection = Some(Selection::Place(invoice));
+        assert_eq!(app.toggle_happy_path(), Some(false));
+        assert_eq!(app.breadboard.happy_path, vec![setup]);
+    }
+
+    #[test]
+    fn test_file_filter_and_list_keys() {
+        let mut app = App::new();
+        app.state.mode = Mode::OpenFile;
+        app.config.boards_dir = Some("boards".to_string());
+        let entry = |path: String| FileEntry { path, modified: None, name: None, places: None, error: None };
+        app.state.file_list = (1..=25).map(|n| entry(format!("boards/board{:02}.toml", n))).collect();
+        app.state.file_list.push(entry("notes.toml".to_string()));
+        app.set_file_filter("BOARD".to_string());
+        assert_eq!(app.visible_files().len(), 25);
+        app.page_list(true);
+        assert_eq!(app.get_selected_file().map(String::as_str), Some("boards/board11.toml"));
+        app.list_end(true);
+        assert_eq!(app.list_position(), Some((24, 25)));
+        app.page_list(true);
+        assert_eq!(app.list_position(), Some((24, 25)));
+        app.page_list(false);
+        app.page_list(false);
+        app.page_list(false);
+        assert_eq!(app.list_position(), Some((0, 25)));
+        // The boards directory isn't part of the label
+        app.set_file_filter("s/".to_string());
+        assert_eq!(app.state.selected_file_index, None);
+        assert_eq!(app.get_selected_file(), None);
+    }
+
+    #[test]
+    fn test_toggle_file_sort() {
+        let mut app = App::new();
+        app.state.mode = Mode::OpenFile;
+        let now = chrono::Local::now();
+        let entry = |path: &str, age: i64| FileEntry {
+            path: path.to_string(),
+            modified: Some(now - chrono::Duration::hours(age)),
+            name: None,
+            places: None,
+            error: None,
+        };
+        app.state.file_list = vec![entry("a.toml", 3), entry("b.toml", 1), entry("c.toml", 2)];
+        app.state.selected_file_index = Some(2);
+        app.toggle_file_sort();
+        assert_eq!(app.state.file_sort, FileSort::Modified);
+        let order: Vec<&str> = app.visible_files().iter().map(|file| file.path.as_str()).collect();
+        assert_eq!(order, ["b.toml", "c.toml", "a.toml"]);
+        // The highlight stays on the same file
+        assert_eq!(app.get_selected_file().map(String::as_str), Some("c.toml"));
+        app.toggle_file_sort();
+        assert_eq!(app.get_selected_file().map(String::as_str), Some("c.toml"));
+        assert_eq!(app.state.selected_file_index, Some(2));
+    }
+
+    #[test]
+    fn test_select_ends() {
+        let mut app = App::new();
+        let first = app.new_place("Invoice".to_string());
+        app.breadboard.places[0].add_affordance(Affordance::new(1, "Pay".to_string()));
+        app.breadboard.places[0].add_affordance(Affordance::new(2, "Download".to_string()));
+        let last = app.new_place("Receipt".to_string());
+
+        app.select_end_place(true);
+        assert_eq!(app.state.selection, Some(Selection::Place(last)));
+        app.select_end_of_previous_place(&last);
+        assert_eq!(app.state.selection, Some(Selection::Affordance { place_id: first, affordance_id: 2 }));
+        app.select_end_affordance(false);
+        assert_eq!(app.state.selection, Some(Selection::Affordance { place_id: first, affordance_id: 1 }));
+        app.select_end_place(false);
+        app.select_end_affordance(true);
+        assert_eq!(app.state.selection, Some(Selection::Affordance { place_id: first, affordance_id: 2 }));
+
+        app.select_next_place(&first);
+        assert_eq!(app.state.selection, Some(Selection::Place(last)));
+        app.select_next_place(&last);
+        assert_eq!(app.state.selection, Some(Selection::Place(last)));
+
+        // Nothing before the first place; a folded place's header is as far as it goes
+        app.select_end_place(false);
+        app.select_end_of_previous_place(&first);
+        assert_eq!(app.state.selection, Some(Selection::Place(first)));
+        app.toggle_fold(first);
+        app.select_end_of_previous_place(&last);
+        assert_eq!(app.state.selection, Some(Selection::Place(first)));
+    }
+
+    #[test]
+    fn test_fold_place() {
+        let mut app = App::new();
+        let place_id = app.new_place("Invoice".to_string());
+        app.breadboard.places[0].add_affordance(Affordance::new(1, "Pay".to_string()));
+        let target = app.new_place("Receipt".to_string());
+        app.breadboard.places[0].affordances[0].connects_to = Some(target);
+        assert_eq!(app.first_destination(&place_id), Some(target));
+        assert_eq!(app.first_destination(&target), None);
+
+        assert!(app.toggle_fold(place_id));
+        assert!(app.visible_affordance_ids(&place_id).is_empty());
+        assert_eq!(app.visible_places().len(), 2);
+        assert!(!app.toggle_fold(place_id));
+        assert_eq!(app.visible_affordance_ids(&place_id), vec![1]);
+    }
+
+    #[test]
+    fn test_step_list() {
+        let mut app = App::new();
+        assert!(!app.step_list(true));
+        // A selection left over from results that are gone stays put
+        app.state.mode = Mode::Incoming;
+        app.state.selected_incoming_result = Some(0);
+        assert!(app.step_list(true));
+        assert_eq!(app.state.selected_incoming_result, Some(0));
+
+        app.state.mode = Mode::Start;
+        app.state.start_items = vec![StartItem::NewBoard, StartItem::Open];
+        app.state.selected_start_index = Some(0);
+        app.step_list(true);
+        app.step_list(true);
+        assert_eq!(app.state.selected_start_index, Some(1));
+        app.step_list(false);
+        app.step_list(false);
+        assert_eq!(app.state.selected_start_index, Some(0));
+    }
+
+    #[test]
+    fn test_type_count() {
+        let mut app = App::new();
+        assert!(!app.type_count("0"));
+        assert!(app.type_count("1"));
+        assert!(app.type_count("2"));
+        assert_eq!(app.state.count.as_deref(), Some("12"));
+        assert!(!app.state.is_searching_places);
+        // Anything else searches, digits and all
+        assert!(app.type_count("a"));
+        assert_eq!(app.state.count, None);
+        assert_eq!(app.state.place_search_buffer, "12a");
+        assert!(!app.type_count("a"));
+
+        // Long counts repeat at most MAX_COUNT times, but search as typed
+        app.state.is_searching_places = false;
+        for digit in ["2", "0", "2", "4"] {
+            assert!(app.type_count(digit));
+        }
+        assert!(app.type_count("x"));
+        assert_eq!(app.state.place_search_buffer, "2024x");
+        for digit in ["2", "0", "2", "4"] {
+            app.type_count(digit);
+        }
+        assert_eq!(app.take_count(), Some(MAX_COUNT));
+        assert_eq!(app.state.count, None);
+    }
+
+    #[test]
+    fn test_pick_icon() {
+        let mut app = App::new();
+        app.state.mode = Mode::Edit;
+        app.state.edit_buffer = "Receipt :mai".to_string();
+        assert_eq!(app.icon_matches().len(), 2);
+        app.state.selected_icon = 1;
+        assert!(app.pick_icon());
+        assert_eq!(app.state.edit_buffer, "Receipt ✉");
+        assert!(!app.pick_icon());
+    }
+
+    #[test]
+    fn test_spelling_fixes() {
+        let mut app = App::new();
+        app.dictionary = Some(Arc::new(Dictionary::from_words("pay\nnow\ncard\nsaved\nthe\nwith\n", &[])));
+        let place_id = app.new_place("Pay".to_string());
+        app.state.selection = Some(Selection::Place(place_id));
+        app.breadboard.places[0].add_affordance(Affordance::new(1, "Pay nwo".to_string()));
+        app.breadboard.places[0].affordances[0].notes = Some("With teh saved card".to_string());
+        assert!(app.selection_fixes().is_empty());
+
+        app.state.selection = Some(Selection::Affordance { place_id, affordance_id: 1 });
+        let fixes = app.selection_fixes();
+        assert_eq!(fixes.iter().map(|f| f.replacement.as_str()).collect::<Vec<_>>(), vec!["now", "the"]);
+        app.apply_fix(&fixes[1]);
+        app.apply_fix(&fixes[0]);
+        let affordance = &app.breadboard.places[0].affordances[0];
+        assert_eq!(affordance.name, "Pay now");
+        assert_eq!(affordance.notes.as_deref(), Some("With the saved card"));
+        assert!(app.selection_fixes().is_empty());
+    }
+
+    #[test]
+    fn test_insert_snippet() {
+        let mut app = App::new();
+        let invoice = app.new_place("Invoice".to_string());
+        let form = app.new_place("Setup Autopay".to_string());
+        app.add_affordance(invoice, "Turn on Autopay".to_string(), Some(form)).unwrap();
+        app.add_affordance(form, "submit".to_string(), None).unwrap();
+        let snippet = Snippet {
+            name: "Standard form".to_string(),
+            affordances: ["Fields", "Submit -> Confirmation", "Cancel -> back"].map(String::from).to_vec(),
+        };
+
+        assert_eq!(app.insert_snippet(form, &snippet), (2, 1));
+        let affordances: Vec<(&str, Option<u32>)> = app.breadboard.find_place(&form).unwrap().affordances.iter()
+            .map(|a| (a.name.as_str(), a.connects_to))
+            .collect();
+        assert_eq!(affordances, vec![("submit", None), ("Fields", None), ("Cancel", Some(invoice))]);
+        assert_eq!(app.breadboard.places.len(), 2);
+
+        // Setup Autopay's Cancel now leads to Invoice, so that is its way back
+        app.insert_snippet(invoice, &snippet);
+        assert_eq!(app.breadboard.find_place(&invoice).unwrap().affordances.last().unwrap().connects_to, Some(form));
+        assert_eq!(app.breadboard.places[2].name, "Confirmation");
+    }
+
+    #[test]
+    fn test_capture_lines_build_the_board() {
+        let mut app = App::new();
+        assert!(app.capture_line("Pay now -> Receipt").is_err());
+        assert_eq!(app.capture_line("Invoice").unwrap(), "Invoice");
+        assert_eq!(app.capture_line("invoice: Turn on Autopay -> Setup Autopay").unwrap(), "Invoice: Turn on Autopay → Setup Autopay");
+        assert_eq!(app.capture_line("Download PDF -> Receipt").unwrap(), "Invoice: Download PDF → Receipt");
+        assert_eq!(app.capture_line("Setup Autopay: Save").unwrap(), "Setup Autopay: Save");
+        // Naming an affordance again connects it rather than adding another
+        assert_eq!(app.capture_line("Setup Autopay: save -> Invoice").unwrap(), "Setup Autopay: Save → Invoice");
+        assert!(app.capture_line(": Save").is_err());
+        assert!(app.capture_line("Invoice:").is_err());
+
+        let names: Vec<&str> = app.breadboard.places.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["Invoice", "Setup Autopay", "Receipt"]);
+        assert_eq!(app.breadboard.places[0].affordances.len(), 2);
+        assert_eq!(app.breadboard.places[1].affordances.len(), 1);
+        assert_eq!(app.state.selection, Some(Selection::Affordance { place_id: 2, affordance_id: 3 }));
+    }
+
+    #[test]
+    fn test_scoped_copies_narrow_the_board() {
+        let mut app = filter_test_app();
+        let invoice_id = app.breadboard.places[0].id;
+        let help_id = app.new_place("Help".to_string());
+        let pdf_id = app.breadboard.places[0].affordances[0].id;
+        app.breadboard.places[0].affordances[1].connects_to = Some(help_id);
+        let names = |app: &App| app.visible_places().iter()
+            .map(|(p, affs)| format!("{}: {}", p.name, affs.iter().map(|a| a.name.as_str()).collect::<Vec<_>>().join(", ")))
+            .collect::<Vec<_>>();
+
+        assert!(app.scoped(ExportScope::Selection).is_err());
+        app.state.selection = Some(Selection::Affordance { place_id: invoice_id, affordance_id: pdf_id });
+        assert_eq!(names(&app.scoped(ExportScope::Selection).unwrap()), vec!["Invoice: Download PDF"]);
+        assert_eq!(names(&app.scoped(ExportScope::Subtree).unwrap()), vec!["Invoice: Download PDF, Pay now", "Help: "]);
+
+        assert!(app.scoped(ExportScope::Filter).is_err());
+        app.state.filter = Some("fields".to_string());
+        let filtered = app.scoped(ExportScope::Filter).unwrap();
+        assert_eq!(names(&filtered), vec!["Setup Autopay: CC Fields"]);
+        assert_eq!(filtered.breadboard.places.len(), 3);
+        assert_eq!(names(&app.scoped(ExportScope::Board).unwrap()), names(&app));
+    }
+
+    #[test]
+    fn test_visible_board_applies_filter() {
+        let mut app = filter_test_app();
+        app.state.filter = Some("cc".to_string());
+
+        let board = app.visible_board();
+        assert_eq!(board.places.len(), 1);
+        assert_eq!(board.places[0].affordances.len(), 1);
+        assert_eq!(app.breadboard.places.len(), 2);
+    }
+
+    #[test]
+    fn test_toggle_graph_view() {
+        let mut app = App::new();
+        assert_eq!(app.state.view, ViewMode::Outline);
+        app.toggle_graph_view();
+        assert_eq!(app.state.view, ViewMode::Graph);
+        app.toggle_graph_view();
+        assert_eq!(app.state.view, ViewMode::Outline);
+
+        app.toggle_swimlanes();
+        assert!(app.state.swimlanes);
+        assert_eq!(app.state.view, ViewMode::Graph);
+        app.toggle_swimlanes();
+        assert!(!app.state.swimlanes);
+        assert_eq!(app.state.view, ViewMode::Graph);
+    }
+
+    #[test]
+    fn test_pan_graph_stops_at_origin() {
+        let mut app = App::new();
+        app.pan_graph(16, 6);
+        assert_eq!(app.state.graph_offset, (16, 6));
+        app.pan_graph(-32, 0);
+        assert_eq!(app.state.graph_offset, (0, 6));
+    }
+
+    #[test]
+    fn test_notes_follow_selection() {
+        let mut app = App::new();
+        let place_id = app.new_place("Invoice".to_string());
+        let affordance_id = app.add_affordance(place_id, "Pay now".to_string(), None).unwrap();
+
+        app.state.selection = Some(Selection::Affordance { place_id, affordance_id });
+        assert!(app.set_selected_notes("Card only.\n\nNo PayPal yet.\n\n"));
+        assert_eq!(app.selected_notes(), Some("Card only.\n\nNo PayPal yet."));
+
+        app.state.selection = Some(Selection::Place(place_id));
+        assert_eq!(app.selected_notes(), None);
+
+        app.state.selection = Some(Selection::Affordance { place_id, affordance_id });
+        app.set_selected_notes("  \n");
+        assert_eq!(app.selected_notes(), None);
+    }
+
+    #[test]
+    fn test_filter_moves_hidden_selection() {
+        let mut app = filter_test_app();
+        let setup_id = app.breadboard.places[1].id;
+        app.state.selection = Some(Selection::Place(app.breadboard.places[0].id));
+
+        app.state.filter = Some("cc".to_string());
+        app.ensure_selection_visible();
+        assert_eq!(app.state.selection, Some(Selection::Place(setup_id)));
+    }
+
     #[test]
     fn test_selection_with_affordance() {
         let mut app = App::new();
@@ -441,4 +3300,268 @@ mod tests {
         assert!(selected.is_some());
         assert_eq!(selected.unwrap().name, "Test Place");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_breadcrumb_and_jump_up() {
+        let mut app = filter_test_app();
+        app.breadboard.places[1].group = Some("billing".to_string());
+        app.new_place("Autopay Done".to_string());
+        app.breadboard.places[2].group = Some("billing".to_string());
+        let (invoice, setup, done) = (app.breadboard.places[0].id, app.breadboard.places[1].id, app.breadboard.places[2].id);
+        let fields = app.breadboard.places[1].affordances[0].id;
+
+        app.state.selection = Some(Selection::Place(done));
+        let labels: Vec<String> = app.breadcrumb().into_iter().map(|c| c.label).collect();
+        assert_eq!(labels, vec!["New Breadboard", "billing", "Autopay Done"]);
+        assert_eq!(app.breadcrumb()[1].target, Some(Selection::Place(setup)));
+
+        // Place → first of its group → first place of the board
+        assert!(app.jump_up());
+        assert_eq!(app.state.selection, Some(Selection::Place(setup)));
+        assert!(app.jump_up());
+        assert_eq!(app.state.selection, Some(Selection::Place(invoice)));
+        assert!(!app.jump_up());
+        assert_eq!(app.state.navigation_trail, vec![done, setup]);
+
+        app.state.selection = Some(Selection::Affordance { place_id: setup, affordance_id: fields });
+        assert_eq!(app.breadcrumb().last().unwrap().label, "CC Fields");
+        assert!(app.jump_up());
+        assert_eq!(app.state.selection, Some(Selection::Place(setup)));
+    }
+
+    #[test]
+    fn test_drill_in_and_out() {
+        let mut app = filter_test_app();
+        let invoice = app.breadboard.places[0].id;
+        app.state.selection = Some(Selection::Place(invoice));
+        app.mark_saved();
+
+        assert_eq!(app.drill_in().as_deref(), Some("Invoice"));
+        assert!(app.breadboard.places.is_empty());
+        assert!(!app.is_dirty());
+        let items = app.new_place("Line items".to_string());
+        app.new_place("Totals".to_string());
+        assert_eq!(items, invoice); // Each board numbers its own places
+        app.state.selection = Some(Selection::Place(items));
+        assert!(app.is_dirty());
+
+        let crumbs = app.breadcrumb();
+        let labels: Vec<&str> = crumbs.iter().map(|c| c.label.as_str()).collect();
+        assert_eq!(labels, vec!["New Breadboard", "Invoice", "Line items"]);
+        assert_eq!(crumbs[1].depth, 0);
+        assert_eq!(crumbs[2].depth, 1);
+
+        // The saved board has the inner places where they belong
+        let root = app.root_board();
+        assert_eq!(root.places.len(), 2);
+        assert_eq!(root.places[0].inner_places(), 2);
+        assert_eq!(root.places[0].title(), "Invoice ⊞");
+
+        let mut renamed = root.clone();
+        renamed.places[0].board.as_mut().unwrap().places[0].name = "Items".to_string();
+        app.set_root_board(renamed);
+        assert_eq!(app.breadboard.places[0].name, "Items");
+
+        // Up from the inner board's places lands on the place around them
+        assert!(app.jump_up());
+        assert_eq!(app.state.drill_stack.len(), 0);
+        assert_eq!(app.state.selection, Some(Selection::Place(invoice)));
+        assert_eq!(app.breadboard.places[0].inner_places(), 2);
+        assert!(!app.drill_out());
+    }
+
+    #[test]
+    fn test_restore_snapshot() {
+        let mut app = filter_test_app();
+        assert_eq!(app.restore_snapshot(), None);
+        let invoice = app.breadboard.places[0].id;
+        let pay = app.breadboard.places[0].affordances[0].id;
+        app.state.selection = Some(Selection::Affordance { place_id: invoice, affordance_id: pay });
+
+        app.take_snapshot("deleting Pay".to_string());
+        app.breadboard.places[0].affordances.clear();
+        app.take_snapshot("deleting Invoice".to_string());
+        app.breadboard.places.remove(0);
+        app.state.selection = None;
+
+        assert_eq!(app.restore_snapshot().as_deref(), Some("deleting Invoice"));
+        assert!(app.breadboard.places[0].affordances.is_empty());
+        // The affordance selected then is gone, so the first place is selected
+        assert_eq!(app.state.selection, Some(Selection::Place(invoice)));
+        assert_eq!(app.restore_snapshot().as_deref(), Some("deleting Pay"));
+        assert_eq!(app.breadboard.places[0].affordances[0].id, pay);
+        assert_eq!(app.state.selection, Some(Selection::Affordance { place_id: invoice, affordance_id: pay }));
+
+        app.config.history.max_snapshots = 2;
+        for index in 0..4 {
+            app.take_snapshot(index.to_string());
+        }
+        assert_eq!(app.state.snapshots.labels().collect::<Vec<_>>(), vec!["2", "3"]);
+    }
+
+    #[test]
+    fn test_named_views() {
+        let mut app = App::new();
+        let invoice = app.new_place("Invoice".to_string());
+        app.new_place("Receipt".to_string());
+        assert_eq!(app.save_view("  ").unwrap_err().to_string(), "A view needs a name");
+
+        app.state.view = ViewMode::Graph;
+        app.state.graph_zoom = Detail::Titles;
+        assert_eq!(app.save_view("Exec summary").unwrap(), "Saved the view Exec summary");
+        app.state.view = ViewMode::Outline;
+        app.state.filter = Some("rece".to_string());
+        assert_eq!(app.save_view("V1 only").unwrap(), "Saved the view V1 only");
+        app.state.collapsed = true;
+        assert_eq!(app.save_view("v1 ONLY").unwrap(), "Updated the view v1 ONLY");
+        assert_eq!(app.breadboard.views.len(), 2);
+        assert_eq!(app.breadboard.views[1].describe(&locale::ENGLISH), "outline, collapsed, filter \"rece\"");
+        assert_eq!(app.breadboard.views[1].describe(&locale::GERMAN), "Gliederung, eingeklappt, Filter \"rece\"");
+
+        // Views are kept in the board file
+        let text = toml::to_string(&app.breadboard).unwrap();
+        app.breadboard = toml::from_str(&text).unwrap();
+
+        app.state.filter = None;
+        app.state.collapsed = false;
+        app.state.selection = Some(Selection::Place(invoice));
+        app.state.selected_view = Some(1);
+        assert_eq!(app.apply_selected_view().as_deref(), Some("v1 ONLY"));
+        assert!(app.state.collapsed);
+        assert_eq!(app.state.filter.as_deref(), Some("rece"));
+        // The selection moves onto what the filter leaves
+        assert_eq!(app.get_selected_place().map(|p| p.name.as_str()), Some("Receipt"));
+
+        app.state.selected_view = Some(0);
+        app.apply_selected_view();
+        assert_eq!((app.state.view, app.state.graph_zoom, app.state.filter.as_deref()), (ViewMode::Graph, Detail::Titles, None));
+
+        assert_eq!(app.remove_selected_view().as_deref(), Some("Exec summary"));
+        assert_eq!(app.state.selected_view, Some(0));
+        app.remove_selected_view();
+        assert_eq!(app.state.selected_view, None);
+    }
+
+    #[test]
+    fn test_restore_from_trash() {
+        let mut app = filter_test_app();
+        let invoice = app.breadboard.places[0].id;
+        let pay = app.breadboard.places[0].affordances[1].id;
+        assert_eq!(app.delete(&Selection::Affordance { place_id: invoice, affordance_id: pay }).as_deref(), Some("Pay now"));
+        assert_eq!(app.delete(&Selection::Place(invoice)).as_deref(), Some("Invoice"));
+
+        // Newest first: the place, then the affordance in it
+        app.state.selected_trash = Some(1);
+        assert!(app.restore_selected_trash().is_err());
+        app.state.selected_trash = Some(0);
+        assert_eq!(app.restore_selected_trash().unwrap(), "Invoice");
+        assert_eq!(app.state.selection, Some(Selection::Place(invoice)));
+        assert_eq!(app.restore_selected_trash().unwrap(), "Pay now");
+        assert_eq!(app.state.selection, Some(Selection::Affordance { place_id: invoice, affordance_id: pay }));
+        assert_eq!(app.breadboard.places[0].affordances.len(), 2);
+        assert!(app.state.trash.is_empty());
+
+        // Only the board it was deleted from takes it back
+        app.delete(&Selection::Place(invoice));
+        app.state.selection = app.breadboard.places.first().map(|p| Selection::Place(p.id));
+        app.drill_in();
+        app.state.selected_trash = Some(0);
+        assert!(app.restore_selected_trash().is_err());
+        app.drill_out();
+        assert!(app.restore_selected_trash().is_ok());
+    }
+
+    #[test]
+    fn test_search_match() {
+        let mut app = filter_test_app();
+        let (invoice, setup) = (app.breadboard.places[0].id, app.breadboard.places[1].id);
+        app.start_place_search();
+        // Nothing is highlighted before something is typed
+        assert_eq!(app.search_match(&Selection::Place(invoice)), None);
+
+        app.state.place_search_buffer.push('i');
+        app.update_place_search();
+        assert_eq!(app.search_match(&Selection::Place(invoice)), Some(true));
+        app.state.place_search_buffer.push('n');
+        app.update_place_search();
+        assert_eq!(app.search_match(&Selection::Place(setup)), None);
+        app.clear_place_search();
+        assert_eq!(app.search_match(&Selection::Place(invoice)), None);
+    }
+
+    #[test]
+    fn test_search_history_and_repeat() {
+        let mut app = filter_test_app();
+        let setup = app.new_place("Setup Invoicing".to_string());
+        let (invoice, autopay) = (app.breadboard.places[0].id, app.breadboard.places[1].id);
+        assert_eq!(app.repeat_search(true), None);
+
+        for query in ["pay", "inv", "pay"] {
+            app.start_place_search();
+            app.state.place_search_buffer = query.to_string();
+            app.remember_search();
+        }
+        assert_eq!(app.state.search_history, vec!["inv", "pay"]);
+
+        app.start_place_search();
+        app.state.place_search_buffer = "x".to_string();
+        assert!(app.recall_search(true));
+        assert_eq!(app.state.place_search_buffer, "pay");
+        assert_eq!(app.state.place_search_results, vec![autopay]);
+        assert!(app.recall_search(true));
+        assert_eq!(app.state.place_search_buffer, "inv");
+        assert!(!app.recall_search(true));
+        assert!(app.recall_search(false));
+        assert!(app.recall_search(false));
+        assert_eq!(app.state.place_search_buffer, "");
+        app.clear_place_search();
+
+        // F3 goes round the matches of the last query
+        app.state.place_search_buffer = "inv".to_string();
+        app.remember_search();
+        app.state.selection = Some(Selection::Place(autopay));
+        assert_eq!(app.repeat_search(true), Some(("Setup Invoicing".to_string(), 2, 2)));
+        assert_eq!(app.repeat_search(true), Some(("Invoice".to_string(), 1, 2)));
+        assert_eq!(app.repeat_search(false), Some(("Setup Invoicing".to_string(), 2, 2)));
+        assert_eq!(app.state.selection, Some(Selection::Place(setup)));
+        app.state.selection = Some(Selection::Place(invoice));
+        assert_eq!(app.repeat_search(false), Some(("Setup Invoicing".to_string(), 2, 2)));
+    }
+
+    #[test]
+    fn test_start_screen_and_examples() {
+        let mut app = App::new();
+        app.start_screen(None);
+        assert_eq!(app.state.start_items[..3], [StartItem::NewBoard, StartItem::Tutorial, StartItem::Open]);
+        assert_eq!(app.state.start_items.len(), 3 + examples::EXAMPLES.len());
+
+        app.start_screen(Some("flow.toml".to_string()));
+        assert_eq!(app.state.start_items[2].label(&locale::ENGLISH), "Open recent: flow.toml");
+        assert_eq!(app.get_selected_start_item(), Some(&StartItem::NewBoard));
+
+        app.state.current_filename = Some("flow.toml".to_string());
+        app.open_example(0).unwrap();
+        assert_eq!(app.get_selected_place().unwrap().name, "Invoice");
+        assert!(app.state.current_filename.is_none());
+        assert!(app.is_dirty());
+    }
+
+    #[test]
+    fn test_add_comment() {
+        let mut app = filter_test_app();
+        app.config.author = Some("ryan".to_string());
+        let invoice = &app.breadboard.places[0];
+        let (place_id, affordance_id) = (invoice.id, invoice.affordances[1].id);
+
+        app.state.selection = Some(Selection::Affordance { place_id, affordance_id });
+        assert_eq!(app.add_comment("  "), None);
+        assert_eq!(app.add_comment("Which card? "), Some("Pay now".to_string()));
+        assert_eq!(app.selected_comments()[0].author, "ryan");
+        assert_eq!(app.selected_comments()[0].text, "Which card?");
+
+        app.state.selection = Some(Selection::Place(place_id));
+        assert!(app.selected_comments().is_empty());
+        app.add_comment("Fine");
+        assert_eq!(app.breadboard.places[0].comments.len(), 1);
+    }
+}
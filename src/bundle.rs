@@ -0,0 +1,139 @@
+use anyhow::{bail, Context, Result};
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use crate::models::Breadboard;
+
+// A .bboard bundle is a zip of the board and the files its places have
+// attached, so a board can be shared with its sketches as a single file.
+// Inside, the board is board.toml and the files sit in attachments/, which
+// the board's attachment paths point to.
+const BOARD_ENTRY: &str = "board.toml";
+const ATTACHMENTS_DIR: &str = "attachments";
+
+pub fn is_bundle(path: &Path) -> bool {
+    path.extension().is_some_and(|extension| extension == "bboard")
+}
+
+// Where an attachment of the board in `board_dir` is on disk. Relative
+// paths are relative to the board file.
+pub fn resolve(board_dir: &Path, attachment: &str) -> PathBuf {
+    board_dir.join(attachment)
+}
+
+// Zip `breadboard` and its attachments into `bundle`, returning how many
+// files were attached. Every attachment has to be there.
+pub fn write(breadboard: &Breadboard, board_dir: &Path, bundle: &Path) -> Result<usize> {
+    let mut board = breadboard.clone();
+    let mut files: Vec<(String, PathBuf)> = Vec::new();
+    for attachment in board.places.iter_mut().flat_map(|p| &mut p.attachments) {
+        let source = resolve(board_dir, attachment);
+        if !source.is_file() {
+            bail!("attachment {} not found", source.display());
+        }
+        let entry = match files.iter().find(|(_, path)| *path == source) {
+            Some((entry, _)) => entry.clone(),
+            None => {
+                let name = source.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+                // Files of the same name from different folders are numbered
+                let mut entry = format!("{}/{}", ATTACHMENTS_DIR, name);
+                let mut copy = 1;
+                while files.iter().any(|(e, _)| *e == entry) {
+                    copy += 1;
+                    entry = format!("{}/{}-{}", ATTACHMENTS_DIR, copy, name);
+                }
+                files.push((entry.clone(), source));
+                entry
+            }
+        };
+        *attachment = entry;
+    }
+
+    let file = File::create(bundle).with_context(|| format!("Failed to create {}", bundle.display()))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default();
+    zip.start_file(BOARD_ENTRY, options)?;
+    zip.write_all(toml::to_string_pretty(&board).context("Failed to serialize breadboard to TOML")?.as_bytes())?;
+    for (entry, source) in &files {
+        zip.start_file(entry.as_str(), options)?;
+        zip.write_all(&fs::read(source).with_context(|| format!("Failed to read {}", source.display()))?)?;
+    }
+    zip.finish()?;
+    Ok(files.len())
+}
+
+// Unpack `bundle` into a folder named after it, next to it, and return the
+// board file in there: billing.bboard becomes billing/billing.toml. An
+// existing folder is left alone rather than overwritten.
+pub fn unpack(bundle: &Path) -> Result<PathBuf> {
+    let dir = bundle.with_extension("");
+    if dir.exists() {
+        bail!("{} already exists: open the board in it, or move it away to unpack the bundle again", dir.display());
+    }
+    let file = File::open(bundle).with_context(|| format!("Failed to open {}", bundle.display()))?;
+    let mut zip = zip::ZipArchive::new(file).context("Not a bboard bundle")?;
+    if zip.index_for_name(BOARD_ENTRY).is_none() {
+        bail!("{} has no {}", bundle.display(), BOARD_ENTRY);
+    }
+
+    let stem = dir.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    let board = dir.join(format!("{}.toml", stem));
+    for index in 0..zip.len() {
+        let mut entry = zip.by_index(index)?;
+        // Entries reaching outside the folder are skipped
+        let Some(name) = entry.enclosed_name() else {
+            continue;
+        };
+        if entry.is_dir() {
+            continue;
+        }
+        let target = if name == Path::new(BOARD_ENTRY) { board.clone() } else { dir.join(name) };
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut contents = Vec::new();
+        entry.read_to_end(&mut contents)?;
+        fs::write(&target, contents).with_context(|| format!("Failed to write {}", target.display()))?;
+    }
+    Ok(board)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Place;
+
+    #[test]
+    fn test_bundle_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("sketches")).unwrap();
+        fs::write(dir.path().join("sketches/invoice.png"), b"png").unwrap();
+        fs::write(dir.path().join("invoice.png"), b"other").unwrap();
+
+        let mut breadboard = Breadboard::new("Billing".to_string());
+        let mut invoice = Place::new(1, "Invoice".to_string());
+        invoice.attachments = vec!["sketches/invoice.png".to_string(), "invoice.png".to_string()];
+        breadboard.add_place(invoice);
+        let mut receipt = Place::new(2, "Receipt".to_string());
+        receipt.attachments = vec!["sketches/invoice.png".to_string()];
+        breadboard.add_place(receipt);
+
+        let bundle = dir.path().join("billing.bboard");
+        assert!(is_bundle(&bundle));
+        assert_eq!(write(&breadboard, dir.path(), &bundle).unwrap(), 2);
+
+        let board = unpack(&bundle).unwrap();
+        assert_eq!(board, dir.path().join("billing/billing.toml"));
+        let unpacked: Breadboard = toml::from_str(&fs::read_to_string(&board).unwrap()).unwrap();
+        assert_eq!(unpacked.places[0].attachments, vec!["attachments/invoice.png", "attachments/2-invoice.png"]);
+        assert_eq!(unpacked.places[1].attachments, vec!["attachments/invoice.png"]);
+        assert_eq!(fs::read(dir.path().join("billing/attachments/2-invoice.png")).unwrap(), b"other");
+
+        // Unpacking again doesn't overwrite the folder
+        assert!(unpack(&bundle).is_err());
+
+        breadboard.places[1].attachments.push("missing.png".to_string());
+        assert!(write(&breadboard, dir.path(), &bundle).is_err());
+    }
+}
@@ -0,0 +1,164 @@
+use crate::models::{Affordance, Attribution, Breadboard, ChangeEntry, Place};
+
+// The board's own history: every save adds a line saying who changed what,
+// so a place that vanished between meetings can be traced to a save.
+
+// What changed from `before` (the board as last saved, if it ever was) to
+// `after`, in a few words. None when nothing did.
+pub fn summarize(before: Option<&Breadboard>, after: &Breadboard) -> Option<String> {
+    let Some(before) = before else {
+        return Some(format!("created with {} places", after.places.len()));
+    };
+
+    let mut changes = Vec::new();
+    if before.name != after.name {
+        changes.push(format!("renamed the board to {}", after.name));
+    }
+    for place in &after.places {
+        match before.find_place(&place.id) {
+            None => changes.push(format!("added {}", place.name)),
+            Some(old) if old.name != place.name => changes.push(format!("renamed {} to {}", old.name, place.name)),
+            Some(_) => {}
+        }
+    }
+    for place in before.places.iter().filter(|p| after.find_place(&p.id).is_none()) {
+        changes.push(format!("removed {}", place.name));
+    }
+
+    let affordances = |board: &Breadboard| -> Vec<(u32, String)> {
+        board.places.iter()
+            .flat_map(|p| &p.affordances)
+            .map(|a| (a.id, toml::to_string(a).unwrap_or_default()))
+            .collect()
+    };
+    let (old, new) = (affordances(before), affordances(after));
+    let added = new.iter().filter(|(id, _)| !old.iter().any(|(o, _)| o == id)).count();
+    let removed = old.iter().filter(|(id, _)| !new.iter().any(|(n, _)| n == id)).count();
+    let changed = new.iter().filter(|(id, text)| old.iter().any(|(o, old_text)| o == id && old_text != text)).count();
+    for (count, what) in [(added, "added"), (removed, "removed"), (changed, "changed")] {
+        match count {
+            0 => {}
+            1 => changes.push(format!("1 affordance {}", what)),
+            n => changes.push(format!("{} affordances {}", n, what)),
+        }
+    }
+
+    if changes.is_empty() {
+        // Notes, groups, the happy path and the like
+        let unchanged = |board: &Breadboard| {
+            let mut board = board.clone();
+            board.changelog.clear();
+            toml::to_string(&board).ok()
+        };
+        if unchanged(before) == unchanged(after) {
+            return None;
+        }
+        changes.push("edited details".to_string());
+    }
+    Some(changes.join(", "))
+}
+
+// Add what changed since `before` to the board's change log, signed by
+// `author`, and stamp the places and affordances that changed with who and when
+pub fn record(before: Option<&Breadboard>, after: &mut Breadboard, author: String) {
+    if let Some(summary) = summarize(before, after) {
+        stamp(before, after, &author);
+        after.changelog.push(ChangeEntry::new(author, summary));
+    }
+}
+
+fn stamp(before: Option<&Breadboard>, after: &mut Breadboard, author: &str) {
+    // Places are compared without their affordances, which are stamped apart
+    let bare = |place: &Place| Place { affordances: Vec::new(), attribution: Attribution::default(), ..place.clone() };
+    let unstamped = |affordance: &Affordance| Affordance { attribution: Attribution::default(), ..affordance.clone() };
+    let old_affordances: Vec<&Affordance> = before.iter().flat_map(|b| &b.places).flat_map(|p| &p.affordances).collect();
+
+    for place in &mut after.places {
+        match before.and_then(|b| b.find_place(&place.id)) {
+            None => place.attribution = Attribution::created(author.to_string()),
+            Some(old) if toml::to_string(&bare(old)).ok() != toml::to_string(&bare(place)).ok() => {
+                place.attribution.modify(author.to_string());
+            }
+            Some(_) => {}
+        }
+        for affordance in &mut place.affordances {
+            match old_affordances.iter().find(|a| a.id == affordance.id) {
+                None => affordance.attribution = Attribution::created(author.to_string()),
+                Some(old) if toml::to_string(&unstamped(old)).ok() != toml::to_string(&unstamped(affordance)).ok() => {
+                    affordance.attribution.modify(author.to_string());
+                }
+                Some(_) => {}
+            }
+        }
+    }
+}
+
+// The change log as the history panel shows it, newest first
+pub fn describe(breadboard: &Breadboard) -> String {
+    breadboard.changelog.iter().rev()
+        .map(ChangeEntry::describe)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{testing, Affordance, Place};
+
+    fn board() -> Breadboard {
+        let mut breadboard = testing::board(&["Invoice", "Help"], &[]);
+        breadboard.name = "Billing".to_string();
+        let pay = breadboard.generate_affordance_id();
+        breadboard.places[0].add_affordance(Affordance::new(pay, "Pay".to_string()));
+        breadboard
+    }
+
+    #[test]
+    fn test_summarize_changes() {
+        let before = board();
+        assert_eq!(summarize(None, &before).as_deref(), Some("created with 2 places"));
+        assert_eq!(summarize(Some(&before), &before), None);
+
+        let mut after = before.clone();
+        after.places.retain(|p| p.id != 2);
+        after.places[0].name = "Bill".to_string();
+        after.add_place(Place::new(3, "Receipt".to_string()));
+        after.places[0].affordances[0].connects_to = Some(3);
+        after.places[0].add_affordance(Affordance::new(2, "Print".to_string()));
+        assert_eq!(
+            summarize(Some(&before), &after).as_deref(),
+            Some("renamed Invoice to Bill, added Receipt, removed Help, 1 affordance added, 1 affordance changed"),
+        );
+
+        let mut noted = before.clone();
+        noted.places[1].notes = Some("FAQ".to_string());
+        assert_eq!(summarize(Some(&before), &noted).as_deref(), Some("edited details"));
+    }
+
+    #[test]
+    fn test_record_appends() {
+        let before = board();
+        let mut after = before.clone();
+        after.places[1].name = "Support".to_string();
+        record(Some(&before), &mut after, "sam".to_string());
+        record(Some(&after.clone()), &mut after, "sam".to_string());
+        assert_eq!(after.changelog.len(), 1);
+        assert_eq!(after.changelog[0].author, "sam");
+        assert!(describe(&after).ends_with("sam: renamed Help to Support"));
+
+        // Only what changed is stamped
+        assert_eq!(after.places[1].attribution.modified_by.as_deref(), Some("sam"));
+        assert_eq!(after.places[0].attribution, Attribution::default());
+        assert_eq!(after.places[0].affordances[0].attribution, Attribution::default());
+
+        let saved = after.clone();
+        after.places[0].affordances[0].status = crate::models::Status::Built;
+        after.places[0].add_affordance(Affordance::new(2, "Print".to_string()));
+        record(Some(&saved), &mut after, "kim".to_string());
+        assert_eq!(after.places[0].attribution, Attribution::default());
+        assert_eq!(after.places[0].affordances[0].attribution.modified_by.as_deref(), Some("kim"));
+        assert_eq!(after.places[0].affordances[1].attribution.created_by.as_deref(), Some("kim"));
+        assert_eq!(after.places[0].affordances[1].attribution.modified_by, None);
+    }
+}
@@ -0,0 +1,1332 @@
+use crate::api::{Api, DEFAULT_ADDRESS};
+use crate::app::{App, ExportScope, Selection};
+use crate::export::{document_board, render_document, tasks, Document, Task};
+use crate::extract::{extract, replace_with_link};
+use crate::file::FileManager;
+use crate::import::{import, ImportFormat, ImportSummary};
+use crate::lint::{lint, Issue};
+use crate::locale::ENGLISH;
+use crate::logging;
+use crate::config::Paper;
+use crate::models::{Affordance, Breadboard, Place, ViewMode};
+use crate::rpc::Session;
+use crate::site;
+use crate::watch::{Target, Watcher};
+use crate::analysis::{group_progress, progress, random_walks, Progress};
+use crate::ui::{points_label, progress_lines, view_snapshot};
+use crate::workspace::Workspace;
+use anyhow::{anyhow, bail, Context, Result};
+use clap::error::ErrorKind;
+use clap::{Args, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+use std::io::IsTerminal;
+use std::path::Path;
+use std::process::Command;
+use tracing_subscriber::filter::LevelFilter;
+
+// Headless subcommands, run instead of the TUI. Each returns the process
+// exit code, one of the three below. With --json, each prints one JSON
+// document on stdout rather than text: its result, or {"error": ...} when it
+// couldn't run.
+
+pub const EXIT_OK: i32 = 0;
+pub const EXIT_PROBLEMS: i32 = 1; // Ran, and found problems: lint issues, places no walk reached
+pub const EXIT_ERROR: i32 = 2;    // Couldn't run: bad arguments, unreadable files
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+impl Format {
+    // What --json asks for
+    fn of(json: bool) -> Self {
+        if json { Format::Json } else { Format::Text }
+    }
+}
+
+// The TUI's color themes for --theme: just the one it has always had, so far
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub enum Theme {
+    #[default]
+    Default,
+}
+
+// The result as JSON on stdout
+fn print_json(value: &impl Serialize) -> i32 {
+    match serde_json::to_string_pretty(value) {
+        Ok(json) => {
+            println!("{}", json);
+            EXIT_OK
+        }
+        Err(e) => {
+            eprintln!("bboard: {}", e);
+            EXIT_ERROR
+        }
+    }
+}
+
+// What stopped a subcommand: on stderr, or with --json as its output
+fn fail(format: Format, message: String) -> i32 {
+    match format {
+        Format::Text => eprintln!("{}", message),
+        Format::Json => println!("{}", serde_json::json!({ "error": message })),
+    }
+    EXIT_ERROR
+}
+
+// Arguments that parsed but don't go together, with the command's usage
+// to put them right
+fn usage_error(format: Format, command: &str, e: anyhow::Error) -> i32 {
+    if format == Format::Text {
+        eprintln!("bboard {}: {:#}", command, e);
+        let mut cli = Cli::command();
+        cli.build();
+        if let Some(subcommand) = cli.find_subcommand_mut(command) {
+            eprintln!("{}", subcommand.render_usage());
+        }
+        return EXIT_ERROR;
+    }
+    fail(format, format!("bboard {}: {:#}", command, e))
+}
+
+#[derive(Serialize)]
+struct FileReport<'a> {
+    file: &'a str,
+    issues: Vec<Issue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>, // Why the file couldn't be checked
+}
+
+// The command line. Without a command, or with open, new or diff, bboard
+// starts the TUI; the other commands run without it. The global flags may
+// come before or after the command.
+#[derive(Debug, Parser)]
+#[command(name = "bboard", about = "Breadboard user flows in the terminal")]
+pub struct Cli {
+    #[arg(long, global = true, value_name = "FILE", help = "Config file to use instead of the usual one")]
+    pub config: Option<String>,
+    #[arg(long, global = true, help = "Open the board without letting it change")]
+    pub read_only: bool,
+    #[arg(long, global = true, value_name = "NAME", value_enum, help = "Color theme for the TUI")]
+    pub theme: Option<Theme>,
+    #[arg(long, global = true, value_name = "LEVEL", value_parser = logging::parse_level, help = "What to log: off, error, warn, info, debug or trace")]
+    pub log_level: Option<LevelFilter>,
+    #[arg(value_name = "BOARD", help = "Board to open")]
+    pub board: Option<String>,
+    #[command(subcommand)]
+    pub command: Option<Commands>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Commands {
+    #[command(about = "Open a board in the TUI")]
+    Open {
+        #[arg(value_name = "BOARD")]
+        board: Option<String>,
+    },
+    #[command(about = "Start a new board, saved to the file given")]
+    New {
+        #[arg(value_name = "BOARD")]
+        board: String,
+    },
+    #[command(visible_alias = "compare", about = "Show a board read-only beside an older version of it")]
+    Diff {
+        old: String,
+        new: String,
+    },
+    #[command(about = "Check boards for dead ends, orphans and broken rules")]
+    Lint(LintOptions),
+    #[command(about = "Print a board's outline or graph")]
+    Render(RenderOptions),
+    #[command(about = "Show how much of each board is built")]
+    Stats(StatsOptions),
+    #[command(about = "Walk random paths through a board")]
+    Walk(WalkOptions),
+    #[command(about = "Write a walkthrough, pitch, task list, print pages, web page or diagram")]
+    Export(ExportOptions),
+    #[command(about = "Build a board from a screen inventory, digraph or board")]
+    Import(ImportOptions),
+    #[command(about = "Copy or move a place and what it reaches to a new board")]
+    Extract(ExtractOptions),
+    #[command(about = "Publish a directory of boards as a static site")]
+    Site(SiteOptions),
+    #[command(about = "Re-export a board whenever it's saved")]
+    Watch(WatchOptions),
+    #[command(about = "Serve JSON-RPC on stdin and stdout")]
+    Rpc {
+        #[arg(value_name = "BOARD")]
+        file: Option<String>,
+    },
+    #[command(alias = "serve-api", about = "Serve the REST API")] // Its old name
+    Serve(ServeOptions),
+}
+
+// The command line, or an exit with clap's message when it's wrong. With
+// --json the message is the {"error": ...} document a command would print.
+pub fn parse() -> Cli {
+    let args: Vec<String> = std::env::args().collect();
+    match parse_from(&args) {
+        Ok(cli) => cli,
+        Err(e) if e.use_stderr() && args.iter().any(|arg| arg == "--json") => {
+            let message = e.to_string();
+            let first = message.lines().next().unwrap_or_default();
+            std::process::exit(fail(Format::Json, format!("bboard: {}", first.trim_start_matches("error: "))))
+        }
+        Err(e) => e.exit(),
+    }
+}
+
+fn parse_from<I, T>(args: I) -> Result<Cli, clap::Error>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<std::ffi::OsString> + Clone,
+{
+    let matches = Cli::command().try_get_matches_from(args)?;
+    let cli = Cli::from_arg_matches(&matches)?;
+    let command = matches.subcommand_name().unwrap_or("open");
+    if let Some(board) = cli.board.as_ref().filter(|_| cli.command.is_some()) {
+        return Err(Cli::command().error(
+            ErrorKind::ArgumentConflict,
+            format!("{} takes its boards after it, not {} before", command, board),
+        ));
+    }
+    if (cli.config.is_some() || cli.read_only) && !matches!(command, "open" | "new" | "diff") {
+        return Err(Cli::command().error(
+            ErrorKind::ArgumentConflict,
+            format!("--config and --read-only only apply to opening a board, not {}", command),
+        ));
+    }
+    Ok(cli)
+}
+
+// Run a headless command, returning the exit code
+pub fn run_headless(command: Commands) -> i32 {
+    match command {
+        Commands::Lint(options) => run_lint(&options),
+        Commands::Render(options) => run_render(&options),
+        Commands::Stats(options) => run_stats(&options),
+        Commands::Walk(options) => run_walk(&options),
+        Commands::Export(options) => run_export(&options),
+        Commands::Import(options) => run_import(&options),
+        Commands::Extract(options) => run_extract(&options),
+        Commands::Site(options) => run_site(&options),
+        Commands::Watch(options) => run_watch(options),
+        Commands::Rpc { file } => run_rpc(file.as_deref()),
+        Commands::Serve(options) => run_serve_api(options),
+        Commands::Open { .. } | Commands::New { .. } | Commands::Diff { .. } => unreachable!("{:?} opens the TUI", command),
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct LintOptions {
+    #[arg(long, value_enum, help = "Output format, overriding --json")]
+    format: Option<Format>,
+    #[arg(long, help = "Print JSON instead of text")]
+    json: bool,
+    #[arg(required = true, value_name = "FILE")]
+    files: Vec<String>,
+}
+
+// bboard lint [--format text|json] <file>...
+fn run_lint(options: &LintOptions) -> i32 {
+    let format = options.format.unwrap_or(Format::of(options.json));
+    let file_manager = FileManager::new();
+    let mut reports = Vec::new();
+    for file in &options.files {
+        match file_manager.load_from_file(file) {
+            Ok(breadboard) => reports.push(FileReport { file, issues: lint(&breadboard), error: None }),
+            Err(e) => {
+                if format == Format::Text {
+                    eprintln!("{}: {:#}", file, e);
+                }
+                reports.push(FileReport { file, issues: Vec::new(), error: Some(format!("{:#}", e)) });
+            }
+        }
+    }
+
+    match format {
+        Format::Text => {
+            for report in &reports {
+                for issue in &report.issues {
+                    println!("{}: {}", report.file, issue.message);
+                }
+            }
+        }
+        Format::Json => {
+            if print_json(&reports) != EXIT_OK {
+                return EXIT_ERROR;
+            }
+        }
+    }
+
+    if reports.iter().any(|r| r.error.is_some()) {
+        EXIT_ERROR
+    } else if reports.iter().any(|r| !r.issues.is_empty()) {
+        EXIT_PROBLEMS
+    } else {
+        EXIT_OK
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Args)]
+pub struct RenderOptions {
+    #[arg(long, help = "The graph instead of the outline")]
+    graph: bool,
+    #[arg(long, help = "The graph in a lane per group")]
+    swimlanes: bool,
+    #[arg(long, help = "One line per place")]
+    collapsed: bool,
+    #[arg(long, value_name = "QUERY", help = "Only what matches the query")]
+    filter: Option<String>,
+    #[arg(long, value_name = "NAME", help = "Only the place of that name")]
+    place: Option<String>,
+    #[arg(long, requires = "place", help = "With --place, also every place it leads to")]
+    reachable: bool,
+    #[arg(long, value_name = "COLUMNS", help = "Wrap at this width rather than the terminal's")]
+    width: Option<usize>,
+    #[arg(long, help = "Keep cut affordances")]
+    include_cut: bool,
+    #[arg(long, help = "Print JSON instead of text")]
+    json: bool,
+    #[arg(value_name = "FILE")]
+    file: String,
+}
+
+// What render and export print with --json: the text they'd print, and
+// where it came from
+#[derive(Serialize)]
+struct TextReport<'a> {
+    file: &'a str,
+    text: String,
+}
+
+// bboard render [--graph] [--swimlanes] [--collapsed] [--filter <query>] [--place <name> [--reachable]] [--width <columns>] [--include-cut] <file>
+fn run_render(options: &RenderOptions) -> i32 {
+    let format = Format::of(options.json);
+    match (render(options), format) {
+        (Ok(text), Format::Text) => {
+            print!("{}", text);
+            EXIT_OK
+        }
+        (Ok(text), Format::Json) => print_json(&TextReport { file: &options.file, text }),
+        (Err(e), _) => fail(format, format!("{}: {:#}", options.file, e)),
+    }
+}
+
+fn render(options: &RenderOptions) -> Result<String> {
+    if Workspace::is_workspace_file(Path::new(&options.file)) {
+        return render_workspace(options);
+    }
+
+    let mut app = App::new();
+    app.breadboard = FileManager::new().load_from_file(&options.file)?;
+    render_board(app, options)
+}
+
+fn render_board(mut app: App, options: &RenderOptions) -> Result<String> {
+    app.state.collapsed = options.collapsed;
+    app.state.filter = options.filter.clone();
+    app.config.export_cut = options.include_cut;
+    if options.graph || options.swimlanes {
+        app.state.view = ViewMode::Graph;
+    }
+    app.state.swimlanes = options.swimlanes;
+
+    let app = scope_to_place(app, options.place.as_deref(), options.reachable)?;
+    Ok(view_snapshot(&app, render_width(options)))
+}
+
+// Narrow an export to the named place, or with `reachable` to it and every
+// place it leads to
+fn scope_to_place(mut app: App, place: Option<&str>, reachable: bool) -> Result<App> {
+    let Some(name) = place else {
+        return Ok(app);
+    };
+    let id = app.breadboard.places.iter()
+        .find(|p| p.name.trim().to_lowercase() == name.trim().to_lowercase())
+        .map(|p| p.id)
+        .ok_or_else(|| anyhow!("no place named \"{}\"", name))?;
+    app.state.selection = Some(Selection::Place(id));
+    app.scoped(if reachable { ExportScope::Subtree } else { ExportScope::Selection })
+}
+
+// Wrap like the TUI on a terminal; keep lines whole when piped
+fn render_width(options: &RenderOptions) -> usize {
+    options.width.unwrap_or_else(|| {
+        if std::io::stdout().is_terminal() {
+            crossterm::terminal::size().map(|(w, _)| w as usize).unwrap_or(0)
+        } else {
+            0
+        }
+    })
+}
+
+// Every board of a workspace one after the other, each under its title.
+// Cross-board links show the board and place they lead to.
+fn render_workspace(options: &RenderOptions) -> Result<String> {
+    if options.place.is_some() {
+        bail!("--place needs a single board, not a workspace");
+    }
+    let workspace = Workspace::load(Path::new(&options.file))?;
+    let file_manager = FileManager::new();
+
+    let mut sections = Vec::new();
+    for board in &workspace.boards {
+        let path = workspace.path_of(board).to_string_lossy().to_string();
+        let mut app = App::new();
+        app.breadboard = file_manager.load_from_file(&path).map_err(|e| anyhow!("{}: {:#}", board.file, e))?;
+        app.state.external_places = workspace.places_outside(&file_manager, Some(&path));
+        sections.push(format!("# {}\n\n{}", board.title, render_board(app, options)?));
+    }
+
+    Ok(sections.join("\n"))
+}
+
+// One board's stats, as --json prints them
+#[derive(Serialize)]
+struct StatsReport<'a> {
+    file: &'a str,
+    name: String,
+    total: Progress,
+    places: Vec<NamedProgress>,
+    groups: Vec<NamedProgress>,
+}
+
+#[derive(Serialize)]
+struct NamedProgress {
+    name: String,
+    #[serde(flatten)]
+    progress: Progress,
+}
+
+#[derive(Debug, Args)]
+pub struct StatsOptions {
+    #[arg(long, help = "Print JSON instead of text")]
+    json: bool,
+    #[arg(required = true, value_name = "FILE")]
+    files: Vec<String>,
+}
+
+// bboard stats <file>...: how much of each board is built, place by place
+fn run_stats(options: &StatsOptions) -> i32 {
+    let format = Format::of(options.json);
+    let file_manager = FileManager::new();
+    let mut boards = Vec::new();
+    for file in &options.files {
+        match file_manager.load_from_file(file) {
+            Ok(breadboard) => boards.push((file.as_str(), breadboard)),
+            Err(e) => return fail(format, format!("{}: {:#}", file, e)),
+        }
+    }
+    match format {
+        Format::Text => {
+            print!("{}", boards.iter().map(|(_, breadboard)| stats(breadboard)).collect::<Vec<_>>().join("\n"));
+            EXIT_OK
+        }
+        Format::Json => print_json(&boards.iter().map(|(file, breadboard)| stats_report(file, breadboard)).collect::<Vec<_>>()),
+    }
+}
+
+fn stats(breadboard: &Breadboard) -> String {
+    let total = progress(breadboard).1;
+
+    let mut report = format!(
+        "{}: {}/{} affordances built, {} building, {} planned",
+        breadboard.name, total.built, total.total(), total.building, total.planned,
+    );
+    if total.cut > 0 {
+        report.push_str(&format!(", {} cut", total.cut));
+    }
+    if total.points > 0.0 {
+        report.push_str(&format!(", {} estimated", points_label(total.points, &ENGLISH)));
+    }
+    report.push_str("\n\n");
+    for line in progress_lines(breadboard, &ENGLISH) {
+        report.push_str(&line);
+        report.push('\n');
+    }
+    report
+}
+
+fn stats_report<'a>(file: &'a str, breadboard: &Breadboard) -> StatsReport<'a> {
+    let (places, total) = progress(breadboard);
+    StatsReport {
+        file,
+        name: breadboard.name.clone(),
+        total,
+        places: places.into_iter()
+            .filter_map(|(id, progress)| breadboard.find_place(&id).map(|place| NamedProgress { name: place.name.clone(), progress }))
+            .collect(),
+        groups: group_progress(breadboard).into_iter().map(|(name, progress)| NamedProgress { name, progress }).collect(),
+    }
+}
+
+#[derive(Debug, PartialEq, Args)]
+pub struct WalkOptions {
+    #[arg(long, default_value_t = 1000, value_parser = positive, help = "How many walks to take")]
+    walks: usize,
+    #[arg(long, default_value_t = 50, value_parser = positive, help = "Steps each walk takes at most")]
+    steps: usize,
+    #[arg(long, help = "Repeat a run; otherwise every run walks differently")]
+    seed: Option<u64>,
+    #[arg(long, help = "Print JSON instead of text")]
+    json: bool,
+    #[arg(value_name = "FILE")]
+    file: String,
+}
+
+fn positive(value: &str) -> Result<usize> {
+    value.parse().ok().filter(|n| *n > 0).ok_or_else(|| anyhow!("needs a positive number"))
+}
+
+// Places and affordances reached by fewer than this share of walks are
+// listed as rarely visited
+const RARE_WALKS_PERCENT: usize = 1;
+
+// What random walks found, as the text and --json say it
+#[derive(Debug, Serialize)]
+struct WalkReport {
+    board: String,
+    entry: String,
+    walks: usize,
+    steps: usize,
+    seed: u64,
+    never_visited: Visits,
+    rarely_visited: Visits, // Visited, but by under RARE_WALKS_PERCENT of walks
+}
+
+#[derive(Debug, Default, Serialize)]
+struct Visits {
+    places: Vec<String>,
+    affordances: Vec<PlaceAffordance>,
+}
+
+#[derive(Debug, Serialize)]
+struct PlaceAffordance {
+    place: String,
+    affordance: String,
+}
+
+impl Visits {
+    fn is_empty(&self) -> bool {
+        self.places.is_empty() && self.affordances.is_empty()
+    }
+}
+
+// bboard walk [--walks <n>] [--steps <n>] [--seed <n>] <file>
+fn run_walk(options: &WalkOptions) -> i32 {
+    let format = Format::of(options.json);
+    match walk(options) {
+        Ok(report) => {
+            let code = match format {
+                Format::Text => {
+                    print!("{}", walk_text(&report));
+                    EXIT_OK
+                }
+                Format::Json => print_json(&report),
+            };
+            match code {
+                EXIT_OK if !report.never_visited.is_empty() => EXIT_PROBLEMS,
+                code => code,
+            }
+        }
+        Err(e) => fail(format, format!("{}: {:#}", options.file, e)),
+    }
+}
+
+fn walk(options: &WalkOptions) -> Result<WalkReport> {
+    let breadboard = FileManager::new().load_from_file(&options.file)?;
+    let Some(entry) = breadboard.places.first() else {
+        bail!("the board has no places");
+    };
+    let seed = options.seed.unwrap_or_else(|| chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default() as u64);
+    let walks = random_walks(&breadboard, options.walks, options.steps, seed);
+
+    let rare = (options.walks * RARE_WALKS_PERCENT).div_ceil(100);
+    let (never_places, never_affordances) = walks.fewer_than(&breadboard, 1);
+    let (rare_places, rare_affordances) = walks.fewer_than(&breadboard, rare);
+    let visits = |places: Vec<&Place>, affordances: Vec<(&Place, &Affordance)>| Visits {
+        places: places.into_iter().map(|p| p.name.clone()).collect(),
+        affordances: affordances.into_iter()
+            .map(|(p, a)| PlaceAffordance { place: p.name.clone(), affordance: a.name.clone() })
+            .collect(),
+    };
+    Ok(WalkReport {
+        board: breadboard.name.clone(),
+        entry: entry.name.clone(),
+        walks: options.walks,
+        steps: options.steps,
+        seed,
+        rarely_visited: visits(
+            rare_places.into_iter().filter(|p| !never_places.iter().any(|n| n.id == p.id)).collect(),
+            rare_affordances.into_iter().filter(|(_, a)| !never_affordances.iter().any(|(_, n)| n.id == a.id)).collect(),
+        ),
+        never_visited: visits(never_places, never_affordances),
+    })
+}
+
+fn walk_text(report: &WalkReport) -> String {
+    let mut text = format!(
+        "{}: {} random walks of up to {} steps from {}\n",
+        report.board, report.walks, report.steps, report.entry,
+    );
+    let sections = [
+        ("Never visited".to_string(), &report.never_visited),
+        (format!("Rarely visited (by under {}% of walks)", RARE_WALKS_PERCENT), &report.rarely_visited),
+    ];
+    for (title, visits) in sections {
+        if visits.is_empty() {
+            continue;
+        }
+        text.push_str(&format!("\n{}:\n", title));
+        for place in &visits.places {
+            text.push_str(&format!("  {}\n", place));
+        }
+        for PlaceAffordance { place, affordance } in &visits.affordances {
+            text.push_str(&format!("  {}: {}\n", place, affordance));
+        }
+    }
+    if report.never_visited.is_empty() {
+        text.push_str("\nEvery place and affordance was visited\n");
+    }
+    text
+}
+
+#[derive(Debug, PartialEq, Args)]
+pub struct ExportOptions {
+    #[arg(long = "format", value_name = "FORMAT", value_parser = document, help = "walkthrough, pitch, tasks, print, html, mermaid or svg")]
+    document: Document,
+    #[arg(long, help = "With --format tasks, a task per affordance rather than per place")]
+    per_affordance: bool,
+    #[arg(long, help = "Create GitHub issues instead of printing the task list")]
+    gh: bool,
+    #[arg(long, requires = "gh", value_name = "OWNER/NAME", help = "Repository for --gh, when not the current one")]
+    repo: Option<String>,
+    #[arg(long, value_name = "SIZE", value_parser = paper, help = "Page size for --format print, a4 or letter, instead of the config's")]
+    paper: Option<Paper>,
+    #[arg(long, value_name = "QUERY", help = "Only what matches the query")]
+    filter: Option<String>,
+    #[arg(long, value_name = "NAME", help = "Only the place of that name")]
+    place: Option<String>,
+    #[arg(long, requires = "place", help = "With --place, also every place it leads to")]
+    reachable: bool,
+    #[arg(long, help = "Keep cut affordances")]
+    include_cut: bool,
+    #[arg(long, help = "Print JSON instead of text")]
+    json: bool,
+    #[arg(value_name = "FILE")]
+    file: String,
+}
+
+impl ExportOptions {
+    // The document to write, checking the options only some take
+    fn document(&self) -> Result<Document> {
+        let document = match self.document {
+            Document::Tasks { .. } => Document::Tasks { per_affordance: self.per_affordance },
+            _ if self.per_affordance || self.gh => bail!("--per-affordance and --gh only apply to --format tasks"),
+            other => other,
+        };
+        if self.paper.is_some() && document != Document::Print {
+            bail!("--paper only applies to --format print");
+        }
+        Ok(document)
+    }
+}
+
+fn document(name: &str) -> Result<Document> {
+    Document::parse(name).ok_or_else(|| anyhow!("unknown format {}", name))
+}
+
+fn paper(name: &str) -> Result<Paper> {
+    match name {
+        "a4" => Ok(Paper::A4),
+        "letter" => Ok(Paper::Letter),
+        other => bail!("unknown paper size {}", other),
+    }
+}
+
+// bboard export --format <format> [options] <file>: a document written from
+// the board, on stdout. With --gh, each open task becomes a GitHub issue.
+fn run_export(options: &ExportOptions) -> i32 {
+    let format = Format::of(options.json);
+    let document = match options.document() {
+        Ok(document) => document,
+        Err(e) => return usage_error(format, "export", e),
+    };
+
+    let mut app = App::new();
+    match FileManager::new().load_from_file(&options.file) {
+        Ok(breadboard) => app.breadboard = breadboard,
+        Err(e) => return fail(format, format!("{}: {:#}", options.file, e)),
+    }
+    app.config.export_cut = options.include_cut;
+    if let Some(paper) = options.paper {
+        app.config.paper = paper;
+    }
+    app.state.filter = options.filter.clone();
+
+    let scoped = match &options.place {
+        Some(_) => scope_to_place(app, options.place.as_deref(), options.reachable),
+        None if options.filter.is_some() => app.scoped(ExportScope::Filter),
+        None => Ok(app),
+    };
+    let app = match scoped {
+        Ok(app) => app,
+        Err(e) => return fail(format, format!("{}: {:#}", options.file, e)),
+    };
+
+    match (document, format) {
+        (Document::Tasks { per_affordance }, _) if options.gh => create_issues(&app, per_affordance, options.repo.as_deref(), format),
+        (document, Format::Text) => {
+            print!("{}", render_document(&app, document));
+            EXIT_OK
+        }
+        (document, Format::Json) => print_json(&TextReport { file: &options.file, text: render_document(&app, document) }),
+    }
+}
+
+// One issue per open task, printing each issue's URL, or with --json the
+// list of them
+fn create_issues(app: &App, per_affordance: bool, repo: Option<&str>, format: Format) -> i32 {
+    let mut urls = Vec::new();
+    for task in tasks(&document_board(app), per_affordance).iter().filter(|task| !task.done) {
+        match create_issue(task, repo) {
+            Ok(url) if format == Format::Text => println!("{}", url),
+            Ok(url) => urls.push(url),
+            Err(e) => return fail(format, format!("bboard export: {}: {:#}", task.title, e)),
+        }
+    }
+    match format {
+        Format::Text => EXIT_OK,
+        Format::Json => print_json(&serde_json::json!({ "issues": urls })),
+    }
+}
+
+fn issue_args(task: &Task, repo: Option<&str>) -> Vec<String> {
+    let mut args = vec!["issue".to_string(), "create".to_string(), "--title".to_string(), task.title.clone(), "--body".to_string(), task.body()];
+    if let Some(repo) = repo {
+        args.extend(["--repo".to_string(), repo.to_string()]);
+    }
+    args
+}
+
+// Create one issue with the GitHub CLI; returns the URL it prints
+fn create_issue(task: &Task, repo: Option<&str>) -> Result<String> {
+    let output = Command::new("gh").args(issue_args(task, repo)).output()
+        .context("Failed to run gh (is the GitHub CLI installed?)")?;
+    if !output.status.success() {
+        bail!("gh failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[derive(Debug, PartialEq, Args)]
+pub struct ImportOptions {
+    #[arg(long, value_name = "BOARD", help = "Board to merge into and save, rather than printing a new one")]
+    into: Option<String>,
+    #[arg(long, value_name = "NAME", help = "Group for the places the import adds")]
+    group: Option<String>,
+    #[arg(long, help = "Print JSON instead of text")]
+    json: bool,
+    #[arg(value_name = "FILE", help = "A .csv screen inventory, .dot digraph, .md outline or .toml board")]
+    file: String,
+}
+
+impl ImportOptions {
+    // A blank --group is no group
+    fn group(&self) -> Option<&str> {
+        self.group.as_deref().map(str::trim).filter(|group| !group.is_empty())
+    }
+}
+
+// bboard import [options] <file>: a new board built from a screen inventory,
+// a Graphviz digraph or another board, as TOML on stdout. With --into, it's
+// merged into an existing board instead, which is saved in place.
+fn run_import(options: &ImportOptions) -> i32 {
+    let format = Format::of(options.json);
+
+    // With --json, the new board is printed as JSON, and a merge as what it added
+    match &options.into {
+        Some(into) => match (merge_into_board(&options.file, into, options.group()), format) {
+            (Ok(summary), Format::Text) => {
+                println!("Imported {} into {}", summary.describe(&ENGLISH), into);
+                EXIT_OK
+            }
+            (Ok(summary), Format::Json) => print_json(&summary),
+            (Err(e), _) => fail(format, format!("{}: {:#}", options.file, e)),
+        },
+        None => match (import_board(&options.file, options.group()), format) {
+            (Ok(breadboard), Format::Text) => match toml::to_string_pretty(&breadboard) {
+                Ok(text) => {
+                    print!("{}", text);
+                    EXIT_OK
+                }
+                Err(e) => fail(format, format!("{}: {}", options.file, e)),
+            },
+            (Ok(breadboard), Format::Json) => print_json(&breadboard),
+            (Err(e), _) => fail(format, format!("{}: {:#}", options.file, e)),
+        },
+    }
+}
+
+fn read_import(file: &str) -> Result<(ImportFormat, String)> {
+    let format = ImportFormat::from_path(file)?;
+    let text = std::fs::read_to_string(file).context("Failed to read file")?;
+    Ok((format, text))
+}
+
+fn import_board(file: &str, group: Option<&str>) -> Result<Breadboard> {
+    let (format, text) = read_import(file)?;
+    let name = Path::new(file).file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    let mut breadboard = Breadboard::new(name);
+    import(&mut breadboard, format, &text, group)?;
+    Ok(breadboard)
+}
+
+fn merge_into_board(file: &str, into: &str, group: Option<&str>) -> Result<ImportSummary> {
+    let (format, text) = read_import(file)?;
+    let file_manager = FileManager::new();
+    let mut breadboard = file_manager.load_from_file(into).with_context(|| format!("Failed to load {}", into))?;
+    let summary = import(&mut breadboard, format, &text, group)?;
+    file_manager.save_to_file(&breadboard, into)?;
+    Ok(summary)
+}
+
+#[derive(Debug, Default, PartialEq, Args)]
+pub struct ExtractOptions {
+    #[arg(long, value_name = "NAME", help = "The place to start from")]
+    place: String,
+    #[arg(long, help = "Take the places off the board too, linking to them")]
+    replace: bool,
+    #[arg(long, help = "Print JSON instead of text")]
+    json: bool,
+    #[arg(value_name = "BOARD")]
+    file: String,
+    #[arg(value_name = "NEW")]
+    to: String,
+}
+
+// bboard extract --place <name> [--replace] <board> <new>: the place and every
+// place reachable from it, written as a new board. With --replace they're
+// moved: taken off the original, whose connections into them become
+// cross-board links.
+fn run_extract(options: &ExtractOptions) -> i32 {
+    let format = Format::of(options.json);
+    match (extract_board(options), format) {
+        (Ok(count), Format::Text) => {
+            println!("Extracted {} places to {}", count, options.to);
+            EXIT_OK
+        }
+        (Ok(count), Format::Json) => print_json(&serde_json::json!({ "to": options.to, "places": count, "replaced": options.replace })),
+        (Err(e), _) => fail(format, format!("{}: {:#}", options.file, e)),
+    }
+}
+
+fn extract_board(options: &ExtractOptions) -> Result<usize> {
+    let file_manager = FileManager::new();
+    let mut breadboard = file_manager.load_from_file(&options.file)?;
+    let place = breadboard.places.iter()
+        .find(|p| p.name.trim().to_lowercase() == options.place.trim().to_lowercase())
+        .ok_or_else(|| anyhow!("no place named \"{}\"", options.place))?;
+    let extracted = extract(&breadboard, place.id, place.name.clone());
+    file_manager.save_to_file(&extracted, &options.to)?;
+
+    if options.replace {
+        // Links name the new board relative to the original, as a workspace
+        // next to them lists it
+        let dir = Path::new(&options.file).parent().unwrap_or(Path::new(""));
+        let board = Path::new(&options.to).strip_prefix(dir).map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_else(|_| options.to.clone());
+        replace_with_link(&mut breadboard, &extracted, &board);
+        file_manager.save_to_file(&breadboard, &options.file)?;
+    }
+    Ok(extracted.places.len())
+}
+
+#[derive(Debug, PartialEq, Args)]
+pub struct SiteOptions {
+    #[arg(short, long = "output", default_value = "public", value_name = "OUT", help = "Directory to write the site to")]
+    out: String,
+    #[arg(long, help = "Print JSON instead of text")]
+    json: bool,
+    #[arg(value_name = "DIR")]
+    dir: String,
+}
+
+// bboard site <dir> [-o <out>]: the boards in the directory as a static
+// site, written to ./public unless told otherwise
+fn run_site(options: &SiteOptions) -> i32 {
+    let (format, out) = (Format::of(options.json), &options.out);
+    match (site::build(Path::new(&options.dir), Path::new(out)), format) {
+        (Ok(report), Format::Text) => {
+            for (file, reason) in &report.skipped {
+                eprintln!("Skipped {}: {}", file, reason);
+            }
+            let boards = if report.pages == 1 { "board" } else { "boards" };
+            println!("Wrote {} {} and an index to {}", report.pages, boards, out);
+            EXIT_OK
+        }
+        (Ok(report), Format::Json) => {
+            let skipped: Vec<_> = report.skipped.iter()
+                .map(|(file, reason)| serde_json::json!({ "file": file, "reason": reason }))
+                .collect();
+            print_json(&serde_json::json!({ "out": out, "pages": report.pages, "skipped": skipped }))
+        }
+        (Err(e), _) => fail(format, format!("bboard site: {:#}", e)),
+    }
+}
+
+#[derive(Debug, PartialEq, Args)]
+pub struct WatchOptions {
+    #[arg(long = "export", required = true, value_name = "FORMAT:FILE", value_parser = Target::parse, help = "An export format, or text, and the file to write it to")]
+    targets: Vec<Target>,
+    #[arg(long, help = "Print a line of JSON for each file written or error")]
+    json: bool,
+    #[arg(value_name = "BOARD")]
+    file: String,
+}
+
+// How often bboard watch looks at the board's modification time
+const WATCH_INTERVAL: std::time::Duration = std::time::Duration::from_millis(500);
+
+// bboard watch --export <format>:<file>... <board>: write the exports, then
+// write them again whenever the board is saved, until interrupted. A board
+// that doesn't parse mid-edit is reported and watched on.
+fn run_watch(options: WatchOptions) -> i32 {
+    let format = Format::of(options.json);
+    let mut watcher = Watcher::new(options.file.into(), options.targets);
+    if let Err(e) = watcher.changed() {
+        return fail(format, format!("bboard watch: {:#}", e));
+    }
+    if format == Format::Text {
+        eprintln!("Watching {}: Ctrl+C stops", watcher.file().display());
+    }
+
+    // With --json, each write or error is a line of JSON as it happens
+    let wrote = |path: &Path| {
+        let time = chrono::Local::now();
+        match format {
+            Format::Text => println!("{} Wrote {}", time.format("%H:%M:%S"), path.display()),
+            Format::Json => println!("{}", serde_json::json!({ "time": time.to_rfc3339(), "wrote": path })),
+        }
+    };
+    let warn = |error: String| match format {
+        Format::Text => eprintln!("{}", error),
+        Format::Json => println!("{}", serde_json::json!({ "time": chrono::Local::now().to_rfc3339(), "error": error })),
+    };
+
+    let mut changed = true;
+    let mut failing = false; // So a missing file is reported once, not every poll
+    loop {
+        if changed {
+            match watcher.export() {
+                Ok(paths) => {
+                    for path in paths {
+                        wrote(&path);
+                    }
+                }
+                Err(e) => warn(format!("{}: {:#}", watcher.file().display(), e)),
+            }
+        }
+        std::thread::sleep(WATCH_INTERVAL);
+        changed = match watcher.changed() {
+            Ok(changed) => {
+                failing = false;
+                changed
+            }
+            Err(e) => {
+                if !failing {
+                    warn(format!("{:#}", e));
+                }
+                failing = true;
+                false
+            }
+        };
+    }
+}
+
+// bboard rpc [<file>]: JSON-RPC on stdin/stdout, optionally starting from a board
+fn run_rpc(file: Option<&str>) -> i32 {
+    let mut session = Session::new();
+    if let Some(file) = file {
+        if let Err(e) = session.load(file) {
+            eprintln!("{}: {:#}", file, e);
+            return EXIT_ERROR;
+        }
+    }
+
+    match session.serve(std::io::stdin().lock(), std::io::stdout().lock()) {
+        Ok(()) => EXIT_OK,
+        Err(e) => {
+            eprintln!("bboard rpc: {:#}", e);
+            EXIT_ERROR
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Args)]
+pub struct ServeOptions {
+    #[arg(long, default_value = DEFAULT_ADDRESS, value_name = "ADDRESS", help = "Address to listen on")]
+    bind: String,
+    #[arg(value_name = "BOARD")]
+    file: String,
+}
+
+// bboard serve [--bind <address>] <file>: REST API on localhost unless told otherwise
+fn run_serve_api(options: ServeOptions) -> i32 {
+    match Api::new(options.file).serve(&options.bind) {
+        Ok(()) => EXIT_OK,
+        Err(e) => {
+            eprintln!("bboard serve: {:#}", e);
+            EXIT_ERROR
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Status;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn parse(list: &[&str]) -> Result<Cli, clap::Error> {
+        parse_from(std::iter::once("bboard").chain(list.iter().copied()))
+    }
+
+    fn command(list: &[&str]) -> Commands {
+        parse(list).unwrap().command.expect("a command")
+    }
+
+    #[test]
+    fn test_parse_watch() {
+        let watch = |list: &[&str]| match command(list) {
+            Commands::Watch(options) => options,
+            other => panic!("{:?}", other),
+        };
+        let options = watch(&["watch", "--export", "html:docs/flow.html", "--export=text:flow.txt", "flow.toml"]);
+        assert_eq!((options.file.as_str(), options.targets.len()), ("flow.toml", 2));
+        let options = watch(&["watch", "--export", "mermaid:docs/flow.mmd", "--export", "svg:docs/flow.svg", "flow.toml"]);
+        assert_eq!(options.targets[1], Target::parse("svg:docs/flow.svg").unwrap());
+        assert!(parse(&["watch", "flow.toml"]).is_err());
+        assert!(parse(&["watch", "--export", "slides:a.html", "a.toml"]).is_err());
+        assert!(parse(&["watch", "--export", "html:a.html", "a.toml", "b.toml"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_site() {
+        let site = |list: &[&str]| match command(list) {
+            Commands::Site(options) => (options.dir, options.out),
+            other => panic!("{:?}", other),
+        };
+        assert_eq!(site(&["site", "boards", "-o", "site"]), ("boards".to_string(), "site".to_string()));
+        assert_eq!(site(&["site", "--output=site", "boards"]).1, "site");
+        assert_eq!(site(&["site", "boards"]).1, "public");
+        assert!(parse(&["site"]).is_err());
+        assert!(parse(&["site", "a", "b"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_launch() {
+        let cli = parse(&[]).unwrap();
+        assert!(cli.board.is_none() && cli.command.is_none() && !cli.read_only);
+        let cli = parse(&["a.toml", "--read-only"]).unwrap();
+        assert_eq!((cli.board.as_deref(), cli.read_only), (Some("a.toml"), true));
+        let cli = parse(&["--config", "c.toml", "new", "b.toml"]).unwrap();
+        assert_eq!(cli.config.as_deref(), Some("c.toml"));
+        assert!(matches!(cli.command, Some(Commands::New { board }) if board == "b.toml"));
+        assert!(matches!(command(&["compare", "old.toml", "new.toml"]), Commands::Diff { old, new } if old == "old.toml" && new == "new.toml"));
+        // Global flags may also come after the command
+        assert!(parse(&["open", "a.toml", "--config", "c.toml"]).unwrap().config.is_some());
+
+        // A subcommand's arguments are its own
+        assert!(matches!(command(&["serve-api", "--bind", "0.0.0.0:80", "a.toml"]), Commands::Serve(ServeOptions { bind, .. }) if bind == "0.0.0.0:80"));
+        assert_eq!(parse(&["--log-level", "debug", "lint", "a.toml"]).unwrap().log_level, Some(LevelFilter::DEBUG));
+        assert!(parse(&["--log-level=loud", "a.toml"]).is_err());
+        assert_eq!(parse(&["--help"]).unwrap_err().kind(), ErrorKind::DisplayHelp);
+        assert!(parse(&["--read-only", "lint", "a.toml"]).unwrap_err().to_string()
+            .contains("--config and --read-only only apply to opening a board, not lint"));
+        assert!(parse(&["diff", "old.toml"]).is_err());
+        assert!(parse(&["a.toml", "b.toml"]).is_err());
+        assert!(parse(&["a.toml", "lint", "b.toml"]).is_err());
+        assert!(parse(&["rpc", "--json"]).is_err());
+        // The default theme is the only one so far
+        assert_eq!(parse(&["--theme", "default", "a.toml"]).unwrap().theme, Some(Theme::Default));
+        assert_eq!(parse(&["--theme", "dark", "a.toml"]).unwrap_err().kind(), ErrorKind::InvalidValue);
+    }
+
+    #[test]
+    fn test_parse_lint() {
+        let Commands::Lint(options) = command(&["lint", "a.toml", "--format", "json", "b.toml"]) else {
+            panic!("not lint");
+        };
+        assert_eq!(options.format, Some(Format::Json));
+        assert_eq!(options.files, vec!["a.toml", "b.toml"]);
+
+        assert!(parse(&["lint"]).is_err());
+        assert!(parse(&["lint", "--format", "xml", "a.toml"]).is_err());
+        assert!(parse(&["lint", "--fix", "a.toml"]).is_err());
+        assert!(parse(&["lint", "a.toml", "--format"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_render() {
+        let render = |list: &[&str]| match command(list) {
+            Commands::Render(options) => options,
+            other => panic!("{:?}", other),
+        };
+        assert_eq!(render(&["render", "--collapsed", "--filter", "pay", "--place=Invoice", "flow.toml"]), RenderOptions {
+            file: "flow.toml".to_string(),
+            collapsed: true,
+            filter: Some("pay".to_string()),
+            place: Some("Invoice".to_string()),
+            ..Default::default()
+        });
+
+        assert!(render(&["render", "--include-cut", "a.toml"]).include_cut);
+        assert!(render(&["render", "--swimlanes", "a.toml"]).swimlanes);
+        assert!(render(&["render", "--place", "Invoice", "--reachable", "a.toml"]).reachable);
+        assert!(parse(&["render", "a.toml", "b.toml"]).is_err());
+        assert!(parse(&["render", "--width", "wide", "a.toml"]).is_err());
+        assert!(parse(&["render", "--reachable", "a.toml"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_export() {
+        let export = |list: &[&str]| match command(list) {
+            Commands::Export(options) => options,
+            other => panic!("{:?}", other),
+        };
+        let options = export(&["export", "--format=walkthrough", "--include-cut", "flow.toml"]);
+        assert_eq!((options.document().unwrap(), options.include_cut, options.file.as_str()), (Document::Walkthrough, true, "flow.toml"));
+
+        let options = export(&["export", "--format=pitch", "--place", "Setup", "--reachable", "--filter=pay", "flow.toml"]);
+        assert_eq!((options.place.as_deref(), options.reachable, options.filter.as_deref()), (Some("Setup"), true, Some("pay")));
+
+        let options = export(&["export", "--format", "tasks", "--per-affordance", "--gh", "--repo", "acme/shop", "flow.toml"]);
+        assert_eq!(options.document().unwrap(), Document::Tasks { per_affordance: true });
+        assert!(options.gh);
+        assert_eq!(options.repo.as_deref(), Some("acme/shop"));
+
+        assert_eq!(export(&["export", "--format", "pitch", "flow.toml"]).document().unwrap(), Document::Pitch);
+        assert!(parse(&["export", "flow.toml"]).is_err());
+        assert!(parse(&["export", "--format", "slides", "flow.toml"]).is_err());
+        assert!(export(&["export", "--format", "pitch", "--gh", "flow.toml"]).document().is_err());
+        assert!(parse(&["export", "--format", "tasks", "--repo", "acme/shop", "flow.toml"]).is_err());
+        assert!(parse(&["export", "--format", "pitch", "--reachable", "flow.toml"]).is_err());
+
+        let options = export(&["export", "--format", "print", "--paper", "letter", "flow.toml"]);
+        assert_eq!((options.document().unwrap(), options.paper), (Document::Print, Some(Paper::Letter)));
+        assert!(export(&["export", "--format", "pitch", "--paper", "a4", "flow.toml"]).document().is_err());
+        assert!(parse(&["export", "--format", "print", "--paper", "a3", "flow.toml"]).is_err());
+    }
+
+    #[test]
+    fn test_issue_args() {
+        let task = Task {
+            title: "Invoice".to_string(),
+            done: false,
+            depends_on: vec!["Setup Autopay".to_string()],
+            notes: Some("Shown after every payment".to_string()),
+        };
+        assert_eq!(issue_args(&task, Some("acme/shop")), args(&[
+            "issue", "create", "--title", "Invoice",
+            "--body", "Shown after every payment\n\nDepends on: Setup Autopay",
+            "--repo", "acme/shop",
+        ]));
+    }
+
+    #[test]
+    fn test_render_single_place() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("flow.toml");
+        let mut app = App::new();
+        app.new_place("Invoice".to_string());
+        app.new_place("Setup".to_string());
+        FileManager::new().save_to_file(&app.breadboard, &path).unwrap();
+
+        let mut options = RenderOptions { file: path.to_string_lossy().to_string(), width: Some(80), ..Default::default() };
+        options.place = Some("setup".to_string());
+        assert_eq!(render(&options).unwrap(), "Breadboard\n\n┌─ Setup\n");
+
+        options.place = Some("Missing".to_string());
+        assert!(render(&options).is_err());
+    }
+
+    #[test]
+    fn test_render_reachable_from_place() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("flow.toml");
+        let mut app = App::new();
+        let invoice = app.new_place("Invoice".to_string());
+        let setup = app.new_place("Setup".to_string());
+        let done = app.new_place("Done".to_string());
+        app.add_affordance(invoice, "Turn on Autopay".to_string(), Some(setup)).unwrap();
+        app.add_affordance(setup, "Save".to_string(), Some(done)).unwrap();
+        FileManager::new().save_to_file(&app.breadboard, &path).unwrap();
+
+        let mut options = RenderOptions { file: path.to_string_lossy().to_string(), width: Some(80), ..Default::default() };
+        options.place = Some("Setup".to_string());
+        options.reachable = true;
+        assert_eq!(render(&options).unwrap(), "Breadboard\n\n┌─ Setup (← Invoice)\n├─ Save → Done\n\n┌─ Done (← Setup)\n");
+    }
+
+    #[test]
+    fn test_parse_walk() {
+        let Commands::Walk(options) = command(&["walk", "--walks", "50", "--seed=7", "flow.toml"]) else {
+            panic!("not walk");
+        };
+        assert_eq!(options, WalkOptions { walks: 50, steps: 50, seed: Some(7), json: false, file: "flow.toml".to_string() });
+        assert!(parse(&["walk", "--steps", "0", "flow.toml"]).is_err());
+        assert!(parse(&["walk"]).is_err());
+    }
+
+    #[test]
+    fn test_walk_reports_unvisited() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("flow.toml");
+        let mut app = App::new();
+        let invoice = app.new_place("Invoice".to_string());
+        let setup = app.new_place("Setup".to_string());
+        let admin = app.new_place("Admin".to_string());
+        app.add_affordance(invoice, "Turn on Autopay".to_string(), Some(setup)).unwrap();
+        app.add_affordance(setup, "Back".to_string(), Some(invoice)).unwrap();
+        app.add_affordance(admin, "Refund".to_string(), Some(invoice)).unwrap();
+        FileManager::new().save_to_file(&app.breadboard, &path).unwrap();
+
+        let options = WalkOptions { walks: 10, steps: 5, seed: Some(1), json: false, file: path.to_string_lossy().to_string() };
+        let report = walk(&options).unwrap();
+        assert!(!report.never_visited.is_empty());
+        assert_eq!(walk_text(&report), "New Breadboard: 10 random walks of up to 5 steps from Invoice\n\nNever visited:\n  Admin\n  Admin: Refund\n");
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["never_visited"]["affordances"][0], serde_json::json!({ "place": "Admin", "affordance": "Refund" }));
+        assert_eq!(json["seed"], 1);
+    }
+
+    #[test]
+    fn test_stats_counts_built_affordances() {
+        let mut breadboard = crate::models::Breadboard::new("Flow".to_string());
+        let mut place = crate::models::Place::new(breadboard.generate_place_id(), "Cart".to_string());
+        for (name, status, cut) in [("Pay", Status::Built, false), ("Coupon", Status::Planned, false), ("Gift wrap", Status::Planned, true)] {
+            let mut affordance = crate::models::Affordance::new(breadboard.generate_affordance_id(), name.to_string());
+            affordance.status = status;
+            affordance.cut = cut;
+            affordance.estimate = crate::models::Estimate::parse("M");
+            place.add_affordance(affordance);
+        }
+        breadboard.add_place(place);
+
+        let report = stats(&breadboard);
+        assert!(report.starts_with("Flow: 1/2 affordances built, 0 building, 1 planned, 1 cut, ~4 pts estimated\n\n"));
+        assert!(report.contains("Cart  ████████░░░░░░░░ 1/2 (1 cut) ~4 pts\n"));
+        let json = serde_json::to_value(stats_report("flow.toml", &breadboard)).unwrap();
+        assert_eq!(json["places"][0], serde_json::json!({ "name": "Cart", "planned": 1, "building": 0, "built": 1, "cut": 1, "points": 4.0 }));
+    }
+
+    #[test]
+    fn test_import_board_from_csv() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("screens.csv");
+        std::fs::write(&path, "place,affordance,connects_to\nInvoice,Turn on Autopay,Setup Autopay\n").unwrap();
+
+        let breadboard = import_board(path.to_str().unwrap(), None).unwrap();
+        assert_eq!(breadboard.name, "screens");
+        assert_eq!(breadboard.places.len(), 2);
+        assert_eq!(breadboard.places[0].affordances[0].connects_to, Some(breadboard.places[1].id));
+    }
+
+    #[test]
+    fn test_parse_import() {
+        let import = |list: &[&str]| match command(list) {
+            Commands::Import(options) => options,
+            other => panic!("{:?}", other),
+        };
+        assert_eq!(import(&["import", "--into", "b.toml", "--group=breakout", "theirs.toml"]), ImportOptions {
+            into: Some("b.toml".to_string()),
+            group: Some("breakout".to_string()),
+            json: false,
+            file: "theirs.toml".to_string(),
+        });
+        assert_eq!(import(&["import", "--group", " ", "s.csv"]).group(), None);
+        assert!(parse(&["import", "--into"]).is_err());
+        assert!(parse(&["import", "a.csv", "b.csv"]).is_err());
+    }
+
+    #[test]
+    fn test_merge_into_board_saves_it() {
+        let dir = tempfile::tempdir().unwrap();
+        let ours = dir.path().join("ours.toml");
+        let mut app = App::new();
+        app.new_place("Invoice".to_string());
+        FileManager::new().save_to_file(&app.breadboard, &ours).unwrap();
+        let csv = dir.path().join("breakout.csv");
+        std::fs::write(&csv, "invoice,Download PDF,Receipt\n").unwrap();
+
+        let summary = merge_into_board(csv.to_str().unwrap(), ours.to_str().unwrap(), Some("breakout")).unwrap();
+        assert_eq!((summary.places, summary.affordances), (1, 1));
+        let breadboard = FileManager::new().load_from_file(&ours).unwrap();
+        assert_eq!(breadboard.places.len(), 2);
+        assert_eq!(breadboard.places[1].group.as_deref(), Some("breakout"));
+    }
+
+    #[test]
+    fn test_parse_extract() {
+        let Commands::Extract(options) = command(&["extract", "--place", "Setup", "--replace", "billing.toml", "autopay.toml"]) else {
+            panic!("not extract");
+        };
+        assert_eq!(options, ExtractOptions {
+            place: "Setup".to_string(),
+            replace: true,
+            json: false,
+            file: "billing.toml".to_string(),
+            to: "autopay.toml".to_string(),
+        });
+        assert!(parse(&["extract", "billing.toml", "autopay.toml"]).is_err());
+        assert!(parse(&["extract", "--place", "Setup", "billing.toml"]).is_err());
+    }
+
+    #[test]
+    fn test_extract_board_with_replace() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("billing.toml").to_string_lossy().to_string();
+        let to = dir.path().join("autopay.toml").to_string_lossy().to_string();
+        let mut app = App::new();
+        let invoice = app.new_place("Invoice".to_string());
+        let setup = app.new_place("Setup Autopay".to_string());
+        app.add_affordance(invoice, "Turn on Autopay".to_string(), Some(setup)).unwrap();
+        FileManager::new().save_to_file(&app.breadboard, &file).unwrap();
+
+        let options = ExtractOptions { place: "setup autopay".to_string(), replace: true, file: file.clone(), to: to.clone(), ..Default::default() };
+        assert_eq!(extract_board(&options).unwrap(), 1);
+        let original = FileManager::new().load_from_file(&file).unwrap();
+        assert_eq!(original.places.len(), 1);
+        let link = original.places[0].affordances[0].link.as_ref().unwrap();
+        assert_eq!((link.board.as_str(), link.place_id), ("autopay.toml", setup));
+        assert_eq!(FileManager::new().load_from_file(&to).unwrap().name, "Setup Autopay");
+    }
+
+    #[test]
+    fn test_render_workspace_with_cross_board_link() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut invoices = App::new();
+        let invoice = invoices.new_place("Invoice".to_string());
+        let pay = invoices.add_affordance(invoice, "Turn on Autopay".to_string(), None).unwrap();
+        invoices.link_affordance(invoice, pay, crate::models::BoardLink { board: "setup.toml".to_string(), place_id: 1 });
+        FileManager::new().save_to_file(&invoices.breadboard, dir.path().join("invoices.toml")).unwrap();
+        let mut setup = App::new();
+        setup.new_place("Setup Autopay".to_string());
+        FileManager::new().save_to_file(&setup.breadboard, dir.path().join("setup.toml")).unwrap();
+        let path = dir.path().join("workspace.toml");
+        std::fs::write(&path, "[[boards]]\ntitle = \"Invoices\"\nfile = \"invoices.toml\"\n\n[[boards]]\ntitle = \"Autopay\"\nfile = \"setup.toml\"\n").unwrap();
+
+        let options = RenderOptions { file: path.to_string_lossy().to_string(), width: Some(80), ..Default::default() };
+        assert_eq!(
+            render(&options).unwrap(),
+            "# Invoices\n\nBreadboard\n\n┌─ Invoice\n├─ Turn on Autopay ⇒ Autopay: Setup Autopay\n\n# Autopay\n\nBreadboard\n\n┌─ Setup Autopay\n",
+        );
+    }
+}
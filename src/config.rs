@@ -0,0 +1,300 @@
+use serde::{Deserialize, Serialize};
+use anyhow::{Result, Context};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::locale::Language;
+
+// User settings loaded from `$XDG_CONFIG_HOME/bboard/config.toml`
+// (falling back to `~/.config/bboard/config.toml`). Every field has a
+// default so a partial or missing file is fine.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    // When wiring A → B, also add a "Back" affordance on B pointing to A
+    pub auto_back_link: bool,
+    // Where boards live: the open dialog lists it and startup shows its boards
+    pub boards_dir: Option<String>,
+    // The open dialog also lists boards in subdirectories, skipping what .gitignore does
+    pub search_subdirectories: bool,
+    // Launched without a file: offer the most recently used board, or just open it
+    pub reopen_last_board: ReopenLastBoard,
+    // Connection search lists the affordance's own place last, or not at all
+    pub own_place_in_connect: OwnPlace,
+    // What Enter on a place does: edit its name, fold its affordances away, or follow its first connection
+    pub enter_on_place: EnterOnPlace,
+    // Keep cut affordances in exported and copied views
+    pub export_cut: bool,
+    // Name comments are signed with; `$USER` when unset
+    pub author: Option<String>,
+    // What the top bar shows, e.g. "{dirty}{board} · {selection}" (see `ui::status_template`)
+    pub status_bar: Option<String>,
+    // How sketch previews are drawn; "auto" picks from the terminal
+    pub image_protocol: ImageProtocol,
+    // Page size of the print export: "a4" or "letter"
+    pub paper: Paper,
+    // The terminal's font has Nerd Font icons, so `:` in names offers them too
+    pub nerd_font: bool,
+    // Language of the mode names, hints and dialogs: "en" or "de"
+    pub language: Language,
+    pub keys: Keys,
+    pub hooks: Hooks,
+    pub history: HistoryLimits,
+    pub spelling: Spelling,
+    pub commands: Vec<CustomCommand>,
+    pub snippets: Vec<Snippet>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReopenLastBoard {
+    #[default]
+    Ask,
+    Always,
+    Never,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OwnPlace {
+    #[default]
+    Demote, // Last, marked as this place
+    Hide,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EnterOnPlace {
+    #[default]
+    Edit,
+    Fold, // Hide its affordances in the outline, or show them again
+    Follow, // Go to where its first connected affordance leads
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Paper {
+    #[default]
+    A4,
+    Letter,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageProtocol {
+    #[default]
+    Auto,
+    Kitty,
+    Sixel,
+    Blocks, // Half-block characters, which any color terminal shows
+}
+
+// Shell commands run after board events. They get the board file and the
+// selection in environment variables (see `hooks::CommandContext`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Hooks {
+    pub on_save: Option<String>,
+    pub on_load: Option<String>,
+    pub on_export: Option<String>,
+}
+
+// How much of the board's past Alt+Shift+Z can go back to. The oldest
+// snapshots go first once either limit is passed.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HistoryLimits {
+    pub max_snapshots: usize,
+    pub max_memory_kb: usize,
+}
+
+impl Default for HistoryLimits {
+    fn default() -> Self {
+        Self { max_snapshots: 10, max_memory_kb: 4096 }
+    }
+}
+
+// Spell-checking of names and notes. Without a dictionary, the first of
+// the usual system ones found is used (see `spelling::Dictionary::load`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Spelling {
+    pub enabled: bool,
+    pub dictionary: Option<String>, // A word list or hunspell .dic file
+    pub words: Vec<String>, // Taken as spelled right, like product names
+}
+
+impl Default for Spelling {
+    fn default() -> Self {
+        Self { enabled: true, dictionary: None, words: Vec::new() }
+    }
+}
+
+// Keys for actions, for when a built-in chord doesn't reach bboard: a set
+// of alternatives, bindings of keys like "F12" to actions by name (see
+// `keys::ACTION_NAMES`), and a leader key starting sequences like "f s".
+// They apply in navigate mode and win over the built-in keys.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Keys {
+    pub alternatives: Alternatives,
+    pub bind: BTreeMap<String, String>, // Key → action
+    pub leader: Option<String>,
+    pub sequences: BTreeMap<String, String>, // Keys after the leader → action, on top of the built-in ones
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Alternatives {
+    #[default]
+    None,
+    FunctionKeys, // F4–F9 for Shift+Tab, save, Ctrl+Shift+S, connect, delete and filter
+}
+
+// User-defined entry in the command list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomCommand {
+    pub name: String,
+    pub run: String,
+}
+
+// Named set of affordances inserted into a place in one go. Entries are
+// written as when editing an affordance, so "Submit -> Confirmation"
+// connects it; "-> back" connects to the place that leads here.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snippet {
+    pub name: String,
+    pub affordances: Vec<String>,
+}
+
+impl Config {
+    pub fn config_dir() -> Option<PathBuf> {
+        if let Some(dir) = std::env::var_os("XDG_CONFIG_HOME").filter(|d| !d.is_empty()) {
+            return Some(PathBuf::from(dir).join("bboard"));
+        }
+        std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config").join("bboard"))
+    }
+
+    pub fn default_path() -> Option<PathBuf> {
+        Self::config_dir().map(|dir| dir.join("config.toml"))
+    }
+
+    // The boards directory with a leading `~` expanded
+    pub fn boards_dir(&self) -> Option<PathBuf> {
+        let dir = self.boards_dir.as_deref().filter(|d| !d.trim().is_empty())?;
+        match dir.strip_prefix("~/").or(if dir == "~" { Some("") } else { None }) {
+            Some(rest) => std::env::var_os("HOME").map(|home| PathBuf::from(home).join(rest)),
+            None => Some(PathBuf::from(dir)),
+        }
+    }
+
+    // Who comments are from: the configured author, else the login name
+    pub fn author(&self) -> String {
+        self.author.clone()
+            .filter(|a| !a.trim().is_empty())
+            .or_else(|| std::env::var("USER").ok().filter(|u| !u.is_empty()))
+            .unwrap_or_else(|| "anonymous".to_string())
+    }
+
+    // Load the config file, returning defaults if it doesn't exist
+    pub fn load() -> Result<Self> {
+        match Self::default_path() {
+            Some(path) if path.exists() => Self::load_from(&path),
+            _ => Ok(Self::default()),
+        }
+    }
+
+    pub fn load_from(path: &std::path::Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?;
+        toml::from_str(&content)
+            .with_context(|| format!("Failed to parse config file {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config() {
+        let config = Config::default();
+        assert!(!config.auto_back_link);
+    }
+
+    #[test]
+    fn test_partial_config_uses_defaults() {
+        let config: Config = toml::from_str("").unwrap();
+        assert!(!config.auto_back_link);
+
+        let config: Config = toml::from_str("auto_back_link = true").unwrap();
+        assert!(config.auto_back_link);
+        assert_eq!(config.reopen_last_board, ReopenLastBoard::Ask);
+        assert_eq!(config.enter_on_place, EnterOnPlace::Edit);
+        assert!(config.boards_dir().is_none());
+        assert!(!config.search_subdirectories);
+
+        let config: Config = toml::from_str("boards_dir = \"/srv/boards\"").unwrap();
+        assert_eq!(config.boards_dir(), Some(PathBuf::from("/srv/boards")));
+        assert!(config.hooks.on_save.is_none());
+        assert!(config.commands.is_empty());
+        assert!(config.status_bar.is_none());
+        assert!(config.spelling.enabled);
+        assert_eq!(config.language, Language::English);
+
+        let config: Config = toml::from_str("language = \"de\"").unwrap();
+        assert_eq!(config.language, Language::German);
+
+        let config: Config = toml::from_str("author = \"Ryan\"").unwrap();
+        assert_eq!(config.author(), "Ryan");
+
+        let config: Config = toml::from_str("status_bar = \"{dirty}{board} · {selection}\"").unwrap();
+        assert_eq!(config.status_bar.as_deref(), Some("{dirty}{board} · {selection}"));
+    }
+
+    #[test]
+    fn test_hooks_and_commands() {
+        let config: Config = toml::from_str(r#"
+            [hooks]
+            on_save = "bboard render --graph \"$BBOARD_FILE\" > wiki.txt"
+
+            [[commands]]
+            name = "Lint"
+            run = "bboard lint \"$BBOARD_FILE\""
+        "#).unwrap();
+        assert!(config.hooks.on_save.unwrap().starts_with("bboard render"));
+        assert!(config.hooks.on_export.is_none());
+        assert_eq!(config.commands[0].name, "Lint");
+    }
+
+    #[test]
+    fn test_keys() {
+        let config: Config = toml::from_str(r#"
+            [keys]
+            alternatives = "function_keys"
+            leader = "Space"
+
+            [keys.sequences]
+            "g h" = "export_html"
+
+            [keys.bind]
+            "Ctrl+Alt+s" = "save_as"
+        "#).unwrap();
+        assert_eq!(config.keys.alternatives, Alternatives::FunctionKeys);
+        assert_eq!(config.keys.bind["Ctrl+Alt+s"], "save_as");
+        assert_eq!(config.keys.leader.as_deref(), Some("Space"));
+        assert_eq!(config.keys.sequences["g h"], "export_html");
+    }
+
+    #[test]
+    fn test_snippets() {
+        let config: Config = toml::from_str(r#"
+            [[snippets]]
+            name = "Standard form"
+            affordances = ["Fields", "Validate", "Submit", "Cancel -> back"]
+        "#).unwrap();
+        assert_eq!(config.snippets[0].name, "Standard form");
+        assert_eq!(config.snippets[0].affordances.len(), 4);
+    }
+}
@@ -0,0 +1,532 @@
+use crate::layout::GraphLayout;
+use crate::models::{Affordance, Breadboard, Detail, Place};
+use std::collections::HashMap;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+// Text rendering of the place graph: each place is a box listing its
+// affordances, boxes are arranged in the columns of a `GraphLayout`, and
+// connections are drawn as box-drawing lines from the affordance row to the
+// destination's header. Used by the graph view and by text exports.
+pub struct Diagram {
+    cells: Vec<Vec<char>>,
+    pub boxes: HashMap<u32, PlaceBox>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlaceBox {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+// Marks the second cell of a double-width character
+const WIDE_CONTINUATION: char = '\0';
+
+const UP: u8 = 1;
+const DOWN: u8 = 2;
+const LEFT: u8 = 4;
+const RIGHT: u8 = 8;
+
+const COLUMN_GAP: usize = 4;
+const ROW_GAP: usize = 1;
+
+impl Diagram {
+    pub fn width(&self) -> usize {
+        self.cells.first().map(|row| row.len()).unwrap_or(0)
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.len()
+    }
+
+    // Text of columns `from..to` of row `y` (wide characters count as two).
+    // Ranges past the row or ending before they start give what's left.
+    pub fn segment(&self, y: usize, from: usize, to: usize) -> String {
+        self.cells.get(y)
+            .map(|row| {
+                let to = to.min(row.len());
+                let from = from.min(to);
+                row[from..to].iter()
+                    .filter(|c| **c != WIDE_CONTINUATION)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    // Shrunk copy of the diagram for the minimap: each cell holds the place
+    // whose box covers it, if any
+    pub fn overview(&self, width: usize, height: usize) -> Vec<Vec<Option<u32>>> {
+        let mut cells = vec![vec![None; width]; height];
+        if width == 0 || height == 0 {
+            return cells;
+        }
+
+        let mut ids: Vec<&u32> = self.boxes.keys().collect();
+        ids.sort();
+        for id in ids {
+            let b = self.boxes[id];
+            let (x1, x2) = (scale(b.x, self.width(), width), scale(b.x + b.width - 1, self.width(), width));
+            let (y1, y2) = (scale(b.y, self.height(), height), scale(b.y + b.height - 1, self.height(), height));
+            for row in &mut cells[y1..=y2] {
+                for cell in &mut row[x1..=x2] {
+                    *cell = Some(*id);
+                }
+            }
+        }
+
+        cells
+    }
+
+    pub fn lines(&self) -> Vec<String> {
+        (0..self.height())
+            .map(|y| self.segment(y, 0, self.width()).trim_end().to_string())
+            .collect()
+    }
+}
+
+// Position `pos` of a `full`-sized axis mapped onto an axis of `size` cells
+pub fn scale(pos: usize, full: usize, size: usize) -> usize {
+    if full == 0 || size == 0 {
+        return 0;
+    }
+    (pos * size / full).min(size - 1)
+}
+
+pub fn render_diagram(breadboard: &Breadboard, layout: &GraphLayout, detail: Detail) -> Diagram {
+    let places: HashMap<u32, &Place> = breadboard.places.iter().map(|p| (p.id, p)).collect();
+    let layer_of: HashMap<u32, usize> = layout.layers.iter()
+        .enumerate()
+        .flat_map(|(layer, ids)| ids.iter().map(move |id| (*id, layer)))
+        .collect();
+
+    // Every drawn connection, as (source layer, source place, row index,
+    // destination). Without affordance rows, connections leave from the
+    // place's only row and parallel ones are drawn once.
+    let mut edges = Vec::new();
+    for (layer, ids) in layout.layers.iter().enumerate() {
+        for id in ids {
+            for (index, affordance) in places[id].affordances.iter().enumerate() {
+                for dest_id in affordance.destinations().into_iter().filter(|d| layer_of.contains_key(d)) {
+                    if detail == Detail::Full {
+                        edges.push((layer, *id, index + 1, dest_id));
+                    } else if !edges.iter().any(|(_, from, _, to)| from == id && *to == dest_id) {
+                        edges.push((layer, *id, 0, dest_id));
+                    }
+                }
+            }
+        }
+    }
+
+    // Connections to the next column go straight across; all others (long
+    // jumps, same column, backwards) get their own channel row above the boxes
+    let is_direct = |layer: usize, id: u32, dest_id: u32| dest_id == id || layer_of[&dest_id] == layer + 1;
+    let channel_count = edges.iter().filter(|(layer, id, _, dest)| !is_direct(*layer, *id, *dest)).count();
+    let top = if channel_count > 0 { channel_count + 1 } else { 0 };
+
+    // Each column's gap holds one vertical lane per outgoing connection, plus
+    // a column on either side for connections arriving at its neighbours
+    let mut lanes_per_layer = vec![0usize; layout.layers.len()];
+    for (layer, id, _, dest_id) in &edges {
+        if dest_id != id {
+            lanes_per_layer[*layer] += 1;
+        }
+    }
+
+    let box_height = |id: &u32| match detail {
+        Detail::Full => places[id].affordances.len() + 2,
+        Detail::Titles | Detail::Dots => 1,
+    };
+
+    // Swimlanes are bands of rows, each starting with a header row and tall
+    // enough for the lane's fullest column
+    let mut lane_top = Vec::new();
+    let mut y = top;
+    for lane in &layout.lanes {
+        lane_top.push(y);
+        let content = layout.layers.iter()
+            .map(|ids| ids.iter().filter(|id| lane.places.contains(id)).map(|id| box_height(id) + ROW_GAP).sum::<usize>())
+            .max()
+            .unwrap_or(0);
+        y += 1 + content;
+    }
+
+    let mut boxes = HashMap::new();
+    let mut lane_start = Vec::new();
+    let mut x = 0;
+    for (layer, ids) in layout.layers.iter().enumerate() {
+        let column_width = ids.iter().map(|id| box_width(places[id], detail)).max().unwrap_or(0);
+        let mut next_y = top;
+        let mut next_lane_y: Vec<usize> = lane_top.iter().map(|y| y + 1).collect();
+        for id in ids {
+            let height = box_height(id);
+            let y = match layout.lane_of(*id) {
+                Some(lane) => &mut next_lane_y[lane],
+                None => &mut next_y,
+            };
+            boxes.insert(*id, PlaceBox { x, y: *y, width: column_width, height });
+            *y += height + ROW_GAP;
+        }
+        lane_start.push(x + column_width + 2);
+        x += column_width + COLUMN_GAP + 1 + lanes_per_layer[layer];
+    }
+
+    let width = x.saturating_sub(COLUMN_GAP).max(1) + 1;
+    let height = boxes.values().map(|b| b.y + b.height).max().unwrap_or(0);
+    let mut canvas = Canvas::new(width, height);
+
+    for (id, place_box) in &boxes {
+        match detail {
+            Detail::Full => canvas.draw_box(places[id], place_box),
+            Detail::Titles => canvas.draw_title(places[id], place_box),
+            Detail::Dots => canvas.draw_dot(place_box),
+        }
+    }
+
+    let mut next_lane = vec![0usize; layout.layers.len()];
+    let mut next_channel = 0;
+    let mut arrows = Vec::new();
+    for (layer, id, index, dest_id) in edges {
+        let source = boxes[&id];
+        let row = source.y + index;
+        if dest_id == id {
+            arrows.push((source.x + source.width, row, '↺'));
+            continue;
+        }
+        let dest = boxes[&dest_id];
+
+        let lane_x = lane_start[layer] + next_lane[layer];
+        next_lane[layer] += 1;
+        if detail == Detail::Full {
+            arrows.push((source.x + source.width - 1, row, '├'));
+        }
+
+        // Segments start on the border and end on the arrow cell so both
+        // ends read as continuous lines once those are drawn
+        canvas.line_h(row, source.x + source.width - 1, lane_x);
+
+        if is_direct(layer, id, dest_id) {
+            canvas.line_v(lane_x, row, dest.y);
+            canvas.line_h(dest.y, lane_x, dest.x - 1);
+            arrows.push((dest.x - 1, dest.y, '▶'));
+            continue;
+        }
+
+        let channel_y = next_channel;
+        next_channel += 1;
+        canvas.line_v(lane_x, row, channel_y);
+
+        // Drop down beside the destination: on its left for forward jumps,
+        // on its right for connections going back
+        let (drop_x, arrow_x, arrow) = if dest.x > source.x {
+            (dest.x - 2, dest.x - 1, '▶')
+        } else {
+            (dest.x + dest.width + 1, dest.x + dest.width, '◀')
+        };
+        canvas.line_h(channel_y, lane_x, drop_x);
+        canvas.line_v(drop_x, channel_y, dest.y);
+        canvas.line_h(dest.y, drop_x, arrow_x);
+        arrows.push((arrow_x, dest.y, arrow));
+    }
+
+    canvas.finish_lines();
+    for (lane, y) in layout.lanes.iter().zip(lane_top) {
+        canvas.lane_header(y, lane.group.as_deref().unwrap_or("Ungrouped"));
+    }
+    for (x, y, arrow) in arrows {
+        canvas.put(x, y, arrow);
+    }
+
+    Diagram { cells: canvas.cells, boxes }
+}
+
+fn box_width(place: &Place, detail: Detail) -> usize {
+    match detail {
+        Detail::Full => {
+            let header = place.title().width() + 6; // "┌ " + name + " ─┐"
+            let rows = place.affordances.iter().map(|a| row_label(a).width() + 4).max().unwrap_or(0);
+            header.max(rows)
+        }
+        Detail::Titles => place.title().width() + 4, // "[ " + name + " ]"
+        Detail::Dots => 1,
+    }
+}
+
+// Affordance row text; a diamond marks one whose arrows are labeled outcomes
+fn row_label(affordance: &Affordance) -> String {
+    if affordance.branches.is_empty() {
+        affordance.name.clone()
+    } else {
+        format!("{} ◇", affordance.name)
+    }
+}
+
+struct Canvas {
+    cells: Vec<Vec<char>>,
+    lines: Vec<Vec<u8>>, // Direction bits for connection lines
+    occupied: Vec<Vec<bool>>, // Cells covered by place boxes
+}
+
+impl Canvas {
+    fn new(width: usize, height: usize) -> Self {
+        Self {
+            cells: vec![vec![' '; width]; height],
+            lines: vec![vec![0; width]; height],
+            occupied: vec![vec![false; width]; height],
+        }
+    }
+
+    fn put(&mut self, x: usize, y: usize, c: char) {
+        if let Some(cell) = self.cells.get_mut(y).and_then(|row| row.get_mut(x)) {
+            *cell = c;
+        }
+    }
+
+    // Write text starting at `x`, clipped to `max_x`
+    fn text(&mut self, x: usize, y: usize, text: &str, max_x: usize) {
+        let mut x = x;
+        for c in text.chars() {
+            let w = c.width().unwrap_or(0);
+            if w == 0 {
+                continue;
+            }
+            if x + w > max_x {
+                break;
+            }
+            self.put(x, y, c);
+            if w == 2 {
+                self.put(x + 1, y, WIDE_CONTINUATION);
+            }
+            x += w;
+        }
+    }
+
+    fn draw_box(&mut self, place: &Place, b: &PlaceBox) {
+        let right = b.x + b.width - 1;
+        let bottom = b.y + b.height - 1;
+
+        for y in b.y..=bottom {
+            for x in b.x..=right {
+                if let Some(cell) = self.occupied.get_mut(y).and_then(|row| row.get_mut(x)) {
+                    *cell = true;
+                }
+            }
+        }
+
+        // Systems we don't build get a dashed border
+        let (horizontal, vertical) = if place.is_external() { ('╌', '╎') } else { ('─', '│') };
+        for x in b.x..=right {
+            self.put(x, b.y, horizontal);
+            self.put(x, bottom, horizontal);
+        }
+        for y in b.y..=bottom {
+            self.put(b.x, y, vertical);
+            self.put(right, y, vertical);
+        }
+        self.put(b.x, b.y, '┌');
+        self.put(right, b.y, '┐');
+        self.put(b.x, bottom, '└');
+        self.put(right, bottom, '┘');
+
+        self.put(b.x + 1, b.y, ' ');
+        let title = place.title();
+        self.text(b.x + 2, b.y, &title, right - 1);
+        let after_name = b.x + 2 + title.width();
+        if after_name < right - 1 {
+            self.put(after_name, b.y, ' ');
+        }
+
+        for (index, affordance) in place.affordances.iter().enumerate() {
+            self.text(b.x + 2, b.y + 1 + index, &row_label(affordance), right - 1);
+        }
+    }
+
+    // Single-row place showing only its name, as "[ Name ]"
+    fn draw_title(&mut self, place: &Place, b: &PlaceBox) {
+        let right = b.x + b.width - 1;
+        for x in b.x..=right {
+            self.occupied[b.y][x] = true;
+        }
+        self.put(b.x, b.y, '[');
+        self.text(b.x + 2, b.y, &place.title(), right - 1);
+        self.put(right, b.y, ']');
+    }
+
+    fn draw_dot(&mut self, b: &PlaceBox) {
+        self.occupied[b.y][b.x] = true;
+        self.put(b.x, b.y, '●');
+    }
+
+    // Dotted rule across row `y` naming the lane below it. Connections
+    // crossing the row stay unbroken; the name is cut short before the first
+    // one.
+    fn lane_header(&mut self, y: usize, name: &str) {
+        let free = self.cells[y].iter().take_while(|c| **c == ' ').count();
+        for cell in self.cells[y].iter_mut().filter(|c| **c == ' ') {
+            *cell = '┄';
+        }
+        self.text(0, y, &format!("┄┄ {} ", name), free);
+    }
+
+    fn mark(&mut self, x: usize, y: usize, bits: u8) {
+        if let Some(cell) = self.lines.get_mut(y).and_then(|row| row.get_mut(x)) {
+            *cell |= bits;
+        }
+    }
+
+    // Each cell of a segment records the directions it connects to, so
+    // ends meeting at a lane combine into corners and crossings into '┼'
+    fn line_h(&mut self, y: usize, x1: usize, x2: usize) {
+        let (from, to) = (x1.min(x2), x1.max(x2));
+        for x in from..=to {
+            let mut bits = 0;
+            if x > from { bits |= LEFT; }
+            if x < to { bits |= RIGHT; }
+            self.mark(x, y, bits);
+        }
+    }
+
+    fn line_v(&mut self, x: usize, y1: usize, y2: usize) {
+        let (from, to) = (y1.min(y2), y1.max(y2));
+        for y in from..=to {
+            let mut bits = 0;
+            if y > from { bits |= UP; }
+            if y < to { bits |= DOWN; }
+            self.mark(x, y, bits);
+        }
+    }
+
+    // Convert direction bits into box-drawing characters; lines pass under
+    // place boxes rather than drawing over them
+    fn finish_lines(&mut self) {
+        for y in 0..self.cells.len() {
+            for x in 0..self.cells[y].len() {
+                let bits = self.lines[y][x];
+                if bits == 0 || self.occupied[y][x] {
+                    continue;
+                }
+                self.cells[y][x] = line_char(bits);
+            }
+        }
+    }
+}
+
+fn line_char(bits: u8) -> char {
+    let vertical = bits & (UP | DOWN);
+    let horizontal = bits & (LEFT | RIGHT);
+    match (bits & UP != 0, bits & DOWN != 0, bits & LEFT != 0, bits & RIGHT != 0) {
+        (true, true, true, true) => '┼',
+        (true, true, true, false) => '┤',
+        (true, true, false, true) => '├',
+        (false, true, true, true) => '┬',
+        (true, false, true, true) => '┴',
+        (false, true, false, true) => '┌',
+        (false, true, true, false) => '┐',
+        (true, false, false, true) => '└',
+        (true, false, true, false) => '┘',
+        _ if horizontal != 0 && vertical == 0 => '─',
+        _ => '│',
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::{layered_layout, swimlane_layout};
+    use crate::models::testing::{board, name_affordances};
+
+    fn sample_board() -> Breadboard {
+        let mut breadboard = board(&["Invoice", "Setup"], &[(0, 1), (1, 0)]);
+        breadboard.name = "Autopay".to_string();
+        name_affordances(&mut breadboard, &["Turn on Autopay", "Cancel"]);
+        breadboard
+    }
+
+    #[test]
+    fn test_render_diagram_boxes_and_arrows() {
+        let breadboard = sample_board();
+        let diagram = render_diagram(&breadboard, &layered_layout(&breadboard), Detail::Full);
+        let lines = diagram.lines();
+
+        // Cancel → Invoice goes back, so it is routed over a channel row
+        assert!(lines[0].contains("┌"));
+        assert!(lines[2].starts_with("┌ Invoice "));
+        assert!(lines[3].starts_with("│ Turn on Autopay ├─"));
+        assert!(lines.iter().any(|l| l.contains("▶┌ Setup")));
+        assert!(lines[2].contains("┐◀┘"));
+
+        let invoice = diagram.boxes[&1];
+        let setup = diagram.boxes[&2];
+        assert_eq!(invoice.x, 0);
+        assert!(setup.x > invoice.x + invoice.width);
+    }
+
+    #[test]
+    fn test_overview_keeps_places_apart() {
+        let breadboard = sample_board();
+        let diagram = render_diagram(&breadboard, &layered_layout(&breadboard), Detail::Full);
+        let cells = diagram.overview(10, 4);
+
+        assert_eq!(cells.len(), 4);
+        assert_eq!(cells[3][0], Some(1));
+        assert_eq!(cells[3][7], Some(2));
+        assert_eq!(cells[3][5], None); // Gap between the columns
+        assert!(cells[0].iter().all(|c| c.is_none())); // Channel rows above the boxes
+        assert_eq!(scale(diagram.width() - 1, diagram.width(), 10), 9);
+    }
+
+    #[test]
+    fn test_render_diagram_compact_details() {
+        let breadboard = sample_board();
+        let layout = layered_layout(&breadboard);
+
+        let titles = render_diagram(&breadboard, &layout, Detail::Titles).lines();
+        assert!(titles.iter().any(|l| l.starts_with("[ Invoice ]") && l.contains("▶[ Setup ]")));
+        assert!(titles.iter().all(|l| !l.contains("Autopay")));
+
+        let dots = render_diagram(&breadboard, &layout, Detail::Dots);
+        assert_eq!(dots.boxes[&1].width, 1);
+        assert!(dots.lines().iter().any(|l| l.starts_with("●") && l.contains("▶●")));
+    }
+
+    #[test]
+    fn test_render_diagram_swimlanes() {
+        let mut breadboard = sample_board();
+        breadboard.places[0].group = Some("customer".to_string());
+        breadboard.places[1].group = Some("admin".to_string());
+        let diagram = render_diagram(&breadboard, &swimlane_layout(&breadboard), Detail::Titles);
+        let lines = diagram.lines();
+
+        // Each lane opens with a header row; Setup sits in the second lane
+        let customer = lines.iter().position(|l| l.starts_with("┄┄ customer")).unwrap();
+        let admin = lines.iter().position(|l| l.starts_with("┄┄ admin ┄")).unwrap();
+        assert_eq!(diagram.boxes[&1].y, customer + 1);
+        assert_eq!(diagram.boxes[&2].y, admin + 1);
+
+        // Turn on Autopay crosses into the admin lane without a break
+        let lane_x = diagram.boxes[&1].x + diagram.boxes[&1].width + 2;
+        assert_eq!(diagram.segment(admin, lane_x, lane_x + 1), "│");
+        // A box wider than the viewport asks for a range ending before it starts
+        assert_eq!(diagram.segment(admin, lane_x + 1, lane_x), "");
+        assert_eq!(diagram.segment(admin, diagram.width() + 5, diagram.width() + 1), "");
+        assert!(lines[admin + 1].contains("▶[ Setup ]"));
+    }
+
+    #[test]
+    fn test_zoom_levels_saturate() {
+        assert_eq!(Detail::Full.zoom_in(), Detail::Full);
+        assert_eq!(Detail::Full.zoom_out(), Detail::Titles);
+        assert_eq!(Detail::Titles.zoom_out(), Detail::Dots);
+        assert_eq!(Detail::Dots.zoom_out(), Detail::Dots);
+        assert_eq!(Detail::Dots.zoom_in(), Detail::Titles);
+    }
+
+    #[test]
+    fn test_line_chars() {
+        assert_eq!(line_char(LEFT | RIGHT), '─');
+        assert_eq!(line_char(LEFT | DOWN), '┐');
+        assert_eq!(line_char(UP | RIGHT), '└');
+        assert_eq!(line_char(UP | DOWN | LEFT | RIGHT), '┼');
+        assert_eq!(line_char(LEFT), '─');
+    }
+}
@@ -0,0 +1,197 @@
+use crate::models::{Affordance, Breadboard, Place};
+
+// Two versions of a board side by side. Places and affordances are matched
+// by ID, so a rename shows as one changed row rather than a removal and an
+// addition, and the rows line up so both sides can scroll together.
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Change {
+    Same,
+    Added,
+    Removed,
+    Renamed,
+    Rewired, // Leads somewhere else
+}
+
+// One line of the comparison. A side the row isn't on is empty.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffRow {
+    pub old: String,
+    pub new: String,
+    pub change: Change,
+}
+
+// The comparison being looked at, scrolled to `scroll` rows down
+#[derive(Debug, Clone)]
+pub struct Comparison {
+    pub old_title: String,
+    pub new_title: String,
+    pub rows: Vec<DiffRow>,
+    pub scroll: usize,
+}
+
+impl Comparison {
+    pub fn new(old_title: String, old: &Breadboard, new_title: String, new: &Breadboard) -> Self {
+        Self { old_title, new_title, rows: compare(old, new), scroll: 0 }
+    }
+
+    pub fn scroll_by(&mut self, rows: i32) {
+        let last = self.rows.len().saturating_sub(1);
+        self.scroll = self.scroll.saturating_add_signed(rows as isize).min(last);
+    }
+
+    // Scroll to the start of the next run of changed rows below the top
+    // one, or the previous one above it. False when there's none.
+    pub fn jump_to_change(&mut self, forward: bool) -> bool {
+        let starts = (0..self.rows.len())
+            .filter(|&index| self.rows[index].change != Change::Same)
+            .filter(|&index| index == 0 || self.rows[index - 1].change == Change::Same);
+        let target = if forward {
+            starts.filter(|&index| index > self.scroll).min()
+        } else {
+            starts.filter(|&index| index < self.scroll).max()
+        };
+        match target {
+            Some(index) => {
+                self.scroll = index;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+pub fn compare<'a>(old: &'a Breadboard, new: &'a Breadboard) -> Vec<DiffRow> {
+    let mut rows = Vec::new();
+    for (index, (before, after)) in merged(&old.places, &new.places, |p| p.id).into_iter().enumerate() {
+        if index > 0 {
+            rows.push(DiffRow { old: String::new(), new: String::new(), change: Change::Same });
+        }
+        let header = |place: Option<&Place>| place.map(|p| format!("┌─ {}", p.title())).unwrap_or_default();
+        let change = match (before, after) {
+            (None, _) => Change::Added,
+            (_, None) => Change::Removed,
+            (Some(b), Some(a)) if b.name != a.name => Change::Renamed,
+            _ => Change::Same,
+        };
+        rows.push(DiffRow { old: header(before), new: header(after), change });
+
+        let affordances = |place: Option<&'a Place>| place.map_or(&[][..], |p| p.affordances.as_slice());
+        for (before, after) in merged(affordances(before), affordances(after), |a| a.id) {
+            let change = match (before, after) {
+                (None, _) => Change::Added,
+                (_, None) => Change::Removed,
+                (Some(b), Some(a)) if b.name != a.name => Change::Renamed,
+                (Some(b), Some(a)) if leads_to(old, b) != leads_to(new, a) => Change::Rewired,
+                _ => Change::Same,
+            };
+            rows.push(DiffRow {
+                old: before.map(|a| affordance_line(old, a)).unwrap_or_default(),
+                new: after.map(|a| affordance_line(new, a)).unwrap_or_default(),
+                change,
+            });
+        }
+    }
+    rows
+}
+
+// "2 added, 1 removed, 3 changed", or None when the boards match
+pub fn summary(rows: &[DiffRow]) -> Option<String> {
+    let count = |changes: &[Change]| rows.iter().filter(|row| changes.contains(&row.change)).count();
+    let parts: Vec<String> = [
+        (count(&[Change::Added]), "added"),
+        (count(&[Change::Removed]), "removed"),
+        (count(&[Change::Renamed, Change::Rewired]), "changed"),
+    ]
+    .into_iter()
+    .filter(|(count, _)| *count > 0)
+    .map(|(count, what)| format!("{} {}", count, what))
+    .collect();
+    (!parts.is_empty()).then(|| parts.join(", "))
+}
+
+// Both lists in one order: the new one's, with what only the old one has
+// slotted in where it was
+fn merged<'a, T>(old: &'a [T], new: &'a [T], id: impl Fn(&T) -> u32) -> Vec<(Option<&'a T>, Option<&'a T>)> {
+    let gone = |item: &T| !new.iter().any(|n| id(n) == id(item));
+    let mut rows = Vec::new();
+    let mut rest = old.iter().peekable();
+    for item in new {
+        while let Some(removed) = rest.next_if(|o| gone(o)) {
+            rows.push((Some(removed), None));
+        }
+        rest.next_if(|o| id(o) == id(item));
+        rows.push((old.iter().find(|o| id(o) == id(item)), Some(item)));
+    }
+    rows.extend(rest.filter(|o| gone(o)).map(|o| (Some(o), None)));
+    rows
+}
+
+// Names of where the affordance leads, in `board`, as the rows show them
+fn leads_to(board: &Breadboard, affordance: &Affordance) -> Vec<String> {
+    let mut places: Vec<String> = affordance.destinations().iter()
+        .map(|id| board.find_place(id).map_or("[Unknown]".to_string(), |p| p.name.clone()))
+        .collect();
+    if let Some(link) = &affordance.link {
+        places.push(format!("{} #{}", link.board, link.place_id));
+    }
+    places
+}
+
+fn affordance_line(board: &Breadboard, affordance: &Affordance) -> String {
+    match leads_to(board, affordance).as_slice() {
+        [] => format!("├─ {}", affordance.name),
+        places => format!("├─ {} → {}", affordance.name, places.join(", ")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_lines_up_both_boards() {
+        let mut old = Breadboard::new("Billing".to_string());
+        for (id, name) in [(1, "Invoice"), (2, "Help"), (3, "Receipt")] {
+            old.add_place(Place::new(id, name.to_string()));
+        }
+        old.places[0].add_affordance(Affordance::new(1, "Pay".to_string()).with_connection(3));
+        old.places[0].add_affordance(Affordance::new(2, "Print".to_string()));
+
+        let mut new = old.clone();
+        new.places.retain(|p| p.id != 2);
+        new.places[0].name = "Bill".to_string();
+        new.places[0].affordances[0].connects_to = Some(4);
+        new.places[0].affordances.retain(|a| a.id != 2);
+        new.add_place(Place::new(4, "Confirm".to_string()));
+
+        let rows = compare(&old, &new);
+        let lines: Vec<(&str, &str, Change)> = rows.iter().map(|r| (r.old.as_str(), r.new.as_str(), r.change)).collect();
+        assert_eq!(lines, vec![
+            ("┌─ Invoice", "┌─ Bill", Change::Renamed),
+            ("├─ Pay → Receipt", "├─ Pay → Confirm", Change::Rewired),
+            ("├─ Print", "", Change::Removed),
+            ("", "", Change::Same),
+            ("┌─ Help", "", Change::Removed),
+            ("", "", Change::Same),
+            ("┌─ Receipt", "┌─ Receipt", Change::Same),
+            ("", "", Change::Same),
+            ("", "┌─ Confirm", Change::Added),
+        ]);
+        assert_eq!(summary(&rows).as_deref(), Some("1 added, 2 removed, 2 changed"));
+        assert_eq!(summary(&compare(&old, &old)), None);
+
+        let mut comparison = Comparison::new("old".to_string(), &old, "new".to_string(), &new);
+        assert!(comparison.jump_to_change(true));
+        assert_eq!(comparison.scroll, 4);
+        assert!(comparison.jump_to_change(true));
+        assert_eq!(comparison.scroll, 8);
+        assert!(!comparison.jump_to_change(true));
+        assert!(comparison.jump_to_change(false));
+        assert_eq!(comparison.scroll, 4);
+        comparison.scroll_by(-10);
+        assert_eq!(comparison.scroll, 0);
+        comparison.scroll_by(100);
+        assert_eq!(comparison.scroll, 8);
+    }
+}
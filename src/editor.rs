@@ -0,0 +1,67 @@
+use anyhow::{bail, Context, Result};
+use std::io::Write;
+use std::process::Command;
+
+// Editing longer text in the user's editor, the way `git commit` does: the
+// text goes to a temporary file, the editor opens it and whatever is saved
+// comes back. The caller has to hand over the terminal first.
+
+// $VISUAL, then $EDITOR, then vi
+pub fn preferred_editor() -> String {
+    ["VISUAL", "EDITOR"].iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string())
+}
+
+// Open `text` in `editor` and return the saved result. The editor command
+// may carry arguments (`code --wait`), so it runs through the shell.
+pub fn edit_text(editor: &str, text: &str) -> Result<String> {
+    // A new file with a random name, so nothing planted at a guessable path
+    // is written through; the .md suffix gets editors to highlight Markdown
+    let mut file = tempfile::Builder::new()
+        .prefix("bboard-notes-")
+        .suffix(".md")
+        .tempfile()
+        .context("Failed to create a temporary file")?;
+    file.write_all(text.as_bytes())
+        .and_then(|()| file.flush())
+        .with_context(|| format!("Failed to write {}", file.path().display()))?;
+    // Closed while the editor has it, and removed when this goes
+    let path = file.into_temp_path();
+
+    let mut shell = if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C").arg(format!("{} \"{}\"", editor, path.display()));
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.arg("-c").arg(format!("{} \"$1\"", editor)).arg(editor).arg(&path);
+        shell
+    };
+    let status = shell.status().with_context(|| format!("Failed to start {}", editor));
+
+    let edited = std::fs::read_to_string(&path);
+
+    let status = status?;
+    if !status.success() {
+        bail!("{} exited with {}", editor, status);
+    }
+    edited.with_context(|| format!("Failed to read back {}", path.display()))
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_text_reads_back_saved_file() {
+        let edited = edit_text("printf 'Second paragraph\\n' >>", "First line\n\n").unwrap();
+        assert_eq!(edited, "First line\n\nSecond paragraph\n");
+    }
+
+    #[test]
+    fn test_failing_editor_is_an_error() {
+        assert!(edit_text("false", "keep me").is_err());
+    }
+}
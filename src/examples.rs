@@ -0,0 +1,42 @@
+use anyhow::{Context, Result};
+
+use crate::models::Breadboard;
+
+// Boards built into the binary, offered on the start screen so there's
+// something to explore before drawing your own
+pub const EXAMPLES: [(&str, &str); 2] = [
+    ("Invoice and Autopay", include_str!("../example.toml")),
+    ("90s personal website", include_str!("../90s-personal-website.toml")),
+];
+
+// The bundled example at `index`, ready to open
+pub fn load(index: usize) -> Result<Breadboard> {
+    let (name, text) = EXAMPLES.get(index).context("no such example")?;
+    let mut breadboard: Breadboard = toml::from_str(text)
+        .with_context(|| format!("Failed to parse the {} example", name))?;
+    breadboard.sync_id_counters();
+    Ok(breadboard)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_examples_load() {
+        for index in 0..EXAMPLES.len() {
+            assert!(!load(index).unwrap().places.is_empty());
+        }
+        assert_eq!(load(0).unwrap().places[0].name, "Invoice");
+        // Every link in the examples leads to a place on the same board
+        for index in 0..EXAMPLES.len() {
+            let breadboard = load(index).unwrap();
+            for affordance in breadboard.places.iter().flat_map(|p| &p.affordances) {
+                for dest in affordance.destinations() {
+                    assert!(breadboard.find_place(&dest).is_some(), "{} leads to {}", affordance.name, dest);
+                }
+            }
+        }
+        assert!(load(EXAMPLES.len()).is_err());
+    }
+}
@@ -0,0 +1,511 @@
+use crate::app::App;
+use crate::diagram::render_diagram;
+use crate::layout::layered_layout;
+use crate::locale::ENGLISH;
+use crate::models::{Affordance, Breadboard, Detail, Place, Status};
+use crate::html;
+use crate::mermaid;
+use crate::print;
+use crate::svg;
+use std::collections::HashSet;
+
+// Documents written from a board, as opposed to snapshots of a view: prose
+// and Markdown meant to be pasted into pitches and trackers, pages, and
+// diagrams for other tools.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Document {
+    Walkthrough, // Numbered steps along the main path from the entry place
+    Pitch,       // Shape Up pitch skeleton around the board's diagram
+    Tasks { per_affordance: bool }, // Checklist of places, or of affordances, to build
+    Print,       // HTML pages to print, with a connection index
+    Html,        // One web page with a clickable graph and a foldable outline
+    Mermaid,     // Flowchart for docs that render Mermaid
+    Svg,         // The graph as an image
+}
+
+impl Document {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "walkthrough" => Some(Document::Walkthrough),
+            "pitch" => Some(Document::Pitch),
+            "tasks" => Some(Document::Tasks { per_affordance: false }),
+            "print" => Some(Document::Print),
+            "html" => Some(Document::Html),
+            "mermaid" => Some(Document::Mermaid),
+            "svg" => Some(Document::Svg),
+            _ => None,
+        }
+    }
+
+    // Appended to the board's file stem when suggesting where to save
+    pub fn file_suffix(self) -> &'static str {
+        match self {
+            Document::Walkthrough => "-walkthrough.md",
+            Document::Pitch => "-pitch.md",
+            Document::Tasks { .. } => "-tasks.md",
+            Document::Print => "-print.html",
+            Document::Html => ".html",
+            Document::Mermaid => ".mmd",
+            Document::Svg => ".svg",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Document::Walkthrough => "Walkthrough",
+            Document::Pitch => "Pitch",
+            Document::Tasks { .. } => "Task list",
+            Document::Print => "Print pages",
+            Document::Html => "HTML page",
+            Document::Mermaid => "Mermaid flowchart",
+            Document::Svg => "SVG image",
+        }
+    }
+}
+
+// Cut affordances are left out, as in view exports, unless the config keeps
+// them. A pitch always lists them, as no-gos.
+pub fn render_document(app: &App, document: Document) -> String {
+    let breadboard = document_board(app);
+    match document {
+        Document::Walkthrough => walkthrough(&breadboard),
+        Document::Pitch => pitch(&scoped_board(app)),
+        Document::Tasks { per_affordance } => task_list(&breadboard, &tasks(&breadboard, per_affordance)),
+        Document::Print => print::html(&breadboard, app.config.paper),
+        Document::Html => html::page(&breadboard),
+        Document::Mermaid => mermaid::flowchart(&breadboard),
+        Document::Svg => svg::image(&breadboard),
+    }
+}
+
+// Documents cover the whole board whatever the filter, unless the app is a
+// scoped copy
+fn scoped_board(app: &App) -> Breadboard {
+    if app.state.scope.is_some() {
+        app.visible_board()
+    } else {
+        app.breadboard.clone()
+    }
+}
+
+// The board documents are written from
+pub fn document_board(app: &App) -> Breadboard {
+    let mut breadboard = scoped_board(app);
+    if !app.config.export_cut {
+        for place in &mut breadboard.places {
+            place.affordances.retain(|a| !a.cut);
+        }
+    }
+    breadboard
+}
+
+// Something to build: a place, or one affordance of a place. It depends on
+// the places its connections lead to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Task {
+    pub title: String,
+    pub done: bool,
+    pub depends_on: Vec<String>,
+    pub notes: Option<String>,
+}
+
+impl Task {
+    // Issue body: the notes, then what the task depends on
+    pub fn body(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(notes) = &self.notes {
+            parts.push(notes.clone());
+        }
+        if !self.depends_on.is_empty() {
+            parts.push(format!("Depends on: {}", self.depends_on.join(", ")));
+        }
+        parts.join("\n\n")
+    }
+}
+
+// A place is done once it has affordances and all of them are built
+pub fn tasks(breadboard: &Breadboard, per_affordance: bool) -> Vec<Task> {
+    let names = |dests: Vec<u32>, own: u32| -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for place in dests.iter().filter(|id| **id != own).filter_map(|id| breadboard.find_place(id)) {
+            if !names.contains(&place.name) {
+                names.push(place.name.clone());
+            }
+        }
+        names
+    };
+    let notes = |notes: &Option<String>| notes.as_deref().map(str::trim).filter(|n| !n.is_empty()).map(str::to_string);
+
+    let mut tasks = Vec::new();
+    for place in &breadboard.places {
+        if per_affordance {
+            tasks.extend(place.affordances.iter().map(|affordance| Task {
+                title: format!("{}: {}", place.name, affordance.name),
+                done: affordance.status == Status::Built,
+                depends_on: names(affordance.destinations(), place.id),
+                notes: notes(&affordance.notes),
+            }));
+        } else {
+            tasks.push(Task {
+                title: place.name.clone(),
+                done: !place.affordances.is_empty() && place.affordances.iter().all(|a| a.status == Status::Built),
+                depends_on: names(place.affordances.iter().flat_map(|a| a.destinations()).collect(), place.id),
+                notes: notes(&place.notes),
+            });
+        }
+    }
+    tasks
+}
+
+// Markdown task list, built tasks checked off
+pub fn task_list(breadboard: &Breadboard, tasks: &[Task]) -> String {
+    let mut lines = vec![format!("# {} tasks", breadboard.name), String::new()];
+    for task in tasks {
+        let mut line = format!("- [{}] {}", if task.done { "x" } else { " " }, task.title);
+        if !task.depends_on.is_empty() {
+            line.push_str(&format!(" (depends on {})", task.depends_on.join(", ")));
+        }
+        lines.push(line);
+        if let Some(notes) = &task.notes {
+            lines.extend(notes.lines().map(|l| format!("  {}", l).trim_end().to_string()));
+        }
+    }
+
+    let mut text = lines.join("\n");
+    text.push('\n');
+    text
+}
+
+// One way out of a place: an affordance, or one labeled outcome of it
+struct Choice<'a> {
+    affordance: &'a Affordance,
+    outcome: Option<&'a str>,
+    dest: Option<u32>,
+}
+
+impl Choice<'_> {
+    fn text(&self) -> String {
+        match self.outcome {
+            Some(label) => format!("{} ({})", self.affordance.name, label),
+            None => self.affordance.name.clone(),
+        }
+    }
+}
+
+fn choices(place: &Place) -> Vec<Choice<'_>> {
+    let mut choices = Vec::new();
+    for affordance in &place.affordances {
+        if affordance.connects_to.is_some() || affordance.branches.is_empty() {
+            choices.push(Choice { affordance, outcome: None, dest: affordance.connects_to });
+        }
+        for branch in &affordance.branches {
+            choices.push(Choice { affordance, outcome: Some(&branch.label), dest: branch.connects_to });
+        }
+    }
+    choices
+}
+
+// The board told as numbered steps: from the entry place, each step takes
+// the first way on to a place not yet described. Every other way out of a
+// step's place is a sub-item, and one leading somewhere new is walked the
+// same way in a nested list.
+pub fn walkthrough(breadboard: &Breadboard) -> String {
+    let mut lines = vec![format!("# {} walkthrough", breadboard.name), String::new()];
+    match breadboard.places.first() {
+        Some(entry) => {
+            let mut described = HashSet::new();
+            walk(breadboard, entry.id, "", &mut described, &mut lines);
+        }
+        None => lines.push("The board has no places yet.".to_string()),
+    }
+
+    let mut text = lines.join("\n");
+    text.push('\n');
+    text
+}
+
+fn walk(breadboard: &Breadboard, start: u32, indent: &str, described: &mut HashSet<u32>, lines: &mut Vec<String>) {
+    // Claim the whole main path before any side branch, so a side branch
+    // can't wander into a place the main path reaches later
+    let mut path = vec![start];
+    described.insert(start);
+    while let Some(next) = breadboard.find_place(path.last().unwrap())
+        .and_then(|place| main_choice(breadboard, place, described))
+        .and_then(|choice| choice.dest)
+    {
+        described.insert(next);
+        path.push(next);
+    }
+
+    for (index, place_id) in path.iter().enumerate() {
+        let Some(place) = breadboard.find_place(place_id) else { continue };
+        let next = path.get(index + 1).copied();
+        let choices = choices(place);
+        let main = next.and_then(|next| choices.iter().position(|c| c.dest == Some(next)));
+
+        let step = match main {
+            Some(main) => format!("On {}, the user taps {}", place.name, choices[main].text()),
+            None => format!("The user arrives at {}", place.name),
+        };
+        lines.push(format!("{}{}. {}", indent, index + 1, step));
+
+        let sub_indent = format!("{}   ", indent);
+        for (choice_index, choice) in choices.iter().enumerate() {
+            if Some(choice_index) == main {
+                continue;
+            }
+            let verb = if main.is_some() { "Or taps" } else { "Taps" };
+            let dest = choice.dest.and_then(|id| breadboard.find_place(&id));
+            match dest {
+                Some(dest) if dest.id == place.id => {
+                    lines.push(format!("{}- {} {} (stays on {})", sub_indent, verb, choice.text(), place.name));
+                }
+                Some(dest) => {
+                    lines.push(format!("{}- {} {} → {}", sub_indent, verb, choice.text(), dest.name));
+                    if !described.contains(&dest.id) {
+                        walk(breadboard, dest.id, &format!("{}  ", sub_indent), described, lines);
+                    }
+                }
+                None => lines.push(format!("{}- {} {}", sub_indent, verb, choice.text())),
+            }
+        }
+    }
+}
+
+// Markdown skeleton of a Shape Up pitch. The Solution holds the breadboard
+// diagram and a section per place with its notes and affordances; cut
+// affordances are left out of it and listed under No-gos. The other sections
+// are prompts to fill in.
+pub fn pitch(breadboard: &Breadboard) -> String {
+    let mut scoped = breadboard.clone();
+    for place in &mut scoped.places {
+        place.affordances.retain(|a| !a.cut);
+    }
+
+    let mut lines = vec![
+        format!("# {}", breadboard.name),
+        String::new(),
+        "## Problem".to_string(),
+        String::new(),
+        "<!-- What's broken today, and for whom? -->".to_string(),
+        String::new(),
+        "## Appetite".to_string(),
+        String::new(),
+        "<!-- How much time is this worth, e.g. two weeks or six weeks? -->".to_string(),
+        String::new(),
+        "## Solution".to_string(),
+        String::new(),
+    ];
+
+    if !scoped.places.is_empty() {
+        lines.push("```".to_string());
+        lines.extend(render_diagram(&scoped, &layered_layout(&scoped), Detail::Full).lines());
+        lines.push("```".to_string());
+        lines.push(String::new());
+    }
+
+    for place in &scoped.places {
+        lines.push(format!("### {}", place.title()));
+        lines.push(String::new());
+        if let Some(notes) = place.notes.as_deref().map(str::trim).filter(|n| !n.is_empty()) {
+            lines.push(notes.to_string());
+            lines.push(String::new());
+        }
+        if !place.comments.is_empty() {
+            lines.extend(place.comments.iter().map(|comment| format!("> {}", comment.describe(&ENGLISH))));
+            lines.push(String::new());
+        }
+        if place.affordances.is_empty() {
+            continue;
+        }
+        for choice in choices(place) {
+            let dest = choice.dest.and_then(|id| scoped.find_place(&id));
+            match dest {
+                Some(dest) => lines.push(format!("- {} → {}", choice.text(), dest.name)),
+                None => lines.push(format!("- {}", choice.text())),
+            }
+            // Notes once per affordance, under its last outcome
+            let last_of_affordance = choice.outcome.is_none()
+                || choice.affordance.branches.last().map(|b| b.label.as_str()) == choice.outcome;
+            if let Some(notes) = choice.affordance.notes.as_deref().filter(|_| last_of_affordance) {
+                lines.extend(notes.trim().lines().map(|line| format!("  {}", line).trim_end().to_string()));
+            }
+            if last_of_affordance {
+                lines.extend(choice.affordance.comments.iter().map(|comment| format!("  > {}", comment.describe(&ENGLISH))));
+            }
+        }
+        lines.push(String::new());
+    }
+
+    if !breadboard.glossary.is_empty() {
+        lines.push("### Glossary".to_string());
+        lines.push(String::new());
+        lines.extend(breadboard.glossary.iter().map(|(term, definition)| format!("- **{}**: {}", term, definition)));
+        lines.push(String::new());
+    }
+
+    lines.push("## Rabbit holes".to_string());
+    lines.push(String::new());
+    let holes = breadboard.rabbit_holes();
+    if holes.is_empty() {
+        lines.push("<!-- Risky details worth calling out up front -->".to_string());
+    }
+    for (name, notes) in holes {
+        lines.push(format!("- {}", name));
+        lines.extend(notes.into_iter().flat_map(str::lines).map(|line| format!("  {}", line).trim_end().to_string()));
+    }
+    lines.push(String::new());
+    lines.push("## No-gos".to_string());
+    lines.push(String::new());
+    let cut: Vec<String> = breadboard.places.iter()
+        .flat_map(|place| place.affordances.iter().filter(|a| a.cut).map(move |a| format!("- {} on {}", a.name, place.name)))
+        .collect();
+    if cut.is_empty() {
+        lines.push("<!-- What's deliberately out of scope? -->".to_string());
+    } else {
+        lines.extend(cut);
+    }
+
+    let mut text = lines.join("\n");
+    text.push('\n');
+    text
+}
+
+// The first way out of `place` that leads to a place not yet described
+fn main_choice<'a>(breadboard: &Breadboard, place: &'a Place, described: &HashSet<u32>) -> Option<Choice<'a>> {
+    choices(place).into_iter().find(|choice| {
+        choice.dest.is_some_and(|id| !described.contains(&id) && breadboard.find_place(&id).is_some())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::testing::{board, name_affordances};
+    use crate::models::{Branch, Comment};
+
+    fn autopay() -> Breadboard {
+        let mut breadboard = board(&["Invoice", "Setup Autopay", "Confirmation", "Help"], &[(0, 1), (0, 3), (1, 2), (1, 0), (3, 0)]);
+        breadboard.name = "Autopay".to_string();
+        name_affordances(&mut breadboard, &["Turn on Autopay", "Get help", "Save", "Cancel", "Back"]);
+        let fields = breadboard.generate_affordance_id();
+        breadboard.places[1].affordances.insert(0, Affordance::new(fields, "CC Fields".to_string()));
+        breadboard
+    }
+
+    #[test]
+    fn test_walkthrough_follows_main_path_with_side_branches() {
+        let text = walkthrough(&autopay());
+        assert_eq!(text, "\
+# Autopay walkthrough
+
+1. On Invoice, the user taps Turn on Autopay
+   - Or taps Get help → Help
+     1. The user arrives at Help
+        - Taps Back → Invoice
+2. On Setup Autopay, the user taps Save
+   - Or taps CC Fields
+   - Or taps Cancel → Invoice
+3. The user arrives at Confirmation
+");
+    }
+
+    #[test]
+    fn test_walkthrough_names_outcomes() {
+        let mut breadboard = autopay();
+        breadboard.places[2].add_affordance(Affordance::new(7, "Pay".to_string()));
+        breadboard.places[2].affordances[0].branches = vec![
+            Branch { label: "declined".to_string(), connects_to: Some(2) },
+            Branch { label: "paid".to_string(), connects_to: None },
+        ];
+
+        let text = walkthrough(&breadboard);
+        assert!(text.contains("3. The user arrives at Confirmation\n   - Taps Pay (declined) → Setup Autopay\n   - Taps Pay (paid)\n"));
+    }
+
+    #[test]
+    fn test_pitch_describes_places_and_lists_cut_as_no_gos() {
+        let mut breadboard = autopay();
+        breadboard.places[0].notes = Some("Shown after every payment".to_string());
+        breadboard.places[1].affordances[0].notes = Some("Card number, expiry\nand CVC".to_string());
+        breadboard.places[1].affordances[2].cut = true;
+
+        let text = pitch(&breadboard);
+        let sections: Vec<&str> = text.lines().filter(|l| l.starts_with("## ")).collect();
+        assert_eq!(sections, vec!["## Problem", "## Appetite", "## Solution", "## Rabbit holes", "## No-gos"]);
+
+        assert!(text.contains("```\n"));
+        assert!(text.contains("▶┌ Setup Autopay"));
+        assert!(text.contains("### Invoice\n\nShown after every payment\n\n- Turn on Autopay → Setup Autopay\n- Get help → Help\n"));
+        assert!(text.contains("### Setup Autopay\n\n- CC Fields\n  Card number, expiry\n  and CVC\n- Save → Confirmation\n\n"));
+        assert!(text.ends_with("## No-gos\n\n- Cancel on Setup Autopay\n"));
+    }
+
+    #[test]
+    fn test_pitch_lists_rabbit_holes() {
+        let mut breadboard = autopay();
+        breadboard.places[1].rabbit_hole = true;
+        breadboard.places[1].affordances[1].rabbit_hole = true;
+        breadboard.places[1].affordances[1].notes = Some("Retries when the bank is down?".to_string());
+        let text = pitch(&breadboard);
+        assert!(text.contains("## Rabbit holes\n\n- Setup Autopay\n- Setup Autopay › Save\n  Retries when the bank is down?\n\n## No-gos"));
+    }
+
+    #[test]
+    fn test_pitch_keeps_comments() {
+        let mut breadboard = autopay();
+        let comment = |text: &str| Comment { author: "ryan".to_string(), at: "not a time".to_string(), text: text.to_string() };
+        breadboard.places[0].comments.push(comment("Too busy?"));
+        breadboard.places[1].affordances[0].comments.push(comment("Use the saved card"));
+
+        let text = pitch(&breadboard);
+        assert!(text.contains("### Invoice\n\n> ryan, not a time: Too busy?\n\n- Turn on Autopay"));
+        assert!(text.contains("- CC Fields\n  > ryan, not a time: Use the saved card\n- Save"));
+    }
+
+    #[test]
+    fn test_pitch_lists_the_glossary() {
+        let mut breadboard = autopay();
+        breadboard.glossary.insert("Autopay".to_string(), "Paying each invoice automatically".to_string());
+        let text = pitch(&breadboard);
+        assert!(text.contains("### Glossary\n\n- **Autopay**: Paying each invoice automatically\n\n## Rabbit holes"));
+
+        // The glossary survives a save
+        let saved: Breadboard = toml::from_str(&toml::to_string_pretty(&breadboard).unwrap()).unwrap();
+        assert_eq!(saved.glossary, breadboard.glossary);
+    }
+
+    #[test]
+    fn test_task_list_per_place_and_per_affordance() {
+        let mut breadboard = autopay();
+        breadboard.places[3].affordances[0].status = Status::Built;
+        breadboard.places[3].notes = Some("FAQ and contact form".to_string());
+
+        let text = task_list(&breadboard, &tasks(&breadboard, false));
+        assert_eq!(text, "\
+# Autopay tasks
+
+- [ ] Invoice (depends on Setup Autopay, Help)
+- [ ] Setup Autopay (depends on Confirmation, Invoice)
+- [ ] Confirmation
+- [x] Help (depends on Invoice)
+  FAQ and contact form
+");
+
+        let per_affordance = tasks(&breadboard, true);
+        assert_eq!(per_affordance.len(), 6);
+        assert_eq!(per_affordance[2], Task {
+            title: "Setup Autopay: CC Fields".to_string(),
+            done: false,
+            depends_on: Vec::new(),
+            notes: None,
+        });
+        assert_eq!(per_affordance[5].body(), "Depends on: Invoice");
+    }
+
+    #[test]
+    fn test_walkthrough_of_empty_board() {
+        let text = walkthrough(&Breadboard::new("Empty".to_string()));
+        assert_eq!(text, "# Empty walkthrough\n\nThe board has no places yet.\n");
+    }
+}
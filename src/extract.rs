@@ -0,0 +1,92 @@
+use crate::models::{BoardLink, Breadboard};
+
+// Splitting a board that has grown too big: a place and everything reachable
+// from it become a board of their own.
+
+// The board made of `start` and every place reachable from it, with `start`
+// first as its entry. IDs are kept, so connections between the places still
+// hold. The original board is left as it was.
+pub fn extract(breadboard: &Breadboard, start: u32, name: String) -> Breadboard {
+    let reachable = breadboard.reachable_from(start);
+    let mut extracted = Breadboard::new(name);
+    extracted.places = breadboard.places.iter()
+        .filter(|place| reachable.contains(&place.id))
+        .cloned()
+        .collect();
+    if let Some(index) = extracted.places.iter().position(|place| place.id == start) {
+        let entry = extracted.places.remove(index);
+        extracted.places.insert(0, entry);
+    }
+    extracted.sync_id_counters();
+    extracted
+}
+
+// Take the places of `extracted` off `breadboard`. Affordances that led into
+// them link to the same place in the new board instead, saved as `board`
+// (the file as the workspace will list it). Outcomes can't hold links, so
+// ones that led into the new board lose their connection. Returns how many
+// affordances were relinked.
+pub fn replace_with_link(breadboard: &mut Breadboard, extracted: &Breadboard, board: &str) -> usize {
+    let moved = |id: &u32| extracted.find_place(id).is_some();
+    breadboard.places.retain(|place| !moved(&place.id));
+
+    let mut relinked = 0;
+    for affordance in breadboard.places.iter_mut().flat_map(|place| place.affordances.iter_mut()) {
+        if let Some(dest) = affordance.connects_to.filter(moved) {
+            affordance.connects_to = None;
+            affordance.link = Some(BoardLink { board: board.to_string(), place_id: dest });
+            relinked += 1;
+        }
+        for branch in &mut affordance.branches {
+            if branch.connects_to.is_some_and(|dest| moved(&dest)) {
+                branch.connects_to = None;
+            }
+        }
+    }
+    relinked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::testing::{board, name_affordances};
+    use crate::models::Branch;
+
+    fn autopay() -> Breadboard {
+        let mut breadboard = board(&["Invoice", "Setup Autopay", "Confirmation", "Help"], &[(0, 1), (0, 3), (1, 2), (3, 0)]);
+        breadboard.name = "Billing".to_string();
+        name_affordances(&mut breadboard, &["Turn on Autopay", "Get help", "Save", "Back"]);
+        breadboard.places[3].affordances[0].branches = vec![Branch { label: "to setup".to_string(), connects_to: Some(2) }];
+        breadboard
+    }
+
+    #[test]
+    fn test_extract_reachable_places() {
+        let breadboard = autopay();
+        let extracted = extract(&breadboard, 2, "Autopay".to_string());
+        let names: Vec<&str> = extracted.places.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["Setup Autopay", "Confirmation"]);
+        assert_eq!(extracted.places[0].affordances[0].connects_to, Some(3));
+        assert_eq!(extracted.next_place_id, 4);
+        assert_eq!(breadboard.places.len(), 4);
+
+        // Help leads back to Invoice, which reaches everything
+        assert_eq!(extract(&breadboard, 4, "Help".to_string()).places[0].name, "Help");
+        assert_eq!(extract(&breadboard, 4, "Help".to_string()).places.len(), 4);
+    }
+
+    #[test]
+    fn test_replace_with_link() {
+        let mut breadboard = autopay();
+        let extracted = extract(&breadboard, 2, "Autopay".to_string());
+        assert_eq!(replace_with_link(&mut breadboard, &extracted, "autopay.toml"), 1);
+
+        let names: Vec<&str> = breadboard.places.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["Invoice", "Help"]);
+        let turn_on = &breadboard.places[0].affordances[0];
+        assert_eq!(turn_on.connects_to, None);
+        assert_eq!(turn_on.link, Some(BoardLink { board: "autopay.toml".to_string(), place_id: 2 }));
+        assert_eq!(breadboard.places[0].affordances[1].connects_to, Some(4));
+        assert_eq!(breadboard.places[1].affordances[0].branches[0].connects_to, None);
+    }
+}
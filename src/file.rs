@@ -1,54 +1,105 @@
+use crate::locale::{self, Strings};
 use crate::models::Breadboard;
+use crate::workspace::Workspace;
 use anyhow::{Result, Context};
 use std::fs;
 use std::path::Path;
 
 pub struct FileManager;
 
+// One board in the boards directory, as listed by the library view
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoardSummary {
+    pub path: String,
+    pub name: String,
+    pub places: usize,
+    pub modified: Option<chrono::DateTime<chrono::Local>>,
+}
+
+// One file in the open dialog, with what a quick look at it found
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileEntry {
+    pub path: String,
+    pub modified: Option<chrono::DateTime<chrono::Local>>,
+    pub name: Option<String>, // The board's name, if it reads as one
+    pub places: Option<usize>, // None for files without places, like workspaces
+    pub error: Option<Unopenable>, // Why it can't be opened as a board or workspace
+}
+
+// Why a listed file can't be opened
+#[derive(Debug, Clone, PartialEq)]
+pub enum Unopenable {
+    Unreadable(String),
+    NotToml(String), // The parser's message
+    NotABoard,
+}
+
+impl Unopenable {
+    pub fn describe(&self, s: &Strings) -> String {
+        match self {
+            Unopenable::Unreadable(error) => locale::fill(s.file_unreadable, &[error]),
+            Unopenable::NotToml(error) => locale::fill(s.file_not_toml, &[error]),
+            Unopenable::NotABoard => s.file_not_board.to_string(),
+        }
+    }
+}
+
 impl FileManager {
     pub fn new() -> Self {
         Self
     }
 
     pub fn save_to_file<P: AsRef<Path>>(&self, breadboard: &Breadboard, path: P) -> Result<()> {
+        let path = path.as_ref();
         let toml_string = toml::to_string_pretty(breadboard)
             .context("Failed to serialize breadboard to TOML")?;
 
-        fs::write(path, toml_string)
-            .context("Failed to write TOML to file")?;
+        if let Err(e) = fs::write(path, &toml_string) {
+            tracing::warn!(path = %path.display(), error = %e, "failed to save board");
+            return Err(e).context("Failed to write TOML to file");
+        }
+        tracing::debug!(path = %path.display(), bytes = toml_string.len(), places = breadboard.places.len(), "saved board");
 
         Ok(())
     }
 
     pub fn load_from_file<P: AsRef<Path>>(&self, path: P) -> Result<Breadboard> {
+        let path = path.as_ref();
         let content = fs::read_to_string(path)
             .context("Failed to read TOML file")?;
 
-        let breadboard: Breadboard = toml::from_str(&content)
-            .context("Failed to parse TOML as Breadboard")?;
+        let breadboard: Breadboard = match toml::from_str(&content) {
+            Ok(breadboard) => breadboard,
+            Err(e) => {
+                tracing::debug!(path = %path.display(), error = %e, "not a board");
+                return Err(e).context("Failed to parse TOML as Breadboard");
+            }
+        };
+        tracing::debug!(path = %path.display(), bytes = content.len(), places = breadboard.places.len(), "loaded board");
 
         Ok(breadboard)
     }
 
-    pub fn list_toml_files(&self) -> Result<Vec<String>> {
-        let current_dir = std::env::current_dir()
-            .context("Failed to get current directory")?;
+    // Whether `path` is a file the open dialog lists: TOML, or a .bboard
+    // bundle
+    pub fn is_listed(path: &Path) -> bool {
+        path.is_file() && path.extension().is_some_and(|extension| extension == "toml" || extension == "bboard")
+    }
 
+    // File names of the TOML files (and .bboard bundles) directly inside
+    // `dir`, sorted
+    pub fn list_toml_files_in(&self, dir: &Path) -> Result<Vec<String>> {
         let mut toml_files = Vec::new();
 
-        for entry in fs::read_dir(current_dir)
-            .context("Failed to read current directory")? {
+        for entry in fs::read_dir(dir)
+            .with_context(|| format!("Failed to read directory {}", dir.display()))? {
             let entry = entry.context("Failed to read directory entry")?;
             let path = entry.path();
 
-            if path.is_file() {
-                if let Some(extension) = path.extension() {
-                    if extension == "toml" {
-                        if let Some(filename) = path.file_name() {
-                            if let Some(filename_str) = filename.to_str() {
-                                toml_files.push(filename_str.to_string());
-                            }
-                        }
+            if Self::is_listed(&path) {
+                if let Some(filename) = path.file_name() {
+                    if let Some(filename_str) = filename.to_str() {
+                        toml_files.push(filename_str.to_string());
                     }
                 }
             }
@@ -57,6 +108,74 @@ impl FileManager {
         toml_files.sort();
         Ok(toml_files)
     }
+
+    // Every board in `dir`, most recently modified first. TOML files that
+    // aren't boards are left out.
+    pub fn library(&self, dir: &Path) -> Result<Vec<BoardSummary>> {
+        let mut boards: Vec<BoardSummary> = self.list_toml_files_in(dir)?
+            .into_iter()
+            .filter_map(|name| self.summarize(&dir.join(name)))
+            .collect();
+
+        boards.sort_by_key(|board| std::cmp::Reverse(board.modified));
+        Ok(boards)
+    }
+
+    // The open dialog's entry for `path`: its modification time, and the
+    // board's name and place count. The file is read as the board or
+    // workspace it says it is, so only files that open are listed as such;
+    // the others say why they can't be.
+    pub fn peek(&self, path: &str) -> FileEntry {
+        let modified = fs::metadata(path).and_then(|m| m.modified()).ok().map(Into::into);
+        let mut entry = FileEntry { path: path.to_string(), modified, name: None, places: None, error: None };
+        if crate::bundle::is_bundle(Path::new(path)) {
+            return entry;
+        }
+        let table = match fs::read_to_string(path) {
+            Ok(content) => content.parse::<toml::Table>(),
+            Err(e) => {
+                entry.error = Some(Unopenable::Unreadable(e.to_string()));
+                return entry;
+            }
+        };
+        let table = match table {
+            Ok(table) => table,
+            Err(e) => {
+                entry.error = Some(Unopenable::NotToml(e.message().to_string()));
+                return entry;
+            }
+        };
+        // Told apart the way opening the file tells them apart
+        let workspace = table.contains_key("boards") && !table.contains_key("places");
+        let value = toml::Value::Table(table);
+        if workspace {
+            match value.try_into::<Workspace>() {
+                Ok(workspace) => entry.name = Some(workspace.name).filter(|name| !name.is_empty()),
+                Err(_) => entry.error = Some(Unopenable::NotABoard),
+            }
+        } else {
+            match value.try_into::<Breadboard>() {
+                Ok(breadboard) => {
+                    entry.places = Some(breadboard.places.len());
+                    entry.name = Some(breadboard.name);
+                }
+                Err(_) => entry.error = Some(Unopenable::NotABoard),
+            }
+        }
+        entry
+    }
+
+    // Library entry for the board at `path`, or None if it isn't a board
+    pub fn summarize(&self, path: &Path) -> Option<BoardSummary> {
+        let breadboard = self.load_from_file(path).ok()?;
+        let modified = fs::metadata(path).and_then(|m| m.modified()).ok().map(Into::into);
+        Some(BoardSummary {
+            path: path.to_string_lossy().to_string(),
+            name: breadboard.name,
+            places: breadboard.places.len(),
+            modified,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -65,6 +184,48 @@ mod tests {
     use tempfile::NamedTempFile;
     use std::io::Write;
 
+    #[test]
+    fn test_peek() -> Result<()> {
+        let fm = FileManager::new();
+        let mut breadboard = Breadboard::new("Checkout".to_string());
+        let place_id = breadboard.generate_place_id();
+        breadboard.add_place(crate::models::Place::new(place_id, "Cart".to_string()));
+        let affordance_id = breadboard.generate_affordance_id();
+        breadboard.places[0].add_affordance(crate::models::Affordance::new(affordance_id, "Reload".to_string()).with_connection(place_id));
+        let board_file = NamedTempFile::new()?;
+        fm.save_to_file(&breadboard, board_file.path())?;
+
+        let entry = fm.peek(&board_file.path().to_string_lossy());
+        assert_eq!(entry.name.as_deref(), Some("Checkout"));
+        assert_eq!(entry.places, Some(1));
+        assert!(entry.modified.is_some());
+        assert_eq!(entry.error, None);
+
+        // A Cargo.toml is TOML, but not a board
+        let mut cargo = NamedTempFile::new()?;
+        writeln!(cargo, "[package]\nname = \"bboard\"")?;
+        let entry = fm.peek(&cargo.path().to_string_lossy());
+        assert_eq!((entry.name, entry.places), (None, None));
+        assert_eq!(entry.error, Some(Unopenable::NotABoard));
+
+        let mut broken = NamedTempFile::new()?;
+        writeln!(broken, "name = ")?;
+        let entry = fm.peek(&broken.path().to_string_lossy());
+        assert!(matches!(entry.error, Some(Unopenable::NotToml(_))));
+
+        // Named, with places, but not a board that would open
+        let mut almost = NamedTempFile::new()?;
+        write!(almost, "{}", fs::read_to_string(board_file.path())?.replace("connects_to = 1", "connects_to = \"550e8400\""))?;
+        let entry = fm.peek(&almost.path().to_string_lossy());
+        assert_eq!((entry.name, entry.places, entry.error), (None, None, Some(Unopenable::NotABoard)));
+
+        let mut workspace = NamedTempFile::new()?;
+        writeln!(workspace, "name = \"Billing\"\n[[boards]]\ntitle = \"Invoices\"\nfile = \"a.toml\"")?;
+        let entry = fm.peek(&workspace.path().to_string_lossy());
+        assert_eq!((entry.name.as_deref(), entry.places, entry.error), (Some("Billing"), None, None));
+        Ok(())
+    }
+
     #[test]
     fn test_file_manager_new() {
         let _fm = FileManager::new();
@@ -118,6 +279,27 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_library_lists_only_boards() -> Result<()> {
+        let fm = FileManager::new();
+        let dir = tempfile::tempdir()?;
+        let mut breadboard = Breadboard::new("Checkout".to_string());
+        let place_id = breadboard.generate_place_id();
+        breadboard.add_place(crate::models::Place::new(place_id, "Cart".to_string()));
+        fm.save_to_file(&breadboard, dir.path().join("checkout.toml"))?;
+        fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"x\"\n")?;
+        fs::write(dir.path().join("notes.txt"), "not toml")?;
+
+        let library = fm.library(dir.path())?;
+        assert_eq!(library.len(), 1);
+        assert_eq!(library[0].name, "Checkout");
+        assert_eq!(library[0].places, 1);
+        assert!(library[0].path.ends_with("checkout.toml"));
+        assert!(library[0].modified.is_some());
+
+        Ok(())
+    }
+
     #[test]
     fn test_save_complex_breadboard() -> Result<()> {
         let fm = FileManager::new();
@@ -0,0 +1,76 @@
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+// The board's glossary: the words a team keeps tripping over, defined next
+// to the flow that uses them.
+
+// Where glossary terms appear in `text`, as byte ranges in order. Terms match
+// whole words, ignoring ASCII case; a longer term wins over one inside it.
+pub fn find_terms(text: &str, glossary: &BTreeMap<String, String>) -> Vec<Range<usize>> {
+    let mut terms: Vec<&str> = glossary.keys().map(|t| t.trim()).filter(|t| !t.is_empty()).collect();
+    terms.sort_by_key(|term| std::cmp::Reverse(term.len()));
+
+    let is_word = |c: Option<char>| c.is_some_and(char::is_alphanumeric);
+    let mut found = Vec::new();
+    let mut start = 0;
+    'text: while start < text.len() {
+        let before = text[..start].chars().next_back();
+        if !is_word(before) {
+            for term in &terms {
+                let end = start + term.len();
+                let matches = text.get(start..end).is_some_and(|candidate| candidate.eq_ignore_ascii_case(term));
+                if matches && !is_word(text[end..].chars().next()) {
+                    found.push(start..end);
+                    start = end;
+                    continue 'text;
+                }
+            }
+        }
+        start += text[start..].chars().next().map_or(1, char::len_utf8);
+    }
+    found
+}
+
+// The glossary as the viewer shows it, one term per line. Terms that appear
+// in `selected` (the selection's name) come first, marked with ▸.
+pub fn describe(glossary: &BTreeMap<String, String>, selected: &str) -> String {
+    let used: Vec<&str> = find_terms(selected, glossary).into_iter().map(|range| &selected[range]).collect();
+    let is_used = |term: &str| used.iter().any(|u| u.eq_ignore_ascii_case(term.trim()));
+    let (first, rest): (Vec<_>, Vec<_>) = glossary.iter().partition(|(term, _)| is_used(term));
+    first.into_iter().map(|(term, definition)| format!("▸ {} — {}", term, definition))
+        .chain(rest.into_iter().map(|(term, definition)| format!("  {} — {}", term, definition)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glossary() -> BTreeMap<String, String> {
+        BTreeMap::from([
+            ("Autopay".to_string(), "Paying each invoice automatically".to_string()),
+            ("ACH".to_string(), "Bank transfer".to_string()),
+            ("ACH debit".to_string(), "Pulling money from the customer's bank".to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_find_terms() {
+        let text = "Turn on autopay by ACH debit";
+        let found: Vec<&str> = find_terms(text, &glossary()).into_iter().map(|r| &text[r]).collect();
+        assert_eq!(found, vec!["autopay", "ACH debit"]);
+
+        // Only whole words
+        assert!(find_terms("Autopayment via CACHE", &glossary()).is_empty());
+        assert_eq!(find_terms("é ACH", &glossary()), vec![3..6]);
+    }
+
+    #[test]
+    fn test_describe_puts_used_terms_first() {
+        assert_eq!(
+            describe(&glossary(), "Setup Autopay"),
+            "▸ Autopay — Paying each invoice automatically\n  ACH — Bank transfer\n  ACH debit — Pulling money from the customer's bank",
+        );
+    }
+}
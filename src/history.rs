@@ -0,0 +1,159 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::app::Selection;
+use crate::config::HistoryLimits;
+use crate::locale::{self, Strings};
+use crate::models::{Breadboard, Place};
+
+// Snapshots of the board taken before destructive operations, for
+// Alt+Shift+Z to put back. Places that didn't change between snapshots are
+// shared rather than copied, so a big board costs little more per snapshot
+// than the places the operation touched. How many are kept, and how much
+// memory they may take, comes from the config's [history] table.
+
+// The board as it was before a delete, merge or other sweeping change
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    pub label: String, // What was about to happen: "deleting Invoice"
+    board: Breadboard, // Everything but the places, which are below
+    board_size: usize,
+    places: Vec<Arc<SharedPlace>>,
+    pub selection: Option<Selection>,
+}
+
+#[derive(Debug)]
+struct SharedPlace {
+    place: Place,
+    size: usize, // Rough bytes it takes: its length as TOML
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    snapshots: Vec<Snapshot>, // Newest last
+}
+
+impl History {
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.snapshots.clear();
+    }
+
+    pub fn labels(&self) -> impl Iterator<Item = &str> {
+        self.snapshots.iter().map(|s| s.label.as_str())
+    }
+
+    // Keep `board` as it is now, then drop the oldest snapshots until the
+    // limits hold. The newest is always kept.
+    pub fn push(&mut self, label: String, board: &Breadboard, selection: Option<Selection>, limits: &HistoryLimits) {
+        let previous = self.snapshots.last().map_or(&[][..], |s| s.places.as_slice());
+        let places = board.places.iter()
+            .map(|place| match previous.iter().find(|shared| shared.place.id == place.id && shared.place == *place) {
+                Some(shared) => Arc::clone(shared),
+                None => Arc::new(SharedPlace { place: place.clone(), size: weigh(place) }),
+            })
+            .collect();
+        let shell = Breadboard { places: Vec::new(), ..board.clone() };
+        let board_size = weigh(&shell);
+        self.snapshots.push(Snapshot { label, board: shell, board_size, places, selection });
+
+        let max_bytes = limits.max_memory_kb.saturating_mul(1024);
+        while self.snapshots.len() > limits.max_snapshots.max(1) || (self.snapshots.len() > 1 && self.memory() > max_bytes) {
+            self.snapshots.remove(0);
+        }
+    }
+
+    // The newest snapshot's label, board and selection, taken off the history
+    pub fn pop(&mut self) -> Option<(String, Breadboard, Option<Selection>)> {
+        let snapshot = self.snapshots.pop()?;
+        let mut board = snapshot.board;
+        board.places = snapshot.places.iter().map(|shared| shared.place.clone()).collect();
+        Some((snapshot.label, board, snapshot.selection))
+    }
+
+    // Rough bytes the snapshots take, counting each shared place once
+    pub fn memory(&self) -> usize {
+        let mut seen = HashSet::new();
+        self.snapshots.iter()
+            .map(|snapshot| {
+                let places: usize = snapshot.places.iter()
+                    .filter(|shared| seen.insert(Arc::as_ptr(shared)))
+                    .map(|shared| shared.size)
+                    .sum();
+                snapshot.board_size + places
+            })
+            .sum()
+    }
+
+    // "3 snapshots, ~12 KB", for the progress panel to show what the
+    // history costs
+    pub fn describe(&self, s: &Strings) -> Option<String> {
+        let count = match self.len() {
+            0 => return None,
+            1 => s.snapshot.to_string(),
+            n => locale::fill(s.snapshots, &[&n.to_string()]),
+        };
+        Some(locale::fill(s.history_size, &[&count, &self.memory().div_ceil(1024).to_string()]))
+    }
+}
+
+fn weigh<T: serde::Serialize>(value: &T) -> usize {
+    toml::to_string(value).map_or(0, |text| text.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::locale::ENGLISH;
+
+    fn board(places: usize) -> Breadboard {
+        let mut board = Breadboard::new("Billing".to_string());
+        for id in 1..=places as u32 {
+            board.add_place(Place::new(id, format!("Place {} with a fairly long name", id)));
+        }
+        board
+    }
+
+    #[test]
+    fn test_unchanged_places_are_shared() {
+        let limits = HistoryLimits::default();
+        let mut history = History::default();
+        let mut board = board(50);
+        history.push("first".to_string(), &board, None, &limits);
+        let one = history.memory();
+
+        board.places[0].name = "Invoice".to_string();
+        history.push("second".to_string(), &board, Some(Selection::Place(1)), &limits);
+        // The second snapshot adds its board and the one renamed place
+        assert!(history.memory() < one + one / 10);
+
+        let (label, restored, selection) = history.pop().unwrap();
+        assert_eq!((label.as_str(), selection), ("second", Some(Selection::Place(1))));
+        assert_eq!(restored, board);
+        assert_eq!(history.pop().unwrap().1.places[0].name, "Place 1 with a fairly long name");
+        assert!(history.pop().is_none());
+    }
+
+    #[test]
+    fn test_limits_drop_the_oldest() {
+        let mut history = History::default();
+        let limits = HistoryLimits { max_snapshots: 3, ..HistoryLimits::default() };
+        for index in 0..5 {
+            history.push(index.to_string(), &board(index), None, &limits);
+        }
+        assert_eq!(history.labels().collect::<Vec<_>>(), vec!["2", "3", "4"]);
+
+        // Over the memory limit, only the newest is kept
+        let limits = HistoryLimits { max_snapshots: 10, max_memory_kb: 1 };
+        history.push("big".to_string(), &board(100), None, &limits);
+        assert_eq!(history.labels().collect::<Vec<_>>(), vec!["big"]);
+        assert_eq!(history.describe(&ENGLISH).unwrap(), format!("1 snapshot, ~{} KB", history.memory().div_ceil(1024)));
+    }
+}
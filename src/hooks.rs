@@ -0,0 +1,178 @@
+use crate::app::{App, Selection};
+use anyhow::{Context, Result};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+// Running hooks and custom commands from the config. Commands go through the
+// shell and learn about the board from environment variables:
+//
+//   BBOARD_EVENT           save, load, export or command
+//   BBOARD_FILE            Board file (empty if it was never saved)
+//   BBOARD_EXPORT          File just exported (on_export only)
+//   BBOARD_PLACE_ID        Selected place, or the selected affordance's place
+//   BBOARD_PLACE
+//   BBOARD_AFFORDANCE_ID   Selected affordance, if any
+//   BBOARD_AFFORDANCE
+#[derive(Debug, Default, PartialEq)]
+pub struct CommandContext {
+    pub event: &'static str,
+    pub file: Option<String>,
+    pub export: Option<String>,
+    pub place: Option<(u32, String)>,
+    pub affordance: Option<(u32, String)>,
+}
+
+impl CommandContext {
+    pub fn from_app(app: &App, event: &'static str) -> Self {
+        let place = app.get_selected_place().map(|p| (p.id, p.name.clone()));
+        let affordance = match &app.state.selection {
+            Some(Selection::Affordance { place_id, affordance_id }) => app.breadboard.find_place(place_id)
+                .and_then(|p| p.affordances.iter().find(|a| &a.id == affordance_id))
+                .map(|a| (a.id, a.name.clone())),
+            _ => None,
+        };
+
+        Self {
+            event,
+            file: app.state.current_filename.clone(),
+            export: None,
+            place,
+            affordance,
+        }
+    }
+
+    fn env(&self) -> Vec<(&'static str, String)> {
+        let mut vars = vec![
+            ("BBOARD_EVENT", self.event.to_string()),
+            ("BBOARD_FILE", self.file.clone().unwrap_or_default()),
+        ];
+        if let Some(export) = &self.export {
+            vars.push(("BBOARD_EXPORT", export.clone()));
+        }
+        if let Some((id, name)) = &self.place {
+            vars.push(("BBOARD_PLACE_ID", id.to_string()));
+            vars.push(("BBOARD_PLACE", name.clone()));
+        }
+        if let Some((id, name)) = &self.affordance {
+            vars.push(("BBOARD_AFFORDANCE_ID", id.to_string()));
+            vars.push(("BBOARD_AFFORDANCE", name.clone()));
+        }
+        vars
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CommandOutput {
+    pub success: bool,
+    pub text: String, // stdout followed by stderr
+}
+
+// A hook or command running on a background thread, so a slow one doesn't
+// freeze the screen. Cloning shares it. A command still running when bboard
+// quits carries on without it.
+#[derive(Debug, Clone)]
+pub struct Running {
+    pub title: String, // What the status line calls it
+    receiver: Arc<Mutex<Receiver<Result<CommandOutput, String>>>>,
+}
+
+impl Running {
+    pub fn start(title: String, command: String, context: CommandContext) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            // Nobody to tell if the app has gone
+            let _ = sender.send(run(&command, &context).map_err(|e| format!("{:#}", e)));
+        });
+        Self { title, receiver: Arc::new(Mutex::new(receiver)) }
+    }
+
+    // How it went, once it has finished, without waiting
+    pub fn finished(&self) -> Option<Result<CommandOutput, String>> {
+        let receiver = self.receiver.lock().ok()?;
+        match receiver.try_recv() {
+            Ok(result) => Some(result),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Err(format!("{} stopped without a result", self.title))),
+        }
+    }
+}
+
+// Run `command` through the shell and wait for it. Its output is captured
+// so it can't draw over the TUI.
+pub fn run(command: &str, context: &CommandContext) -> Result<CommandOutput> {
+    let mut shell = if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C");
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.arg("-c");
+        shell
+    };
+
+    let output = shell.arg(command)
+        .envs(context.env())
+        .stdin(Stdio::null())
+        .output()
+        .with_context(|| format!("Failed to run {}", command))?;
+
+    let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+    text.push_str(&String::from_utf8_lossy(&output.stderr));
+    Ok(CommandOutput { success: output.status.success(), text: text.trim_end().to_string() })
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_sees_context() {
+        let mut app = App::new();
+        let place_id = app.new_place("Invoice".to_string());
+        let affordance_id = app.add_affordance(place_id, "Pay now".to_string(), None).unwrap();
+        app.state.selection = Some(Selection::Affordance { place_id, affordance_id });
+        app.state.current_filename = Some("flow.toml".to_string());
+
+        let context = CommandContext::from_app(&app, "command");
+        let output = run("echo \"$BBOARD_EVENT $BBOARD_FILE $BBOARD_PLACE/$BBOARD_AFFORDANCE\"", &context).unwrap();
+        assert_eq!(output, CommandOutput { success: true, text: "command flow.toml Invoice/Pay now".to_string() });
+    }
+
+    #[test]
+    fn test_running_in_the_background() {
+        let running = Running::start("slow".to_string(), "sleep 0.1; echo done".to_string(), CommandContext::default());
+        assert_eq!(running.finished(), None);
+        let mut result = None;
+        for _ in 0..500 {
+            result = running.finished();
+            if result.is_some() {
+                break;
+            }
+            thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert_eq!(result, Some(Ok(CommandOutput { success: true, text: "done".to_string() })));
+    }
+
+    #[test]
+    fn test_finished_commands_are_reported() {
+        let mut app = App::new();
+        app.state.running_commands.push(Running::start("Build".to_string(), "echo built; exit 1".to_string(), CommandContext::default()));
+        for _ in 0..500 {
+            app.poll_commands();
+            if app.state.running_commands.is_empty() {
+                break;
+            }
+            thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert_eq!(app.state.output_panel, Some(("Build failed".to_string(), "built".to_string())));
+    }
+
+    #[test]
+    fn test_failing_command_keeps_stderr() {
+        let output = run("echo oops >&2; exit 3", &CommandContext::default()).unwrap();
+        assert!(!output.success);
+        assert_eq!(output.text, "oops");
+    }
+}
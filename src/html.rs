@@ -0,0 +1,226 @@
+use crate::diagram::render_diagram;
+use crate::layout::layered_layout;
+use crate::models::{Affordance, Breadboard, Detail, Place};
+use std::collections::HashMap;
+
+// A board as one self-contained web page, for people who won't install a
+// TUI: the graph diagram with each place's title linking to it in the
+// outline below, places that fold open and shut, and a search box that
+// narrows the outline and picks out matching places in the graph. The
+// styles and script are inline, so the file works opened from disk.
+
+pub fn page(breadboard: &Breadboard) -> String {
+    site_page(breadboard, None)
+}
+
+// Where a page sits among the others of a static site (`site.rs`)
+pub struct SiteLinks<'a> {
+    pub index: &'a str,                    // The index page's file
+    pub pages: &'a HashMap<String, String>, // Board file to its page's file
+}
+
+// The page, linking back to the site's index when it's part of one, and
+// with links into other boards going to their pages
+pub fn site_page(breadboard: &Breadboard, site: Option<&SiteLinks>) -> String {
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+    out.push_str(&format!("<title>{}</title>\n<style>\n{}</style>\n</head>\n<body>\n", escape(&breadboard.name), STYLE));
+    out.push_str("<header>\n");
+    if let Some(site) = site {
+        out.push_str(&format!("<a href=\"{}\">All boards</a>\n", escape(site.index)));
+    }
+    out.push_str(&format!("<h1>{}</h1>\n", escape(&breadboard.name)));
+    out.push_str("<input id=\"search\" type=\"search\" placeholder=\"Search places and affordances\" autofocus>\n");
+    out.push_str("<button id=\"expand\">Expand all</button> <button id=\"collapse\">Collapse all</button>\n</header>\n");
+
+    if breadboard.places.is_empty() {
+        out.push_str("<p>The board has no places yet.</p>\n");
+    } else {
+        out.push_str("<section id=\"graph\">\n<h2>Graph</h2>\n");
+        out.push_str(&diagram(breadboard));
+        out.push_str("</section>\n<section id=\"outline\">\n<h2>Outline</h2>\n");
+        for place in &breadboard.places {
+            out.push_str(&outline_place(breadboard, place, site));
+        }
+        out.push_str("</section>\n");
+    }
+
+    out.push_str(&format!("<script>\n{}</script>\n</body>\n</html>\n", SCRIPT));
+    out
+}
+
+pub fn anchor(place_id: u32) -> String {
+    format!("place-{}", place_id)
+}
+
+// The graph as text, with each box's title row a link to its place
+fn diagram(breadboard: &Breadboard) -> String {
+    let diagram = render_diagram(breadboard, &layered_layout(breadboard), Detail::Full);
+    let mut titles: Vec<(u32, usize, usize, usize)> = diagram.boxes.iter()
+        .map(|(id, b)| (*id, b.x, b.y, b.width))
+        .collect();
+    titles.sort_by_key(|&(_, x, y, _)| (y, x));
+
+    let mut out = String::from("<pre class=\"diagram\">");
+    for y in 0..diagram.height() {
+        let mut at = 0;
+        let mut line = String::new();
+        for &(id, x, _, width) in titles.iter().filter(|t| t.2 == y) {
+            line.push_str(&escape(&diagram.segment(y, at, x)));
+            line.push_str(&format!("<a href=\"#{}\">{}</a>", anchor(id), escape(&diagram.segment(y, x, x + width))));
+            at = x + width;
+        }
+        line.push_str(&escape(&diagram.segment(y, at, diagram.width())));
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out.push_str("</pre>\n");
+    out
+}
+
+fn outline_place(breadboard: &Breadboard, place: &Place, site: Option<&SiteLinks>) -> String {
+    let link = |id: u32| match breadboard.find_place(&id) {
+        Some(dest) => format!("<a href=\"#{}\">{}</a>", anchor(id), escape(&dest.name)),
+        None => "[Unknown]".to_string(),
+    };
+    let mut sources: Vec<u32> = Vec::new();
+    for source in &breadboard.places {
+        if source.affordances.iter().any(|a| a.destinations().contains(&place.id)) && !sources.contains(&source.id) {
+            sources.push(source.id);
+        }
+    }
+
+    // What the search box matches: the place's name, its affordances' and its notes
+    let mut words = vec![place.name.clone()];
+    words.extend(place.affordances.iter().map(|a| a.name.clone()));
+    words.extend(place.notes.clone());
+
+    let mut out = format!(
+        "<details class=\"place\" id=\"{}\" data-search=\"{}\" open>\n<summary>{}",
+        anchor(place.id),
+        escape(&words.join(" ").to_lowercase()),
+        escape(&place.title()),
+    );
+    if !sources.is_empty() {
+        let sources: Vec<String> = sources.into_iter().map(link).collect();
+        out.push_str(&format!(" <span class=\"incoming\">← {}</span>", sources.join(", ")));
+    }
+    out.push_str("</summary>\n");
+    if let Some(notes) = place.notes.as_deref().map(str::trim).filter(|n| !n.is_empty()) {
+        out.push_str(&format!("<p class=\"notes\">{}</p>\n", escape(notes)));
+    }
+    if !place.affordances.is_empty() {
+        out.push_str("<ul>\n");
+        for affordance in &place.affordances {
+            out.push_str(&format!("<li>{}", affordance_text(affordance, &link, site)));
+            if !affordance.branches.is_empty() {
+                out.push_str("\n<ul>\n");
+                for branch in &affordance.branches {
+                    match branch.connects_to {
+                        Some(dest) => out.push_str(&format!("<li>If {} → {}</li>\n", escape(&branch.label), link(dest))),
+                        None => out.push_str(&format!("<li>If {}</li>\n", escape(&branch.label))),
+                    }
+                }
+                out.push_str("</ul>\n");
+            }
+            out.push_str("</li>\n");
+        }
+        out.push_str("</ul>\n");
+    }
+    out.push_str("</details>\n");
+    out
+}
+
+fn affordance_text(affordance: &Affordance, link: &impl Fn(u32) -> String, site: Option<&SiteLinks>) -> String {
+    let name = escape(&affordance.name);
+    match (&affordance.connects_to, &affordance.link) {
+        (Some(dest), _) => format!("{} → {}", name, link(*dest)),
+        (None, Some(board_link)) => match site.and_then(|site| site.pages.get(&board_link.board)) {
+            Some(page) => format!(
+                "{} ⇒ <a href=\"{}#{}\">{}</a>",
+                name,
+                escape(page),
+                anchor(board_link.place_id),
+                escape(&board_link.board),
+            ),
+            None => format!("{} ⇒ {}", name, escape(&board_link.board)),
+        },
+        (None, None) => name,
+    }
+}
+
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+const STYLE: &str = "\
+body { font: 15px/1.45 system-ui, sans-serif; margin: 0 auto; max-width: 72rem; padding: 1rem; }
+header { display: flex; flex-wrap: wrap; gap: .5rem; align-items: center; }
+header h1 { margin: 0 1rem 0 0; font-size: 1.4rem; }
+#search { flex: 1; min-width: 12rem; font: inherit; padding: .3rem .5rem; }
+h2 { font-size: 1.1rem; margin: 1.5rem 0 .5rem; }
+.diagram { overflow-x: auto; line-height: 1.2; padding: .5rem; background: #f6f6f6; }
+.diagram a { color: inherit; text-decoration: none; }
+.diagram a:hover, .diagram a.match { background: #ffe066; }
+.place { border-left: 3px solid #9ac; padding: .2rem .6rem; margin: .4rem 0; }
+.place:target { border-color: #e80; background: #fff8e6; }
+summary { cursor: pointer; font-weight: 600; }
+.incoming { font-weight: normal; color: #666; font-size: .9em; }
+.notes { font-style: italic; white-space: pre-line; margin: .3rem 0; }
+ul { margin: .2rem 0; }
+";
+
+const SCRIPT: &str = "\
+const search = document.getElementById('search');
+const places = Array.from(document.querySelectorAll('.place'));
+search.addEventListener('input', () => {
+  const query = search.value.trim().toLowerCase();
+  for (const place of places) {
+    const hit = !query || place.dataset.search.includes(query);
+    place.hidden = !hit;
+    if (query && hit) place.open = true;
+    for (const title of document.querySelectorAll('.diagram a[href=\"#' + place.id + '\"]')) {
+      title.classList.toggle('match', Boolean(query) && hit);
+    }
+  }
+});
+// Following a link to a folded place opens it
+function openTarget() {
+  const target = document.getElementById(location.hash.slice(1));
+  if (target && target.tagName === 'DETAILS') {
+    target.hidden = false;
+    target.open = true;
+  }
+}
+window.addEventListener('hashchange', openTarget);
+openTarget();
+document.getElementById('expand').addEventListener('click', () => places.forEach(place => place.open = true));
+document.getElementById('collapse').addEventListener('click', () => places.forEach(place => place.open = false));
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_links_graph_and_outline() {
+        let mut breadboard = Breadboard::new("Autopay & more".to_string());
+        breadboard.add_place(Place::new(1, "Invoice".to_string()));
+        breadboard.add_place(Place::new(2, "Setup".to_string()));
+        breadboard.places[0].add_affordance(Affordance::new(1, "Turn on <Autopay>".to_string()).with_connection(2));
+        breadboard.places[1].add_affordance(Affordance::new(2, "Cancel".to_string()).with_connection(1));
+        breadboard.places[1].notes = Some("Card form".to_string());
+
+        let html = page(&breadboard);
+        assert!(html.contains("<title>Autopay &amp; more</title>"));
+        // Each box's title row links to the place in the outline
+        assert!(html.contains("<a href=\"#place-1\">┌ Invoice"));
+        assert!(html.contains("<a href=\"#place-2\">┌ Setup"));
+        assert!(html.contains("<details class=\"place\" id=\"place-2\" data-search=\"setup cancel card form\" open>"));
+        assert!(html.contains("<summary>Setup <span class=\"incoming\">← <a href=\"#place-1\">Invoice</a></span></summary>"));
+        assert!(html.contains("<li>Turn on &lt;Autopay&gt; → <a href=\"#place-2\">Setup</a></li>"));
+
+        assert!(page(&Breadboard::new("Empty".to_string())).contains("The board has no places yet."));
+    }
+}
@@ -0,0 +1,139 @@
+// Icons offered while editing a name: typing `:` and part of an icon's
+// name, like `:mail`, lists the matching ones to put in. They're picked to
+// take the same number of columns in every terminal: symbols drawn as text
+// are one column wide, emoji two.
+
+pub struct Icon {
+    pub name: &'static str,
+    pub glyph: &'static str,
+}
+
+const fn icon(name: &'static str, glyph: &'static str) -> Icon {
+    Icon { name, glyph }
+}
+
+pub const ICONS: &[Icon] = &[
+    icon("email", "✉"),
+    icon("gear", "⚙"),
+    icon("cloud", "☁"),
+    icon("screen", "▭"),
+    icon("modal", "◫"),
+    icon("check", "✓"),
+    icon("cross", "✗"),
+    icon("star", "★"),
+    icon("warning", "⚠"),
+    icon("flag", "⚑"),
+    icon("arrow", "→"),
+    icon("phone", "☎"),
+    icon("heart", "♥"),
+    icon("done", "✅"),
+    icon("no", "❌"),
+    icon("lock", "🔒"),
+    icon("unlock", "🔓"),
+    icon("key", "🔑"),
+    icon("bell", "🔔"),
+    icon("user", "👤"),
+    icon("users", "👥"),
+    icon("cart", "🛒"),
+    icon("card", "💳"),
+    icon("money", "💰"),
+    icon("mobile", "📱"),
+    icon("mail", "📧"),
+    icon("inbox", "📥"),
+    icon("calendar", "📅"),
+    icon("clock", "⏰"),
+    icon("search", "🔍"),
+    icon("home", "🏠"),
+    icon("page", "📄"),
+    icon("folder", "📁"),
+    icon("chart", "📊"),
+    icon("rocket", "🚀"),
+    icon("bug", "🐛"),
+    icon("fire", "🔥"),
+    icon("sparkles", "✨"),
+    icon("idea", "💡"),
+    icon("link", "🔗"),
+    icon("pin", "📌"),
+    icon("globe", "🌐"),
+    icon("robot", "🤖"),
+    icon("wave", "👋"),
+];
+
+// Nerd Font icons, offered when the config says the terminal's font has them
+pub const NERD_FONT_ICONS: &[Icon] = &[
+    icon("nf-envelope", "\u{f0e0}"),
+    icon("nf-cog", "\u{f013}"),
+    icon("nf-user", "\u{f007}"),
+    icon("nf-lock", "\u{f023}"),
+    icon("nf-home", "\u{f015}"),
+    icon("nf-search", "\u{f002}"),
+    icon("nf-bell", "\u{f0f3}"),
+    icon("nf-cart", "\u{f07a}"),
+    icon("nf-credit-card", "\u{f09d}"),
+    icon("nf-cloud", "\u{f0c2}"),
+    icon("nf-database", "\u{f1c0}"),
+    icon("nf-github", "\u{f09b}"),
+];
+
+// The icon name being typed at the end of `text`: where its `:` is, and
+// what follows. The `:` has to start the text or a word, so "Step 1:" isn't
+// taken for one.
+pub fn query(text: &str) -> Option<(usize, &str)> {
+    let colon = text.rfind(':')?;
+    let typed = &text[colon + 1..];
+    let starts_word = text[..colon].chars().next_back().is_none_or(char::is_whitespace);
+    let is_name = typed.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+    (starts_word && is_name).then_some((colon, typed))
+}
+
+// Icons whose name has `typed` in it, those starting with it first
+pub fn matching(typed: &str, nerd_font: bool) -> Vec<&'static Icon> {
+    let typed = typed.to_lowercase();
+    let icons = ICONS.iter().chain(NERD_FONT_ICONS.iter().filter(|_| nerd_font));
+    let (mut starting, containing): (Vec<_>, Vec<_>) = icons
+        .filter(|icon| icon.name.contains(&typed))
+        .partition(|icon| icon.name.starts_with(&typed));
+    starting.extend(containing);
+    starting
+}
+
+// `name` without the emoji variation selectors pasted symbols often carry:
+// "✉️" is drawn two columns wide by some terminals and one by others, and
+// "✉" one column by all of them
+pub fn width_safe(name: &str) -> String {
+    name.replace('\u{FE0F}', "")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+    #[test]
+    fn test_query() {
+        assert_eq!(query(":ma"), Some((0, "ma")));
+        assert_eq!(query("Inbox :"), Some((6, "")));
+        assert_eq!(query("Step 1: pay"), None);
+        assert_eq!(query("Step 1:"), None);
+        assert_eq!(query("Inbox"), None);
+    }
+
+    #[test]
+    fn test_matching() {
+        let names: Vec<&str> = matching("mai", false).iter().map(|icon| icon.name).collect();
+        assert_eq!(names, vec!["mail", "email"]);
+        assert!(matching("nf-", false).is_empty());
+        assert_eq!(matching("nf-cog", true).len(), 1);
+    }
+
+    #[test]
+    fn test_icons_are_width_safe() {
+        // Measured whole or char by char, each takes the same columns
+        for icon in ICONS.iter().chain(NERD_FONT_ICONS) {
+            let by_char: usize = icon.glyph.chars().map(|c| c.width().unwrap_or(0)).sum();
+            assert_eq!(icon.glyph.width(), by_char, "{}", icon.name);
+            assert_eq!(width_safe(icon.glyph), icon.glyph);
+        }
+        assert_eq!(width_safe("✉\u{FE0F} Receipt"), "✉ Receipt");
+    }
+}
@@ -0,0 +1,751 @@
+use crate::app::split_arrow;
+use crate::locale::{self, Strings};
+use crate::models::{Affordance, Breadboard, Place};
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+// What an import added to the board, for the status message
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct ImportSummary {
+    pub places: usize,
+    pub affordances: usize,
+}
+
+impl ImportSummary {
+    pub fn describe(&self, s: &Strings) -> String {
+        locale::fill(s.imported_counts, &[&self.places.to_string(), &self.affordances.to_string()])
+    }
+}
+
+// File formats boards can be imported from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImportFormat {
+    Csv,
+    Dot,
+    Board, // Another bboard board
+    Notes, // Rough meeting notes, see `parse_notes`
+}
+
+impl ImportFormat {
+    // Told apart by extension: .csv, .dot/.gv for Graphviz, .toml for a board
+    // or .txt/.md for notes
+    pub fn from_path(path: &str) -> Result<Self> {
+        let extension = Path::new(path).extension().map(|e| e.to_string_lossy().to_lowercase());
+        match extension.as_deref() {
+            Some("csv") => Ok(ImportFormat::Csv),
+            Some("dot") | Some("gv") => Ok(ImportFormat::Dot),
+            Some("toml") => Ok(ImportFormat::Board),
+            Some("txt") | Some("md") => Ok(ImportFormat::Notes),
+            _ => bail!("can't tell the format of {}: expected a .csv, .dot, .gv, .toml, .txt or .md file", path),
+        }
+    }
+}
+
+// Read `text` as a board of its own, then merge it into `breadboard`, with
+// any new places put into `group`
+pub fn import(breadboard: &mut Breadboard, format: ImportFormat, text: &str, group: Option<&str>) -> Result<ImportSummary> {
+    let mut imported = Breadboard::new(String::new());
+    match format {
+        ImportFormat::Csv => {
+            import_csv(&mut imported, text)?;
+        }
+        ImportFormat::Dot => {
+            import_dot(&mut imported, text)?;
+        }
+        ImportFormat::Board => imported = toml::from_str(text).context("Failed to parse TOML as Breadboard")?,
+        ImportFormat::Notes => imported = parse_notes(text).0,
+    }
+    Ok(merge(breadboard, &imported, group))
+}
+
+// Fold `imported` into `breadboard`. Places are reconciled by name: one
+// named like an existing place (ignoring case) merges into it, the others
+// are added with fresh IDs and, when given, `group`. Affordances are
+// reconciled the same way within their place; new ones get fresh IDs, and
+// connections and outcomes are remapped to the merged places.
+pub fn merge(breadboard: &mut Breadboard, imported: &Breadboard, group: Option<&str>) -> ImportSummary {
+    breadboard.sync_id_counters();
+    let mut summary = ImportSummary::default();
+
+    let mut place_ids: HashMap<u32, u32> = HashMap::new();
+    for place in &imported.places {
+        let id = match find_place_by_name(breadboard, &place.name) {
+            Some(id) => id,
+            None => {
+                let id = breadboard.generate_place_id();
+                breadboard.add_place(Place {
+                    id,
+                    group: group.map(str::to_string).or_else(|| place.group.clone()),
+                    affordances: Vec::new(),
+                    ..place.clone()
+                });
+                summary.places += 1;
+                id
+            }
+        };
+        place_ids.insert(place.id, id);
+    }
+
+    let remap = |id: Option<u32>| id.and_then(|id| place_ids.get(&id).copied());
+    for place in &imported.places {
+        let target = place_ids[&place.id];
+        for affordance in &place.affordances {
+            let existing = breadboard.find_place(&target)
+                .and_then(|p| p.affordances.iter().position(|a| a.name.trim().eq_ignore_ascii_case(affordance.name.trim())));
+            match existing {
+                Some(position) => {
+                    let current = &mut breadboard.find_place_mut(&target).unwrap().affordances[position];
+                    if current.connects_to.is_none() && current.link.is_none() {
+                        current.connects_to = remap(affordance.connects_to);
+                    }
+                }
+                None => {
+                    let mut copy = affordance.clone();
+                    copy.id = breadboard.generate_affordance_id();
+                    copy.connects_to = remap(affordance.connects_to);
+                    for branch in &mut copy.branches {
+                        branch.connects_to = remap(branch.connects_to);
+                    }
+                    breadboard.find_place_mut(&target).unwrap().add_affordance(copy);
+                    summary.affordances += 1;
+                }
+            }
+        }
+    }
+
+    summary
+}
+
+// Apply a screen inventory to `breadboard`. Each row is
+// `place,affordance,connects_to`: the place, optionally one of its
+// affordances, and optionally the place that affordance leads to. Places are
+// matched by name (ignoring case) and created when missing, so importing
+// into an existing board extends its places instead of duplicating them. An
+// affordance the place already has only gains the row's connection if it had
+// none. A first row naming the columns is skipped.
+pub fn import_csv(breadboard: &mut Breadboard, text: &str) -> Result<ImportSummary> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(text.as_bytes());
+
+    breadboard.sync_id_counters();
+    let mut summary = ImportSummary::default();
+    for (index, record) in reader.records().enumerate() {
+        let record = record.context("Failed to read CSV")?;
+        let line = record.position().map(|p| p.line()).unwrap_or(index as u64 + 1);
+        let field = |i: usize| record.get(i).unwrap_or("");
+
+        if index == 0 && field(0).eq_ignore_ascii_case("place") && field(1).eq_ignore_ascii_case("affordance") {
+            continue;
+        }
+        if record.iter().all(str::is_empty) {
+            continue;
+        }
+        if record.len() > 3 {
+            bail!("line {}: expected place,affordance,connects_to but found {} columns", line, record.len());
+        }
+        if field(0).is_empty() {
+            bail!("line {}: missing place name", line);
+        }
+        if field(1).is_empty() && !field(2).is_empty() {
+            bail!("line {}: a connection needs an affordance", line);
+        }
+
+        let place_id = find_or_add_place(breadboard, field(0), &mut summary);
+        if field(1).is_empty() {
+            continue;
+        }
+        let dest_id = (!field(2).is_empty()).then(|| find_or_add_place(breadboard, field(2), &mut summary));
+
+        let existing = breadboard.find_place(&place_id)
+            .and_then(|place| place.affordances.iter().position(|a| a.name.eq_ignore_ascii_case(field(1))));
+        match existing {
+            Some(position) => {
+                let affordance = &mut breadboard.find_place_mut(&place_id).unwrap().affordances[position];
+                if affordance.connects_to.is_none() {
+                    affordance.connects_to = dest_id;
+                }
+            }
+            None => {
+                let mut affordance = Affordance::new(breadboard.generate_affordance_id(), field(1).to_string());
+                affordance.connects_to = dest_id;
+                breadboard.find_place_mut(&place_id).unwrap().add_affordance(affordance);
+                summary.affordances += 1;
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+// Apply a Graphviz digraph to `breadboard`: nodes become places (named by
+// their label, or their ID without one) and each edge an affordance on its
+// source place, named by the edge's label or else after its destination.
+// Places are matched by name as in `import_csv`. Styling attributes and
+// default `node`/`edge`/`graph` statements are ignored; anything that would
+// change the meaning (subgraphs, ports, HTML labels, undirected edges) is an
+// error rather than silently lost.
+pub fn import_dot(breadboard: &mut Breadboard, text: &str) -> Result<ImportSummary> {
+    let graph = DotParser::new(text)?.parse()?;
+
+    breadboard.sync_id_counters();
+    let mut summary = ImportSummary::default();
+    let mut place_ids = Vec::new();
+    for node in &graph.nodes {
+        place_ids.push(find_or_add_place(breadboard, node.label.as_deref().unwrap_or(&node.id), &mut summary));
+    }
+
+    for edge in &graph.edges {
+        let (from, to) = (place_ids[edge.from], place_ids[edge.to]);
+        let name = match &edge.label {
+            Some(label) => label.clone(),
+            None => breadboard.find_place(&to).map(|p| p.name.clone()).unwrap_or_default(),
+        };
+        let exists = breadboard.find_place(&from)
+            .is_some_and(|place| place.affordances.iter().any(|a| a.name.eq_ignore_ascii_case(&name) && a.connects_to == Some(to)));
+        if exists {
+            continue;
+        }
+        let affordance = Affordance::new(breadboard.generate_affordance_id(), name).with_connection(to);
+        breadboard.find_place_mut(&from).unwrap().add_affordance(affordance);
+        summary.affordances += 1;
+    }
+
+    Ok(summary)
+}
+
+#[derive(Debug, Default)]
+struct DotGraph {
+    nodes: Vec<DotNode>, // In order of first mention
+    edges: Vec<DotEdge>,
+}
+
+#[derive(Debug)]
+struct DotNode {
+    id: String,
+    label: Option<String>,
+}
+
+#[derive(Debug)]
+struct DotEdge {
+    from: usize, // Indices into `nodes`
+    to: usize,
+    label: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Id(String), // Identifier, number or quoted string
+    Punct(&'static str),
+}
+
+struct DotParser {
+    tokens: Vec<(Token, usize)>, // With the line each token starts on
+    position: usize,
+    graph: DotGraph,
+}
+
+impl DotParser {
+    fn new(text: &str) -> Result<Self> {
+        Ok(Self { tokens: tokenize(text)?, position: 0, graph: DotGraph::default() })
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(token, _)| token)
+    }
+
+    fn line(&self) -> usize {
+        self.tokens.get(self.position).or(self.tokens.last()).map(|(_, line)| *line).unwrap_or(1)
+    }
+
+    fn next(&mut self) -> Result<Token> {
+        let token = self.peek().cloned().ok_or_else(|| anyhow!("line {}: unexpected end of file", self.line()))?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    fn expect(&mut self, punct: &str) -> Result<()> {
+        let line = self.line();
+        match self.next()? {
+            Token::Punct(p) if p == punct => Ok(()),
+            other => bail!("line {}: expected {} but found {}", line, punct, describe(&other)),
+        }
+    }
+
+    fn eat(&mut self, punct: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Punct(p)) if *p == punct) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn id(&mut self) -> Result<String> {
+        let line = self.line();
+        match self.next()? {
+            Token::Id(id) => Ok(id),
+            other => bail!("line {}: expected a name but found {}", line, describe(&other)),
+        }
+    }
+
+    fn parse(mut self) -> Result<DotGraph> {
+        if keyword(self.peek(), "strict") {
+            self.position += 1;
+        }
+        if keyword(self.peek(), "graph") {
+            bail!("line {}: undirected graphs aren't supported, use digraph with -> edges", self.line());
+        }
+        if !keyword(self.peek(), "digraph") {
+            bail!("line {}: expected digraph", self.line());
+        }
+        self.position += 1;
+        if matches!(self.peek(), Some(Token::Id(_))) {
+            self.position += 1;
+        }
+        self.expect("{")?;
+
+        while !self.eat("}") {
+            self.statement()?;
+            self.eat(";");
+        }
+        if self.peek().is_some() {
+            bail!("line {}: only one graph per file is supported", self.line());
+        }
+        Ok(self.graph)
+    }
+
+    fn statement(&mut self) -> Result<()> {
+        let line = self.line();
+        if keyword(self.peek(), "subgraph") || self.peek() == Some(&Token::Punct("{")) {
+            bail!("line {}: subgraphs aren't supported", line);
+        }
+        if ["graph", "node", "edge"].iter().any(|k| keyword(self.peek(), k)) {
+            self.position += 1;
+            self.attributes()?;
+            return Ok(());
+        }
+
+        let first = self.id()?;
+        if self.eat("=") {
+            self.id()?; // Graph attribute such as rankdir=LR
+            return Ok(());
+        }
+
+        let mut chain = vec![first];
+        loop {
+            match self.peek() {
+                Some(Token::Punct("->")) => {
+                    self.position += 1;
+                    if keyword(self.peek(), "subgraph") || self.peek() == Some(&Token::Punct("{")) {
+                        bail!("line {}: edges to subgraphs aren't supported", self.line());
+                    }
+                    chain.push(self.id()?);
+                }
+                Some(Token::Punct("--")) => bail!("line {}: undirected edges aren't supported, use ->", self.line()),
+                Some(Token::Punct(":")) => bail!("line {}: node ports aren't supported", self.line()),
+                _ => break,
+            }
+        }
+        let label = self.attributes()?.into_iter().rev().find(|(name, _)| name == "label").map(|(_, value)| value);
+
+        let indices: Vec<usize> = chain.iter().map(|id| self.node(id)).collect();
+        if indices.len() == 1 {
+            if label.is_some() {
+                self.graph.nodes[indices[0]].label = label;
+            }
+        } else {
+            for pair in indices.windows(2) {
+                self.graph.edges.push(DotEdge { from: pair[0], to: pair[1], label: label.clone() });
+            }
+        }
+        Ok(())
+    }
+
+    // Any number of `[name=value, ...]` lists
+    fn attributes(&mut self) -> Result<Vec<(String, String)>> {
+        let mut attributes = Vec::new();
+        while self.eat("[") {
+            while !self.eat("]") {
+                let name = self.id()?;
+                self.expect("=")?;
+                attributes.push((name, self.id()?));
+                if !self.eat(",") {
+                    self.eat(";");
+                }
+            }
+        }
+        Ok(attributes)
+    }
+
+    fn node(&mut self, id: &str) -> usize {
+        match self.graph.nodes.iter().position(|node| node.id == id) {
+            Some(index) => index,
+            None => {
+                self.graph.nodes.push(DotNode { id: id.to_string(), label: None });
+                self.graph.nodes.len() - 1
+            }
+        }
+    }
+}
+
+fn keyword(token: Option<&Token>, word: &str) -> bool {
+    matches!(token, Some(Token::Id(id)) if id.eq_ignore_ascii_case(word))
+}
+
+fn describe(token: &Token) -> String {
+    match token {
+        Token::Id(id) => format!("\"{}\"", id),
+        Token::Punct(p) => format!("'{}'", p),
+    }
+}
+
+const PUNCTUATION: [&str; 11] = ["->", "--", "{", "}", "[", "]", ";", ",", "=", ":", "+"];
+
+fn tokenize(text: &str) -> Result<Vec<(Token, usize)>> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut i = 0;
+    let mut line_start = true;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '\n' {
+            line += 1;
+            line_start = true;
+            i += 1;
+            continue;
+        }
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        // Comments: //, /* */ and preprocessor-style # lines
+        if (c == '#' && line_start) || (c == '/' && chars.get(i + 1) == Some(&'/')) {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            continue;
+        }
+        line_start = false;
+        if c == '/' && chars.get(i + 1) == Some(&'*') {
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                if chars[i] == '\n' {
+                    line += 1;
+                }
+                i += 1;
+            }
+            i += 2;
+            continue;
+        }
+
+        if c == '"' {
+            let start_line = line;
+            let mut value = String::new();
+            i += 1;
+            loop {
+                match chars.get(i) {
+                    None => bail!("line {}: unterminated string", start_line),
+                    Some('"') => break,
+                    Some('\\') if chars.get(i + 1) == Some(&'"') => {
+                        value.push('"');
+                        i += 1;
+                    }
+                    Some('\\') if chars.get(i + 1) == Some(&'\n') => {
+                        line += 1;
+                        i += 1;
+                    }
+                    Some('\\') if matches!(chars.get(i + 1), Some('n') | Some('l') | Some('r')) => {
+                        value.push(' ');
+                        i += 1;
+                    }
+                    Some(other) => {
+                        if *other == '\n' {
+                            line += 1;
+                        }
+                        value.push(*other);
+                    }
+                }
+                i += 1;
+            }
+            i += 1;
+            tokens.push((Token::Id(value.split_whitespace().collect::<Vec<_>>().join(" ")), start_line));
+            continue;
+        }
+        if c == '<' {
+            bail!("line {}: HTML labels aren't supported", line);
+        }
+
+        if let Some(punct) = PUNCTUATION.iter().find(|p| chars[i..].starts_with(&p.chars().collect::<Vec<_>>())) {
+            if *punct == "+" {
+                bail!("line {}: string concatenation with + isn't supported", line);
+            }
+            tokens.push((Token::Punct(punct), line));
+            i += punct.len();
+            continue;
+        }
+
+        if c.is_alphanumeric() || c == '_' || c == '.' || c == '-' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '.'
+                || (chars[i] == '-' && i == start)) {
+                i += 1;
+            }
+            tokens.push((Token::Id(chars[start..i].iter().collect()), line));
+            continue;
+        }
+
+        bail!("line {}: unexpected character '{}'", line, c);
+    }
+
+    Ok(tokens)
+}
+
+// Rough meeting notes read with a few forgiving rules: a line ending in ':'
+// starts a place, a line starting with '-', '*' or '•' is an affordance on
+// the place above it, and an arrow in an affordance ("->" or "→") connects it
+// to the place named after the arrow. Other lines are prose; how many were
+// skipped comes back with the board.
+pub fn parse_notes(text: &str) -> (Breadboard, usize) {
+    let mut breadboard = Breadboard::new(String::new());
+    let mut summary = ImportSummary::default();
+    let mut place = None;
+    let mut skipped = 0;
+
+    for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let bullet = line.strip_prefix(['-', '*', '•']).map(str::trim);
+        if let Some(name) = line.strip_suffix(':') {
+            let name = name.trim_start_matches(['#', '-', '*', '•']).trim();
+            if !name.is_empty() {
+                place = Some(find_or_add_place(&mut breadboard, name, &mut summary));
+                continue;
+            }
+        } else if let (Some(text), Some(place_id)) = (bullet, place) {
+            let (name, dest) = split_arrow(text);
+            if !name.is_empty() {
+                let id = breadboard.generate_affordance_id();
+                let mut affordance = Affordance::new(id, name.to_string());
+                affordance.connects_to = dest.filter(|d| !d.is_empty()).map(|d| find_or_add_place(&mut breadboard, d, &mut summary));
+                breadboard.find_place_mut(&place_id).unwrap().add_affordance(affordance);
+                continue;
+            }
+        }
+        skipped += 1;
+    }
+
+    (breadboard, skipped)
+}
+
+// What merging `imported` into `breadboard` would bring in, as an outline
+// with the places it would add marked "(new)"
+pub fn preview(breadboard: &Breadboard, imported: &Breadboard) -> String {
+    let mut lines = Vec::new();
+    for place in &imported.places {
+        let new = if find_place_by_name(breadboard, &place.name).is_none() { " (new)" } else { "" };
+        lines.push(format!("{}{}", place.name, new));
+        for affordance in &place.affordances {
+            match affordance.connects_to.and_then(|id| imported.find_place(&id)) {
+                Some(dest) => lines.push(format!("  - {} → {}", affordance.name, dest.name)),
+                None => lines.push(format!("  - {}", affordance.name)),
+            }
+        }
+    }
+    lines.join("\n")
+}
+
+fn find_place_by_name(breadboard: &Breadboard, name: &str) -> Option<u32> {
+    breadboard.places.iter().find(|p| p.name.trim().eq_ignore_ascii_case(name.trim())).map(|p| p.id)
+}
+
+fn find_or_add_place(breadboard: &mut Breadboard, name: &str, summary: &mut ImportSummary) -> u32 {
+    if let Some(id) = find_place_by_name(breadboard, name) {
+        return id;
+    }
+    let id = breadboard.generate_place_id();
+    breadboard.add_place(Place::new(id, name.to_string()));
+    summary.places += 1;
+    id
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Branch;
+
+    #[test]
+    fn test_import_csv_builds_places_and_connections() {
+        let mut breadboard = Breadboard::new("Billing".to_string());
+        let summary = import_csv(&mut breadboard, "\
+place,affordance,connects_to
+Invoice,Turn on Autopay,Setup Autopay
+Invoice,\"Pay now, in full\",
+Setup Autopay,Save,Confirmation
+Confirmation
+").unwrap();
+
+        assert_eq!(summary, ImportSummary { places: 3, affordances: 3 });
+        let names: Vec<&str> = breadboard.places.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["Invoice", "Setup Autopay", "Confirmation"]);
+        assert_eq!(breadboard.places[0].affordances[0].connects_to, Some(breadboard.places[1].id));
+        assert_eq!(breadboard.places[0].affordances[1].name, "Pay now, in full");
+        assert_eq!(breadboard.places[0].affordances[1].connects_to, None);
+    }
+
+    #[test]
+    fn test_import_csv_merges_by_name() {
+        let mut breadboard = Breadboard::new("Billing".to_string());
+        import_csv(&mut breadboard, "Invoice,Turn on Autopay\n").unwrap();
+
+        let summary = import_csv(&mut breadboard, "invoice,turn on autopay,Setup\ninvoice,Download PDF\n").unwrap();
+        assert_eq!(summary, ImportSummary { places: 1, affordances: 1 });
+        assert_eq!(breadboard.places.len(), 2);
+        assert_eq!(breadboard.places[0].affordances.len(), 2);
+        assert_eq!(breadboard.places[0].affordances[0].connects_to, Some(breadboard.places[1].id));
+    }
+
+    #[test]
+    fn test_import_dot_builds_places_and_labeled_edges() {
+        let mut breadboard = Breadboard::new("Billing".to_string());
+        let summary = import_dot(&mut breadboard, r#"
+            // Legacy billing sketch
+            digraph billing {
+                rankdir=LR;
+                node [shape=box, style="rounded"];
+                invoice [label="Invoice"];
+                setup [label="Setup\nAutopay"]
+                invoice -> setup [label="Turn on Autopay", color=blue];
+                setup -> done -> invoice
+                /* no label: named after the destination */
+            }
+        "#).unwrap();
+
+        assert_eq!(summary, ImportSummary { places: 3, affordances: 3 });
+        let names: Vec<&str> = breadboard.places.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["Invoice", "Setup Autopay", "done"]);
+        let affordances: Vec<(&str, Option<u32>)> = breadboard.places.iter()
+            .flat_map(|p| p.affordances.iter().map(|a| (a.name.as_str(), a.connects_to)))
+            .collect();
+        assert_eq!(affordances, vec![("Turn on Autopay", Some(2)), ("done", Some(3)), ("Invoice", Some(1))]);
+
+        // Importing again adds nothing
+        let again = import_dot(&mut breadboard, "digraph { invoice [label=Invoice]; invoice -> Confirmation }").unwrap();
+        assert_eq!(again, ImportSummary { places: 1, affordances: 1 });
+        assert_eq!(breadboard.places[0].affordances.len(), 2);
+    }
+
+    #[test]
+    fn test_import_dot_rejects_unsupported_features() {
+        let error = |text: &str| import_dot(&mut Breadboard::new("Billing".to_string()), text).unwrap_err().to_string();
+        assert_eq!(error("graph { a -- b }"), "line 1: undirected graphs aren't supported, use digraph with -> edges");
+        assert_eq!(error("digraph {\n  subgraph cluster_admin { a }\n}"), "line 2: subgraphs aren't supported");
+        assert_eq!(error("digraph { a -> {b c} }"), "line 1: edges to subgraphs aren't supported");
+        assert_eq!(error("digraph { a:out -> b }"), "line 1: node ports aren't supported");
+        assert_eq!(error("digraph { a [label=<<b>A</b>>] }"), "line 1: HTML labels aren't supported");
+        assert_eq!(error("digraph { a -> }"), "line 1: expected a name but found '}'");
+        assert_eq!(error("digraph { a [label=\"A] }"), "line 1: unterminated string");
+    }
+
+    #[test]
+    fn test_merge_reconciles_names_and_regenerates_ids() {
+        // Two breakout boards numbered from 1 independently
+        let mut ours = Breadboard::new("Ours".to_string());
+        import_csv(&mut ours, "Invoice,Turn on Autopay,Setup Autopay\n").unwrap();
+        let mut theirs = Breadboard::new("Theirs".to_string());
+        import_csv(&mut theirs, "Setup Autopay,Save,Confirmation\ninvoice,Download PDF\ninvoice,Turn on autopay,Help\n").unwrap();
+        theirs.places[0].affordances[0].branches = vec![Branch { label: "declined".to_string(), connects_to: Some(1) }];
+        theirs.places[1].group = Some("billing".to_string());
+
+        let summary = merge(&mut ours, &theirs, Some("breakout"));
+        assert_eq!(summary, ImportSummary { places: 2, affordances: 2 });
+
+        let names: Vec<(&str, Option<&str>)> = ours.places.iter().map(|p| (p.name.as_str(), p.group.as_deref())).collect();
+        assert_eq!(names, vec![("Invoice", None), ("Setup Autopay", None), ("Confirmation", Some("breakout")), ("Help", Some("breakout"))]);
+
+        // Setup Autopay's Save now leads to our Confirmation; its "declined"
+        // outcome to what was their place 1, our Setup Autopay
+        let save = &ours.places[1].affordances[0];
+        assert_eq!(save.connects_to, Some(3));
+        assert_eq!(save.branches[0].connects_to, Some(2));
+        assert_eq!(save.id, 2);
+
+        // Our Turn on Autopay keeps its connection; Download PDF is new
+        let invoice: Vec<(&str, Option<u32>)> = ours.places[0].affordances.iter().map(|a| (a.name.as_str(), a.connects_to)).collect();
+        assert_eq!(invoice, vec![("Turn on Autopay", Some(2)), ("Download PDF", None)]);
+        assert_eq!(ours.places[0].affordances[1].id, 3);
+    }
+
+    #[test]
+    fn test_import_board_into_group() {
+        let mut breadboard = Breadboard::new("Ours".to_string());
+        let text = toml::to_string(&{
+            let mut theirs = Breadboard::new("Theirs".to_string());
+            import_csv(&mut theirs, "Refunds,Approve,Refunded\n").unwrap();
+            theirs
+        }).unwrap();
+
+        let summary = import(&mut breadboard, ImportFormat::Board, &text, Some("admin")).unwrap();
+        assert_eq!(summary, ImportSummary { places: 2, affordances: 1 });
+        assert!(breadboard.places.iter().all(|p| p.group.as_deref() == Some("admin")));
+        assert!(import(&mut breadboard, ImportFormat::Board, "name = 1", None).is_err());
+    }
+
+    #[test]
+    fn test_parse_notes() {
+        let (breadboard, skipped) = parse_notes("\
+Breakout B, Tuesday
+
+## Invoice:
+- Turn on Autopay -> Setup Autopay
+  * Download PDF
+We argued about this one for a while
+Setup Autopay:
+• Save → Confirmation
+- Cancel -> invoice
+-
+");
+        assert_eq!(skipped, 3);
+        let names: Vec<&str> = breadboard.places.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["Invoice", "Setup Autopay", "Confirmation"]);
+        assert_eq!(preview(&Breadboard::new(String::new()), &breadboard).lines().next(), Some("Invoice (new)"));
+
+        let mut ours = Breadboard::new("Ours".to_string());
+        ours.add_place(Place::new(1, "Invoice".to_string()));
+        assert_eq!(preview(&ours, &breadboard), "\
+Invoice
+  - Turn on Autopay → Setup Autopay
+  - Download PDF
+Setup Autopay (new)
+  - Save → Confirmation
+  - Cancel → Invoice
+Confirmation (new)");
+    }
+
+    #[test]
+    fn test_import_format_from_extension() {
+        assert_eq!(ImportFormat::from_path("screens.CSV").unwrap(), ImportFormat::Csv);
+        assert_eq!(ImportFormat::from_path("legacy.gv").unwrap(), ImportFormat::Dot);
+        assert_eq!(ImportFormat::from_path("breakout.toml").unwrap(), ImportFormat::Board);
+        assert_eq!(ImportFormat::from_path("notes.md").unwrap(), ImportFormat::Notes);
+        assert!(ImportFormat::from_path("screens.xlsx").is_err());
+    }
+
+    #[test]
+    fn test_import_csv_reports_bad_rows() {
+        let mut breadboard = Breadboard::new("Billing".to_string());
+        let error = import_csv(&mut breadboard, "Invoice,Pay\n,Pay,Invoice\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 2: missing place name");
+
+        let error = import_csv(&mut breadboard, "Invoice,,Setup\n").unwrap_err();
+        assert_eq!(error.to_string(), "line 1: a connection needs an affordance");
+
+        let error = import_csv(&mut breadboard, "Invoice,Pay,Setup,extra\n").unwrap_err();
+        assert!(error.to_string().starts_with("line 1: expected place,affordance,connects_to"));
+    }
+}
@@ -1,5 +1,7 @@
-use crossterm::event::{self, KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{self, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 use anyhow::Result;
+use crate::export::Document;
+use crate::keys::{Key, Keymap, Pending, Sequence};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Mode {
@@ -8,10 +10,31 @@ pub enum Mode {
     Connect,  // For creating connections with search
     OpenFile,  // For opening files
     SaveFile,  // For entering filename to save
+    ImportFile,  // For entering a CSV, DOT or board file to merge into the board, then a group
     ConfirmDelete,  // For confirming place deletion
+    ConfirmReopen,  // For offering the last board on startup
+    ConfirmRecover,  // For offering back edits left by a bboard that didn't exit cleanly
+    ConfirmLock,  // For warning that someone else has the board open
+    Library,  // For picking a board from the boards directory on startup
+    Incoming,  // For picking a place that links into the selected one
+    Cycles,  // For browsing loops formed by connections
+    Branches,  // For managing the labeled outcomes of an affordance
+    Filter,  // For typing a live board filter
+    Commands,  // For picking a custom command from the config
+    Capture,  // For typing `Place: Affordance -> Place` lines that build the board
+    ConfirmNotes,  // For checking the structure read from pasted notes before merging it
+    Snippets,  // For picking a set of affordances from the config to insert
+    Start,  // For picking how to begin when started without a board
+    Comment,  // For typing a comment on the selection
+    Estimate,  // For typing the selected affordance's estimate
+    Compare,  // For looking at two versions of a board side by side
+    Trash,  // For picking something deleted this session to put back
+    Views,  // For picking one of the board's named views
+    ViewName,  // For typing the name to save the current view under
+    Spelling,  // For picking a correction for a typo in the selection
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Action {
     None,
     Quit,
@@ -24,56 +47,354 @@ pub enum Action {
     NewPlace,
     NewAffordance,
     ToggleCollapsed,
+    ToggleGraphView,
+    ToggleMinimap,
+    ToggleSwimlanes,
+    ToggleProgress,
+    PanGraph(i32, i32), // Scroll the graph view by (columns, rows)
+    Click { column: u16, row: u16 },
+    JumpUp,
+    EndPlace { last: bool }, // First or last place
+    EndAffordance { last: bool }, // First or last affordance of the selected place
+    PageList { down: bool }, // A page through the open list
+    ListEnd { last: bool }, // First or last entry of the open list
+    DrillIn,
+    ZoomGraph { zoom_in: bool },
     Filter,
     Save,
     SaveAs,
+    ExportView,
+    ExportDocument(Document),
+    ExportBundle,
+    Attach,
+    CycleExportScope,
+    Capture,
+    PasteNotes,
+    Extract { replace: bool },
+    Import,
+    CopyView,
+    CopyPlace,
+    Rewire,
+    ToggleMark,
+    ToggleReach,
+    ToggleFileSort, // Open dialog by name or by modification time
+    ToggleHappyPath,
+    ShowFullText,
+    ShowGlossary,
+    AddComment,
+    Estimate,
+    ShowHistory,
+    PastePlace,
+    ShowCommands,
+    ShowSnippets,
+    ShowSpelling,
+    SwitchBoard,
+    EditNotes,
+    Suspend,
     Open,
     EnterEditMode,
     EnterConnectMode,
+    ShowIncoming,
+    ShowCycles,
+    ShowBranches,
+    ConnectWithBack,
+    TogglePreview,
+    ToggleSketch,
     RemoveConnection,
+    ToggleCut,
+    ToggleRabbitHole,
+    ShowRabbitHoles,
+    RestoreSnapshot,
+    PeekSearchResult, // Select the highlighted jump search result, keeping the search open
+    RepeatSearch { forward: bool }, // Select the next or previous place matching the last jump search
+    ShowTrash,
+    ShowViews,
+    Compare,
+    Scroll(i32), // Move the comparison by this many rows
+    JumpToChange { forward: bool },
+    AdvanceStatus,
+    CyclePlaceKind,
+    ReverseConnection { remove_original: bool },
     Delete,
+    Leader(Pending), // The leader key, or a key of a sequence after it, with more to come
+    LeaderMissed(String), // A sequence nothing is bound to, or empty when it was given up
     Edit(String),
 }
 
-pub struct InputHandler;
+impl Action {
+    // Whether a count typed before the action repeats it, as `5↓` moves
+    // down five times
+    pub fn repeats(&self) -> bool {
+        matches!(
+            self,
+            Action::NavigateUp
+                | Action::NavigateDown
+                | Action::NavigateRight
+                | Action::NavigateLeft
+                | Action::JumpUp
+                | Action::PanGraph(..)
+                | Action::ZoomGraph { .. }
+                | Action::RepeatSearch { .. }
+        )
+    }
+
+    // Whether the action changes the board (or saves it), which a read-only
+    // board refuses. Looking around, filtering, exporting and copying don't.
+    pub fn edits_board(&self) -> bool {
+        matches!(
+            self,
+            Action::NewPlace
+                | Action::NewAffordance
+                | Action::Save
+                | Action::Capture
+                | Action::PasteNotes
+                | Action::Extract { .. }
+                | Action::Import
+                | Action::Rewire
+                | Action::ToggleHappyPath
+                | Action::AddComment
+                | Action::Estimate
+                | Action::Attach
+                | Action::PastePlace
+                | Action::ShowSnippets
+                | Action::ShowSpelling
+                | Action::EditNotes
+                | Action::EnterEditMode
+                | Action::EnterConnectMode
+                | Action::ConnectWithBack
+                | Action::RemoveConnection
+                | Action::ToggleCut
+                | Action::ToggleRabbitHole
+                | Action::AdvanceStatus
+                | Action::CyclePlaceKind
+                | Action::ReverseConnection { .. }
+                | Action::Delete
+                | Action::RestoreSnapshot
+        )
+    }
+}
+
+const PAN_STEP: (i32, i32) = (16, 6); // Columns and rows per Shift+Arrow
+const SCROLL_STEP: (i32, i32) = (4, 3); // Columns and rows per mouse wheel tick
+const COMPARE_PAGE: i32 = 10; // Rows per Page Up/Down when comparing boards
+
+pub struct InputHandler {
+    drag_from: Option<(u16, u16)>, // Last mouse position while dragging the graph
+    keymap: Keymap, // Keys bound in the config
+    typed: Option<Vec<Key>>, // Keys typed after the leader
+}
 
 impl InputHandler {
-    pub fn new() -> Self {
-        Self
+    pub fn new(keymap: Keymap) -> Self {
+        Self { drag_from: None, keymap, typed: None }
     }
 
-    pub fn read_action(&self, mode: Mode) -> Result<Action> {
+    pub fn read_action(&mut self, mode: Mode) -> Result<Action> {
         if !event::poll(std::time::Duration::from_millis(16))? {
             return Ok(Action::None);
         }
 
         let event = event::read()?;
 
-        if let event::Event::Key(key) = event {
-            return Ok(self.handle_key_event(key, mode));
+        match event {
+            event::Event::Key(key) => return Ok(self.handle_key_event(key, mode)),
+            event::Event::Mouse(mouse) if mode == Mode::Navigate => return Ok(self.handle_mouse_event(mouse)),
+            _ => {}
         }
 
         Ok(Action::None)
     }
 
-    fn handle_key_event(&self, key: KeyEvent, mode: Mode) -> Action {
+    fn handle_key_event(&mut self, key: KeyEvent, mode: Mode) -> Action {
+        // Raw mode swallows the terminal's own Ctrl+Z, so it arrives as a key
+        if key.code == KeyCode::Char('z') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            return Action::Suspend;
+        }
+        if mode == Mode::Navigate {
+            if let Some(typed) = self.typed.take() {
+                return self.continue_sequence(typed, key);
+            }
+            if self.keymap.is_leader(&key) {
+                self.typed = Some(Vec::new());
+                return Action::Leader(self.keymap.pending(&[]));
+            }
+            if let Some(action) = self.keymap.action(&key) {
+                return action;
+            }
+        }
+
         match mode {
             Mode::Navigate => self.handle_navigate_key(key, mode),
-            Mode::Edit => self.handle_edit_key(key),
+            Mode::Edit | Mode::Capture | Mode::Comment | Mode::Estimate | Mode::ViewName => self.handle_edit_key(key),
             Mode::Connect => self.handle_connect_key(key),
             Mode::OpenFile => self.handle_open_file_key(key),
-            Mode::SaveFile => self.handle_save_file_key(key),
-            Mode::ConfirmDelete => self.handle_confirm_delete_key(key),
+            Mode::SaveFile | Mode::ImportFile => self.handle_save_file_key(key),
+            Mode::ConfirmDelete | Mode::ConfirmReopen | Mode::ConfirmRecover | Mode::ConfirmLock | Mode::ConfirmNotes => self.handle_confirm_key(key),
+            Mode::Filter => self.handle_filter_key(key),
+            Mode::Incoming => self.handle_incoming_key(key),
+            Mode::Cycles => self.handle_cycles_key(key),
+            Mode::Branches => self.handle_branches_key(key),
+            Mode::Views => self.handle_views_key(key),
+            Mode::Commands | Mode::Snippets | Mode::Trash | Mode::Spelling => self.handle_commands_key(key),
+            Mode::Library => self.handle_library_key(key),
+            Mode::Start => self.handle_start_key(key),
+            Mode::Compare => self.handle_compare_key(key),
+        }
+    }
+
+    // The next key of a leader sequence: Esc gives it up and Backspace takes
+    // back the last key
+    fn continue_sequence(&mut self, mut typed: Vec<Key>, key: KeyEvent) -> Action {
+        match key.code {
+            KeyCode::Esc => return Action::LeaderMissed(String::new()),
+            KeyCode::Backspace => {
+                typed.pop();
+            }
+            _ => typed.push(Key::from_event(&key)),
+        }
+        match self.keymap.sequence(&typed) {
+            Sequence::Complete(action) => action,
+            Sequence::Unbound if !typed.is_empty() => Action::LeaderMissed(self.keymap.typed_label(&typed)),
+            _ => {
+                let pending = self.keymap.pending(&typed);
+                self.typed = Some(typed);
+                Action::Leader(pending)
+            }
+        }
+    }
+
+    // Wheel and drag pan the graph view; Ctrl+wheel zooms it
+    fn handle_mouse_event(&mut self, mouse: MouseEvent) -> Action {
+        let horizontal = mouse.modifiers.contains(KeyModifiers::SHIFT);
+        let zoom = mouse.modifiers.contains(KeyModifiers::CONTROL);
+        match mouse.kind {
+            MouseEventKind::ScrollUp if zoom => Action::ZoomGraph { zoom_in: true },
+            MouseEventKind::ScrollDown if zoom => Action::ZoomGraph { zoom_in: false },
+            MouseEventKind::ScrollUp if horizontal => Action::PanGraph(-SCROLL_STEP.0, 0),
+            MouseEventKind::ScrollDown if horizontal => Action::PanGraph(SCROLL_STEP.0, 0),
+            MouseEventKind::ScrollUp => Action::PanGraph(0, -SCROLL_STEP.1),
+            MouseEventKind::ScrollDown => Action::PanGraph(0, SCROLL_STEP.1),
+            MouseEventKind::ScrollLeft => Action::PanGraph(-SCROLL_STEP.0, 0),
+            MouseEventKind::ScrollRight => Action::PanGraph(SCROLL_STEP.0, 0),
+            MouseEventKind::Down(MouseButton::Left) => {
+                self.drag_from = Some((mouse.column, mouse.row));
+                Action::Click { column: mouse.column, row: mouse.row }
+            }
+            MouseEventKind::Drag(MouseButton::Left) => {
+                // Dragging moves the canvas with the pointer
+                let Some((x, y)) = self.drag_from.replace((mouse.column, mouse.row)) else {
+                    return Action::None;
+                };
+                Action::PanGraph(x as i32 - mouse.column as i32, y as i32 - mouse.row as i32)
+            }
+            MouseEventKind::Up(MouseButton::Left) => {
+                self.drag_from = None;
+                Action::None
+            }
+            _ => Action::None,
         }
     }
 
     fn handle_navigate_key(&self, key: KeyEvent, mode: Mode) -> Action {
         match key.code {
+            KeyCode::Up if key.modifiers.contains(KeyModifiers::SHIFT) => Action::PanGraph(0, -PAN_STEP.1),
+            KeyCode::Down if key.modifiers.contains(KeyModifiers::SHIFT) => Action::PanGraph(0, PAN_STEP.1),
+            KeyCode::Left if key.modifiers.contains(KeyModifiers::SHIFT) => Action::PanGraph(-PAN_STEP.0, 0),
+            KeyCode::Right if key.modifiers.contains(KeyModifiers::SHIFT) => Action::PanGraph(PAN_STEP.0, 0),
+            KeyCode::Up if key.modifiers.contains(KeyModifiers::ALT) => Action::JumpUp, // Alt+↑ goes up a breadcrumb level
+            KeyCode::Down if key.modifiers.contains(KeyModifiers::ALT) => Action::DrillIn, // Alt+↓ opens the places inside the selected one
+            KeyCode::Home if key.modifiers.contains(KeyModifiers::CONTROL) => Action::EndPlace { last: false },
+            KeyCode::End if key.modifiers.contains(KeyModifiers::CONTROL) => Action::EndPlace { last: true },
+            KeyCode::Home => Action::EndAffordance { last: false },
+            KeyCode::End => Action::EndAffordance { last: true },
+            KeyCode::PageUp => Action::PageList { down: false }, // Through place search results
+            KeyCode::PageDown => Action::PageList { down: true },
             KeyCode::Up => Action::NavigateUp,
             KeyCode::Down => Action::NavigateDown,
             KeyCode::Tab => Action::NavigateRight,
             KeyCode::BackTab => Action::NavigateLeft,
+            KeyCode::Left => Action::ShowIncoming, // ← lists places linking here
+            KeyCode::Right => Action::TogglePreview, // → peeks at the connected place
+            // Ctrl+Enter arrives as Ctrl+J from terminals that send it as a line feed
+            KeyCode::Enter if key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => Action::PeekSearchResult,
+            KeyCode::Char('j') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::PeekSearchResult,
             KeyCode::Enter => Action::Select,
+            // F3 finds the last search's next match and F2 the one before:
+            // terminals send Shift+F3 looking like a cursor position report
+            KeyCode::F(3) => Action::RepeatSearch { forward: true },
+            KeyCode::F(2) => Action::RepeatSearch { forward: false },
+            KeyCode::Char('t') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Action::ExportDocument(Document::Walkthrough) // Alt+T writes the board as numbered steps
+            }
+            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Action::ExportDocument(Document::Pitch) // Alt+D writes a pitch skeleton around the board
+            }
+            KeyCode::Char('a') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Action::ExportDocument(Document::Tasks { per_affordance: false }) // Alt+A writes a task list of places
+            }
+            KeyCode::Char('P') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Action::ExportDocument(Document::Print) // Alt+Shift+P writes pages to print
+            }
+            KeyCode::Char('H') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Action::ExportDocument(Document::Html) // Alt+Shift+H writes a web page to click through
+            }
+            KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Action::Extract { replace: false } // Alt+S copies the selected place's subtree to a new board
+            }
+            KeyCode::Char('S') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Action::Extract { replace: true } // Alt+Shift+S moves it there, linking to it from this board
+            }
+            KeyCode::Char('g') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Action::ToggleMark // Alt+G marks the selection for connecting several at once
+            }
+            KeyCode::Char('z') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Action::ShowFullText // Alt+Z shows whatever was cut short with … in full
+            }
+            KeyCode::Char('D') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Action::ShowTrash // Alt+Shift+D lists what was deleted this session, to put back
+            }
+            KeyCode::Char('Z') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Action::RestoreSnapshot // Alt+Shift+Z undoes the last delete, merge or bulk connect
+            }
+            KeyCode::Char('h') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Action::ToggleHappyPath // Alt+H adds the place to the happy path, or takes it off
+            }
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Action::Rewire // Alt+U changes where the selected affordance leads
+            }
+            KeyCode::Char('U') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Action::ShowRabbitHoles // Alt+Shift+U reviews the unknowns: every rabbit hole with its notes
+            }
+            KeyCode::Char('f') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Action::ShowSnippets // Alt+F lists the affordance snippets from the config
+            }
+            KeyCode::Char('T') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Action::ShowSpelling // Alt+Shift+T offers corrections for the typos in the selection
+            }
+            KeyCode::Char('v') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Action::PasteNotes // Alt+V reads rough notes from the clipboard
+            }
+            KeyCode::Char('V') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Action::ShowViews // Alt+Shift+V picks or saves one of the board's named views
+            }
+            KeyCode::Char('q') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Action::Capture // Alt+Q types the board in line by line
+            }
+            KeyCode::Char('i') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Action::Import // Alt+I merges a CSV, Graphviz or board file into the board
+            }
+            KeyCode::Char('e') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Action::ExportView // Alt+E writes the current view to a text file
+            }
+            KeyCode::Char('j') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Action::Attach // Alt+J attaches a sketch or other file to the selected place
+            }
+            KeyCode::Char('e') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                Action::ToggleSketch // Ctrl+E shows the selected place's sketch
+            }
+            KeyCode::Char('b') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                Action::ExportBundle // Ctrl+B zips the board with its attachments
+            }
             KeyCode::Char('e') => {
                 if mode == Mode::Navigate {
                     Action::EnterEditMode
@@ -92,13 +413,7 @@ impl InputHandler {
                     Action::Back
                 }
             },
-            KeyCode::Esc => {
-                if mode == Mode::Edit {
-                    Action::Back // Cancel edit
-                } else {
-                    Action::Back
-                }
-            },
+            KeyCode::Esc => Action::Back,
 
             KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                 Action::EnterConnectMode
@@ -106,18 +421,69 @@ impl InputHandler {
             KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                 Action::RemoveConnection
             }
+            KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Action::ReverseConnection { remove_original: false } // Alt+R adds the return link
+            }
+            KeyCode::Char('R') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Action::ReverseConnection { remove_original: true } // Alt+Shift+R swaps the link
+            }
+            KeyCode::Char('x') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                Action::ToggleCut // Ctrl+X cuts the affordance from scope, or restores it
+            }
             KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                 Action::NewPlace
             }
             KeyCode::Char('a') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                 Action::NewAffordance
             }
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Action::ShowCycles // Alt+C lists the loops formed by connections
+            }
             KeyCode::Char('c') if !key.modifiers.contains(KeyModifiers::CONTROL) => {
                 Action::ToggleCollapsed
             }
             KeyCode::Char('f') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                 Action::Filter
             }
+            KeyCode::Char('g') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                Action::ToggleGraphView
+            }
+            KeyCode::Char('m') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Action::ToggleMinimap
+            }
+            KeyCode::Char('l') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Action::ToggleSwimlanes // Alt+L draws the graph with one lane per group
+            }
+            KeyCode::Char('+') | KeyCode::Char('=') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Action::ZoomGraph { zoom_in: true }
+            }
+            KeyCode::Char('-') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Action::ZoomGraph { zoom_in: false }
+            }
+            KeyCode::Char('b') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Action::AdvanceStatus // Alt+B moves the affordance from planned to building to built
+            }
+            KeyCode::Char('o') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Action::ShowBranches // Alt+O lists the affordance's outcomes
+            }
+            KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Action::ToggleProgress // Alt+P shows how much of each place is built
+            }
+            KeyCode::Char('k') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Action::CyclePlaceKind // Alt+K steps the place through screen, modal, email, job and external
+            }
+            KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Action::EditNotes // Alt+N opens the selection's notes in $EDITOR
+            }
+            KeyCode::Char('w') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Action::SwitchBoard // Alt+W lists the boards of the open workspace
+            }
+            KeyCode::Char('x') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Action::ShowCommands // Alt+X lists the custom commands from the config
+            }
+            KeyCode::Char('y') if key.modifiers.contains(KeyModifiers::ALT) => {
+                Action::CopyView // Alt+Y copies the current view to the clipboard
+            }
             KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) && key.modifiers.contains(KeyModifiers::SHIFT) => {
                 Action::SaveAs
             }
@@ -130,6 +496,30 @@ impl InputHandler {
             KeyCode::Char('q') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                 Action::Quit
             }
+            KeyCode::Char('k') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                Action::AddComment // Ctrl+K comments on the selection
+            }
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                Action::ToggleRabbitHole // Ctrl+U flags the selection as a rabbit hole, or unflags it
+            }
+            KeyCode::Char('v') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                Action::Compare // Ctrl+V sets the board beside another version of it
+            }
+            KeyCode::Char('w') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                Action::Estimate // Ctrl+W weighs the selected affordance: points or a t-shirt size
+            }
+            KeyCode::Char('l') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                Action::ShowHistory // Ctrl+L shows the board's change log
+            }
+            KeyCode::Char('t') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                Action::ShowGlossary // Ctrl+T lists the board's glossary terms
+            }
+            KeyCode::Char('y') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                Action::CopyPlace // Ctrl+Y copies the selected place, to paste on this board or another
+            }
+            KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                Action::PastePlace
+            }
 
             // Any other character starts place search
             KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL)
@@ -143,8 +533,11 @@ impl InputHandler {
 
     fn handle_edit_key(&self, key: KeyEvent) -> Action {
         match key.code {
-            KeyCode::Enter => Action::Select, // Save changes and exit edit mode
+            KeyCode::Enter => Action::Select, // Save changes and exit edit mode, or put in the highlighted icon
             KeyCode::Esc => Action::Back, // Cancel edit
+            KeyCode::Tab => Action::Edit(String::from("tab")), // Put in the highlighted icon
+            KeyCode::Up => Action::NavigateUp, // Move through the icons offered
+            KeyCode::Down => Action::NavigateDown,
             KeyCode::Backspace => Action::Edit(String::from("backspace")),
             KeyCode::Delete => Action::Edit(String::from("delete")),
             KeyCode::Left => Action::Edit(String::from("left")),
@@ -160,7 +553,9 @@ impl InputHandler {
 
     fn handle_connect_key(&self, key: KeyEvent) -> Action {
         match key.code {
+            KeyCode::Enter if key.modifiers.contains(KeyModifiers::ALT) => Action::ConnectWithBack, // Connect and add a "Back" link
             KeyCode::Enter => Action::Select, // Create connection with selected place
+            KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::RemoveConnection, // Ctrl+R disconnects instead
             KeyCode::Esc => Action::Back, // Cancel connection mode
             KeyCode::Backspace => Action::Edit(String::from("backspace")),
             KeyCode::Delete => Action::Edit(String::from("delete")),
@@ -168,12 +563,10 @@ impl InputHandler {
             KeyCode::Down => Action::NavigateDown, // Navigate search results
             KeyCode::Left => Action::Edit(String::from("left")),
             KeyCode::Right => Action::Edit(String::from("right")),
-            KeyCode::Home => Action::Edit(String::from("home")),
-            KeyCode::End => Action::Edit(String::from("end")),
 
             KeyCode::Char(c) => Action::Edit(c.to_string()),
 
-            _ => Action::None,
+            _ => list_key(key),
         }
     }
 
@@ -183,12 +576,13 @@ impl InputHandler {
             KeyCode::Esc => Action::Back, // Cancel file opening
             KeyCode::Up => Action::NavigateUp, // Navigate file list
             KeyCode::Down => Action::NavigateDown, // Navigate file list
-            KeyCode::Left => Action::Edit(String::from("left")),
-            KeyCode::Right => Action::Edit(String::from("right")),
-            KeyCode::Home => Action::Edit(String::from("home")),
-            KeyCode::End => Action::Edit(String::from("end")),
+            KeyCode::Tab => Action::ToggleFileSort, // Newest first, or by name
+            KeyCode::Backspace => Action::Edit(String::from("backspace")),
+            KeyCode::Delete => Action::Edit(String::from("delete")),
 
-            _ => Action::None,
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => Action::Edit(c.to_string()), // Filter the list by name
+
+            _ => list_key(key),
         }
     }
 
@@ -196,6 +590,7 @@ impl InputHandler {
         match key.code {
             KeyCode::Enter => Action::Select, // Save with entered filename
             KeyCode::Esc => Action::Back, // Cancel save
+            KeyCode::Tab => Action::CycleExportScope, // Board, selection, subtree or filter
             KeyCode::Backspace => Action::Edit(String::from("backspace")),
             KeyCode::Delete => Action::Edit(String::from("delete")),
             KeyCode::Left => Action::Edit(String::from("left")),
@@ -209,11 +604,135 @@ impl InputHandler {
         }
     }
 
-    fn handle_confirm_delete_key(&self, key: KeyEvent) -> Action {
+    fn handle_filter_key(&self, key: KeyEvent) -> Action {
+        match key.code {
+            KeyCode::Enter => Action::Select, // Keep filter and return to navigation
+            KeyCode::Esc => Action::Back, // Clear filter
+            KeyCode::Backspace => Action::Edit(String::from("backspace")),
+            KeyCode::Delete => Action::Edit(String::from("delete")),
+            KeyCode::Up => Action::NavigateUp, // Move through the filtered board
+            KeyCode::Down => Action::NavigateDown,
+            KeyCode::Tab => Action::NavigateRight,
+            KeyCode::BackTab => Action::NavigateLeft,
+
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => Action::Edit(c.to_string()),
+
+            _ => Action::None,
+        }
+    }
+
+    fn handle_incoming_key(&self, key: KeyEvent) -> Action {
+        match key.code {
+            KeyCode::Enter | KeyCode::Right => Action::Select, // Jump to selected source
+            KeyCode::Esc | KeyCode::Left | KeyCode::Backspace => Action::Back, // Cancel
+            KeyCode::Tab => Action::ToggleReach, // Every place that can reach here, or just direct links
+            KeyCode::Up => Action::NavigateUp, // Navigate sources
+            KeyCode::Down => Action::NavigateDown,
+            _ => list_key(key),
+        }
+    }
+
+    fn handle_cycles_key(&self, key: KeyEvent) -> Action {
+        match key.code {
+            KeyCode::Enter => Action::Select, // Stay on the selected cycle
+            KeyCode::Esc | KeyCode::Backspace => Action::Back, // Return to where you were
+            KeyCode::Up => Action::NavigateUp, // Navigate cycles
+            KeyCode::Down => Action::NavigateDown,
+            _ => list_key(key),
+        }
+    }
+
+    fn handle_compare_key(&self, key: KeyEvent) -> Action {
+        match key.code {
+            KeyCode::Esc | KeyCode::Backspace | KeyCode::Char('q') => Action::Back,
+            KeyCode::Up => Action::Scroll(-1),
+            KeyCode::Down => Action::Scroll(1),
+            KeyCode::PageUp => Action::Scroll(-COMPARE_PAGE),
+            KeyCode::PageDown | KeyCode::Char(' ') => Action::Scroll(COMPARE_PAGE),
+            KeyCode::Tab | KeyCode::Char('n') => Action::JumpToChange { forward: true }, // Next change
+            KeyCode::BackTab | KeyCode::Char('N') | KeyCode::Char('p') => Action::JumpToChange { forward: false },
+            _ => Action::None,
+        }
+    }
+
+    fn handle_branches_key(&self, key: KeyEvent) -> Action {
+        match key.code {
+            KeyCode::Enter => Action::Select, // Follow the outcome
+            KeyCode::Esc | KeyCode::Backspace => Action::Back,
+            KeyCode::Up => Action::NavigateUp, // Navigate outcomes
+            KeyCode::Down => Action::NavigateDown,
+            KeyCode::Char('a') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::NewAffordance, // Add an outcome
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::EnterConnectMode, // Pick its destination
+            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::Delete,
+            KeyCode::Delete => Action::Delete,
+            KeyCode::Char('e') => Action::EnterEditMode, // Rename the outcome
+            _ => list_key(key),
+        }
+    }
+
+    fn handle_views_key(&self, key: KeyEvent) -> Action {
+        match key.code {
+            KeyCode::Enter => Action::Select, // Show the board this way
+            KeyCode::Esc | KeyCode::Backspace => Action::Back,
+            KeyCode::Up => Action::NavigateUp,
+            KeyCode::Down => Action::NavigateDown,
+            KeyCode::Char('a') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::NewAffordance, // Save the current view
+            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::Delete,
+            KeyCode::Delete => Action::Delete,
+            _ => list_key(key),
+        }
+    }
+
+    fn handle_commands_key(&self, key: KeyEvent) -> Action {
+        match key.code {
+            KeyCode::Enter => Action::Select, // Run selected command
+            KeyCode::Esc | KeyCode::Backspace => Action::Back, // Cancel
+            KeyCode::Up => Action::NavigateUp, // Navigate commands
+            KeyCode::Down => Action::NavigateDown,
+            _ => list_key(key),
+        }
+    }
+
+    fn handle_library_key(&self, key: KeyEvent) -> Action {
+        match key.code {
+            KeyCode::Enter => Action::Select, // Open selected board
+            KeyCode::Esc => Action::Back, // Start a new board instead
+            KeyCode::Up => Action::NavigateUp, // Navigate boards
+            KeyCode::Down => Action::NavigateDown,
+            _ => list_key(key),
+        }
+    }
+
+    fn handle_start_key(&self, key: KeyEvent) -> Action {
         match key.code {
-            KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => Action::Select, // Confirm deletion
-            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => Action::Back, // Cancel deletion
+            KeyCode::Char('q') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::Quit,
+            KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::NewPlace,
+            KeyCode::Char('o') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::Open,
+            KeyCode::Enter => Action::Select, // Do the selected entry
+            KeyCode::Esc => Action::Back, // Stay on the blank board
+            KeyCode::Up => Action::NavigateUp,
+            KeyCode::Down => Action::NavigateDown,
+            _ => list_key(key),
+        }
+    }
+
+    fn handle_confirm_key(&self, key: KeyEvent) -> Action {
+        match key.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => Action::Select, // Confirm
+            KeyCode::Char('j') | KeyCode::Char('J') => Action::Select, // "Ja", as the German dialogs offer
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => Action::Back, // Cancel
             _ => Action::None,
         }
     }
-}
\ No newline at end of file
+}
+
+// Page Up/Down and Home/End move through any picker's list
+fn list_key(key: KeyEvent) -> Action {
+    match key.code {
+        KeyCode::PageUp => Action::PageList { down: false },
+        KeyCode::PageDown => Action::PageList { down: true },
+        KeyCode::Home => Action::ListEnd { last: false },
+        KeyCode::End => Action::ListEnd { last: true },
+        _ => Action::None,
+    }
+}
@@ -0,0 +1,533 @@
+use anyhow::{anyhow, bail, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::config::{Alternatives, Keys};
+use crate::export::Document;
+use crate::input::Action;
+use crate::locale::{self, Strings};
+
+// Keys bound to actions by the config, for when a built-in chord doesn't
+// make it through the terminal or the keyboard layout. They're written like
+// "F12", "Ctrl+Alt+s" or "Shift+Tab", and checked for chords the terminal
+// can't tell apart from others. With a leader key set, it starts mnemonic
+// sequences of plain keys, like Space f s to save.
+
+// What the "function_keys" alternatives bind: the chords terminals and
+// layouts most often lose, each on a function key
+const FUNCTION_KEYS: &[(&str, &str)] = &[
+    ("F4", "navigate_left"), // Shift+Tab
+    ("F5", "save"),
+    ("F6", "save_as"), // Ctrl+Shift+S
+    ("F7", "connect"),
+    ("F8", "delete"),
+    ("F9", "filter"),
+];
+
+// Sequences typed after the leader key, when the config sets one
+const LEADER_SEQUENCES: &[(&str, &str)] = &[
+    ("f s", "save"),
+    ("f a", "save_as"),
+    ("f o", "open"),
+    ("f w", "switch_board"),
+    ("f i", "import"),
+    ("f q", "quit"),
+    ("p n", "new_place"),
+    ("p a", "new_affordance"),
+    ("p e", "edit"),
+    ("p c", "connect"),
+    ("p d", "delete"),
+    ("p k", "cycle_place_kind"),
+    ("p m", "toggle_mark"),
+    ("v g", "toggle_graph_view"),
+    ("v c", "toggle_collapsed"),
+    ("v l", "toggle_swimlanes"),
+    ("v m", "toggle_minimap"),
+    ("v p", "toggle_progress"),
+    ("v f", "filter"),
+    ("v v", "show_views"),
+    ("e v", "export_view"),
+    ("e w", "export_walkthrough"),
+    ("e p", "export_pitch"),
+    ("e t", "export_tasks"),
+    ("e h", "export_html"),
+    ("e m", "export_mermaid"),
+    ("e s", "export_svg"),
+    ("e b", "export_bundle"),
+    ("s g", "show_glossary"),
+    ("s h", "show_history"),
+    ("s t", "show_trash"),
+    ("s c", "show_cycles"),
+    ("s i", "show_incoming"),
+    ("s x", "show_commands"),
+];
+
+// What the first keys of the leader sequences above open, for the popup
+fn leader_groups(s: &'static Strings) -> Vec<(&'static str, &'static str)> {
+    vec![("f", s.leader_file), ("p", s.leader_place), ("v", s.leader_view), ("e", s.leader_export), ("s", s.leader_show)]
+}
+
+// Every action a key can be bound to, by the name the config uses
+pub const ACTION_NAMES: &[&str] = &[
+    "quit", "navigate_up", "navigate_down", "navigate_right", "navigate_left", "select", "back", "new_place",
+    "new_affordance", "toggle_collapsed", "toggle_graph_view", "toggle_minimap", "toggle_swimlanes",
+    "toggle_progress", "jump_up", "first_place", "last_place", "first_affordance", "last_affordance", "drill_in", "zoom_in", "zoom_out", "filter", "save", "save_as", "export_view",
+    "export_walkthrough", "export_pitch", "export_tasks", "export_print", "export_html", "export_mermaid", "export_svg", "export_bundle", "attach",
+    "capture", "paste_notes", "copy_subtree", "move_subtree", "import", "copy_view", "copy_place", "rewire",
+    "toggle_mark", "toggle_happy_path", "show_full_text", "show_glossary", "add_comment", "estimate",
+    "show_history", "paste_place", "show_commands", "show_snippets", "show_spelling", "switch_board",
+    "edit_notes", "suspend", "open", "edit", "connect", "show_incoming", "show_cycles", "show_branches",
+    "toggle_preview", "toggle_sketch", "remove_connection", "toggle_cut", "toggle_rabbit_hole",
+    "show_rabbit_holes", "restore_snapshot", "next_match", "previous_match", "show_trash", "show_views",
+    "compare", "advance_status", "cycle_place_kind", "add_return_link", "swap_link", "delete",
+];
+
+// The action called `name` in the config
+pub fn action(name: &str) -> Option<Action> {
+    Some(match name {
+        "quit" => Action::Quit,
+        "navigate_up" => Action::NavigateUp,
+        "navigate_down" => Action::NavigateDown,
+        "navigate_right" => Action::NavigateRight,
+        "navigate_left" => Action::NavigateLeft,
+        "select" => Action::Select,
+        "back" => Action::Back,
+        "new_place" => Action::NewPlace,
+        "new_affordance" => Action::NewAffordance,
+        "toggle_collapsed" => Action::ToggleCollapsed,
+        "toggle_graph_view" => Action::ToggleGraphView,
+        "toggle_minimap" => Action::ToggleMinimap,
+        "toggle_swimlanes" => Action::ToggleSwimlanes,
+        "toggle_progress" => Action::ToggleProgress,
+        "jump_up" => Action::JumpUp,
+        "first_place" => Action::EndPlace { last: false },
+        "last_place" => Action::EndPlace { last: true },
+        "first_affordance" => Action::EndAffordance { last: false },
+        "last_affordance" => Action::EndAffordance { last: true },
+        "drill_in" => Action::DrillIn,
+        "zoom_in" => Action::ZoomGraph { zoom_in: true },
+        "zoom_out" => Action::ZoomGraph { zoom_in: false },
+        "filter" => Action::Filter,
+        "save" => Action::Save,
+        "save_as" => Action::SaveAs,
+        "export_view" => Action::ExportView,
+        "export_walkthrough" => Action::ExportDocument(Document::Walkthrough),
+        "export_pitch" => Action::ExportDocument(Document::Pitch),
+        "export_tasks" => Action::ExportDocument(Document::Tasks { per_affordance: false }),
+        "export_print" => Action::ExportDocument(Document::Print),
+        "export_html" => Action::ExportDocument(Document::Html),
+        "export_mermaid" => Action::ExportDocument(Document::Mermaid),
+        "export_svg" => Action::ExportDocument(Document::Svg),
+        "export_bundle" => Action::ExportBundle,
+        "attach" => Action::Attach,
+        "capture" => Action::Capture,
+        "paste_notes" => Action::PasteNotes,
+        "copy_subtree" => Action::Extract { replace: false },
+        "move_subtree" => Action::Extract { replace: true },
+        "import" => Action::Import,
+        "copy_view" => Action::CopyView,
+        "copy_place" => Action::CopyPlace,
+        "rewire" => Action::Rewire,
+        "toggle_mark" => Action::ToggleMark,
+        "toggle_happy_path" => Action::ToggleHappyPath,
+        "show_full_text" => Action::ShowFullText,
+        "show_glossary" => Action::ShowGlossary,
+        "add_comment" => Action::AddComment,
+        "estimate" => Action::Estimate,
+        "show_history" => Action::ShowHistory,
+        "paste_place" => Action::PastePlace,
+        "show_commands" => Action::ShowCommands,
+        "show_snippets" => Action::ShowSnippets,
+        "show_spelling" => Action::ShowSpelling,
+        "switch_board" => Action::SwitchBoard,
+        "edit_notes" => Action::EditNotes,
+        "suspend" => Action::Suspend,
+        "open" => Action::Open,
+        "edit" => Action::EnterEditMode,
+        "connect" => Action::EnterConnectMode,
+        "show_incoming" => Action::ShowIncoming,
+        "show_cycles" => Action::ShowCycles,
+        "show_branches" => Action::ShowBranches,
+        "toggle_preview" => Action::TogglePreview,
+        "toggle_sketch" => Action::ToggleSketch,
+        "remove_connection" => Action::RemoveConnection,
+        "toggle_cut" => Action::ToggleCut,
+        "toggle_rabbit_hole" => Action::ToggleRabbitHole,
+        "show_rabbit_holes" => Action::ShowRabbitHoles,
+        "restore_snapshot" => Action::RestoreSnapshot,
+        "next_match" => Action::RepeatSearch { forward: true },
+        "previous_match" => Action::RepeatSearch { forward: false },
+        "show_trash" => Action::ShowTrash,
+        "show_views" => Action::ShowViews,
+        "compare" => Action::Compare,
+        "advance_status" => Action::AdvanceStatus,
+        "cycle_place_kind" => Action::CyclePlaceKind,
+        "add_return_link" => Action::ReverseConnection { remove_original: false },
+        "swap_link" => Action::ReverseConnection { remove_original: true },
+        "delete" => Action::Delete,
+        _ => return None,
+    })
+}
+
+// A key with the modifiers held with it. Letters are kept in lower case,
+// with Shift in the modifiers, however the terminal sent them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Key {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl Key {
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let modifiers = modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT);
+        match code {
+            KeyCode::Char(c) if c.is_uppercase() => Self {
+                code: KeyCode::Char(c.to_lowercase().next().unwrap_or(c)),
+                modifiers: modifiers | KeyModifiers::SHIFT,
+            },
+            // Shift+Tab arrives as BackTab, with or without Shift
+            KeyCode::BackTab => Self { code: KeyCode::Tab, modifiers: modifiers | KeyModifiers::SHIFT },
+            code => Self { code, modifiers },
+        }
+    }
+
+    pub fn from_event(event: &KeyEvent) -> Self {
+        Self::new(event.code, event.modifiers)
+    }
+
+    // As the config would write it, with capitals for shifted letters
+    pub fn label(&self) -> String {
+        let mut label = String::new();
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            label.push_str("Ctrl+");
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            label.push_str("Alt+");
+        }
+        let shifted_letter = matches!(self.code, KeyCode::Char(c) if c.is_alphabetic());
+        if self.modifiers.contains(KeyModifiers::SHIFT) && !shifted_letter {
+            label.push_str("Shift+");
+        }
+        match self.code {
+            KeyCode::Char(' ') => label.push_str("Space"),
+            KeyCode::Char(c) if self.modifiers.contains(KeyModifiers::SHIFT) => label.extend(c.to_uppercase()),
+            KeyCode::Char(c) => label.push(c),
+            KeyCode::F(n) => label.push_str(&format!("F{}", n)),
+            KeyCode::PageUp => label.push_str("PageUp"),
+            KeyCode::PageDown => label.push_str("PageDown"),
+            code => label.push_str(&format!("{:?}", code)),
+        }
+        label
+    }
+
+    // "Ctrl+Alt+s", "F12", "Shift+Tab", "Space"
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut parts: Vec<&str> = text.split('+').map(str::trim).collect();
+        // "Alt++" is Alt with the plus key
+        if text.ends_with("++") {
+            parts.truncate(parts.len() - 2);
+            parts.push("+");
+        }
+        let (name, held) = parts.split_last().ok_or_else(|| anyhow!("no key in \"{}\"", text))?;
+        for modifier in held {
+            modifiers |= match modifier.to_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" | "meta" | "option" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => bail!("unknown modifier \"{}\" in \"{}\"", modifier, text),
+            };
+        }
+        let code = match name.to_lowercase().as_str() {
+            "" => bail!("no key in \"{}\"", text),
+            "space" => KeyCode::Char(' '),
+            "tab" => KeyCode::Tab,
+            "enter" | "return" => KeyCode::Enter,
+            "esc" | "escape" => KeyCode::Esc,
+            "backspace" => KeyCode::Backspace,
+            "delete" | "del" => KeyCode::Delete,
+            "insert" | "ins" => KeyCode::Insert,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" | "pgup" => KeyCode::PageUp,
+            "pagedown" | "pgdn" => KeyCode::PageDown,
+            lower => match lower.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                Some(n @ 1..=24) => KeyCode::F(n),
+                _ => {
+                    let mut chars = name.chars();
+                    match (chars.next(), chars.next()) {
+                        (Some(c), None) => KeyCode::Char(c),
+                        _ => bail!("unknown key \"{}\" in \"{}\"", name, text),
+                    }
+                }
+            },
+        };
+        Ok(Self::new(code, modifiers))
+    }
+
+    // Why the terminal can't be relied on to send this key as itself, if it can't
+    pub fn ambiguity(&self, s: &Strings) -> Option<String> {
+        let ctrl = self.modifiers.contains(KeyModifiers::CONTROL);
+        let shift = self.modifiers.contains(KeyModifiers::SHIFT);
+        match self.code {
+            KeyCode::Char(c) if ctrl && shift && c.is_alphabetic() => {
+                Some(locale::fill(s.key_sent_as_ctrl, &[&c.to_string()]))
+            }
+            KeyCode::Char('z') if ctrl => Some(s.key_suspends.to_string()),
+            KeyCode::Char(c) if ctrl => {
+                let same = match c {
+                    'i' => "Tab",
+                    'm' => "Enter",
+                    'h' => "Backspace",
+                    '[' => "Esc",
+                    c if c.is_ascii_digit() => return Some(s.key_ctrl_digit.to_string()),
+                    _ => return None,
+                };
+                Some(locale::fill(s.key_sent_as, &[same]))
+            }
+            KeyCode::Tab | KeyCode::Enter | KeyCode::Backspace if ctrl || (shift && self.code != KeyCode::Tab) => {
+                Some(s.key_modifier_dropped.to_string())
+            }
+            KeyCode::F(1..=4) if shift => Some(s.key_looks_like_another.to_string()),
+            _ => None,
+        }
+    }
+}
+
+// The bindings from the config, looked up before the built-in keys of
+// navigate mode
+#[derive(Debug, Default)]
+pub struct Keymap {
+    bindings: Vec<(Key, &'static str)>,
+    leader: Option<Key>,
+    sequences: Vec<(Vec<Key>, &'static str)>, // Typed after the leader
+    groups: Vec<(&'static str, &'static str)>, // The popup's "+file" and the like, by first key
+    more: &'static str, // The popup's label for other keys that start sequences
+}
+
+// Where the keys typed after the leader have got to
+pub enum Sequence {
+    Complete(Action),
+    Partial, // More keys to come
+    Unbound, // Nothing starts this way
+}
+
+// A leader sequence being typed: the keys so far, and what each key that
+// can come next does
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pending {
+    pub typed: String,
+    pub next: Vec<(String, String)>,
+}
+
+impl Keymap {
+    // The keymap `keys` describes, and a warning in the words of `s` for each
+    // binding that was left out or that the terminal might not deliver
+    pub fn new(keys: &Keys, s: &'static Strings) -> (Self, Vec<String>) {
+        let alternatives: &[(&str, &str)] = match keys.alternatives {
+            Alternatives::None => &[],
+            Alternatives::FunctionKeys => FUNCTION_KEYS,
+        };
+        let mut keymap = Self { groups: leader_groups(s), more: s.leader_more, ..Self::default() };
+        let mut warnings = Vec::new();
+        // Bindings in the config come after the alternatives, so they win
+        let configured = keys.bind.iter().map(|(key, name)| (key.as_str(), name.as_str()));
+        for (text, name) in alternatives.iter().copied().chain(configured) {
+            let Some(name) = ACTION_NAMES.iter().copied().find(|known| *known == name.trim()) else {
+                warnings.push(locale::fill(s.key_no_action, &[text, name]));
+                continue;
+            };
+            match Key::parse(text) {
+                Ok(key) => {
+                    if let Some(reason) = key.ambiguity(s) {
+                        warnings.push(locale::fill(s.key_may_not_work, &[text, name, &reason]));
+                    }
+                    keymap.bindings.retain(|(bound, _)| *bound != key);
+                    keymap.bindings.push((key, name));
+                }
+                Err(e) => warnings.push(locale::fill(s.key_invalid, &[text, &e.to_string()])),
+            }
+        }
+        if let Some(text) = &keys.leader {
+            keymap.leader_sequences(text, keys, s, &mut warnings);
+        }
+        (keymap, warnings)
+    }
+
+    fn leader_sequences(&mut self, leader: &str, keys: &Keys, s: &Strings, warnings: &mut Vec<String>) {
+        match Key::parse(leader) {
+            Ok(key) => {
+                if let Some(reason) = key.ambiguity(s) {
+                    warnings.push(locale::fill(s.leader_may_not_work, &[leader, &reason]));
+                }
+                self.leader = Some(key);
+            }
+            Err(e) => {
+                warnings.push(locale::fill(s.leader_invalid, &[leader, &e.to_string()]));
+                return;
+            }
+        }
+        let configured = keys.sequences.iter().map(|(keys, name)| (keys.as_str(), name.as_str()));
+        for (text, name) in LEADER_SEQUENCES.iter().copied().chain(configured) {
+            let Some(name) = ACTION_NAMES.iter().copied().find(|known| *known == name.trim()) else {
+                warnings.push(locale::fill(s.leader_no_action, &[text, name]));
+                continue;
+            };
+            match text.split_whitespace().map(Key::parse).collect::<Result<Vec<_>>>() {
+                Ok(steps) if steps.is_empty() => warnings.push(locale::fill(s.leader_no_keys, &[name])),
+                Ok(steps) => {
+                    self.sequences.retain(|(bound, _)| *bound != steps);
+                    self.sequences.push((steps, name));
+                }
+                Err(e) => warnings.push(locale::fill(s.leader_invalid_sequence, &[text, &e.to_string()])),
+            }
+        }
+        // A sequence that starts with another one is never reached
+        for (steps, name) in &self.sequences {
+            if let Some((_, shorter)) = self.sequences.iter().find(|(other, _)| other.len() < steps.len() && steps.starts_with(other)) {
+                warnings.push(locale::fill(s.leader_unreachable, &[name, shorter]));
+            }
+        }
+    }
+
+    pub fn action(&self, event: &KeyEvent) -> Option<Action> {
+        let key = Key::from_event(event);
+        self.bindings.iter().find(|(bound, _)| *bound == key).and_then(|(_, name)| action(name))
+    }
+
+    pub fn is_leader(&self, event: &KeyEvent) -> bool {
+        self.leader == Some(Key::from_event(event))
+    }
+
+    pub fn sequence(&self, typed: &[Key]) -> Sequence {
+        if let Some((_, name)) = self.sequences.iter().find(|(steps, _)| steps.as_slice() == typed) {
+            return action(name).map_or(Sequence::Unbound, Sequence::Complete);
+        }
+        if self.sequences.iter().any(|(steps, _)| steps.starts_with(typed)) {
+            Sequence::Partial
+        } else {
+            Sequence::Unbound
+        }
+    }
+
+    // The leader and `typed` as they'd be written, like "Space f"
+    pub fn typed_label(&self, typed: &[Key]) -> String {
+        self.leader.iter().chain(typed).map(Key::label).collect::<Vec<_>>().join(" ")
+    }
+
+    // What can follow `typed`: each next key with the action it runs, or
+    // "+group" for a key that starts more sequences
+    pub fn pending(&self, typed: &[Key]) -> Pending {
+        let mut next: Vec<(String, String)> = Vec::new();
+        for (steps, name) in self.sequences.iter().filter(|(steps, _)| steps.len() > typed.len() && steps.starts_with(typed)) {
+            let key = steps[typed.len()].label();
+            if next.iter().any(|(bound, _)| *bound == key) {
+                continue;
+            }
+            let what = if steps.len() == typed.len() + 1 {
+                name.replace('_', " ")
+            } else {
+                let group = self.groups.iter().find(|(first, _)| typed.is_empty() && *first == key);
+                group.map_or(self.more, |(_, group)| group).to_string()
+            };
+            next.push((key, what));
+        }
+        next.sort();
+        Pending { typed: self.typed_label(typed), next }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::locale::{ENGLISH, GERMAN};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_parse_keys() {
+        assert_eq!(Key::parse("F12").unwrap(), Key::new(KeyCode::F(12), KeyModifiers::NONE));
+        assert_eq!(Key::parse("ctrl+alt+S").unwrap(), Key::new(KeyCode::Char('s'), KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT));
+        assert_eq!(Key::parse("Alt++").unwrap(), Key::new(KeyCode::Char('+'), KeyModifiers::ALT));
+        assert_eq!(Key::parse("Shift+Tab").unwrap(), Key::new(KeyCode::BackTab, KeyModifiers::NONE));
+        assert!(Key::parse("Hyper+x").is_err());
+        assert!(Key::parse("F99").is_err());
+        assert_eq!(Key::parse("Ctrl+Shift+s").unwrap().label(), "Ctrl+S");
+        assert_eq!(Key::parse("space").unwrap().label(), "Space");
+    }
+
+    #[test]
+    fn test_ambiguous_keys() {
+        assert!(Key::parse("Ctrl+Shift+S").unwrap().ambiguity(&ENGLISH).is_some());
+        assert!(Key::parse("Ctrl+i").unwrap().ambiguity(&ENGLISH).is_some());
+        assert!(Key::parse("Shift+F3").unwrap().ambiguity(&ENGLISH).is_some());
+        assert!(Key::parse("Ctrl+Alt+s").unwrap().ambiguity(&ENGLISH).is_none());
+        assert!(Key::parse("Shift+Tab").unwrap().ambiguity(&ENGLISH).is_none());
+    }
+
+    #[test]
+    fn test_keymap() {
+        let keys = Keys {
+            alternatives: Alternatives::FunctionKeys,
+            bind: BTreeMap::from([
+                ("F5".to_string(), "copy_view".to_string()),
+                ("Ctrl+Shift+S".to_string(), "save_as".to_string()),
+                ("F2".to_string(), "fly".to_string()),
+            ]),
+            ..Keys::default()
+        };
+        let (keymap, warnings) = Keymap::new(&keys, &ENGLISH);
+        assert_eq!(warnings.len(), 2);
+        let press = |code, modifiers| keymap.action(&KeyEvent::new(code, modifiers));
+        assert!(matches!(press(KeyCode::F(6), KeyModifiers::NONE), Some(Action::SaveAs)));
+        assert!(matches!(press(KeyCode::F(5), KeyModifiers::NONE), Some(Action::CopyView)));
+        assert!(matches!(press(KeyCode::Char('S'), KeyModifiers::CONTROL | KeyModifiers::SHIFT), Some(Action::SaveAs)));
+        assert!(press(KeyCode::F(2), KeyModifiers::NONE).is_none());
+
+        let (_, warnings) = Keymap::new(&keys, &GERMAN);
+        assert!(warnings.contains(&"Taste F2: keine Aktion namens \"fly\"".to_string()), "{:?}", warnings);
+    }
+
+    #[test]
+    fn test_leader_sequences() {
+        let keys = Keys {
+            leader: Some("Space".to_string()),
+            sequences: BTreeMap::from([("f s".to_string(), "copy_view".to_string()), ("g g".to_string(), "jump_up".to_string())]),
+            ..Keys::default()
+        };
+        let (keymap, warnings) = Keymap::new(&keys, &ENGLISH);
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        assert!(keymap.is_leader(&KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE)));
+        let key = |c| Key::new(KeyCode::Char(c), KeyModifiers::NONE);
+        assert!(matches!(keymap.sequence(&[key('f')]), Sequence::Partial));
+        assert!(matches!(keymap.sequence(&[key('f'), key('s')]), Sequence::Complete(Action::CopyView)));
+        assert!(matches!(keymap.sequence(&[key('g'), key('g')]), Sequence::Complete(Action::JumpUp)));
+        assert!(matches!(keymap.sequence(&[key('z')]), Sequence::Unbound));
+
+        let pending = keymap.pending(&[]);
+        assert_eq!(pending.typed, "Space");
+        assert!(pending.next.contains(&("f".to_string(), "+file".to_string())));
+        assert!(pending.next.contains(&("g".to_string(), "+more".to_string())));
+        let pending = keymap.pending(&[key('f')]);
+        assert_eq!(pending.typed, "Space f");
+        assert!(pending.next.contains(&("s".to_string(), "copy view".to_string())));
+        let (keymap, _) = Keymap::new(&keys, &GERMAN);
+        assert!(keymap.pending(&[]).next.contains(&("f".to_string(), "+Datei".to_string())));
+
+        // Without a leader there are no sequences
+        let (keymap, _) = Keymap::new(&Keys::default(), &ENGLISH);
+        assert!(matches!(keymap.sequence(&[key('f'), key('s')]), Sequence::Unbound));
+        let keys = Keys { leader: Some("Space".to_string()), sequences: BTreeMap::from([("f".to_string(), "save".to_string())]), ..Keys::default() };
+        let warnings = Keymap::new(&keys, &GERMAN).1;
+        assert!(warnings.contains(&"Leader-Folge für save_as ist nicht tippbar: save kommt zuerst".to_string()), "{:?}", warnings);
+    }
+
+    #[test]
+    fn test_every_action_name_binds() {
+        for name in ACTION_NAMES {
+            assert!(action(name).is_some(), "{}", name);
+        }
+    }
+}
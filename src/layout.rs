@@ -0,0 +1,351 @@
+use crate::models::Breadboard;
+use std::collections::{HashMap, HashSet};
+
+// Layered (Sugiyama-style) layout of the place graph. Places are assigned to
+// columns ("layers") so that connections flow left to right, with the entry
+// place in the leftmost layer, then each layer is reordered to reduce edge
+// crossings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphLayout {
+    pub layers: Vec<Vec<u32>>, // Place IDs per layer, top to bottom
+    pub lanes: Vec<Lane>,      // Horizontal bands by group; empty when not split into lanes
+}
+
+// One swimlane: the places of a group, or of no group when `group` is None
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lane {
+    pub group: Option<String>,
+    pub places: Vec<u32>,
+}
+
+const ORDERING_SWEEPS: usize = 8;
+
+impl GraphLayout {
+    #[cfg(test)]
+    pub fn layer_of(&self, place_id: u32) -> Option<usize> {
+        self.layers.iter().position(|layer| layer.contains(&place_id))
+    }
+
+    pub fn lane_of(&self, place_id: u32) -> Option<usize> {
+        self.lanes.iter().position(|lane| lane.places.contains(&place_id))
+    }
+
+    // Number of crossings between edges that connect adjacent layers
+    pub fn count_crossings(&self, edges: &[(u32, u32)]) -> usize {
+        let positions = self.positions();
+        let mut crossings = 0;
+
+        for layer in 0..self.layers.len().saturating_sub(1) {
+            let between: Vec<(usize, usize)> = edges.iter()
+                .filter_map(|(from, to)| {
+                    let (from_layer, from_pos) = positions.get(from)?;
+                    let (to_layer, to_pos) = positions.get(to)?;
+                    if *from_layer == layer && *to_layer == layer + 1 {
+                        Some((*from_pos, *to_pos))
+                    } else {
+                        None
+                    }
+                })
+                .collect();
+
+            for (i, a) in between.iter().enumerate() {
+                for b in &between[i + 1..] {
+                    if (a.0 < b.0 && a.1 > b.1) || (a.0 > b.0 && a.1 < b.1) {
+                        crossings += 1;
+                    }
+                }
+            }
+        }
+
+        crossings
+    }
+
+    fn positions(&self) -> HashMap<u32, (usize, usize)> {
+        self.layers.iter()
+            .enumerate()
+            .flat_map(|(layer, ids)| ids.iter().enumerate().map(move |(pos, id)| (*id, (layer, pos))))
+            .collect()
+    }
+}
+
+// Unique place-to-place edges, ignoring self-links and dangling connections
+pub fn place_edges(breadboard: &Breadboard) -> Vec<(u32, u32)> {
+    let known: HashSet<u32> = breadboard.places.iter().map(|p| p.id).collect();
+    let mut seen = HashSet::new();
+    let mut edges = Vec::new();
+
+    for place in &breadboard.places {
+        for affordance in &place.affordances {
+            for dest_id in affordance.destinations() {
+                if dest_id != place.id && known.contains(&dest_id) && seen.insert((place.id, dest_id)) {
+                    edges.push((place.id, dest_id));
+                }
+            }
+        }
+    }
+
+    edges
+}
+
+pub fn layered_layout(breadboard: &Breadboard) -> GraphLayout {
+    let edges = place_edges(breadboard);
+    let (order, dag_edges) = break_cycles(breadboard, &edges);
+
+    // Longest-path layering: every place sits one layer right of its furthest
+    // predecessor
+    let mut layer_of: HashMap<u32, usize> = HashMap::new();
+    for id in topological_order(&order, &dag_edges) {
+        let layer = dag_edges.iter()
+            .filter(|(_, to)| *to == id)
+            .filter_map(|(from, _)| layer_of.get(from).map(|l| l + 1))
+            .max()
+            .unwrap_or(0);
+        layer_of.insert(id, layer);
+    }
+
+    let layer_count = layer_of.values().max().map(|m| m + 1).unwrap_or(0);
+    let mut layers: Vec<Vec<u32>> = vec![Vec::new(); layer_count];
+    for id in &order {
+        layers[layer_of[id]].push(*id);
+    }
+
+    let mut layout = GraphLayout { layers, lanes: Vec::new() };
+    reduce_crossings(&mut layout, &dag_edges);
+    layout
+}
+
+// The layered layout split into one lane per group, in the order groups
+// first appear on the board, with ungrouped places in a last lane. Layers
+// keep their columns; within a layer, places are sorted by lane and keep
+// their crossing-reduced order inside it.
+pub fn swimlane_layout(breadboard: &Breadboard) -> GraphLayout {
+    let mut layout = layered_layout(breadboard);
+
+    let mut lanes: Vec<Lane> = Vec::new();
+    for place in &breadboard.places {
+        match lanes.iter_mut().find(|lane| lane.group == place.group) {
+            Some(lane) => lane.places.push(place.id),
+            None => lanes.push(Lane { group: place.group.clone(), places: vec![place.id] }),
+        }
+    }
+    if let Some(ungrouped) = lanes.iter().position(|lane| lane.group.is_none()) {
+        let lane = lanes.remove(ungrouped);
+        lanes.push(lane);
+    }
+
+    layout.lanes = lanes;
+    for layer in 0..layout.layers.len() {
+        let mut ids = layout.layers[layer].clone();
+        ids.sort_by_key(|id| layout.lane_of(*id));
+        layout.layers[layer] = ids;
+    }
+    layout
+}
+
+// Walk places depth-first from the entry place, then from any place not yet
+// reached, in board order. Edges leading back to a place still on the DFS
+// path close a cycle and are left out, so the entry place never has incoming
+// edges. Returns the visit order (used to seed in-layer ordering) and the
+// remaining acyclic edges.
+fn break_cycles(breadboard: &Breadboard, edges: &[(u32, u32)]) -> (Vec<u32>, Vec<(u32, u32)>) {
+    let mut visited = HashSet::new();
+    let mut on_path = HashSet::new();
+    let mut back_edges = HashSet::new();
+    let mut order = Vec::new();
+
+    for root in breadboard.places.iter().map(|p| p.id) {
+        if visited.contains(&root) {
+            continue;
+        }
+        visited.insert(root);
+        on_path.insert(root);
+        order.push(root);
+        let mut stack = vec![(root, 0usize)];
+
+        while let Some((id, next)) = stack.last_mut() {
+            let id = *id;
+            let outgoing: Vec<u32> = edges.iter().filter(|(from, _)| *from == id).map(|(_, to)| *to).collect();
+            if *next >= outgoing.len() {
+                on_path.remove(&id);
+                stack.pop();
+                continue;
+            }
+            let to = outgoing[*next];
+            *next += 1;
+
+            if on_path.contains(&to) {
+                back_edges.insert((id, to));
+            } else if visited.insert(to) {
+                on_path.insert(to);
+                order.push(to);
+                stack.push((to, 0));
+            }
+        }
+    }
+
+    let dag_edges = edges.iter().filter(|e| !back_edges.contains(e)).copied().collect();
+    (order, dag_edges)
+}
+
+// Kahn's algorithm, breaking ties by visit order
+fn topological_order(order: &[u32], dag_edges: &[(u32, u32)]) -> Vec<u32> {
+    let mut in_degree: HashMap<u32, usize> = order.iter().map(|id| (*id, 0)).collect();
+    for (_, to) in dag_edges {
+        *in_degree.entry(*to).or_default() += 1;
+    }
+
+    let mut result = Vec::with_capacity(order.len());
+    let mut placed = HashSet::new();
+    while result.len() < order.len() {
+        let Some(next) = order.iter().find(|id| !placed.contains(*id) && in_degree[*id] == 0).copied() else {
+            break;
+        };
+        placed.insert(next);
+        result.push(next);
+        for (_, to) in dag_edges.iter().filter(|(from, _)| *from == next) {
+            if let Some(degree) = in_degree.get_mut(to) {
+                *degree -= 1;
+            }
+        }
+    }
+
+    result
+}
+
+// Barycenter heuristic: repeatedly sort each layer by the average position of
+// its neighbours in the adjacent layer, sweeping down and then up. The entry
+// place stays on top of the first layer.
+fn reduce_crossings(layout: &mut GraphLayout, edges: &[(u32, u32)]) {
+    let mut best = layout.layers.clone();
+    let mut best_crossings = layout.count_crossings(edges);
+
+    for sweep in 0..ORDERING_SWEEPS {
+        let downward = sweep % 2 == 0;
+        let layer_indices: Vec<usize> = if downward {
+            (1..layout.layers.len()).collect()
+        } else {
+            (0..layout.layers.len().saturating_sub(1)).rev().collect()
+        };
+
+        for layer in layer_indices {
+            let neighbour_layer = if downward { layer - 1 } else { layer + 1 };
+            let neighbour_pos: HashMap<u32, usize> = layout.layers[neighbour_layer].iter()
+                .enumerate()
+                .map(|(i, id)| (*id, i))
+                .collect();
+
+            let current = layout.layers[layer].clone();
+            let mut keyed: Vec<(f64, usize, u32)> = current.iter()
+                .enumerate()
+                .map(|(i, id)| {
+                    let neighbours: Vec<usize> = edges.iter()
+                        .filter_map(|(from, to)| {
+                            if downward && to == id {
+                                neighbour_pos.get(from).copied()
+                            } else if !downward && from == id {
+                                neighbour_pos.get(to).copied()
+                            } else {
+                                None
+                            }
+                        })
+                        .collect();
+                    let barycenter = if neighbours.is_empty() {
+                        i as f64
+                    } else {
+                        neighbours.iter().sum::<usize>() as f64 / neighbours.len() as f64
+                    };
+                    (barycenter, i, *id)
+                })
+                .collect();
+
+            keyed.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+            let mut reordered: Vec<u32> = keyed.into_iter().map(|(_, _, id)| id).collect();
+
+            if layer == 0 {
+                if let Some(entry) = best[0].first().copied() {
+                    reordered.retain(|id| *id != entry);
+                    reordered.insert(0, entry);
+                }
+            }
+            layout.layers[layer] = reordered;
+        }
+
+        let crossings = layout.count_crossings(edges);
+        if crossings < best_crossings {
+            best_crossings = crossings;
+            best = layout.layers.clone();
+        }
+    }
+
+    layout.layers = best;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::testing::board;
+    use crate::models::Affordance;
+
+    #[test]
+    fn test_chain_layers_left_to_right() {
+        let breadboard = board(&["A", "B", "C"], &[(0, 1), (1, 2)]);
+        let layout = layered_layout(&breadboard);
+        assert_eq!(layout.layers, vec![vec![1], vec![2], vec![3]]);
+    }
+
+    #[test]
+    fn test_cycle_keeps_entry_leftmost() {
+        // Invoice → Setup → Confirm → Invoice
+        let breadboard = board(&["Invoice", "Setup", "Confirm"], &[(0, 1), (1, 2), (2, 0)]);
+        let layout = layered_layout(&breadboard);
+        assert_eq!(layout.layer_of(1), Some(0));
+        assert_eq!(layout.layer_of(2), Some(1));
+        assert_eq!(layout.layer_of(3), Some(2));
+    }
+
+    #[test]
+    fn test_long_path_pushes_place_right() {
+        // A → B → C and A → C: C belongs after B, not next to it
+        let breadboard = board(&["A", "B", "C"], &[(0, 2), (0, 1), (1, 2)]);
+        let layout = layered_layout(&breadboard);
+        assert_eq!(layout.layer_of(3), Some(2));
+    }
+
+    #[test]
+    fn test_ordering_removes_crossings() {
+        let edges = vec![(1, 4), (2, 3)];
+        let mut layout = GraphLayout { layers: vec![vec![1, 2], vec![3, 4]], lanes: Vec::new() };
+        assert_eq!(layout.count_crossings(&edges), 1);
+
+        reduce_crossings(&mut layout, &edges);
+        assert_eq!(layout.count_crossings(&edges), 0);
+        assert_eq!(layout.layers[0][0], 1); // Entry stays on top
+    }
+
+    #[test]
+    fn test_swimlanes_follow_group_order() {
+        // Checkout (customer) → Fraud check (system) → Review (admin) → Receipt (customer)
+        let mut breadboard = board(&["Checkout", "Fraud check", "Review", "Receipt", "Help"], &[(0, 1), (1, 2), (2, 3)]);
+        for (index, group) in ["customer", "system", "admin", "customer"].iter().enumerate() {
+            breadboard.places[index].group = Some(group.to_string());
+        }
+
+        let layout = swimlane_layout(&breadboard);
+        let groups: Vec<Option<&str>> = layout.lanes.iter().map(|lane| lane.group.as_deref()).collect();
+        assert_eq!(groups, vec![Some("customer"), Some("system"), Some("admin"), None]);
+        assert_eq!(layout.lanes[0].places, vec![1, 4]);
+        assert_eq!(layout.lane_of(5), Some(3));
+
+        // Columns are the same as without lanes; Help sorts below Checkout
+        assert_eq!(layout.layer_of(4), Some(3));
+        assert_eq!(layout.layers[0], vec![1, 5]);
+    }
+
+    #[test]
+    fn test_place_edges_skip_self_and_dangling() {
+        let mut breadboard = board(&["A", "B"], &[(0, 0), (0, 1), (0, 1)]);
+        let id = breadboard.generate_affordance_id();
+        breadboard.places[1].add_affordance(Affordance::new(id, "gone".to_string()).with_connection(99));
+        assert_eq!(place_edges(&breadboard), vec![(1, 2)]);
+    }
+}
@@ -0,0 +1,407 @@
+use crate::models::{Breadboard, Rule};
+use crate::naming;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+// Board-health checks: problems in a breadboard's structure that are easy
+// to miss while editing, such as connections to deleted places or places
+// nobody can navigate to.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueKind {
+    DanglingConnection, // Affordance connects to a place that doesn't exist
+    OrphanPlace,        // Place with no connections in or out
+    DuplicateName,      // Two places, or two affordances of one place, share a name
+    UnreachablePlace,   // Place that can't be reached from the entry place
+    BrokenHappyPath,    // A step of the happy path no longer leads to the next
+    RuleBroken,         // Goes against one of the board's own rules
+    Naming,             // Named against the board's naming convention
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Issue {
+    pub kind: IssueKind,
+    pub place_id: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub affordance_id: Option<u32>,
+    pub message: String,
+}
+
+pub fn lint(breadboard: &Breadboard) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    let known: HashSet<u32> = breadboard.places.iter().map(|p| p.id).collect();
+
+    for place in &breadboard.places {
+        for affordance in &place.affordances {
+            if let Some(dest_id) = affordance.connects_to.filter(|d| !known.contains(d)) {
+                issues.push(Issue {
+                    kind: IssueKind::DanglingConnection,
+                    place_id: place.id,
+                    affordance_id: Some(affordance.id),
+                    message: format!("\"{}\" in \"{}\" connects to missing place {}", affordance.name, place.name, dest_id),
+                });
+            }
+            for branch in &affordance.branches {
+                if let Some(dest_id) = branch.connects_to.filter(|d| !known.contains(d)) {
+                    issues.push(Issue {
+                        kind: IssueKind::DanglingConnection,
+                        place_id: place.id,
+                        affordance_id: Some(affordance.id),
+                        message: format!(
+                            "Outcome \"{}\" of \"{}\" in \"{}\" connects to missing place {}",
+                            branch.label, affordance.name, place.name, dest_id,
+                        ),
+                    });
+                }
+            }
+        }
+    }
+
+    let mut linked = HashSet::new();
+    for place in &breadboard.places {
+        for dest_id in place.affordances.iter().flat_map(|a| a.destinations()).filter(|d| known.contains(d) && *d != place.id) {
+            linked.insert(place.id);
+            linked.insert(dest_id);
+        }
+    }
+    // External systems often sit at the edge of a flow, so they are never
+    // orphaned or unreachable
+    let orphans: HashSet<u32> = if breadboard.places.len() > 1 {
+        breadboard.places.iter()
+            .filter(|p| !p.is_external() && !linked.contains(&p.id))
+            .map(|p| p.id)
+            .collect()
+    } else {
+        HashSet::new()
+    };
+    for place in breadboard.places.iter().filter(|p| orphans.contains(&p.id)) {
+        issues.push(Issue {
+            kind: IssueKind::OrphanPlace,
+            place_id: place.id,
+            affordance_id: None,
+            message: format!("\"{}\" has no connections", place.name),
+        });
+    }
+
+    let mut first_with_name: HashMap<String, &str> = HashMap::new();
+    for place in &breadboard.places {
+        if let Some(first) = first_with_name.get(&name_key(&place.name)) {
+            issues.push(Issue {
+                kind: IssueKind::DuplicateName,
+                place_id: place.id,
+                affordance_id: None,
+                message: format!("Place \"{}\" has the same name as \"{}\"", place.name, first),
+            });
+        } else {
+            first_with_name.insert(name_key(&place.name), &place.name);
+        }
+
+        let mut seen = HashSet::new();
+        for affordance in &place.affordances {
+            if !seen.insert(name_key(&affordance.name)) {
+                issues.push(Issue {
+                    kind: IssueKind::DuplicateName,
+                    place_id: place.id,
+                    affordance_id: Some(affordance.id),
+                    message: format!("\"{}\" appears more than once in \"{}\"", affordance.name, place.name),
+                });
+            }
+        }
+    }
+
+    // Orphans are already reported, so only flag connected places that the
+    // entry place has no path to
+    let reachable = reachable_from_entry(breadboard);
+    for place in &breadboard.places {
+        if !reachable.contains(&place.id) && !orphans.contains(&place.id) && !place.is_external() {
+            issues.push(Issue {
+                kind: IssueKind::UnreachablePlace,
+                place_id: place.id,
+                affordance_id: None,
+                message: format!("\"{}\" can't be reached from \"{}\"", place.name, breadboard.places[0].name),
+            });
+        }
+    }
+
+    let name = |id: u32| breadboard.find_place(&id)
+        .map_or(format!("deleted place {}", id), |place| format!("\"{}\"", place.name));
+    for (from, to) in breadboard.happy_path_breaks() {
+        issues.push(Issue {
+            kind: IssueKind::BrokenHappyPath,
+            place_id: from,
+            affordance_id: None,
+            message: format!("Happy path: {} no longer leads to {}", name(from), name(to)),
+        });
+    }
+
+    issues.extend(rule_issues(breadboard));
+    issues
+}
+
+// Where the board goes against its own rules: connections from one group
+// into a group they may not lead to, places with no way back to where every
+// place has to lead back to, and names that don't follow its naming
+// convention. External systems don't need a way back.
+pub fn rule_issues(breadboard: &Breadboard) -> Vec<Issue> {
+    let mut issues = naming_issues(breadboard);
+    let group_is = |id: u32, group: &str| breadboard.find_place(&id)
+        .and_then(|p| p.group.as_deref())
+        .is_some_and(|g| name_key(g) == name_key(group));
+
+    for rule in &breadboard.rules {
+        match rule {
+            Rule::NoConnection { from, to } => {
+                for place in breadboard.places.iter().filter(|p| group_is(p.id, from)) {
+                    for affordance in &place.affordances {
+                        let Some(dest) = affordance.destinations().into_iter().find(|d| group_is(*d, to)) else {
+                            continue;
+                        };
+                        let dest_name = breadboard.find_place(&dest).map_or("", |p| p.name.as_str());
+                        issues.push(Issue {
+                            kind: IssueKind::RuleBroken,
+                            place_id: place.id,
+                            affordance_id: Some(affordance.id),
+                            message: format!(
+                                "Rule: \"{}\" in \"{}\" leads to \"{}\", but {} places may not connect to {} places",
+                                affordance.name, place.name, dest_name, from, to,
+                            ),
+                        });
+                    }
+                }
+            }
+            Rule::WayBack { to } => {
+                let targets: Vec<u32> = breadboard.places.iter()
+                    .filter(|p| name_key(&p.name) == name_key(to))
+                    .map(|p| p.id)
+                    .collect();
+                if targets.is_empty() {
+                    if let Some(first) = breadboard.places.first() {
+                        issues.push(Issue {
+                            kind: IssueKind::RuleBroken,
+                            place_id: first.id,
+                            affordance_id: None,
+                            message: format!("Rule: every place needs a way back to \"{}\", but there's no place called that", to),
+                        });
+                    }
+                    continue;
+                }
+                let leads_back = leading_to(breadboard, &targets);
+                for place in breadboard.places.iter().filter(|p| !leads_back.contains(&p.id) && !p.is_external()) {
+                    issues.push(Issue {
+                        kind: IssueKind::RuleBroken,
+                        place_id: place.id,
+                        affordance_id: None,
+                        message: format!("Rule: \"{}\" has no way back to \"{}\"", place.name, to),
+                    });
+                }
+            }
+        }
+    }
+    issues
+}
+
+// Names in the wrong case, and affordances that don't start with a verb
+fn naming_issues(breadboard: &Breadboard) -> Vec<Issue> {
+    let Some(naming) = &breadboard.naming else {
+        return Vec::new();
+    };
+    let mut issues = Vec::new();
+    let wrong_case = |name: &str, place: bool| {
+        let case = if place { naming.places } else { naming.affordances }?;
+        naming::suggestion(naming, name, place, &breadboard.glossary)
+            .map(|fixed| format!("Naming: \"{}\" in {} is \"{}\"", name, case.label(), fixed))
+    };
+    for place in &breadboard.places {
+        if let Some(message) = wrong_case(&place.name, true) {
+            issues.push(Issue { kind: IssueKind::Naming, place_id: place.id, affordance_id: None, message });
+        }
+        for affordance in &place.affordances {
+            let mut messages: Vec<String> = wrong_case(&affordance.name, false).into_iter().collect();
+            if naming.verb_first && !naming::starts_with_verb(naming, &affordance.name) {
+                messages.push(format!("Naming: \"{}\" in \"{}\" doesn't start with a verb", affordance.name, place.name));
+            }
+            issues.extend(messages.into_iter().map(|message| Issue {
+                kind: IssueKind::Naming,
+                place_id: place.id,
+                affordance_id: Some(affordance.id),
+                message,
+            }));
+        }
+    }
+    issues
+}
+
+// Places with a path to any of `targets`, the targets included
+fn leading_to(breadboard: &Breadboard, targets: &[u32]) -> HashSet<u32> {
+    let mut sources: HashMap<u32, Vec<u32>> = HashMap::new();
+    for place in &breadboard.places {
+        for dest in place.affordances.iter().flat_map(|a| a.destinations()) {
+            sources.entry(dest).or_default().push(place.id);
+        }
+    }
+    let mut found = HashSet::new();
+    let mut stack = targets.to_vec();
+    while let Some(id) = stack.pop() {
+        if found.insert(id) {
+            stack.extend(sources.get(&id).into_iter().flatten());
+        }
+    }
+    found
+}
+
+// Names compare without case or surrounding whitespace
+fn name_key(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+// Places whose name another place also has, each with its 1-based position
+// among the places sharing that name
+pub fn duplicate_places(breadboard: &Breadboard) -> HashMap<u32, usize> {
+    let mut by_name: HashMap<String, Vec<u32>> = HashMap::new();
+    for place in &breadboard.places {
+        by_name.entry(name_key(&place.name)).or_default().push(place.id);
+    }
+
+    by_name.into_values()
+        .filter(|ids| ids.len() > 1)
+        .flat_map(|ids| ids.into_iter().enumerate().map(|(index, id)| (id, index + 1)))
+        .collect()
+}
+
+// Places reachable by following connections from the first (entry) place
+fn reachable_from_entry(breadboard: &Breadboard) -> HashSet<u32> {
+    breadboard.places.first()
+        .map(|entry| breadboard.reachable_from(entry.id))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::testing::board;
+    use crate::models::{Affordance, Branch, Case, Naming, PlaceKind};
+
+    fn kinds(issues: &[Issue]) -> Vec<(IssueKind, u32)> {
+        issues.iter().map(|i| (i.kind, i.place_id)).collect()
+    }
+
+    #[test]
+    fn test_healthy_board_has_no_issues() {
+        let breadboard = board(&["Invoice", "Setup", "Confirm"], &[(0, 1), (1, 2), (2, 0)]);
+        assert!(lint(&breadboard).is_empty());
+        assert!(lint(&board(&["Only"], &[])).is_empty());
+    }
+
+    #[test]
+    fn test_broken_happy_path() {
+        let mut breadboard = board(&["Invoice", "Setup", "Done"], &[(0, 1), (1, 2)]);
+        breadboard.happy_path = vec![1, 2, 3];
+        assert!(lint(&breadboard).is_empty());
+
+        breadboard.places[1].affordances.clear();
+        let issues = lint(&breadboard);
+        assert_eq!(kinds(&issues), vec![(IssueKind::OrphanPlace, 3), (IssueKind::BrokenHappyPath, 2)]);
+        assert_eq!(issues[1].message, "Happy path: \"Setup\" no longer leads to \"Done\"");
+    }
+
+    #[test]
+    fn test_dangling_and_orphan() {
+        let mut breadboard = board(&["Invoice", "Setup", "Lonely"], &[(0, 1)]);
+        breadboard.places[1].add_affordance(Affordance::new(9, "Gone".to_string()).with_connection(42));
+
+        let issues = lint(&breadboard);
+        assert_eq!(kinds(&issues), vec![(IssueKind::DanglingConnection, 2), (IssueKind::OrphanPlace, 3)]);
+        assert_eq!(issues[0].affordance_id, Some(9));
+
+        // An outcome can dangle too, and one that leads somewhere links its places
+        breadboard.places[1].affordances[0].branches = vec![
+            Branch { label: "failure".to_string(), connects_to: Some(43) },
+            Branch { label: "success".to_string(), connects_to: Some(3) },
+        ];
+        let issues = lint(&breadboard);
+        assert_eq!(kinds(&issues), vec![(IssueKind::DanglingConnection, 2), (IssueKind::DanglingConnection, 2)]);
+        assert!(issues[1].message.starts_with("Outcome \"failure\""));
+    }
+
+    #[test]
+    fn test_unreachable_places_are_connected_but_cut_off() {
+        // C ⇄ D form their own island
+        let breadboard = board(&["A", "B", "C", "D"], &[(0, 1), (2, 3), (3, 2)]);
+        assert_eq!(kinds(&lint(&breadboard)), vec![(IssueKind::UnreachablePlace, 3), (IssueKind::UnreachablePlace, 4)]);
+    }
+
+    #[test]
+    fn test_external_places_are_exempt_from_reachability() {
+        // Stripe is never linked, and calls back into a page nothing else reaches
+        let mut breadboard = board(&["Cart", "Pay", "Stripe", "Webhook"], &[(0, 1), (2, 3)]);
+        breadboard.places[2].kind = Some(PlaceKind::External);
+        assert_eq!(kinds(&lint(&breadboard)), vec![(IssueKind::UnreachablePlace, 4)]);
+    }
+
+    #[test]
+    fn test_rules() {
+        // Home → Settings → Admin, Welcome email → Admin, Admin → Home
+        let mut breadboard = board(&["Home", "Settings", "Welcome email", "Admin"], &[(0, 1), (1, 3), (2, 3), (3, 0)]);
+        breadboard.places[2].group = Some("Email".to_string());
+        breadboard.places[3].group = Some("admin".to_string());
+        breadboard.rules = vec![
+            Rule::NoConnection { from: "email".to_string(), to: "admin".to_string() },
+            Rule::WayBack { to: "home".to_string() },
+        ];
+        // The email is unreachable; only the rules are looked at here
+        let issues = rule_issues(&breadboard);
+        assert_eq!(kinds(&issues), vec![(IssueKind::RuleBroken, 3)]);
+        assert_eq!(issues[0].affordance_id, Some(3));
+        assert_eq!(issues[0].message, "Rule: \"to 3\" in \"Welcome email\" leads to \"Admin\", but email places may not connect to admin places");
+
+        // Settings loses its way on, so back
+        breadboard.places[1].affordances.clear();
+        assert_eq!(kinds(&rule_issues(&breadboard)), vec![(IssueKind::RuleBroken, 3), (IssueKind::RuleBroken, 2)]);
+
+        breadboard.rules = vec![Rule::WayBack { to: "Start".to_string() }];
+        let issues = rule_issues(&breadboard);
+        assert_eq!(kinds(&issues), vec![(IssueKind::RuleBroken, 1)]);
+        assert!(issues[0].message.ends_with("but there's no place called that"));
+
+        // Rules read from the board file
+        let toml = "rule = \"way_back\"\nto = \"Home\"\n";
+        assert_eq!(toml::from_str::<Rule>(toml).unwrap(), Rule::WayBack { to: "Home".to_string() });
+    }
+
+    #[test]
+    fn test_naming_convention() {
+        let mut breadboard = board(&["Invoice", "setup autopay"], &[(0, 1), (1, 0)]);
+        assert!(rule_issues(&breadboard).is_empty());
+
+        breadboard.naming = Some(Naming { places: Some(Case::Title), ..Naming::default() });
+        let issues = rule_issues(&breadboard);
+        assert_eq!(kinds(&issues), vec![(IssueKind::Naming, 2)]);
+        assert_eq!(issues[0].message, "Naming: \"setup autopay\" in title case is \"Setup Autopay\"");
+
+        // The test board's affordances are named "to 1" and the like
+        breadboard.naming.as_mut().unwrap().verb_first = true;
+        breadboard.places[0].affordances[0].name = "Set up autopay".to_string();
+        let issues = rule_issues(&breadboard);
+        assert_eq!(kinds(&issues), vec![(IssueKind::Naming, 2), (IssueKind::Naming, 2)]);
+        assert_eq!(issues[1].affordance_id, Some(2));
+        assert_eq!(issues[1].message, "Naming: \"to 0\" in \"setup autopay\" doesn't start with a verb");
+
+        // Read from the board file
+        let toml = "places = \"title\"\naffordances = \"sentence\"\n";
+        assert_eq!(toml::from_str::<Naming>(toml).unwrap().affordances, Some(Case::Sentence));
+    }
+
+    #[test]
+    fn test_duplicate_names() {
+        let mut breadboard = board(&["Invoice", "invoice "], &[(0, 1), (1, 0)]);
+        breadboard.places[0].add_affordance(Affordance::new(10, "Pay".to_string()));
+        breadboard.places[0].add_affordance(Affordance::new(11, "pay".to_string()));
+
+        let issues = lint(&breadboard);
+        assert_eq!(kinds(&issues), vec![(IssueKind::DuplicateName, 1), (IssueKind::DuplicateName, 2)]);
+        assert_eq!(issues[0].affordance_id, Some(11));
+
+        assert_eq!(duplicate_places(&breadboard), HashMap::from([(1, 1), (2, 2)]));
+        assert!(duplicate_places(&board(&["A", "B"], &[])).is_empty());
+    }
+}
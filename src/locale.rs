@@ -0,0 +1,1243 @@
+use serde::{Deserialize, Serialize};
+
+// The words the UI shows in each language the config can pick: mode names,
+// the status bar's prompts, hints and messages, the confirmation dialogs,
+// panel titles, key binding warnings, the tutorial and the legends of a
+// copied view. Board content
+// and the file exports stay as written. A `{}` in a string stands for a value
+// put in with `fill`, in order.
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    #[default]
+    #[serde(alias = "en")]
+    English,
+    #[serde(alias = "de", alias = "deutsch")]
+    German,
+}
+
+impl Language {
+    pub fn strings(self) -> &'static Strings {
+        match self {
+            Language::English => &ENGLISH,
+            Language::German => &GERMAN,
+        }
+    }
+}
+
+// `template` with each `{}` replaced by the next of `values`
+pub fn fill(template: &str, values: &[&str]) -> String {
+    let mut filled = String::new();
+    let mut values = values.iter();
+    let mut parts = template.split("{}").peekable();
+    while let Some(part) = parts.next() {
+        filled.push_str(part);
+        if parts.peek().is_some() {
+            filled.push_str(values.next().copied().unwrap_or_default());
+        }
+    }
+    filled
+}
+
+// `template` split at its first `{}`, for prompts that style the value
+// apart from the words around it
+pub fn around(template: &str) -> (&str, &str) {
+    template.split_once("{}").unwrap_or((template, ""))
+}
+
+pub struct Strings {
+    // Mode line
+    pub mode: &'static str,
+    pub mode_navigate: &'static str,
+    pub mode_edit: &'static str,
+    pub mode_connect: &'static str,
+    pub mode_save_file: &'static str,
+    pub mode_open_file: &'static str,
+    pub mode_import: &'static str,
+    pub mode_confirm_delete: &'static str,
+    pub mode_reopen: &'static str,
+    pub mode_recover: &'static str,
+    pub mode_locked: &'static str,
+    pub mode_library: &'static str,
+    pub mode_filter: &'static str,
+    pub mode_incoming: &'static str,
+    pub mode_cycles: &'static str,
+    pub mode_outcomes: &'static str,
+    pub mode_commands: &'static str,
+    pub mode_snippets: &'static str,
+    pub mode_trash: &'static str,
+    pub mode_spelling: &'static str,
+    pub mode_views: &'static str,
+    pub mode_start: &'static str,
+    pub mode_capture: &'static str,
+    pub mode_comment: &'static str,
+    pub mode_estimate: &'static str,
+    pub mode_compare: &'static str,
+    pub read_only: &'static str,
+    pub view_graph: &'static str,
+    pub view_swimlanes: &'static str,
+    pub view_titles: &'static str, // "{} (titles)", the graph zoomed out
+    pub view_dots: &'static str,
+    pub view_collapsed: &'static str,
+    pub view_expanded: &'static str,
+    pub marked: &'static str, // "{} marked"
+
+    // Status bar prompts and hints
+    pub jump_prompt: &'static str,
+    pub jump_hint: &'static str,
+    pub edit_prompt: &'static str,
+    pub edit_hint: &'static str,
+    pub edit_affordance_hint: &'static str,
+    pub connect_prompt: &'static str,
+    pub connect_marked_prompt: &'static str, // "Connect {} marked to: "
+    pub connect_hint: &'static str,
+    pub connect_prefilled_hint: &'static str,
+    pub save_prompt: &'static str,
+    pub save_hint: &'static str,
+    pub export_view_prompt: &'static str, // "Export view{} to: ", with the scope
+    pub export_document_prompt: &'static str, // "Export {}{} to: ", the document and the scope
+    pub export_hint: &'static str,
+    pub copy_subtree_prompt: &'static str,
+    pub move_subtree_prompt: &'static str,
+    pub bundle_prompt: &'static str,
+    pub attach_prompt: &'static str,
+    pub attach_hint: &'static str,
+    pub compare_prompt: &'static str,
+    pub compare_hint: &'static str,
+    pub import_prompt: &'static str,
+    pub import_hint: &'static str,
+    pub import_group_prompt: &'static str,
+    pub import_group_hint: &'static str,
+    pub incoming_prompt: &'static str,
+    pub incoming_hint: &'static str,
+    pub outcomes_prompt: &'static str,
+    pub outcomes_hint: &'static str,
+    pub cycle_count: &'static str, // "1 cycle"
+    pub cycles_count: &'static str, // "{} cycles"
+    pub cycles_hint: &'static str,
+    pub notes_prompt: &'static str,
+    pub notes_hint: &'static str,
+    pub capture_prompt: &'static str,
+    pub capture_hint: &'static str,
+    pub comment_prompt: &'static str, // "Comment as {}: "
+    pub comment_hint: &'static str,
+    pub compared: &'static str, // "Compared: {}"
+    pub no_differences: &'static str,
+    pub compared_hint: &'static str,
+    pub estimate_prompt: &'static str,
+    pub estimate_hint: &'static str,
+    pub commands_prompt: &'static str,
+    pub commands_hint: &'static str,
+    pub welcome: &'static str,
+    pub welcome_hint: &'static str,
+    pub snippets_prompt: &'static str,
+    pub snippets_hint: &'static str,
+    pub trash_prompt: &'static str,
+    pub trash_hint: &'static str,
+    pub spelling_prompt: &'static str,
+    pub spelling_hint: &'static str,
+    pub views_prompt: &'static str,
+    pub views_hint: &'static str,
+    pub view_name_prompt: &'static str,
+    pub view_name_hint: &'static str,
+    pub filter_prompt: &'static str,
+    pub filter_hint: &'static str,
+    pub open_file_prompt: &'static str,
+    pub open_file_hint: &'static str,
+    pub library_prompt: &'static str,
+    pub library_hint: &'static str,
+    pub library_new_board_hint: &'static str,
+    pub places_count: &'static str, // "Places: {} "
+    pub type_to_search: &'static str,
+    pub comments_count: &'static str, // "Comments: {} "
+    pub notes_line: &'static str, // "Notes: {}"
+
+    // Confirmation dialogs
+    pub reopen_prompt: &'static str, // "Reopen {}? "
+    pub reopen_hint: &'static str,
+    pub recover_prompt: &'static str, // "Recover unsaved changes to {} from {}? "
+    pub recover_hint: &'static str,
+    pub locked_prompt: &'static str, // "Open elsewhere by {}. Open read-only? "
+    pub locked_hint: &'static str,
+    pub delete_prompt: &'static str, // "Delete '{}' with {} affordance(s) and {} connection(s)? "
+    pub delete_hint: &'static str,
+
+    // Panels and lists
+    pub unknown: &'static str,
+    pub tutorial_title: &'static str, // "Tutorial {}/{} ...", the step and the number of steps
+    pub tutorial_done: &'static str,
+    pub panel_title: &'static str, // "{} (Esc to close)"
+    pub sketch_title: &'static str, // "Sketch: {}"
+    pub unreadable_image: &'static str,
+    pub progress_title: &'static str, // "Progress {}/{} built"
+    pub progress_points_title: &'static str, // "Progress {}/{} built, {}", with the points
+    pub history_line: &'static str, // "History: {}"
+    pub undo_line: &'static str, // "Alt+Shift+Z: back to before {}"
+    pub empty_board: &'static str,
+    pub controls: &'static str,
+    pub control_new_place: &'static str,
+    pub control_open: &'static str,
+    pub control_start: &'static str,
+    pub control_quit: &'static str,
+    pub empty_inner_board: &'static str, // "Nothing inside {} yet. ..."
+    pub control_back_out: &'static str,
+    pub breadboarding: &'static str,
+    pub breadboarding_intro: &'static str,
+    pub start_title: &'static str,
+    pub start_new_board: &'static str,
+    pub start_tutorial: &'static str,
+    pub start_recent: &'static str, // "Open recent: {}"
+    pub start_open: &'static str,
+    pub start_example: &'static str, // "Load example: {}"
+    pub start_new_board_key: &'static str,
+    pub start_open_key: &'static str,
+    pub no_matches: &'static str,
+    pub no_places_found: &'static str,
+    pub current_connection: &'static str,
+    pub this_place: &'static str,
+    pub connect_title: &'static str,
+    pub looking_for_boards: &'static str,
+    pub no_files_in: &'static str, // "No TOML files found in {}"
+    pub no_files: &'static str,
+    pub no_files_match: &'static str, // "No files match \"{}\""
+    pub file_places: &'static str, // " ({} places)"
+    pub files_by_name_title: &'static str,
+    pub files_by_date_title: &'static str,
+    pub files_found: &'static str, // " {} {} found", the spinner and the count
+    pub library_places: &'static str, // "  {} places  {}  {}", the count, time and file
+    pub workspace_title: &'static str, // "Workspace: {}"
+    pub boards_in_title: &'static str, // "Boards in {}"
+    pub boards_title: &'static str,
+    pub via: &'static str, // " via {}"
+    pub steps_away: &'static str, // " ({} steps away)"
+    pub reach_title: &'static str, // "Places that can reach {} ..."
+    pub linking_title: &'static str, // "Places linking to {} ..."
+    pub not_connected: &'static str,
+    pub no_outcomes: &'static str,
+    pub outcomes_title: &'static str, // "Outcomes of {}"
+    pub back_and_forth: &'static str,
+    pub cycles_title: &'static str,
+    pub commands_title: &'static str,
+    pub snippets_for_title: &'static str, // "Snippets for {}"
+    pub snippets_title: &'static str,
+    pub in_the_notes: &'static str,
+    pub typos_title: &'static str, // "Typos in {}"
+    pub trash_title: &'static str, // "Trash ({})"
+    pub no_views: &'static str,
+    pub views_title: &'static str, // "Views ({})"
+    pub icons_title: &'static str,
+    pub nothing_bound_yet: &'static str,
+    pub leader_title: &'static str, // "{} … (Esc to cancel)", the keys typed so far
+    pub jump_title: &'static str, // "Jump to place: {}"
+    pub full_text_title: &'static str,
+    pub rabbit_holes_title: &'static str,
+    pub history_title: &'static str,
+    pub glossary_title: &'static str,
+    pub notes_title: &'static str,
+    pub command_failed: &'static str, // "{} failed"
+    pub unsaved_board: &'static str,
+    pub as_saved: &'static str, // "{} as saved"
+    pub collapsed_legend: &'static str,
+    pub external_systems: &'static str,
+    pub progress_total: &'static str,
+    pub progress_cut: &'static str, // " ({} cut)"
+    pub point: &'static str, // "~{} pt"
+    pub points: &'static str, // "~{} pts"
+    pub snapshot: &'static str,
+    pub snapshots: &'static str, // "{} snapshots"
+    pub history_size: &'static str, // "{}, ~{} KB", the snapshots and their size
+    pub an_unsaved_board: &'static str,
+    pub file_unreadable: &'static str, // "can't be read: {}"
+    pub file_not_toml: &'static str, // "not valid TOML: {}"
+    pub file_not_board: &'static str,
+    pub trashed_place: &'static str,
+    pub trashed_affordance_count: &'static str,
+    pub trashed_affordances_count: &'static str, // "{} affordances"
+    pub trashed_connection_in: &'static str,
+    pub trashed_connections_in: &'static str, // "{} connections in"
+    pub trashed_affordance: &'static str, // "affordance in {}"
+    pub trashed_connected_affordance: &'static str, // "affordance in {}, connected"
+    pub saved_view_outline: &'static str,
+    pub saved_view_collapsed: &'static str,
+    pub saved_view_graph: &'static str,
+    pub saved_view_full: &'static str,
+    pub saved_view_titles: &'static str,
+    pub saved_view_dots: &'static str,
+    pub saved_view_swimlanes: &'static str,
+    pub saved_view_filter: &'static str, // "filter \"{}\""
+
+    // Selection details
+    pub group_line: &'static str, // "Group: {}"
+    pub linked_from_line: &'static str, // "Linked from: {}"
+    pub leads_to_line: &'static str, // "Leads to: {}"
+    pub nowhere_yet: &'static str,
+    pub outcome_line: &'static str, // "If {}: {}", the outcome and where it leads
+    pub estimate_line: &'static str, // "Estimate: {} ({})", the estimate and its points
+    pub attachments_line: &'static str, // "Attachments: {}"
+    pub comments_line: &'static str, // "Comments ({}):"
+    pub added_by: &'static str, // "Added by {}, {}", who and when
+    pub changed_by: &'static str, // "Changed by {}, {}", who and when
+    pub comment_line: &'static str, // "{}, {}: {}", the author, when and the text
+
+    // Status messages
+    pub more_key_warnings: &'static str, // "{} (and {} more key warnings in the log)"
+    pub read_only_refused: &'static str,
+    pub drilled_into_empty: &'static str, // "Inside {}: ..."
+    pub select_place_to_drill: &'static str,
+    pub cut: &'static str, // "Cut {}"
+    pub uncut: &'static str, // "Restored {}"
+    pub no_changes_below: &'static str,
+    pub no_changes_above: &'static str,
+    pub flagged: &'static str, // "Flagged {} as a rabbit hole"
+    pub unflagged: &'static str, // "Unflagged {} as a rabbit hole"
+    pub no_rabbit_holes: &'static str,
+    pub rabbit_hole_no_notes: &'static str,
+    pub trash_empty: &'static str,
+    pub no_search_to_repeat: &'static str,
+    pub search_match: &'static str, // "{} ({} of {} matching \"{}\")"
+    pub no_search_match: &'static str, // "No places match \"{}\""
+    pub snapshot_restored: &'static str, // "Restored the board from before {}"
+    pub snapshots_left: &'static str, // "... ({} more snapshots)", the label and the count
+    pub no_snapshot: &'static str,
+    pub no_icon: &'static str,
+    pub select_place_to_attach: &'static str,
+    pub select_affordance_to_estimate: &'static str,
+    pub no_history: &'static str,
+    pub no_glossary: &'static str,
+    pub nothing_bound: &'static str, // "Nothing is bound to {}"
+    pub no_workspace: &'static str,
+    pub no_commands: &'static str,
+    pub no_snippets: &'static str,
+    pub select_place_for_snippet: &'static str,
+    pub no_dictionary: &'static str,
+    pub no_typos: &'static str,
+    pub happy_path_step: &'static str, // "{} is step {} of the happy path"
+    pub happy_path_removed: &'static str, // "{} taken off the happy path"
+    pub no_marks: &'static str,
+    pub marks_hint: &'static str, // "{} marked: ..."
+    pub no_sketch: &'static str,
+    pub view_removed: &'static str, // "Removed the view {}"
+    pub nothing_connected: &'static str,
+    pub notes_imported: &'static str, // "Imported {} from the notes"
+    pub commented: &'static str, // "Commented on {} (Alt+Z shows the thread)"
+    pub captured: &'static str, // "Captured {}"
+    pub capture_failed: &'static str, // "Couldn't capture: {}"
+    pub estimated: &'static str, // "Estimated {} at {}"
+    pub estimate_cleared: &'static str, // "Cleared the estimate of {}"
+    pub estimate_invalid: &'static str,
+    pub view_needs_name: &'static str,
+    pub view_saved: &'static str, // "Saved the view {}"
+    pub view_updated: &'static str, // "Updated the view {}"
+    pub place_added: &'static str, // "Added place {}"
+    pub load_failed: &'static str, // "Failed to load {}: {}", the file and the error
+    pub load_failed_kept: &'static str, // "Failed to load {}, kept the open board: {}"
+    pub list_files_failed: &'static str, // "Failed to list board files: {}"
+    pub example_opened: &'static str, // "Opened the {} example (Ctrl+S saves a copy)"
+    pub recover_failed: &'static str, // "Failed to recover: {}"
+    pub compare_failed: &'static str, // "Failed to compare: {}"
+    pub view_exported: &'static str, // "View exported to {}"
+    pub view_export_failed: &'static str, // "Failed to export view: {}"
+    pub document_exported: &'static str, // "{} exported to {}", the document and the file
+    pub document_export_failed: &'static str, // "Failed to export {}: {}", the document and the error
+    pub extract_failed: &'static str, // "Failed to extract to {}: {}"
+    pub places_copied: &'static str, // "Copied {} places to {}"
+    pub places_moved: &'static str, // "Moved {} places to {}, {} connections now link there"
+    pub bundled: &'static str, // "Bundled the board and {} attached files into {}"
+    pub bundle_failed: &'static str, // "Failed to bundle: {}"
+    pub attached: &'static str, // "Attached {} to {}", the file and the place
+    pub attach_failed: &'static str, // "Failed to attach: {}"
+    pub imported: &'static str, // "Imported {} from {}", what was added and the file
+    pub import_failed: &'static str, // "Failed to import {}: {}"
+    pub restored_from_trash: &'static str, // "Restored {} from the trash"
+    pub showing_view: &'static str, // "Showing the view {}"
+    pub outcome_not_connected: &'static str,
+    pub snippet_added: &'static str, // "Added {} affordances from {}"
+    pub snippet_skipped: &'static str, // " ({} already there)"
+    pub typo_fixed: &'static str, // "Changed {} to {}"
+    pub not_in_workspace: &'static str, // "{} isn't part of the open workspace"
+    pub own_place: &'static str, // "{} is this affordance's own place: ..."
+    pub own_place_outcome: &'static str, // "{} is this outcome's own place: ..."
+    pub outcomes_stay_on_board: &'static str,
+    pub marked_stay_on_board: &'static str,
+    pub connected_affordance: &'static str, // "connected 1 affordance to {}"
+    pub connected_affordances: &'static str, // "connected {} affordances to {}"
+    pub linked_place: &'static str, // "added \"Go to {}\" to 1 place"
+    pub linked_places: &'static str, // "added \"Go to {}\" to {} places", the place and the count
+    pub already_leads_to: &'static str, // "Every marked place already leads to {}"
+    pub connection_removed: &'static str,
+    pub connections_removed: &'static str, // "Removed the connections of {} affordances"
+    pub editing_anyway: &'static str, // "Editing anyway: saves may overwrite those of {}"
+    pub marks_cleared: &'static str,
+    pub tutorial_closed: &'static str,
+    pub connection_swapped: &'static str,
+    pub return_connection_added: &'static str,
+    pub nothing_to_reverse: &'static str,
+    pub happy_path_broken: &'static str, // "Saved, but the happy path is broken: {}"
+    pub select_place_to_extract: &'static str,
+    pub clipboard_failed: &'static str, // "Failed to read the clipboard: {}"
+    pub no_places_in_notes: &'static str,
+    pub skipped_line: &'static str,
+    pub skipped_lines: &'static str, // "\n\nSkipped {} other lines"
+    pub place_copied: &'static str, // "Copied {}: Ctrl+P pastes it, ..."
+    pub select_place_to_copy: &'static str,
+    pub nothing_copied: &'static str,
+    pub pasted: &'static str, // "Pasted {}"
+    pub pasted_remapped: &'static str, // "; connected to places here: {}"
+    pub pasted_dropped: &'static str, // "; not on this board, left unconnected: {}"
+    pub view_copied: &'static str,
+    pub view_copy_failed: &'static str, // "Failed to copy view: {}"
+    pub notes_saved: &'static str,
+    pub no_notes: &'static str,
+    pub recovered: &'static str, // "Recovered the changes from {} (Ctrl+S saves them)"
+    pub compared_with: &'static str, // "Compared with {}: {}"
+    pub no_differences_from: &'static str, // "No differences from {}"
+    pub lock_failed: &'static str, // "Couldn't lock the board: {}"
+    pub command_finished: &'static str, // "{} finished"
+    pub command_running: &'static str, // "Running {}…"
+    pub not_connected_yet: &'static str,
+    pub no_incoming: &'static str, // "No places link to {}"
+    pub no_cycles: &'static str,
+    pub hook_title: &'static str, // "on_{} hook", the event
+    pub imported_counts: &'static str, // "{} places and {} affordances"
+    pub snapshot_deleting: &'static str, // "deleting {}"
+    pub snapshot_importing_notes: &'static str,
+    pub snapshot_importing: &'static str, // "importing {}"
+    pub snapshot_connecting: &'static str, // "connecting {} marked items"
+    pub snapshot_moving: &'static str, // "moving places to {}"
+    pub snapshot_recovering: &'static str,
+
+    // Key bindings
+    pub key_no_action: &'static str, // "Key {}: no action called \"{}\""
+    pub key_may_not_work: &'static str, // "Key {} for {} may not work: {}", the key, action and why
+    pub key_invalid: &'static str, // "Key {}: {}", the key and what's wrong with it
+    pub key_sent_as_ctrl: &'static str, // "most terminals send it as Ctrl+{}"
+    pub key_suspends: &'static str,
+    pub key_ctrl_digit: &'static str,
+    pub key_sent_as: &'static str, // "terminals send it as {}"
+    pub key_modifier_dropped: &'static str,
+    pub key_looks_like_another: &'static str,
+    pub leader_may_not_work: &'static str, // "Leader key {} may not work: {}"
+    pub leader_invalid: &'static str, // "Leader key {}: {}"
+    pub leader_no_action: &'static str, // "Leader {}: no action called \"{}\""
+    pub leader_no_keys: &'static str, // "Leader sequence for {} has no keys"
+    pub leader_invalid_sequence: &'static str, // "Leader {}: {}", the sequence and what's wrong with it
+    pub leader_unreachable: &'static str, // "Leader sequence for {} can't be typed: {} comes first"
+    pub leader_file: &'static str,
+    pub leader_place: &'static str,
+    pub leader_view: &'static str,
+    pub leader_export: &'static str,
+    pub leader_show: &'static str,
+    pub leader_more: &'static str,
+
+    // Tutorial
+    pub tutorial_create_place: &'static str,
+    pub tutorial_second_place: &'static str,
+    pub tutorial_add_affordance: &'static str,
+    pub tutorial_connect: &'static str,
+    pub tutorial_follow: &'static str,
+    pub tutorial_save: &'static str,
+    pub tutorial_done_prompt: &'static str,
+}
+
+pub static ENGLISH: Strings = Strings {
+    mode: "Mode: ",
+    mode_navigate: "NAVIGATE",
+    mode_edit: "EDIT",
+    mode_connect: "CONNECT",
+    mode_save_file: "SAVE FILE",
+    mode_open_file: "OPEN FILE",
+    mode_import: "IMPORT",
+    mode_confirm_delete: "CONFIRM DELETE",
+    mode_reopen: "REOPEN",
+    mode_recover: "RECOVER",
+    mode_locked: "LOCKED",
+    mode_library: "LIBRARY",
+    mode_filter: "FILTER",
+    mode_incoming: "INCOMING",
+    mode_cycles: "CYCLES",
+    mode_outcomes: "OUTCOMES",
+    mode_commands: "COMMANDS",
+    mode_snippets: "SNIPPETS",
+    mode_trash: "TRASH",
+    mode_spelling: "SPELLING",
+    mode_views: "VIEWS",
+    mode_start: "START",
+    mode_capture: "CAPTURE",
+    mode_comment: "COMMENT",
+    mode_estimate: "ESTIMATE",
+    mode_compare: "COMPARE",
+    read_only: "🔒 READ-ONLY",
+    view_graph: "Graph",
+    view_swimlanes: "Swimlanes",
+    view_titles: "{} (titles)",
+    view_dots: "{} (dots)",
+    view_collapsed: "Collapsed",
+    view_expanded: "Expanded",
+    marked: "{} marked",
+
+    jump_prompt: "Jump to: ",
+    jump_hint: " (type to filter, ↑/↓ to select, ↑ at the top for earlier searches, Enter to jump, Ctrl+Enter to look without closing, Esc to cancel)",
+    edit_prompt: "Editing: ",
+    edit_hint: " (Enter to save, Esc to cancel)",
+    edit_affordance_hint: " (-> Place to connect; Enter to save, Esc to cancel)",
+    connect_prompt: "Connect to: ",
+    connect_marked_prompt: "Connect {} marked to: ",
+    connect_hint: " (↑/↓ to select, Enter to connect, Ctrl+R to disconnect, Esc to cancel)",
+    connect_prefilled_hint: " (type to search, ↑/↓ to select, Enter to connect, Ctrl+R to disconnect, Esc to cancel)",
+    save_prompt: "Save as: ",
+    save_hint: " (Enter to save, Esc to cancel)",
+    export_view_prompt: "Export view{} to: ",
+    export_document_prompt: "Export {}{} to: ",
+    export_hint: " (Tab: scope; Enter to save, Esc to cancel)",
+    copy_subtree_prompt: "Copy subtree to board: ",
+    move_subtree_prompt: "Move subtree to board: ",
+    bundle_prompt: "Bundle board and attachments to: ",
+    attach_prompt: "Attach file: ",
+    attach_hint: " (Enter to attach, Esc to cancel)",
+    compare_prompt: "Compare with board: ",
+    compare_hint: " (the board's own file for the last save; Enter to compare, Esc to cancel)",
+    import_prompt: "Import from: ",
+    import_hint: " (.csv, .dot, .toml, .txt or .md; Enter to continue, Esc to cancel)",
+    import_group_prompt: "Group for imported places: ",
+    import_group_hint: " (empty for none; Enter to import, Esc to cancel)",
+    incoming_prompt: "Linked from: ",
+    incoming_hint: " (↑/↓ to select, Enter to jump, Esc to cancel)",
+    outcomes_prompt: "Outcomes of: ",
+    outcomes_hint: " (Enter to follow, Ctrl+A to add, e to rename, Ctrl+C to connect, Ctrl+D to remove, Esc to go back)",
+    cycle_count: "1 cycle",
+    cycles_count: "{} cycles",
+    cycles_hint: " (↑/↓ to select, Enter to stay here, Esc to go back)",
+    notes_prompt: "Add this to the board? ",
+    notes_hint: "(Enter or y to import, Esc or n to cancel)",
+    capture_prompt: "Capture: ",
+    capture_hint: " (Place: Affordance -> Place; Enter to add, Esc when done)",
+    comment_prompt: "Comment as {}: ",
+    comment_hint: " (Enter to post, Esc to cancel)",
+    compared: "Compared: {}",
+    no_differences: "no differences",
+    compared_hint: " (↑/↓ and PgUp/PgDn scroll, n/N next/previous change, Esc to close)",
+    estimate_prompt: "Estimate: ",
+    estimate_hint: " (points or XS/S/M/L/XL; empty clears it; Enter to set, Esc to cancel)",
+    commands_prompt: "Run command: ",
+    commands_hint: " (↑/↓ to select, Enter to run, Esc to cancel)",
+    welcome: "Welcome to bboard ",
+    welcome_hint: " (↑/↓ to select, Enter to choose, Esc for a blank board)",
+    snippets_prompt: "Insert snippet: ",
+    snippets_hint: " (↑/↓ to select, Enter to insert, Esc to cancel)",
+    trash_prompt: "Restore from the trash: ",
+    trash_hint: " (↑/↓ to select, Enter to put it back, Esc to cancel)",
+    spelling_prompt: "Fix a typo: ",
+    spelling_hint: " (↑/↓ to select, Enter to change it, Esc when done)",
+    views_prompt: "Views: ",
+    views_hint: " (Enter to show, Ctrl+A to save the current view, Ctrl+D to remove, Esc to close)",
+    view_name_prompt: "Save the current view as: ",
+    view_name_hint: " (a view's name replaces it; Enter to save, Esc to cancel)",
+    filter_prompt: "Filter: ",
+    filter_hint: " (type to narrow, ↑/↓ to move, Enter to keep, Esc to clear)",
+    open_file_prompt: "Select file to open: ",
+    open_file_hint: " (type to filter, ↑/↓ to select, Tab to sort, Enter to open, Esc to cancel)",
+    library_prompt: "Open a board: ",
+    library_hint: " (↑/↓ to select, Enter to open, Esc to cancel)",
+    library_new_board_hint: " (↑/↓ to select, Enter to open, Esc for a new board)",
+    places_count: "Places: {} ",
+    type_to_search: "(type to search) ",
+    comments_count: "Comments: {} ",
+    notes_line: "Notes: {}",
+
+    reopen_prompt: "Reopen {}? ",
+    reopen_hint: "(Y/Enter to open, N/Esc for the start screen)",
+    recover_prompt: "Recover unsaved changes to {} from {}? ",
+    recover_hint: "(Y/Enter to restore, N/Esc to discard)",
+    locked_prompt: "Open elsewhere by {}. Open read-only? ",
+    locked_hint: "(Y/Enter to look without editing, N/Esc to edit anyway)",
+    delete_prompt: "Delete '{}' with {} affordance(s) and {} connection(s)? ",
+    delete_hint: "(Y/Enter to confirm, N/Esc to cancel)",
+    unknown: "Unknown",
+    tutorial_title: "Tutorial {}/{} (Esc with nowhere to go back ends it)",
+    tutorial_done: "Tutorial done",
+    panel_title: "{} (Esc to close)",
+    sketch_title: "Sketch: {}",
+    unreadable_image: "Can't read this image",
+    progress_title: "Progress {}/{} built",
+    progress_points_title: "Progress {}/{} built, {}",
+    history_line: "History: {}",
+    undo_line: "Alt+Shift+Z: back to before {}",
+    empty_board: "No places yet. Press Ctrl+N to create a place.",
+    controls: "Controls:",
+    control_new_place: "  Ctrl+N - New place",
+    control_open: "  Ctrl+O - Open file",
+    control_start: "  Enter  - Start screen: recent board and examples",
+    control_quit: "  Ctrl+Q - Quit",
+    empty_inner_board: "Nothing inside {} yet. Press Ctrl+N to add a place to it.",
+    control_back_out: "  Alt+↑  - Back out to the board around it",
+    breadboarding: "Breadboarding",
+    breadboarding_intro: "A breadboard sketches a feature as words, not pictures: the places a user \
+        can be (screens, dialogs, emails), the affordances in each (buttons, fields, links) and where each \
+        affordance leads. It settles the flow before anyone argues about the layout. Add places, list what \
+        can be done in each, connect the affordances, then follow the connections to see if the flow holds up.",
+    start_title: "Start",
+    start_new_board: "Create a new board",
+    start_tutorial: "Take the tutorial",
+    start_recent: "Open recent: {}",
+    start_open: "Open a board file…",
+    start_example: "Load example: {}",
+    start_new_board_key: "  Ctrl+N",
+    start_open_key: "  Ctrl+O",
+    no_matches: "No places or affordances match the filter",
+    no_places_found: "No places found",
+    current_connection: " (current)",
+    this_place: " (this place)",
+    connect_title: "Select place to connect to (Ctrl+R removes the connection)",
+    looking_for_boards: "Looking for boards…",
+    no_files_in: "No TOML files found in {}",
+    no_files: "No TOML files found in current directory",
+    no_files_match: "No files match \"{}\"",
+    file_places: " ({} places)",
+    files_by_name_title: "Select file to open (by name, Tab: newest first)",
+    files_by_date_title: "Select file to open (newest first, Tab: by name)",
+    files_found: " {} {} found",
+    library_places: "  {} places  {}  {}",
+    workspace_title: "Workspace: {}",
+    boards_in_title: "Boards in {}",
+    boards_title: "Boards",
+    via: " via {}",
+    steps_away: " ({} steps away)",
+    reach_title: "Places that can reach {} (Tab: direct links only)",
+    linking_title: "Places linking to {} (Tab: every place that can reach it)",
+    not_connected: " (not connected)",
+    no_outcomes: "No outcomes yet. Press Ctrl+A to add one, like \"success\" or \"failure\".",
+    outcomes_title: "Outcomes of {}",
+    back_and_forth: " (back and forth)",
+    cycles_title: "Cycles",
+    commands_title: "Commands",
+    snippets_for_title: "Snippets for {}",
+    snippets_title: "Snippets",
+    in_the_notes: "  in the notes",
+    typos_title: "Typos in {}",
+    trash_title: "Trash ({})",
+    no_views: "No views yet: Ctrl+A saves the current one",
+    views_title: "Views ({})",
+    icons_title: "Icons (Tab/Enter to put in)",
+    nothing_bound_yet: "Nothing bound yet",
+    leader_title: "{} … (Esc to cancel)",
+    jump_title: "Jump to place: {}",
+    full_text_title: "Full text",
+    rabbit_holes_title: "Rabbit holes",
+    history_title: "History",
+    glossary_title: "Glossary",
+    notes_title: "Notes",
+    command_failed: "{} failed",
+    unsaved_board: "unsaved board",
+    as_saved: "{} as saved",
+    collapsed_legend: "≡ affordances  ← linked from  → leads to  ✎ notes",
+    external_systems: "── External systems ──",
+    progress_total: "Total",
+    progress_cut: " ({} cut)",
+    point: "~{} pt",
+    points: "~{} pts",
+    snapshot: "1 snapshot",
+    snapshots: "{} snapshots",
+    history_size: "{}, ~{} KB",
+    an_unsaved_board: "an unsaved board",
+    file_unreadable: "can't be read: {}",
+    file_not_toml: "not valid TOML: {}",
+    file_not_board: "not a breadboard",
+    trashed_place: "place",
+    trashed_affordance_count: "1 affordance",
+    trashed_affordances_count: "{} affordances",
+    trashed_connection_in: "1 connection in",
+    trashed_connections_in: "{} connections in",
+    trashed_affordance: "affordance in {}",
+    trashed_connected_affordance: "affordance in {}, connected",
+    saved_view_outline: "outline",
+    saved_view_collapsed: "collapsed",
+    saved_view_graph: "graph",
+    saved_view_full: "full",
+    saved_view_titles: "titles",
+    saved_view_dots: "dots",
+    saved_view_swimlanes: "swimlanes",
+    saved_view_filter: "filter \"{}\"",
+
+    group_line: "Group: {}",
+    linked_from_line: "Linked from: {}",
+    leads_to_line: "Leads to: {}",
+    nowhere_yet: "nowhere yet",
+    outcome_line: "If {}: {}",
+    estimate_line: "Estimate: {} ({})",
+    attachments_line: "Attachments: {}",
+    comments_line: "Comments ({}):",
+    added_by: "Added by {}, {}",
+    changed_by: "Changed by {}, {}",
+    comment_line: "{}, {}: {}",
+
+    more_key_warnings: "{} (and {} more key warnings in the log)",
+    read_only_refused: "Read-only: this board can be explored but not changed",
+    drilled_into_empty: "Inside {}: Ctrl+N adds a place, Alt+↑ goes back out",
+    select_place_to_drill: "Select a place to drill into",
+    cut: "Cut {}",
+    uncut: "Restored {}",
+    no_changes_below: "No more changes below",
+    no_changes_above: "No more changes above",
+    flagged: "Flagged {} as a rabbit hole",
+    unflagged: "Unflagged {} as a rabbit hole",
+    no_rabbit_holes: "No rabbit holes flagged: Ctrl+U flags the selection",
+    rabbit_hole_no_notes: "No notes yet (Alt+N)",
+    trash_empty: "The trash is empty: deleted places and affordances land here",
+    no_search_to_repeat: "No search to repeat yet: type to search for a place",
+    search_match: "{} ({} of {} matching \"{}\")",
+    no_search_match: "No places match \"{}\"",
+    snapshot_restored: "Restored the board from before {}",
+    snapshots_left: "Restored the board from before {} ({} more snapshots)",
+    no_snapshot: "No snapshot to restore: one is taken before deletes, imports and bulk connects",
+    no_icon: "no icon",
+    select_place_to_attach: "Select a place to attach a file to",
+    select_affordance_to_estimate: "Select an affordance to estimate",
+    no_history: "No history yet: each save adds what changed",
+    no_glossary: "No glossary yet: add terms under [glossary] in the board file",
+    nothing_bound: "Nothing is bound to {}",
+    no_workspace: "No workspace open",
+    no_commands: "No commands configured",
+    no_snippets: "No snippets configured",
+    select_place_for_snippet: "Select a place to insert a snippet into",
+    no_dictionary: "No dictionary to check spelling with; set one under [spelling] in the config",
+    no_typos: "No typos to fix in the selection",
+    happy_path_step: "{} is step {} of the happy path",
+    happy_path_removed: "{} taken off the happy path",
+    no_marks: "No marks",
+    marks_hint: "{} marked: Ctrl+C connects them all to one place, Esc clears",
+    no_sketch: "Attach a .png or .jpg to the place with Alt+J to see it here",
+    view_removed: "Removed the view {}",
+    nothing_connected: "Nothing connected yet: Ctrl+C on an affordance connects it",
+    notes_imported: "Imported {} from the notes",
+    commented: "Commented on {} (Alt+Z shows the thread)",
+    captured: "Captured {}",
+    capture_failed: "Couldn't capture: {}",
+    estimated: "Estimated {} at {}",
+    estimate_cleared: "Cleared the estimate of {}",
+    estimate_invalid: "An estimate is a number of points or XS, S, M, L or XL",
+    view_needs_name: "A view needs a name",
+    view_saved: "Saved the view {}",
+    view_updated: "Updated the view {}",
+    place_added: "Added place {}",
+    load_failed: "Failed to load {}: {}",
+    load_failed_kept: "Failed to load {}, kept the open board: {}",
+    list_files_failed: "Failed to list board files: {}",
+    example_opened: "Opened the {} example (Ctrl+S saves a copy)",
+    recover_failed: "Failed to recover: {}",
+    compare_failed: "Failed to compare: {}",
+    view_exported: "View exported to {}",
+    view_export_failed: "Failed to export view: {}",
+    document_exported: "{} exported to {}",
+    document_export_failed: "Failed to export {}: {}",
+    extract_failed: "Failed to extract to {}: {}",
+    places_copied: "Copied {} places to {}",
+    places_moved: "Moved {} places to {}, {} connections now link there",
+    bundled: "Bundled the board and {} attached files into {}",
+    bundle_failed: "Failed to bundle: {}",
+    attached: "Attached {} to {}",
+    attach_failed: "Failed to attach: {}",
+    imported: "Imported {} from {}",
+    import_failed: "Failed to import {}: {}",
+    restored_from_trash: "Restored {} from the trash",
+    showing_view: "Showing the view {}",
+    outcome_not_connected: "This outcome isn't connected yet (Ctrl+C to connect it)",
+    snippet_added: "Added {} affordances from {}",
+    snippet_skipped: " ({} already there)",
+    typo_fixed: "Changed {} to {}",
+    not_in_workspace: "{} isn't part of the open workspace",
+    own_place: "{} is this affordance's own place: Enter again to connect anyway",
+    own_place_outcome: "{} is this outcome's own place: Enter again to connect anyway",
+    outcomes_stay_on_board: "Outcomes can only lead to places on this board",
+    marked_stay_on_board: "Marked items can only be connected to places on this board",
+    connected_affordance: "connected 1 affordance to {}",
+    connected_affordances: "connected {} affordances to {}",
+    linked_place: "added \"Go to {}\" to 1 place",
+    linked_places: "added \"Go to {}\" to {} places",
+    already_leads_to: "Every marked place already leads to {}",
+    connection_removed: "Removed the connections of 1 affordance",
+    connections_removed: "Removed the connections of {} affordances",
+    editing_anyway: "Editing anyway: saves may overwrite those of {}",
+    marks_cleared: "Marks cleared",
+    tutorial_closed: "Tutorial closed",
+    connection_swapped: "Connection swapped",
+    return_connection_added: "Return connection added",
+    nothing_to_reverse: "Selected affordance has no connection to reverse",
+    happy_path_broken: "Saved, but the happy path is broken: {}",
+    select_place_to_extract: "Select a place to extract",
+    clipboard_failed: "Failed to read the clipboard: {}",
+    no_places_in_notes: "No places in the notes: end a line with ':' to start one",
+    skipped_line: "\n\nSkipped 1 other line",
+    skipped_lines: "\n\nSkipped {} other lines",
+    place_copied: "Copied {}: Ctrl+P pastes it, here or on another board",
+    select_place_to_copy: "Select a place to copy",
+    nothing_copied: "Nothing copied: Ctrl+Y copies the selected place",
+    pasted: "Pasted {}",
+    pasted_remapped: "; connected to places here: {}",
+    pasted_dropped: "; not on this board, left unconnected: {}",
+    view_copied: "View copied to clipboard",
+    view_copy_failed: "Failed to copy view: {}",
+    notes_saved: "Notes saved",
+    no_notes: "No notes",
+    recovered: "Recovered the changes from {} (Ctrl+S saves them)",
+    compared_with: "Compared with {}: {}",
+    no_differences_from: "No differences from {}",
+    lock_failed: "Couldn't lock the board: {}",
+    command_running: "Running {}…",
+    command_finished: "{} finished",
+    not_connected_yet: "Not connected yet: Ctrl+C connects it",
+    no_incoming: "No places link to {}",
+    no_cycles: "No cycles: every path through the board ends",
+    hook_title: "on_{} hook",
+    imported_counts: "{} places and {} affordances",
+    snapshot_deleting: "deleting {}",
+    snapshot_importing_notes: "importing the notes",
+    snapshot_importing: "importing {}",
+    snapshot_connecting: "connecting {} marked items",
+    snapshot_moving: "moving places to {}",
+    snapshot_recovering: "recovering unsaved changes",
+
+    key_no_action: "Key {}: no action called \"{}\"",
+    key_may_not_work: "Key {} for {} may not work: {}",
+    key_invalid: "Key {}: {}",
+    key_sent_as_ctrl: "most terminals send it as Ctrl+{}",
+    key_suspends: "Ctrl+Z suspends bboard",
+    key_ctrl_digit: "most terminals don't send Ctrl with digits",
+    key_sent_as: "terminals send it as {}",
+    key_modifier_dropped: "most terminals send it without the modifier",
+    leader_may_not_work: "Leader key {} may not work: {}",
+    leader_invalid: "Leader key {}: {}",
+    leader_no_action: "Leader {}: no action called \"{}\"",
+    leader_no_keys: "Leader sequence for {} has no keys",
+    leader_invalid_sequence: "Leader {}: {}",
+    leader_unreachable: "Leader sequence for {} can't be typed: {} comes first",
+    leader_file: "+file",
+    leader_place: "+place",
+    leader_view: "+view",
+    leader_export: "+export",
+    leader_show: "+show",
+    leader_more: "+more",
+    key_looks_like_another: "terminals send it looking like another key",
+
+    tutorial_create_place: "A place is somewhere the user can be: a screen, a dialog, an email. \
+        Press Ctrl+N, type a name like \"Invoice\" and press Enter.",
+    tutorial_second_place: "Add the place the first one leads to, such as \"Setup Autopay\": \
+        Ctrl+N again, a name, Enter.",
+    tutorial_add_affordance: "Affordances are what the user can do in a place. Pick a place with Tab, \
+        press Ctrl+A and name one, like \"Turn on Autopay\".",
+    tutorial_connect: "Say where it leads: with the affordance selected, press Ctrl+C, type the \
+        other place's name and press Enter.",
+    tutorial_follow: "Walk the flow: select the connected affordance with ↓ and press Enter to follow \
+        it. Backspace comes back.",
+    tutorial_save: "Press Ctrl+S, type a file name such as \"autopay.toml\" and press Enter.",
+    tutorial_done_prompt: "That's breadboarding: places, what can be done in them, and where it leads. \
+        Press Esc to close the tutorial and keep the board.",
+};
+
+pub static GERMAN: Strings = Strings {
+    mode: "Modus: ",
+    mode_navigate: "NAVIGIEREN",
+    mode_edit: "BEARBEITEN",
+    mode_connect: "VERBINDEN",
+    mode_save_file: "SPEICHERN",
+    mode_open_file: "ÖFFNEN",
+    mode_import: "IMPORT",
+    mode_confirm_delete: "LÖSCHEN BESTÄTIGEN",
+    mode_reopen: "WIEDER ÖFFNEN",
+    mode_recover: "WIEDERHERSTELLEN",
+    mode_locked: "GESPERRT",
+    mode_library: "BIBLIOTHEK",
+    mode_filter: "FILTER",
+    mode_incoming: "EINGEHEND",
+    mode_cycles: "ZYKLEN",
+    mode_outcomes: "ERGEBNISSE",
+    mode_commands: "BEFEHLE",
+    mode_snippets: "BAUSTEINE",
+    mode_trash: "PAPIERKORB",
+    mode_spelling: "RECHTSCHREIBUNG",
+    mode_views: "ANSICHTEN",
+    mode_start: "START",
+    mode_capture: "ERFASSEN",
+    mode_comment: "KOMMENTAR",
+    mode_estimate: "SCHÄTZUNG",
+    mode_compare: "VERGLEICH",
+    read_only: "🔒 NUR LESEN",
+    view_graph: "Graph",
+    view_swimlanes: "Bahnen",
+    view_titles: "{} (Titel)",
+    view_dots: "{} (Punkte)",
+    view_collapsed: "Eingeklappt",
+    view_expanded: "Ausgeklappt",
+    marked: "{} markiert",
+
+    jump_prompt: "Springen zu: ",
+    jump_hint: " (tippen zum Filtern, ↑/↓ zum Auswählen, ↑ ganz oben für frühere Suchen, Enter zum Springen, Strg+Enter zum Ansehen ohne Schließen, Esc zum Abbrechen)",
+    edit_prompt: "Bearbeiten: ",
+    edit_hint: " (Enter zum Speichern, Esc zum Abbrechen)",
+    edit_affordance_hint: " (-> Ort zum Verbinden; Enter zum Speichern, Esc zum Abbrechen)",
+    connect_prompt: "Verbinden mit: ",
+    connect_marked_prompt: "{} markierte verbinden mit: ",
+    connect_hint: " (↑/↓ zum Auswählen, Enter zum Verbinden, Strg+R zum Trennen, Esc zum Abbrechen)",
+    connect_prefilled_hint: " (tippen zum Suchen, ↑/↓ zum Auswählen, Enter zum Verbinden, Strg+R zum Trennen, Esc zum Abbrechen)",
+    save_prompt: "Speichern als: ",
+    save_hint: " (Enter zum Speichern, Esc zum Abbrechen)",
+    export_view_prompt: "Ansicht{} exportieren nach: ",
+    export_document_prompt: "{}{} exportieren nach: ",
+    export_hint: " (Tab: Umfang; Enter zum Speichern, Esc zum Abbrechen)",
+    copy_subtree_prompt: "Teilbaum in Board kopieren: ",
+    move_subtree_prompt: "Teilbaum in Board verschieben: ",
+    bundle_prompt: "Board mit Anhängen bündeln nach: ",
+    attach_prompt: "Datei anhängen: ",
+    attach_hint: " (Enter zum Anhängen, Esc zum Abbrechen)",
+    compare_prompt: "Vergleichen mit Board: ",
+    compare_hint: " (die eigene Datei des Boards für den letzten Stand; Enter zum Vergleichen, Esc zum Abbrechen)",
+    import_prompt: "Importieren aus: ",
+    import_hint: " (.csv, .dot, .toml, .txt oder .md; Enter zum Fortfahren, Esc zum Abbrechen)",
+    import_group_prompt: "Gruppe für importierte Orte: ",
+    import_group_hint: " (leer für keine; Enter zum Importieren, Esc zum Abbrechen)",
+    incoming_prompt: "Verlinkt von: ",
+    incoming_hint: " (↑/↓ zum Auswählen, Enter zum Springen, Esc zum Abbrechen)",
+    outcomes_prompt: "Ergebnisse von: ",
+    outcomes_hint: " (Enter zum Folgen, Strg+A zum Hinzufügen, e zum Umbenennen, Strg+C zum Verbinden, Strg+D zum Entfernen, Esc zurück)",
+    cycle_count: "1 Zyklus",
+    cycles_count: "{} Zyklen",
+    cycles_hint: " (↑/↓ zum Auswählen, Enter zum Bleiben, Esc zurück)",
+    notes_prompt: "Dies zum Board hinzufügen? ",
+    notes_hint: "(Enter oder j zum Importieren, Esc oder n zum Abbrechen)",
+    capture_prompt: "Erfassen: ",
+    capture_hint: " (Ort: Element -> Ort; Enter zum Hinzufügen, Esc wenn fertig)",
+    comment_prompt: "Kommentieren als {}: ",
+    comment_hint: " (Enter zum Posten, Esc zum Abbrechen)",
+    compared: "Verglichen: {}",
+    no_differences: "keine Unterschiede",
+    compared_hint: " (↑/↓ und Bild↑/Bild↓ blättern, n/N nächste/vorige Änderung, Esc zum Schließen)",
+    estimate_prompt: "Schätzung: ",
+    estimate_hint: " (Punkte oder XS/S/M/L/XL; leer löscht sie; Enter zum Setzen, Esc zum Abbrechen)",
+    commands_prompt: "Befehl ausführen: ",
+    commands_hint: " (↑/↓ zum Auswählen, Enter zum Ausführen, Esc zum Abbrechen)",
+    welcome: "Willkommen bei bboard ",
+    welcome_hint: " (↑/↓ zum Auswählen, Enter zum Wählen, Esc für ein leeres Board)",
+    snippets_prompt: "Baustein einfügen: ",
+    snippets_hint: " (↑/↓ zum Auswählen, Enter zum Einfügen, Esc zum Abbrechen)",
+    trash_prompt: "Aus dem Papierkorb holen: ",
+    trash_hint: " (↑/↓ zum Auswählen, Enter zum Zurücklegen, Esc zum Abbrechen)",
+    spelling_prompt: "Tippfehler korrigieren: ",
+    spelling_hint: " (↑/↓ zum Auswählen, Enter zum Ändern, Esc wenn fertig)",
+    views_prompt: "Ansichten: ",
+    views_hint: " (Enter zum Anzeigen, Strg+A speichert die aktuelle Ansicht, Strg+D zum Entfernen, Esc zum Schließen)",
+    view_name_prompt: "Aktuelle Ansicht speichern als: ",
+    view_name_hint: " (ein vorhandener Name wird ersetzt; Enter zum Speichern, Esc zum Abbrechen)",
+    filter_prompt: "Filter: ",
+    filter_hint: " (tippen zum Eingrenzen, ↑/↓ zum Bewegen, Enter zum Behalten, Esc zum Löschen)",
+    open_file_prompt: "Datei zum Öffnen wählen: ",
+    open_file_hint: " (Tippen filtert, ↑/↓ zum Auswählen, Tab sortiert, Enter zum Öffnen, Esc zum Abbrechen)",
+    library_prompt: "Board öffnen: ",
+    library_hint: " (↑/↓ zum Auswählen, Enter zum Öffnen, Esc zum Abbrechen)",
+    library_new_board_hint: " (↑/↓ zum Auswählen, Enter zum Öffnen, Esc für ein neues Board)",
+    places_count: "Orte: {} ",
+    type_to_search: "(tippen zum Suchen) ",
+    comments_count: "Kommentare: {} ",
+    notes_line: "Notizen: {}",
+
+    reopen_prompt: "{} wieder öffnen? ",
+    reopen_hint: "(J/Enter zum Öffnen, N/Esc für den Startbildschirm)",
+    recover_prompt: "Ungespeicherte Änderungen an {} vom {} wiederherstellen? ",
+    recover_hint: "(J/Enter zum Wiederherstellen, N/Esc zum Verwerfen)",
+    locked_prompt: "Anderswo geöffnet von {}. Nur lesend öffnen? ",
+    locked_hint: "(J/Enter zum Ansehen ohne Bearbeiten, N/Esc zum trotzdem Bearbeiten)",
+    delete_prompt: "'{}' mit {} Element(en) und {} Verbindung(en) löschen? ",
+    delete_hint: "(J/Enter zum Bestätigen, N/Esc zum Abbrechen)",
+    unknown: "Unbekannt",
+    tutorial_title: "Tutorial {}/{} (Esc ohne Weg zurück beendet es)",
+    tutorial_done: "Tutorial geschafft",
+    panel_title: "{} (Esc zum Schließen)",
+    sketch_title: "Skizze: {}",
+    unreadable_image: "Dieses Bild lässt sich nicht lesen",
+    progress_title: "Fortschritt: {}/{} gebaut",
+    progress_points_title: "Fortschritt: {}/{} gebaut, {}",
+    history_line: "Verlauf: {}",
+    undo_line: "Alt+Shift+Z: zurück zu vor {}",
+    empty_board: "Noch keine Orte. Strg+N legt einen Ort an.",
+    controls: "Tasten:",
+    control_new_place: "  Strg+N - Neuer Ort",
+    control_open: "  Strg+O - Datei öffnen",
+    control_start: "  Enter  - Startbildschirm: letztes Board und Beispiele",
+    control_quit: "  Strg+Q - Beenden",
+    empty_inner_board: "In {} ist noch nichts. Strg+N fügt einen Ort hinzu.",
+    control_back_out: "  Alt+↑  - Zurück ins Board drumherum",
+    breadboarding: "Breadboarding",
+    breadboarding_intro: "Ein Breadboard skizziert ein Feature in Worten statt Bildern: die Orte, an denen ein Nutzer \
+        sein kann (Bildschirme, Dialoge, E-Mails), die Elemente darin (Knöpfe, Felder, Links) und wohin jedes \
+        Element führt. Es klärt den Ablauf, bevor jemand über das Layout streitet. Orte anlegen, auflisten, was \
+        sich in jedem tun lässt, die Elemente verbinden und dann den Verbindungen folgen, um zu sehen, ob der Ablauf trägt.",
+    start_title: "Start",
+    start_new_board: "Neues Board anlegen",
+    start_tutorial: "Tutorial machen",
+    start_recent: "Zuletzt geöffnet: {}",
+    start_open: "Board-Datei öffnen…",
+    start_example: "Beispiel laden: {}",
+    start_new_board_key: "  Strg+N",
+    start_open_key: "  Strg+O",
+    no_matches: "Keine Orte oder Elemente passen zum Filter",
+    no_places_found: "Keine Orte gefunden",
+    current_connection: " (aktuell)",
+    this_place: " (dieser Ort)",
+    connect_title: "Ort zum Verbinden wählen (Strg+R entfernt die Verbindung)",
+    looking_for_boards: "Suche Boards…",
+    no_files_in: "Keine TOML-Dateien in {} gefunden",
+    no_files: "Keine TOML-Dateien im aktuellen Verzeichnis gefunden",
+    no_files_match: "Keine Dateien passen zu „{}“",
+    file_places: " ({} Orte)",
+    files_by_name_title: "Datei zum Öffnen wählen (nach Name, Tab: neueste zuerst)",
+    files_by_date_title: "Datei zum Öffnen wählen (neueste zuerst, Tab: nach Name)",
+    files_found: " {} {} gefunden",
+    library_places: "  {} Orte  {}  {}",
+    workspace_title: "Arbeitsbereich: {}",
+    boards_in_title: "Boards in {}",
+    boards_title: "Boards",
+    via: " über {}",
+    steps_away: " ({} Schritte entfernt)",
+    reach_title: "Orte, die {} erreichen können (Tab: nur direkte Links)",
+    linking_title: "Orte mit Links zu {} (Tab: jeder Ort, der ihn erreichen kann)",
+    not_connected: " (nicht verbunden)",
+    no_outcomes: "Noch keine Ergebnisse. Strg+A fügt eines hinzu, etwa „Erfolg“ oder „Fehler“.",
+    outcomes_title: "Ergebnisse von {}",
+    back_and_forth: " (hin und zurück)",
+    cycles_title: "Zyklen",
+    commands_title: "Befehle",
+    snippets_for_title: "Bausteine für {}",
+    snippets_title: "Bausteine",
+    in_the_notes: "  in den Notizen",
+    typos_title: "Tippfehler in {}",
+    trash_title: "Papierkorb ({})",
+    no_views: "Noch keine Ansichten: Strg+A speichert die aktuelle",
+    views_title: "Ansichten ({})",
+    icons_title: "Symbole (Tab/Enter zum Einsetzen)",
+    nothing_bound_yet: "Noch nichts belegt",
+    leader_title: "{} … (Esc zum Abbrechen)",
+    jump_title: "Zu Ort springen: {}",
+    full_text_title: "Volltext",
+    rabbit_holes_title: "Kaninchenbaue",
+    history_title: "Verlauf",
+    glossary_title: "Glossar",
+    notes_title: "Notizen",
+    command_failed: "{} fehlgeschlagen",
+    unsaved_board: "ungespeichertes Board",
+    as_saved: "{} wie gespeichert",
+    collapsed_legend: "≡ Elemente  ← verlinkt von  → führt zu  ✎ Notizen",
+    external_systems: "── Externe Systeme ──",
+    progress_total: "Gesamt",
+    progress_cut: " ({} gestrichen)",
+    point: "~{} Pkt.",
+    points: "~{} Pkt.",
+    snapshot: "1 Stand",
+    snapshots: "{} Stände",
+    history_size: "{}, ~{} KB",
+    an_unsaved_board: "einem ungespeicherten Board",
+    file_unreadable: "nicht lesbar: {}",
+    file_not_toml: "kein gültiges TOML: {}",
+    file_not_board: "kein Breadboard",
+    trashed_place: "Ort",
+    trashed_affordance_count: "1 Element",
+    trashed_affordances_count: "{} Elemente",
+    trashed_connection_in: "1 Verbindung hierher",
+    trashed_connections_in: "{} Verbindungen hierher",
+    trashed_affordance: "Element in {}",
+    trashed_connected_affordance: "Element in {}, verbunden",
+    saved_view_outline: "Gliederung",
+    saved_view_collapsed: "eingeklappt",
+    saved_view_graph: "Graph",
+    saved_view_full: "voll",
+    saved_view_titles: "Titel",
+    saved_view_dots: "Punkte",
+    saved_view_swimlanes: "Bahnen",
+    saved_view_filter: "Filter \"{}\"",
+
+    group_line: "Gruppe: {}",
+    linked_from_line: "Verlinkt von: {}",
+    leads_to_line: "Führt zu: {}",
+    nowhere_yet: "noch nirgendwohin",
+    outcome_line: "Wenn {}: {}",
+    estimate_line: "Schätzung: {} ({})",
+    attachments_line: "Anhänge: {}",
+    comments_line: "Kommentare ({}):",
+    added_by: "Hinzugefügt von {}, {}",
+    changed_by: "Geändert von {}, {}",
+    comment_line: "{} am {}: {}",
+
+    more_key_warnings: "{} (und {} weitere Tastenwarnungen im Log)",
+    read_only_refused: "Nur lesend: dieses Board lässt sich erkunden, aber nicht ändern",
+    drilled_into_empty: "In {}: Strg+N fügt einen Ort hinzu, Alt+↑ geht wieder hinaus",
+    select_place_to_drill: "Einen Ort zum Hineingehen wählen",
+    cut: "{} gestrichen",
+    uncut: "{} wiederhergestellt",
+    no_changes_below: "Keine weiteren Änderungen darunter",
+    no_changes_above: "Keine weiteren Änderungen darüber",
+    flagged: "{} als Kaninchenbau markiert",
+    unflagged: "{} nicht mehr als Kaninchenbau markiert",
+    no_rabbit_holes: "Keine Kaninchenbaue markiert: Strg+U markiert die Auswahl",
+    rabbit_hole_no_notes: "Noch keine Notizen (Alt+N)",
+    trash_empty: "Der Papierkorb ist leer: gelöschte Orte und Elemente landen hier",
+    no_search_to_repeat: "Noch keine Suche zum Wiederholen: Tippen sucht einen Ort",
+    search_match: "{} ({} von {} passend zu „{}“)",
+    no_search_match: "Keine Orte passen zu „{}“",
+    snapshot_restored: "Board wiederhergestellt, wie es vor {} war",
+    snapshots_left: "Board wiederhergestellt, wie es vor {} war ({} weitere Stände)",
+    no_snapshot: "Kein Stand zum Wiederherstellen: einer wird vor dem Löschen, Importieren und Verbinden vieler Elemente festgehalten",
+    no_icon: "kein Symbol",
+    select_place_to_attach: "Einen Ort wählen, um eine Datei anzuhängen",
+    select_affordance_to_estimate: "Ein Element zum Schätzen wählen",
+    no_history: "Noch kein Verlauf: jedes Speichern hält fest, was sich geändert hat",
+    no_glossary: "Noch kein Glossar: Begriffe unter [glossary] in der Board-Datei eintragen",
+    nothing_bound: "Auf {} liegt nichts",
+    no_workspace: "Kein Arbeitsbereich geöffnet",
+    no_commands: "Keine Befehle eingerichtet",
+    no_snippets: "Keine Bausteine eingerichtet",
+    select_place_for_snippet: "Einen Ort wählen, um einen Baustein einzufügen",
+    no_dictionary: "Kein Wörterbuch für die Rechtschreibprüfung; eines unter [spelling] in der Konfiguration angeben",
+    no_typos: "Keine Tippfehler in der Auswahl",
+    happy_path_step: "{} ist Schritt {} des Happy Path",
+    happy_path_removed: "{} aus dem Happy Path genommen",
+    no_marks: "Keine Markierungen",
+    marks_hint: "{} markiert: Strg+C verbindet alle mit einem Ort, Esc hebt auf",
+    no_sketch: "Mit Alt+J ein .png oder .jpg an den Ort hängen, um es hier zu sehen",
+    view_removed: "Ansicht {} entfernt",
+    nothing_connected: "Noch nichts verbunden: Strg+C auf einem Element verbindet es",
+    notes_imported: "{} aus den Notizen importiert",
+    commented: "{} kommentiert (Alt+Z zeigt die Unterhaltung)",
+    captured: "{} erfasst",
+    capture_failed: "Erfassen fehlgeschlagen: {}",
+    estimated: "{} auf {} geschätzt",
+    estimate_cleared: "Schätzung von {} gelöscht",
+    estimate_invalid: "Eine Schätzung ist eine Punktzahl oder XS, S, M, L oder XL",
+    view_needs_name: "Eine Ansicht braucht einen Namen",
+    view_saved: "Ansicht {} gespeichert",
+    view_updated: "Ansicht {} aktualisiert",
+    place_added: "Ort {} hinzugefügt",
+    load_failed: "{} ließ sich nicht laden: {}",
+    load_failed_kept: "{} ließ sich nicht laden, das offene Board bleibt: {}",
+    list_files_failed: "Board-Dateien ließen sich nicht auflisten: {}",
+    example_opened: "Beispiel {} geöffnet (Strg+S speichert eine Kopie)",
+    recover_failed: "Wiederherstellen fehlgeschlagen: {}",
+    compare_failed: "Vergleichen fehlgeschlagen: {}",
+    view_exported: "Ansicht exportiert nach {}",
+    view_export_failed: "Ansicht ließ sich nicht exportieren: {}",
+    document_exported: "{} exportiert nach {}",
+    document_export_failed: "{} ließ sich nicht exportieren: {}",
+    extract_failed: "Herauslösen nach {} fehlgeschlagen: {}",
+    places_copied: "{} Orte nach {} kopiert",
+    places_moved: "{} Orte nach {} verschoben, {} Verbindungen führen jetzt dorthin",
+    bundled: "Board und {} angehängte Dateien gebündelt in {}",
+    bundle_failed: "Bündeln fehlgeschlagen: {}",
+    attached: "{} an {} angehängt",
+    attach_failed: "Anhängen fehlgeschlagen: {}",
+    imported: "{} aus {} importiert",
+    import_failed: "{} ließ sich nicht importieren: {}",
+    restored_from_trash: "{} aus dem Papierkorb geholt",
+    showing_view: "Ansicht {} wird gezeigt",
+    outcome_not_connected: "Dieses Ergebnis ist noch nicht verbunden (Strg+C zum Verbinden)",
+    snippet_added: "{} Elemente aus {} hinzugefügt",
+    snippet_skipped: " ({} schon vorhanden)",
+    typo_fixed: "{} zu {} geändert",
+    not_in_workspace: "{} gehört nicht zum offenen Arbeitsbereich",
+    own_place: "{} ist der eigene Ort dieses Elements: nochmal Enter verbindet trotzdem",
+    own_place_outcome: "{} ist der eigene Ort dieses Ergebnisses: nochmal Enter verbindet trotzdem",
+    outcomes_stay_on_board: "Ergebnisse können nur zu Orten auf diesem Board führen",
+    marked_stay_on_board: "Markiertes lässt sich nur mit Orten auf diesem Board verbinden",
+    connected_affordance: "1 Element mit {} verbunden",
+    connected_affordances: "{} Elemente mit {} verbunden",
+    linked_place: "„Go to {}“ bei 1 Ort ergänzt",
+    linked_places: "„Go to {}“ bei {} Orten ergänzt",
+    already_leads_to: "Jeder markierte Ort führt schon zu {}",
+    connection_removed: "Verbindungen von 1 Element entfernt",
+    connections_removed: "Verbindungen von {} Elementen entfernt",
+    editing_anyway: "Trotzdem bearbeiten: Speichern kann das von {} überschreiben",
+    marks_cleared: "Markierungen aufgehoben",
+    tutorial_closed: "Tutorial geschlossen",
+    connection_swapped: "Verbindung umgedreht",
+    return_connection_added: "Rückverbindung hinzugefügt",
+    nothing_to_reverse: "Das gewählte Element hat keine Verbindung zum Umkehren",
+    happy_path_broken: "Gespeichert, aber der Happy Path ist unterbrochen: {}",
+    select_place_to_extract: "Einen Ort zum Herauslösen wählen",
+    clipboard_failed: "Zwischenablage ließ sich nicht lesen: {}",
+    no_places_in_notes: "Keine Orte in den Notizen: eine Zeile, die auf ':' endet, beginnt einen",
+    skipped_line: "\n\n1 weitere Zeile übersprungen",
+    skipped_lines: "\n\n{} weitere Zeilen übersprungen",
+    place_copied: "{} kopiert: Strg+P fügt ihn ein, hier oder auf einem anderen Board",
+    select_place_to_copy: "Einen Ort zum Kopieren wählen",
+    nothing_copied: "Nichts kopiert: Strg+Y kopiert den gewählten Ort",
+    pasted: "{} eingefügt",
+    pasted_remapped: "; mit Orten hier verbunden: {}",
+    pasted_dropped: "; nicht auf diesem Board, unverbunden gelassen: {}",
+    view_copied: "Ansicht in die Zwischenablage kopiert",
+    view_copy_failed: "Ansicht ließ sich nicht kopieren: {}",
+    notes_saved: "Notizen gespeichert",
+    no_notes: "Keine Notizen",
+    recovered: "Änderungen von {} wiederhergestellt (Strg+S speichert sie)",
+    compared_with: "Verglichen mit {}: {}",
+    no_differences_from: "Keine Unterschiede zu {}",
+    lock_failed: "Board ließ sich nicht sperren: {}",
+    command_running: "{} läuft…",
+    command_finished: "{} fertig",
+    not_connected_yet: "Noch nicht verbunden: Strg+C verbindet es",
+    no_incoming: "Keine Orte verlinken auf {}",
+    no_cycles: "Keine Zyklen: jeder Weg durch das Board endet",
+    hook_title: "on_{}-Hook",
+    imported_counts: "{} Orte und {} Elemente",
+    snapshot_deleting: "dem Löschen von {}",
+    snapshot_importing_notes: "dem Import der Notizen",
+    snapshot_importing: "dem Import von {}",
+    snapshot_connecting: "dem Verbinden von {} markierten Elementen",
+    snapshot_moving: "dem Verschieben von Orten nach {}",
+    snapshot_recovering: "der Wiederherstellung ungespeicherter Änderungen",
+
+    key_no_action: "Taste {}: keine Aktion namens \"{}\"",
+    key_may_not_work: "Taste {} für {} geht vielleicht nicht: {}",
+    key_invalid: "Taste {}: {}",
+    key_sent_as_ctrl: "die meisten Terminals senden sie als Strg+{}",
+    key_suspends: "Strg+Z hält bboard an",
+    key_ctrl_digit: "die meisten Terminals senden Strg nicht mit Ziffern",
+    key_sent_as: "Terminals senden sie als {}",
+    key_modifier_dropped: "die meisten Terminals senden sie ohne die Zusatztaste",
+    leader_may_not_work: "Leader-Taste {} geht vielleicht nicht: {}",
+    leader_invalid: "Leader-Taste {}: {}",
+    leader_no_action: "Leader {}: keine Aktion namens \"{}\"",
+    leader_no_keys: "Leader-Folge für {} hat keine Tasten",
+    leader_invalid_sequence: "Leader {}: {}",
+    leader_unreachable: "Leader-Folge für {} ist nicht tippbar: {} kommt zuerst",
+    leader_file: "+Datei",
+    leader_place: "+Ort",
+    leader_view: "+Ansicht",
+    leader_export: "+Export",
+    leader_show: "+Anzeigen",
+    leader_more: "+mehr",
+    key_looks_like_another: "Terminals senden sie wie eine andere Taste",
+
+    tutorial_create_place: "Ein Ort ist, wo der Nutzer sein kann: ein Bildschirm, ein Dialog, eine E-Mail. \
+        Strg+N drücken, einen Namen wie „Rechnung“ tippen und Enter drücken.",
+    tutorial_second_place: "Den Ort hinzufügen, zu dem der erste führt, etwa „Lastschrift einrichten“: \
+        wieder Strg+N, ein Name, Enter.",
+    tutorial_add_affordance: "Elemente sind, was der Nutzer an einem Ort tun kann. Mit Tab einen Ort wählen, \
+        Strg+A drücken und eines benennen, etwa „Lastschrift einschalten“.",
+    tutorial_connect: "Sagen, wohin es führt: mit dem Element gewählt Strg+C drücken, den Namen \
+        des anderen Orts tippen und Enter drücken.",
+    tutorial_follow: "Den Ablauf abgehen: das verbundene Element mit ↓ wählen und Enter drücken, um ihm \
+        zu folgen. Rücktaste führt zurück.",
+    tutorial_save: "Strg+S drücken, einen Dateinamen wie „autopay.toml“ tippen und Enter drücken.",
+    tutorial_done_prompt: "Das ist Breadboarding: Orte, was sich in ihnen tun lässt, und wohin es führt. \
+        Esc schließt das Tutorial und behält das Board.",
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fill() {
+        assert_eq!(fill(ENGLISH.connect_marked_prompt, &["3"]), "Connect 3 marked to: ");
+        assert_eq!(fill(GERMAN.delete_prompt, &["Invoice", "2", "1"]), "'Invoice' mit 2 Element(en) und 1 Verbindung(en) löschen? ");
+        assert_eq!(fill("no values", &["left over"]), "no values");
+        assert_eq!(around(GERMAN.reopen_prompt), ("", " wieder öffnen? "));
+        assert_eq!(fill(GERMAN.search_match, &["Invoice", "1", "2", "inv"]), "Invoice (1 von 2 passend zu „inv“)");
+    }
+}
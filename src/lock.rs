@@ -0,0 +1,84 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// A lock file next to an open board, ".name.toml.lock", saying who has it
+// open. It's advisory: it only lets a second bboard warn before two people
+// overwrite each other's saves, and works on shared drives where OS locks
+// often don't.
+#[derive(Debug)]
+pub struct BoardLock {
+    path: PathBuf,
+    holder: String, // What we wrote, to tell our lock from someone else's
+}
+
+impl BoardLock {
+    // Claim `board` for `author`, replacing any lock already there
+    pub fn acquire(board: &Path, author: &str) -> Result<Self> {
+        let path = lock_path(board);
+        let holder = format!(
+            "{} on {} (pid {}) since {}",
+            author,
+            hostname(),
+            std::process::id(),
+            chrono::Local::now().format("%Y-%m-%d %H:%M"),
+        );
+        fs::write(&path, &holder).with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(Self { path, holder })
+    }
+}
+
+impl Drop for BoardLock {
+    fn drop(&mut self) {
+        // Leave a lock someone took over since
+        if fs::read_to_string(&self.path).is_ok_and(|text| text == self.holder) {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+// Who else has `board` open, as their lock file says
+pub fn holder(board: &Path) -> Option<String> {
+    fs::read_to_string(lock_path(board)).ok()
+        .map(|text| text.trim().to_string())
+        .filter(|text| !text.is_empty())
+}
+
+pub fn lock_path(board: &Path) -> PathBuf {
+    let name = board.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    board.with_file_name(format!(".{}.lock", name))
+}
+
+fn hostname() -> String {
+    std::env::var("HOSTNAME").ok()
+        .or_else(|| fs::read_to_string("/etc/hostname").ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "another computer".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lock_is_seen_and_released() {
+        let dir = tempfile::tempdir().unwrap();
+        let board = dir.path().join("billing.toml");
+        assert_eq!(lock_path(&board), dir.path().join(".billing.toml.lock"));
+        assert_eq!(holder(&board), None);
+
+        let lock = BoardLock::acquire(&board, "kim").unwrap();
+        let held = holder(&board).unwrap();
+        assert!(held.starts_with("kim on "));
+        assert!(held.contains(&format!("(pid {})", std::process::id())));
+        drop(lock);
+        assert_eq!(holder(&board), None);
+
+        // Someone who took the lock over keeps it
+        let lock = BoardLock::acquire(&board, "kim").unwrap();
+        fs::write(lock_path(&board), "sam on laptop").unwrap();
+        drop(lock);
+        assert_eq!(holder(&board).as_deref(), Some("sam on laptop"));
+    }
+}
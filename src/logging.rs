@@ -0,0 +1,83 @@
+use anyhow::{Context, Result};
+use std::fs::{self, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing_subscriber::filter::LevelFilter;
+
+// Logs for debugging reported problems, as JSON lines in
+// `$XDG_STATE_HOME/bboard/bboard.log` (beside the saved UI state). They go
+// to a file because stdout and stderr belong to the TUI. Warnings and errors
+// are logged unless `--log-level` says otherwise; `debug` adds file IO and
+// every key action, `trace` the time each frame took to draw.
+
+const MAX_LOG_BYTES: u64 = 1024 * 1024; // Past this the log starts over, keeping one old file
+
+pub fn default_path() -> Option<PathBuf> {
+    crate::state::SavedState::default_path().map(|state| state.with_file_name("bboard.log"))
+}
+
+// "warn", "debug", "off" and so on, as --log-level takes them
+pub fn parse_level(level: &str) -> Result<LevelFilter> {
+    level.parse().map_err(|_| anyhow::anyhow!("unknown log level {}: use off, error, warn, info, debug or trace", level))
+}
+
+// Start logging to the log file at `level`, or warnings and up. Where the
+// file can't be written, nothing is logged and bboard runs on.
+pub fn init(level: Option<LevelFilter>) {
+    let level = level.unwrap_or(LevelFilter::WARN);
+    if level == LevelFilter::OFF {
+        return;
+    }
+    let Some(path) = default_path() else {
+        return;
+    };
+    let Ok(file) = open(&path) else {
+        return;
+    };
+    tracing_subscriber::fmt()
+        .json()
+        .with_max_level(level)
+        .with_writer(Mutex::new(file))
+        .with_ansi(false)
+        .init();
+
+    // A panic is logged too, as the terminal it prints to is usually gone
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        tracing::error!(panic = %info, "bboard panicked");
+        default_hook(info);
+    }));
+    tracing::info!(version = env!("CARGO_PKG_VERSION"), "started");
+}
+
+// The log file for appending, moving a full one to bboard.log.1 first
+fn open(path: &Path) -> Result<fs::File> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    if fs::metadata(path).is_ok_and(|m| m.len() > MAX_LOG_BYTES) {
+        fs::rename(path, path.with_extension("log.1"))?;
+    }
+    OpenOptions::new().create(true).append(true).open(path)
+        .with_context(|| format!("Failed to open {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_level_and_rotate() {
+        assert_eq!(parse_level("debug").unwrap(), LevelFilter::DEBUG);
+        assert_eq!(parse_level("OFF").unwrap(), LevelFilter::OFF);
+        assert!(parse_level("loud").is_err());
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("logs/bboard.log");
+        open(&path).unwrap();
+        fs::write(&path, vec![b'x'; MAX_LOG_BYTES as usize + 1]).unwrap();
+        open(&path).unwrap();
+        assert_eq!(fs::metadata(&path).unwrap().len(), 0);
+        assert!(dir.path().join("logs/bboard.log.1").exists());
+    }
+}
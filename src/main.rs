@@ -8,23 +8,100 @@ use crossterm::{
     execute,
 };
 use std::io;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 mod app;
 mod models;
 mod ui;
 mod input;
 mod file;
+mod config;
+mod layout;
+mod diagram;
+mod lint;
+mod analysis;
+mod export;
+mod import;
+mod extract;
+mod cli;
+mod rpc;
+mod api;
+mod hooks;
+mod editor;
+mod state;
+mod workspace;
+mod examples;
+mod tutorial;
+mod glossary;
+mod changelog;
+mod lock;
+mod bundle;
+mod sketch;
+mod diff;
+mod trash;
+mod history;
+mod print;
+mod html;
+mod site;
+mod watch;
+mod logging;
+mod recovery;
+mod naming;
+mod spelling;
+mod icons;
+mod locale;
+mod keys;
+mod scan;
+mod mermaid;
+mod svg;
 
-use app::{App, Selection};
+use app::{App, ExportScope, SaveTarget, Selection, StartItem};
+use models::ViewMode;
 use input::{InputHandler, Action, Mode};
 use ui::UI;
 use file::FileManager;
+use config::{Config, EnterOnPlace, ReopenLastBoard};
 use anyhow::Result;
 
+// Frames that take longer than this to draw are logged at debug level
+const SLOW_FRAME: Duration = Duration::from_millis(50);
+
 fn main() -> Result<()> {
-    // Parse command line arguments
-    let args: Vec<String> = std::env::args().collect();
-    let filename = args.get(1);
+    let cli = cli::parse();
+    logging::init(cli.log_level);
+    // The board to open, whether it's one to create, and an old version to
+    // compare it with
+    let (file, new, compare_with) = match cli.command {
+        None => (cli.board, false, None),
+        Some(cli::Commands::Open { board }) => (board, false, None),
+        Some(cli::Commands::New { board }) => (Some(board), true, None),
+        Some(cli::Commands::Diff { old, new }) => (Some(new), false, Some(old)),
+        Some(command) => std::process::exit(cli::run_headless(command)),
+    };
+    if new && file.as_deref().is_some_and(|file| Path::new(file).exists()) {
+        eprintln!("{} already exists: bboard open it instead", file.unwrap_or_default());
+        std::process::exit(cli::EXIT_ERROR);
+    }
+    // A config named on the command line has to be there and parse
+    let config = match &cli.config {
+        Some(path) => match Config::load_from(Path::new(path)) {
+            Ok(config) => Ok(config),
+            Err(e) => {
+                eprintln!("{:#}", e);
+                std::process::exit(cli::EXIT_ERROR);
+            }
+        },
+        None => Config::load(),
+    };
+
+    // A SIGTSTP from outside (`kill -TSTP`) only sets this flag, so the loop
+    // can hand the terminal back before stopping
+    let suspend_requested = Arc::new(AtomicBool::new(false));
+    #[cfg(unix)]
+    signal_hook::flag::register(signal_hook::consts::SIGTSTP, Arc::clone(&suspend_requested))?;
 
     // Setup terminal
     enable_raw_mode()?;
@@ -35,21 +112,50 @@ fn main() -> Result<()> {
 
     // Create app and UI
     let mut app = App::new();
+    app.state.read_only = cli.read_only || compare_with.is_some();
+    match config {
+        Ok(config) => app.config = config,
+        Err(e) => app.state.status_message = Some(format!("{:#}", e)),
+    }
+    match spelling::Dictionary::load(&app.config.spelling) {
+        Ok(dictionary) => app.dictionary = dictionary.map(Arc::new),
+        Err(e) => app.state.status_message = Some(format!("{:#}", e)),
+    }
     let mut ui = UI::new();
-    let input_handler = InputHandler::new();
+    let s = app.config.language.strings();
+    let (keymap, key_warnings) = keys::Keymap::new(&app.config.keys, s);
+    for warning in &key_warnings {
+        tracing::warn!(warning = %warning, "key binding");
+    }
+    if let Some(first) = key_warnings.first().filter(|_| app.state.status_message.is_none()) {
+        app.state.status_message = Some(match key_warnings.len() {
+            1 => first.clone(),
+            count => locale::fill(s.more_key_warnings, &[first, &(count - 1).to_string()]),
+        });
+    }
+    let mut input_handler = InputHandler::new(keymap);
     let file_manager = FileManager::new();
 
+    // Without a file on the command line, the last board is opened or
+    // offered, as configured
+    let last_board = state::SavedState::load().reopenable_board().map(str::to_string);
+    let mut offered_board = None;
+    let filename = match (file.clone().filter(|_| !new), app.config.reopen_last_board) {
+        (Some(file), _) => Some(file),
+        (None, ReopenLastBoard::Always) => last_board,
+        (None, ReopenLastBoard::Ask) => {
+            offered_board = last_board;
+            None
+        }
+        (None, ReopenLastBoard::Never) => None,
+    };
+
     // Load file from command line or create sample data
     let mut loaded_from_file = false;
     if let Some(file) = filename {
         let file_str = file.clone();
-        match file_manager.load_from_file(file) {
-            Ok(mut breadboard) => {
-                breadboard.sync_id_counters();
-                app.breadboard = breadboard;
-                app.state.current_filename = Some(file_str);
-                loaded_from_file = true;
-            }
+        match open_board(&mut app, &file_manager, &file) {
+            Ok(()) => loaded_from_file = true,
             Err(e) => {
                 // Restore terminal before showing error
                 disable_raw_mode()?;
@@ -64,34 +170,111 @@ fn main() -> Result<()> {
         }
     }
 
-    // Start with blank board and prompt for first place name if no file was loaded
+    if let Some(file) = file.as_deref().filter(|_| new) {
+        start_new_board(&mut app, file);
+        loaded_from_file = true;
+    }
+
+    // Without a board, start on the start screen. Declining the library or
+    // the last board goes there too.
     if !loaded_from_file {
-        let default_name = "Place 1".to_string();
-        let place_id = app.breadboard.generate_place_id();
-        let place = models::Place::new(place_id, default_name.clone());
+        app.start_screen(recent_board());
+        app.state.mode = Mode::Start;
+        // Someone who has never used a board gets the tutorial offered first
+        if state::SavedState::default_path().is_some_and(|path| !path.exists()) {
+            app.state.selected_start_index = app.state.start_items.iter().position(|item| *item == StartItem::Tutorial);
+        }
+        if app.start_library(&file_manager, offered_board.as_deref()) {
+            app.state.mode = Mode::Library;
+        } else if offered_board.is_some() {
+            app.state.mode = Mode::ConfirmReopen;
+            app.state.reopen_candidate = offered_board;
+        }
+    }
 
-        app.breadboard.add_place(place);
+    // Edits left by a bboard that didn't exit cleanly are offered back: those
+    // to the board opened, or without one the latest to any board
+    let journal_dir = recovery::default_dir();
+    let board_file = app.state.current_filename.clone().filter(|_| loaded_from_file);
+    let recovered = journal_dir.as_deref().and_then(|dir| recovery::find(dir, board_file.as_deref()));
+    // The board stays locked by a bboard that crashed, and that lock is
+    // taken over; one held by anyone else leaves the edits for later
+    let left_locked = app.state.locked_by.as_deref()
+        .map(|holder| recovered.as_ref().is_some_and(|recovered| recovered.left_lock(holder)));
+    if let Some(recovered) = recovered.filter(|_| left_locked != Some(false)) {
+        if let (Some(true), Some(file)) = (left_locked, &board_file) {
+            app.state.locked_by = None;
+            take_lock(&mut app, file);
+        }
+        app.state.recovery_candidate = Some(recovered);
+        app.state.mode = Mode::ConfirmRecover;
+    }
+    let mut journal = journal_dir.map(recovery::Journal::new);
 
-        // Select the new place and enter edit mode
-        app.state.selection = Some(Selection::Place(place_id));
-        app.state.mode = Mode::Edit;
-        app.state.edit_buffer = default_name;
-    } else {
-        // Set initial selection for loaded files
-        if let Some(first_place) = app.breadboard.places.first() {
-            app.state.selection = Some(Selection::Place(first_place.id));
+    if let Some(old) = compare_with.filter(|_| loaded_from_file) {
+        if let Err(e) = open_comparison(&mut app, &file_manager, &old) {
+            app.state.status_message = Some(locale::fill(s.compare_failed, &[&format!("{:#}", e)]));
         }
     }
 
     // Main event loop
     while !app.should_quit {
-        terminal.draw(|f| ui.render::<CrosstermBackend<std::io::Stdout>>(f, &mut app))?;
+        app.poll_file_scan();
+        app.poll_commands();
+        let started = Instant::now();
+        terminal.draw(|f| ui.render(f, &mut app))?;
+        let elapsed = started.elapsed();
+        if elapsed > SLOW_FRAME {
+            tracing::debug!(ms = elapsed.as_millis() as u64, mode = ?app.state.mode, "slow frame");
+        } else {
+            tracing::trace!(us = elapsed.as_micros() as u64, "frame");
+        }
+        // Images go straight to the terminal, over what was just drawn
+        if let Some(graphics) = ui.take_graphics() {
+            if graphics.redraw {
+                terminal.clear()?;
+                terminal.draw(|f| ui.render(f, &mut app))?;
+            }
+            io::Write::write_all(terminal.backend_mut(), graphics.sequence.as_bytes())?;
+            io::Write::flush(terminal.backend_mut())?;
+        }
 
-        if let Ok(action) = input_handler.read_action(app.state.mode.clone()) {
-            handle_action(&mut app, &file_manager, action)?;
+        let action = input_handler.read_action(app.state.mode.clone());
+        if let Some(action) = action.as_ref().ok().filter(|action| !matches!(action, Action::None)) {
+            tracing::debug!(action = ?action, mode = ?app.state.mode, "action");
+            if !matches!(action, Action::Leader(_)) {
+                app.state.leader = None;
+            }
+            if let Some(journal) = journal.as_mut() {
+                journal.note_change();
+            }
+        }
+        match action {
+            Ok(action) if app.is_read_only() && app.state.mode != Mode::Start && action.edits_board() => {
+                app.state.status_message = Some(s.read_only_refused.to_string());
+            }
+            // The editor needs the terminal, which only the loop owns
+            Ok(Action::EditNotes) => handle_edit_notes(&mut terminal, &mut app)?,
+            Ok(Action::Suspend) => suspend_to_shell(&mut terminal)?,
+            Ok(action) => handle_action(&mut app, &file_manager, action)?,
+            Err(e) => tracing::warn!(error = %e, "failed to read input"),
+        }
+        app.advance_tutorial();
+        if let Some(journal) = journal.as_mut() {
+            if let Err(e) = journal.flush(&app, false) {
+                tracing::warn!(error = %format!("{:#}", e), "failed to write recovery file");
+            }
+        }
+        if suspend_requested.swap(false, Ordering::Relaxed) {
+            suspend_to_shell(&mut terminal)?;
         }
     }
 
+    remember_board_state(&app);
+    if let Some(journal) = journal.as_mut() {
+        journal.discard();
+    }
+
     // Restore terminal
     disable_raw_mode()?;
     execute!(
@@ -105,6 +288,41 @@ fn main() -> Result<()> {
 }
 
 fn handle_action(app: &mut App, file_manager: &FileManager, action: Action) -> Result<()> {
+    let s = app.config.language.strings();
+    // A count typed before a navigation key runs it that many times;
+    // anything else drops the count
+    if !matches!(action, Action::None | Action::Edit(_)) {
+        if let Some(count) = app.take_count().filter(|_| action.repeats()) {
+            for _ in 1..count {
+                handle_action(app, file_manager, action.clone())?;
+            }
+        }
+    }
+
+    // The full text includes the status message, before it goes
+    if matches!(action, Action::ShowFullText) {
+        let text = ui::full_text(app, app.state.status_message.as_deref());
+        app.state.status_message = None;
+        if !text.is_empty() {
+            app.state.output_panel = Some((s.full_text_title.to_string(), text));
+        }
+        return Ok(());
+    }
+
+    // A click picks what was drawn under it, status message included
+    if let Action::Click { column, row } = action {
+        if let Some(crumb) = ui::breadcrumb_at(app, column, row) {
+            app.choose_crumb(&crumb);
+        }
+        app.state.status_message = None;
+        return Ok(());
+    }
+
+    // Status messages only live until the next key press
+    if !matches!(action, Action::None) {
+        app.state.status_message = None;
+    }
+
     match action {
         Action::Quit => app.should_quit = true,
 
@@ -113,31 +331,317 @@ fn handle_action(app: &mut App, file_manager: &FileManager, action: Action) -> R
         Action::NavigateRight => navigate_right(app),
         Action::NavigateLeft => navigate_left(app),
 
+        Action::JumpUp => {
+            app.jump_up();
+        }
+        Action::EndPlace { last } | Action::EndAffordance { last } if app.state.is_searching_places => app.list_end(last),
+        Action::EndPlace { last } => app.select_end_place(last),
+        Action::EndAffordance { last } => app.select_end_affordance(last),
+        Action::PageList { down } => app.page_list(down),
+        Action::ListEnd { last } => app.list_end(last),
+        Action::DrillIn => match app.drill_in() {
+            Some(name) if app.breadboard.places.is_empty() => {
+                app.state.status_message = Some(locale::fill(s.drilled_into_empty, &[&name]));
+            }
+            Some(_) => {}
+            None => app.state.status_message = Some(s.select_place_to_drill.to_string()),
+        },
         Action::Select => handle_select(app, file_manager),
         Action::Back => handle_back(app),
 
         Action::NewPlace => handle_new_place(app),
+        Action::NewAffordance if app.state.mode == Mode::Branches => handle_new_branch(app),
+        Action::NewAffordance if app.state.mode == Mode::Views => {
+            app.state.edit_buffer.clear();
+            app.state.mode = Mode::ViewName;
+        }
         Action::NewAffordance => handle_new_affordance(app),
+        Action::RemoveConnection if app.state.mode == Mode::Connect => complete_connection(app, false, true),
         Action::RemoveConnection => handle_remove_connection(app),
+        Action::ToggleCut => {
+            if let Some((name, cut)) = app.toggle_selected_cut() {
+                let message = if cut { s.cut } else { s.uncut };
+                app.state.status_message = Some(locale::fill(message, &[&name]));
+            }
+        }
+        Action::Compare => {
+            app.state.save_filename = app.state.current_filename.clone().unwrap_or_default();
+            app.state.save_target = SaveTarget::Compare;
+            app.state.mode = Mode::SaveFile;
+        }
+        Action::Scroll(rows) => {
+            if let Some(comparison) = &mut app.state.comparison {
+                comparison.scroll_by(rows);
+            }
+        }
+        Action::JumpToChange { forward } => {
+            if let Some(comparison) = &mut app.state.comparison {
+                if !comparison.jump_to_change(forward) {
+                    let message = if forward { s.no_changes_below } else { s.no_changes_above };
+                    app.state.status_message = Some(message.to_string());
+                }
+            }
+        }
+        Action::ToggleRabbitHole => {
+            if let Some((name, flagged)) = app.toggle_selected_rabbit_hole() {
+                let message = if flagged { s.flagged } else { s.unflagged };
+                app.state.status_message = Some(locale::fill(message, &[&name]));
+            }
+        }
+        Action::ShowRabbitHoles => {
+            let holes = app.breadboard.rabbit_holes();
+            if holes.is_empty() {
+                app.state.status_message = Some(s.no_rabbit_holes.to_string());
+            } else {
+                let text = holes.into_iter()
+                    .map(|(name, notes)| match notes {
+                        Some(notes) => format!("⚑ {}\n{}", name, notes.lines().map(|line| format!("    {}", line)).collect::<Vec<_>>().join("\n")),
+                        None => format!("⚑ {}\n    {}", name, s.rabbit_hole_no_notes),
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n\n");
+                app.state.output_panel = Some((s.rabbit_holes_title.to_string(), text));
+            }
+        }
+        Action::ShowTrash => {
+            if app.state.trash.is_empty() {
+                app.state.status_message = Some(s.trash_empty.to_string());
+            } else {
+                app.state.selected_trash = Some(0);
+                app.state.mode = Mode::Trash;
+            }
+        }
+        Action::ShowViews => {
+            app.state.selected_view = (!app.breadboard.views.is_empty()).then_some(0);
+            app.state.mode = Mode::Views;
+        }
+        Action::PeekSearchResult => {
+            // Jump in the board behind the results, to see the place in context
+            if let Some(id) = app.get_selected_search_place().map(|p| p.id) {
+                app.state.selection = Some(Selection::Place(id));
+                app.remember_search();
+            }
+        }
+        Action::RepeatSearch { forward } => {
+            // From inside the search, its query is the one repeated
+            if app.state.is_searching_places {
+                app.remember_search();
+                app.clear_place_search();
+            }
+            app.state.status_message = Some(match app.state.search_history.last().cloned() {
+                None => s.no_search_to_repeat.to_string(),
+                Some(query) => match app.repeat_search(forward) {
+                    Some((name, at, of)) => locale::fill(s.search_match, &[&name, &at.to_string(), &of.to_string(), &query]),
+                    None => locale::fill(s.no_search_match, &[&query]),
+                },
+            });
+        }
+        Action::RestoreSnapshot => {
+            app.state.status_message = Some(match app.restore_snapshot() {
+                Some(label) if app.state.snapshots.is_empty() => locale::fill(s.snapshot_restored, &[&label]),
+                Some(label) => locale::fill(s.snapshots_left, &[&label, &app.state.snapshots.len().to_string()]),
+                None => s.no_snapshot.to_string(),
+            });
+        }
+        Action::CyclePlaceKind => {
+            if let Some((name, kind)) = app.cycle_selected_place_kind() {
+                let label = kind.map_or(s.no_icon, |k| k.label());
+                app.state.status_message = Some(format!("{}: {}", name, label));
+            }
+        }
+        Action::AdvanceStatus => {
+            if let Some((name, status)) = app.advance_selected_status() {
+                app.state.status_message = Some(format!("{}: {}", name, status.label()));
+            }
+        }
+        Action::ReverseConnection { remove_original } => handle_reverse_connection(app, remove_original),
 
         Action::ToggleCollapsed => app.toggle_collapsed(),
+        Action::ToggleGraphView => app.toggle_graph_view(),
+        Action::ToggleMinimap => app.toggle_minimap(),
+        Action::ToggleSwimlanes => app.toggle_swimlanes(),
+        Action::ToggleProgress => app.toggle_progress(),
+        Action::PanGraph(dx, dy) => {
+            if app.state.view == ViewMode::Graph {
+                app.pan_graph(dx, dy);
+            }
+        }
+        Action::ZoomGraph { zoom_in } => {
+            if app.state.view == ViewMode::Graph {
+                app.zoom_graph(zoom_in);
+            }
+        }
 
         Action::Save => handle_save(app, file_manager)?,
         Action::SaveAs => handle_save_as(app, file_manager)?,
-        Action::Open => handle_enter_open_mode(app, file_manager)?,
+        Action::ExportView => handle_export_view(app),
+        Action::ExportDocument(document) => handle_export_document(app, document),
+        Action::Extract { replace } => handle_extract(app, replace),
+        Action::ExportBundle => {
+            let stem = app.state.current_filename.as_deref()
+                .map(|f| f.strip_suffix(".toml").unwrap_or(f))
+                .unwrap_or("breadboard");
+            app.state.save_filename = format!("{}.bboard", stem);
+            app.state.save_target = SaveTarget::Bundle;
+            app.state.mode = Mode::SaveFile;
+        }
+        Action::Attach => {
+            if app.get_selected_place().is_some() {
+                app.state.save_filename.clear();
+                app.state.save_target = SaveTarget::Attachment;
+                app.state.mode = Mode::SaveFile;
+            } else {
+                app.state.status_message = Some(s.select_place_to_attach.to_string());
+            }
+        }
+        Action::PasteNotes => handle_paste_notes(app),
+        Action::AddComment => {
+            if app.state.selection.is_some() {
+                app.state.edit_buffer.clear();
+                app.state.mode = Mode::Comment;
+            }
+        }
+        Action::Estimate => match app.get_selected_affordance() {
+            Some(affordance) => {
+                app.state.edit_buffer = affordance.estimate.map(|e| e.to_string()).unwrap_or_default();
+                app.state.mode = Mode::Estimate;
+            }
+            None => app.state.status_message = Some(s.select_affordance_to_estimate.to_string()),
+        },
+        Action::Capture => {
+            app.state.capture_place = app.get_selected_place().map(|p| p.id);
+            app.state.edit_buffer.clear();
+            app.state.mode = Mode::Capture;
+        }
+        Action::CycleExportScope => {
+            if app.state.mode == Mode::SaveFile && matches!(app.state.save_target, SaveTarget::ViewSnapshot | SaveTarget::Document(_)) {
+                app.state.export_scope = app.state.export_scope.next();
+            }
+        }
+        Action::Import => {
+            app.state.save_filename = String::from("screens.csv");
+            app.state.mode = Mode::ImportFile;
+        }
+        Action::CopyView => handle_copy_view(app),
+        Action::ShowHistory => {
+            if app.breadboard.changelog.is_empty() {
+                app.state.status_message = Some(s.no_history.to_string());
+            } else {
+                app.state.output_panel = Some((s.history_title.to_string(), changelog::describe(&app.breadboard)));
+            }
+        }
+        Action::ShowGlossary => {
+            if app.breadboard.glossary.is_empty() {
+                app.state.status_message = Some(s.no_glossary.to_string());
+            } else {
+                let selected = app.selection_path().unwrap_or_default();
+                app.state.output_panel = Some((s.glossary_title.to_string(), glossary::describe(&app.breadboard.glossary, &selected)));
+            }
+        }
+        Action::CopyPlace => handle_copy_place(app),
+        Action::PastePlace => handle_paste_place(app),
+        Action::EditNotes | Action::Suspend => {} // Handled by the main loop
+        Action::Leader(pending) => app.state.leader = Some(pending),
+        Action::LeaderMissed(typed) => {
+            if !typed.is_empty() {
+                app.state.status_message = Some(locale::fill(s.nothing_bound, &[&typed]));
+            }
+        }
+        Action::SwitchBoard => {
+            if app.start_workspace_switcher(file_manager) {
+                app.state.mode = Mode::Library;
+            } else {
+                app.state.status_message = Some(s.no_workspace.to_string());
+            }
+        }
+        Action::ShowCommands => {
+            if app.config.commands.is_empty() {
+                app.state.status_message = Some(s.no_commands.to_string());
+            } else {
+                app.state.selected_command = Some(0);
+                app.state.mode = Mode::Commands;
+            }
+        }
+        Action::ShowSnippets => {
+            if app.config.snippets.is_empty() {
+                app.state.status_message = Some(s.no_snippets.to_string());
+            } else if app.get_selected_place().is_none() {
+                app.state.status_message = Some(s.select_place_for_snippet.to_string());
+            } else {
+                app.state.selected_snippet = Some(0);
+                app.state.mode = Mode::Snippets;
+            }
+        }
+        Action::ShowSpelling => {
+            let fixes = app.selection_fixes();
+            if app.dictionary.is_none() {
+                app.state.status_message = Some(s.no_dictionary.to_string());
+            } else if fixes.is_empty() {
+                app.state.status_message = Some(s.no_typos.to_string());
+            } else {
+                app.state.spelling_fixes = fixes;
+                app.state.selected_fix = Some(0);
+                app.state.mode = Mode::Spelling;
+            }
+        }
+        Action::Open => handle_enter_open_mode(app)?,
+        Action::EnterEditMode if app.state.mode == Mode::Branches => handle_edit_branch(app),
         Action::EnterEditMode => handle_enter_edit_mode(app),
+        Action::EnterConnectMode if app.state.mode == Mode::Branches => handle_connect_branch(app),
         Action::EnterConnectMode => handle_enter_connect_mode(app),
+        Action::Rewire => handle_rewire(app),
+        Action::ToggleReach => app.toggle_incoming_reach(),
+        Action::ToggleFileSort => app.toggle_file_sort(),
+        Action::ShowFullText | Action::Click { .. } => {}
+        Action::ToggleHappyPath => {
+            let name = app.get_selected_place().map(|p| p.name.clone()).unwrap_or_default();
+            app.state.status_message = match app.toggle_happy_path() {
+                Some(true) => Some(locale::fill(s.happy_path_step, &[&name, &app.breadboard.happy_path.len().to_string()])),
+                Some(false) => Some(locale::fill(s.happy_path_removed, &[&name])),
+                None => None,
+            };
+        }
+        Action::ToggleMark => {
+            app.toggle_mark();
+            app.state.status_message = Some(match app.state.marked.len() {
+                0 => s.no_marks.to_string(),
+                count => locale::fill(s.marks_hint, &[&count.to_string()]),
+            });
+        }
+        Action::ShowIncoming => handle_show_incoming(app),
+        Action::ShowCycles => handle_show_cycles(app),
+        Action::ShowBranches => {
+            if app.state.mode == Mode::Navigate && !app.state.is_searching_places && app.start_branch_list() {
+                app.state.mode = Mode::Branches;
+            }
+        }
+        Action::TogglePreview => app.toggle_preview(),
+        Action::ToggleSketch => {
+            app.state.show_sketch = !app.state.show_sketch;
+            let has_sketch = app.get_selected_place().and_then(sketch::image_attachment).is_some();
+            if app.state.show_sketch && !has_sketch {
+                app.state.status_message = Some(s.no_sketch.to_string());
+            }
+        }
+        Action::ConnectWithBack => {
+            if app.state.mode == Mode::Connect {
+                complete_connection(app, true, false);
+            }
+        }
+        Action::Delete if app.state.mode == Mode::Branches => app.remove_selected_branch(),
+        Action::Delete if app.state.mode == Mode::Views => {
+            if let Some(name) = app.remove_selected_view() {
+                app.state.status_message = Some(locale::fill(s.view_removed, &[&name]));
+            }
+        }
         Action::Delete => handle_delete(app),
 
         Action::Edit(text_change) => handle_edit(app, text_change),
 
         Action::Filter => {
-            // Simple filter - toggle filtering by currently connected places
-            app.state.filter = match app.state.filter.as_deref() {
-                None => Some("connected".to_string()),
-                Some("connected") => None,
-                Some(_) => Some("connected".to_string()),
-            };
+            // Enter filter mode, keeping any query that is already active
+            app.start_filter();
+            app.state.mode = Mode::Filter;
         }
 
         Action::None => {}
@@ -147,53 +651,47 @@ fn handle_action(app: &mut App, file_manager: &FileManager, action: Action) -> R
 }
 
 fn navigate_up(app: &mut App) {
+    if app.step_list(false) {
+        return;
+    }
     match app.state.mode {
-        Mode::Connect => {
-            // Navigate up in connection search results
-            if let Some(selected_index) = app.state.selected_connection_result {
-                if selected_index > 0 {
-                    app.state.selected_connection_result = Some(selected_index - 1);
-                }
-            }
-        }
-        Mode::OpenFile => {
-            // Navigate up in file list
-            if let Some(selected_index) = app.state.selected_file_index {
-                if selected_index > 0 {
-                    app.state.selected_file_index = Some(selected_index - 1);
-                }
-            }
+        Mode::Edit => {
+            app.state.selected_icon = app.state.selected_icon.saturating_sub(1);
         }
-        Mode::Navigate => {
+        Mode::Navigate | Mode::Filter => {
             if app.state.is_searching_places {
-                // Navigate up in place search results
-                if let Some(selected_index) = app.state.selected_place_result {
-                    if selected_index > 0 {
+                // Navigate up in place search results, and from the first
+                // one back through earlier searches
+                match app.state.selected_place_result {
+                    Some(selected_index) if selected_index > 0 => {
                         app.state.selected_place_result = Some(selected_index - 1);
                     }
+                    _ => {
+                        app.recall_search(true);
+                    }
                 }
             } else {
                 // Up: Navigate within place hierarchy
                 match &app.state.selection {
                     Some(Selection::Affordance { place_id, affordance_id }) => {
                         // If on an affordance, move to previous affordance or back to place
-                        if let Some(place) = app.breadboard.find_place(place_id) {
-                            if let Some(current_index) = place.affordances.iter().position(|a| &a.id == affordance_id) {
-                                if current_index > 0 {
-                                    // Move to previous affordance
-                                    app.state.selection = Some(Selection::Affordance {
-                                        place_id: *place_id,
-                                        affordance_id: place.affordances[current_index - 1].id,
-                                    });
-                                } else {
-                                    // Move back to the place itself
-                                    app.state.selection = Some(Selection::Place(*place_id));
-                                }
+                        let affordance_ids = app.visible_affordance_ids(place_id);
+                        if let Some(current_index) = affordance_ids.iter().position(|id| id == affordance_id) {
+                            if current_index > 0 {
+                                // Move to previous affordance
+                                app.state.selection = Some(Selection::Affordance {
+                                    place_id: *place_id,
+                                    affordance_id: affordance_ids[current_index - 1],
+                                });
+                            } else {
+                                // Move back to the place itself
+                                app.state.selection = Some(Selection::Place(*place_id));
                             }
                         }
                     }
-                    Some(Selection::Place(_)) => {
-                        // On a place, up does nothing (or could go to previous place)
+                    Some(Selection::Place(place_id)) => {
+                        let place_id = *place_id;
+                        app.select_end_of_previous_place(&place_id);
                     }
                     None => {
                         if let Some(first_place) = app.breadboard.places.first() {
@@ -208,57 +706,57 @@ fn navigate_up(app: &mut App) {
 }
 
 fn navigate_down(app: &mut App) {
+    if app.step_list(true) {
+        return;
+    }
     match app.state.mode {
-        Mode::Connect => {
-            // Navigate down in connection search results
-            if let Some(selected_index) = app.state.selected_connection_result {
-                if selected_index < app.state.connection_search_results.len() - 1 {
-                    app.state.selected_connection_result = Some(selected_index + 1);
-                }
-            }
-        }
-        Mode::OpenFile => {
-            // Navigate down in file list
-            if let Some(selected_index) = app.state.selected_file_index {
-                if selected_index < app.state.file_list.len() - 1 {
-                    app.state.selected_file_index = Some(selected_index + 1);
-                }
-            }
+        Mode::Edit if app.state.selected_icon + 1 < app.icon_matches().len() => {
+            app.state.selected_icon += 1;
         }
-        Mode::Navigate => {
+        Mode::Navigate | Mode::Filter => {
             if app.state.is_searching_places {
-                // Navigate down in place search results
-                if let Some(selected_index) = app.state.selected_place_result {
-                    if selected_index < app.state.place_search_results.len() - 1 {
+                // Navigate down in place search results, and from the last
+                // one forward through the searches recalled
+                match app.state.selected_place_result {
+                    Some(selected_index) if selected_index + 1 < app.state.place_search_results.len() => {
                         app.state.selected_place_result = Some(selected_index + 1);
                     }
+                    _ if app.state.search_history_index.is_some() => {
+                        app.recall_search(false);
+                    }
+                    _ => {}
                 }
             } else {
                 // Down: Navigate within place hierarchy
                 match &app.state.selection {
                     Some(Selection::Place(place_id)) => {
-                        // If on a place, move into first affordance if it exists
-                        if let Some(place) = app.breadboard.find_place(place_id) {
-                            if !place.affordances.is_empty() {
+                        // If on a place, move into first affordance if it
+                        // shows one, else on to the next place
+                        let place_id = *place_id;
+                        match app.visible_affordance_ids(&place_id).first().filter(|_| !app.state.collapsed) {
+                            Some(first_id) => {
                                 app.state.selection = Some(Selection::Affordance {
-                                    place_id: *place_id,
-                                    affordance_id: place.affordances[0].id,
+                                    place_id,
+                                    affordance_id: *first_id,
                                 });
                             }
+                            None => app.select_next_place(&place_id),
                         }
                     }
                     Some(Selection::Affordance { place_id, affordance_id }) => {
                         // If on an affordance, move to next affordance
-                        if let Some(place) = app.breadboard.find_place(place_id) {
-                            if let Some(current_index) = place.affordances.iter().position(|a| &a.id == affordance_id) {
-                                if current_index < place.affordances.len() - 1 {
-                                    // Move to next affordance
-                                    app.state.selection = Some(Selection::Affordance {
-                                        place_id: *place_id,
-                                        affordance_id: place.affordances[current_index + 1].id,
-                                    });
-                                }
-                                // If at last affordance, stay there
+                        let place_id = *place_id;
+                        let affordance_ids = app.visible_affordance_ids(&place_id);
+                        if let Some(current_index) = affordance_ids.iter().position(|id| id == affordance_id) {
+                            if current_index + 1 < affordance_ids.len() {
+                                // Move to next affordance
+                                app.state.selection = Some(Selection::Affordance {
+                                    place_id,
+                                    affordance_id: affordance_ids[current_index + 1],
+                                });
+                            } else {
+                                // From the last one, on to the next place
+                                app.select_next_place(&place_id);
                             }
                         }
                     }
@@ -282,18 +780,18 @@ fn navigate_right(app: &mut App) {
         None => None,
     };
 
+    let places = app.visible_place_ids();
     if let Some(current_id) = current_place_id {
-        let places = &app.breadboard.places;
-        if let Some(current_index) = places.iter().position(|p| p.id == current_id) {
+        if let Some(current_index) = places.iter().position(|id| *id == current_id) {
             if current_index < places.len() - 1 {
                 // Move to next place
-                app.state.selection = Some(Selection::Place(places[current_index + 1].id));
+                app.state.selection = Some(Selection::Place(places[current_index + 1]));
             }
         }
     } else {
         // No selection, go to first place
-        if let Some(first_place) = app.breadboard.places.first() {
-            app.state.selection = Some(Selection::Place(first_place.id));
+        if let Some(first_id) = places.first() {
+            app.state.selection = Some(Selection::Place(*first_id));
         }
     }
 }
@@ -306,23 +804,24 @@ fn navigate_left(app: &mut App) {
         None => None,
     };
 
+    let places = app.visible_place_ids();
     if let Some(current_id) = current_place_id {
-        let places = &app.breadboard.places;
-        if let Some(current_index) = places.iter().position(|p| p.id == current_id) {
+        if let Some(current_index) = places.iter().position(|id| *id == current_id) {
             if current_index > 0 {
                 // Move to previous place
-                app.state.selection = Some(Selection::Place(places[current_index - 1].id));
+                app.state.selection = Some(Selection::Place(places[current_index - 1]));
             }
         }
     } else {
         // No selection, go to first place
-        if let Some(first_place) = app.breadboard.places.first() {
-            app.state.selection = Some(Selection::Place(first_place.id));
+        if let Some(first_id) = places.first() {
+            app.state.selection = Some(Selection::Place(*first_id));
         }
     }
 }
 
 fn handle_select(app: &mut App, file_manager: &FileManager) {
+    let s = app.config.language.strings();
     match app.state.mode {
         Mode::Navigate => {
             if app.state.is_searching_places {
@@ -330,6 +829,7 @@ fn handle_select(app: &mut App, file_manager: &FileManager) {
                 if let Some(place) = app.get_selected_search_place() {
                     let place_id = place.id;
                     app.state.selection = Some(Selection::Place(place_id));
+                    app.remember_search();
                     app.clear_place_search();
                 }
             } else {
@@ -340,32 +840,131 @@ fn handle_select(app: &mut App, file_manager: &FileManager) {
                             if let Some(affordance) = place.affordances.iter().find(|a| &a.id == affordance_id) {
                                 if let Some(dest_id) = &affordance.connects_to {
                                     app.navigate_to_place(*dest_id);
+                                } else if let Some(link) = affordance.link.clone() {
+                                    follow_link(app, file_manager, &link);
+                                } else if !affordance.branches.is_empty() && app.start_branch_list() {
+                                    // Outcomes only: pick which one to follow
+                                    app.state.mode = Mode::Branches;
                                 }
                             }
                         }
                     }
-                    Some(Selection::Place(_)) => {
-                        // Could enter edit mode when pressing Enter on a place
+                    Some(Selection::Place(place_id)) => {
+                        let place_id = *place_id;
+                        match app.config.enter_on_place {
+                            EnterOnPlace::Edit if app.is_read_only() => {
+                                app.state.status_message = Some(s.read_only_refused.to_string());
+                            }
+                            EnterOnPlace::Edit => handle_enter_edit_mode(app),
+                            EnterOnPlace::Fold => {
+                                app.toggle_fold(place_id);
+                            }
+                            EnterOnPlace::Follow => match app.first_destination(&place_id) {
+                                Some(dest_id) => app.navigate_to_place(dest_id),
+                                None => app.state.status_message = Some(s.nothing_connected.to_string()),
+                            },
+                        }
+                    }
+                    None if app.breadboard.places.is_empty() && app.state.drill_stack.is_empty() => {
+                        app.start_screen(recent_board());
+                        app.state.mode = Mode::Start;
                     }
                     None => {}
                 }
             }
         }
+        Mode::ConfirmNotes => {
+            app.state.mode = Mode::Navigate;
+            app.state.output_panel = None;
+            if let Some(notes) = app.state.pending_notes.take() {
+                app.take_snapshot(s.snapshot_importing_notes.to_string());
+                let summary = import::merge(&mut app.breadboard, &notes, None);
+                if app.state.selection.is_none() {
+                    app.state.selection = app.breadboard.places.first().map(|p| Selection::Place(p.id));
+                }
+                app.state.status_message = Some(locale::fill(s.notes_imported, &[&summary.describe(s)]));
+            }
+        }
+        Mode::Comment => {
+            app.state.mode = Mode::Navigate;
+            let text = std::mem::take(&mut app.state.edit_buffer);
+            if let Some(name) = app.add_comment(&text) {
+                app.state.status_message = Some(locale::fill(s.commented, &[&name]));
+            }
+        }
+        Mode::Estimate => {
+            // A mistyped estimate stays in the prompt to be fixed
+            match app.set_estimate(&app.state.edit_buffer.clone()) {
+                Ok(message) => {
+                    app.state.mode = Mode::Navigate;
+                    app.state.edit_buffer.clear();
+                    app.state.status_message = Some(message);
+                }
+                Err(e) => app.state.status_message = Some(e.to_string()),
+            }
+        }
+        Mode::Capture => {
+            // Each line goes straight onto the board; capture goes on until Esc
+            let line = std::mem::take(&mut app.state.edit_buffer);
+            if !line.trim().is_empty() {
+                app.state.status_message = Some(match app.capture_line(&line) {
+                    Ok(captured) => locale::fill(s.captured, &[&captured]),
+                    Err(e) => {
+                        app.state.edit_buffer = line;
+                        locale::fill(s.capture_failed, &[&format!("{:#}", e)])
+                    }
+                });
+            }
+        }
+        Mode::Edit if app.state.editing_branch => {
+            let label = std::mem::take(&mut app.state.edit_buffer);
+            app.rename_selected_branch(label);
+            // A new outcome goes straight on to picking where it leads
+            if app.get_selected_branch().is_some_and(|b| b.connects_to.is_none()) {
+                app.state.mode = Mode::Connect;
+                app.start_connection_search();
+            } else {
+                app.state.editing_branch = false;
+                app.state.mode = Mode::Branches;
+            }
+        }
+        Mode::Edit if !app.icon_matches().is_empty() => {
+            app.pick_icon();
+        }
         Mode::Edit => {
             // Complete edit and save the changes
             let selection = app.state.selection.clone();
-            let new_name = app.state.edit_buffer.clone();
+            let new_name = icons::width_safe(&app.state.edit_buffer);
 
             match selection {
                 Some(Selection::Place(place_id)) => {
+                    let new_name = app.conventional_name(&new_name, true);
                     if let Some(place) = app.breadboard.find_place_mut(&place_id) {
                         place.name = new_name;
                     }
                 }
                 Some(Selection::Affordance { place_id, affordance_id }) => {
+                    // "Pay now -> Confirm" also connects it, adding Confirm if
+                    // there's no such place; a bare "->" disconnects it
+                    let (name, dest) = app::split_arrow(&new_name);
+                    let name = app.conventional_name(name, false);
+                    let dest = dest.map(|dest| app.conventional_name(dest, true));
+                    let dest = dest.as_deref();
                     if let Some(place) = app.breadboard.find_place_mut(&place_id) {
                         if let Some(affordance) = place.affordances.iter_mut().find(|a| a.id == affordance_id) {
-                            affordance.name = new_name;
+                            affordance.name = name;
+                            if dest == Some("") {
+                                affordance.connects_to = None;
+                                affordance.link = None;
+                            }
+                        }
+                    }
+                    if let Some(dest) = dest.filter(|d| !d.is_empty()) {
+                        let places = app.breadboard.places.len();
+                        let dest_id = app.place_named(dest);
+                        app.connect_affordance(place_id, affordance_id, dest_id, false);
+                        if app.breadboard.places.len() > places {
+                            app.state.status_message = Some(locale::fill(s.place_added, &[dest]));
                         }
                     }
                 }
@@ -375,35 +974,73 @@ fn handle_select(app: &mut App, file_manager: &FileManager) {
             app.state.edit_buffer.clear();
         }
         Mode::Connect => {
-            // Check what action to take before borrowing mutably
-            let should_remove = app.is_remove_connection_selected();
-            let selected_place_id = if !should_remove {
-                app.get_selected_connection_place().map(|p| p.id)
-            } else {
-                None
-            };
-
-            if let Some(Selection::Affordance { place_id, affordance_id }) = &app.state.selection {
-                if let Some(place) = app.breadboard.find_place_mut(place_id) {
-                    if let Some(affordance) = place.affordances.iter_mut().find(|a| a.id == *affordance_id) {
-                        if should_remove {
-                            // Remove connection
-                            affordance.connects_to = None;
-                        } else if let Some(selected_place_id) = selected_place_id {
-                            // Create connection with selected place
-                            affordance.connects_to = Some(selected_place_id);
-                        }
+            let add_back = app.config.auto_back_link;
+            complete_connection(app, add_back, false);
+        }
+        Mode::Library => {
+            app.state.mode = Mode::Navigate;
+            let filename = app.get_selected_library_board().map(|board| board.path.clone());
+            app.clear_library();
+            if let Some(filename) = filename {
+                if let Err(e) = switch_board(app, file_manager, &filename) {
+                    app.state.status_message = Some(locale::fill(s.load_failed, &[&filename, &format!("{:#}", e)]));
+                }
+            }
+        }
+        Mode::Compare => {
+            // Enter does nothing while comparing; Esc leaves
+        }
+        Mode::Start => {
+            app.state.mode = Mode::Navigate;
+            match app.get_selected_start_item().cloned() {
+                Some(StartItem::NewBoard) => handle_new_place(app),
+                Some(StartItem::Tutorial) => app.start_tutorial(),
+                Some(StartItem::Recent(filename)) => {
+                    if let Err(e) = open_board(app, file_manager, &filename) {
+                        app.state.status_message = Some(locale::fill(s.load_failed, &[&filename, &format!("{:#}", e)]));
+                    }
+                }
+                Some(StartItem::Open) => {
+                    if let Err(e) = handle_enter_open_mode(app) {
+                        app.state.mode = Mode::Start;
+                        app.state.status_message = Some(locale::fill(s.list_files_failed, &[&format!("{:#}", e)]));
                     }
                 }
+                Some(StartItem::Example(index)) => match app.open_example(index) {
+                    Ok(()) => {
+                        app.state.status_message = Some(locale::fill(s.example_opened, &[examples::EXAMPLES[index].0]));
+                    }
+                    Err(e) => app.state.status_message = Some(format!("{:#}", e)),
+                },
+                None => {}
             }
-            // Exit connection mode
+        }
+        Mode::ConfirmReopen => {
+            app.state.mode = Mode::Navigate;
+            if let Some(filename) = app.state.reopen_candidate.take() {
+                if let Err(e) = open_board(app, file_manager, &filename) {
+                    app.state.status_message = Some(locale::fill(s.load_failed, &[&filename, &format!("{:#}", e)]));
+                }
+            }
+        }
+        Mode::ConfirmRecover => {
+            app.state.mode = Mode::Navigate;
+            if let Some(recovered) = app.state.recovery_candidate.take() {
+                if let Err(e) = recover_board(app, file_manager, recovered) {
+                    app.state.status_message = Some(locale::fill(s.recover_failed, &[&format!("{:#}", e)]));
+                }
+            }
+        }
+        Mode::ConfirmLock => {
+            // Look without editing; the board stays read-only
             app.state.mode = Mode::Navigate;
-            app.clear_connection_search();
         }
         Mode::ConfirmDelete => {
             // Confirm deletion - actually delete the place
-            if let Some(Selection::Place(place_id)) = &app.state.pending_deletion {
-                app.breadboard.places.retain(|p| &p.id != place_id);
+            if let Some(item @ Selection::Place(place_id)) = app.state.pending_deletion.clone() {
+                let name = app.breadboard.find_place(&place_id).map(|p| p.name.clone()).unwrap_or_default();
+                app.take_snapshot(locale::fill(app.config.language.strings().snapshot_deleting, &[&name]));
+                app.delete(&item);
                 app.state.selection = None;
                 // Select first place if any remain
                 if let Some(first_place) = app.breadboard.places.first() {
@@ -417,68 +1054,483 @@ fn handle_select(app: &mut App, file_manager: &FileManager) {
         Mode::SaveFile => {
             // Save with entered filename
             let filename = app.state.save_filename.clone();
-            let _ = file_manager.save_to_file(&app.breadboard, &filename);
-            // Set as current filename
-            app.state.current_filename = Some(filename);
-            // Exit save file mode
-            app.state.mode = Mode::Navigate;
+            match app.state.save_target {
+                SaveTarget::Board => {
+                    let saved = save_board(app, file_manager, &filename).is_ok();
+                    // Set as current filename
+                    app.state.current_filename = Some(filename);
+                    if saved {
+                        run_hook(app, "save", None);
+                        warn_broken_happy_path(app);
+                    }
+                }
+                SaveTarget::ViewSnapshot => match app.scoped(app.state.export_scope) {
+                    Ok(scoped) => match std::fs::write(&filename, ui::view_snapshot(&scoped, view_width())) {
+                        Ok(()) => {
+                            app.state.status_message = Some(locale::fill(s.view_exported, &[&filename]));
+                            run_hook(app, "export", Some(filename));
+                        }
+                        Err(e) => app.state.status_message = Some(locale::fill(s.view_export_failed, &[&e.to_string()])),
+                    },
+                    Err(e) => app.state.status_message = Some(locale::fill(s.view_export_failed, &[&format!("{:#}", e)])),
+                },
+                SaveTarget::Document(document) => {
+                    let label = document.label().to_lowercase();
+                    match app.scoped(app.state.export_scope) {
+                        Ok(scoped) => match std::fs::write(&filename, export::render_document(&scoped, document)) {
+                            Ok(()) => {
+                                app.state.status_message = Some(locale::fill(s.document_exported, &[document.label(), &filename]));
+                                run_hook(app, "export", Some(filename));
+                            }
+                            Err(e) => app.state.status_message = Some(locale::fill(s.document_export_failed, &[&label, &e.to_string()])),
+                        },
+                        Err(e) => app.state.status_message = Some(locale::fill(s.document_export_failed, &[&label, &format!("{:#}", e)])),
+                    }
+                }
+                SaveTarget::Extract { replace } => {
+                    app.state.status_message = Some(match extract_to_board(app, file_manager, &filename, replace) {
+                        Ok(message) => message,
+                        Err(e) => locale::fill(s.extract_failed, &[&filename, &format!("{:#}", e)]),
+                    });
+                }
+                SaveTarget::Bundle => {
+                    app.state.status_message = Some(match bundle::write(&app.breadboard, &app.board_dir(), Path::new(&filename)) {
+                        Ok(files) => locale::fill(s.bundled, &[&files.to_string(), &filename]),
+                        Err(e) => locale::fill(s.bundle_failed, &[&format!("{:#}", e)]),
+                    });
+                }
+                SaveTarget::Attachment => {
+                    app.state.status_message = Some(match app.attach_file(&filename) {
+                        Ok(place) => locale::fill(s.attached, &[filename.trim(), &place]),
+                        Err(e) => locale::fill(s.attach_failed, &[&format!("{:#}", e)]),
+                    });
+                }
+                SaveTarget::Compare => {
+                    if let Err(e) = open_comparison(app, file_manager, filename.trim()) {
+                        app.state.status_message = Some(locale::fill(s.compare_failed, &[&format!("{:#}", e)]));
+                    }
+                }
+            }
+            // Exit save file mode, unless into the comparison
+            app.state.mode = if app.state.comparison.is_some() { Mode::Compare } else { Mode::Navigate };
+            app.state.save_target = SaveTarget::Board;
+            app.state.export_scope = ExportScope::Board;
         }
-        Mode::OpenFile => {
-            // Open selected file
-            if let Some(filename) = app.get_selected_file() {
-                let filename_str = filename.clone();
-                match file_manager.load_from_file(filename) {
-                    Ok(mut breadboard) => {
-                        breadboard.sync_id_counters();
-                        app.breadboard = breadboard;
-                        app.state.selection = None;
-                        // Set current filename
-                        app.state.current_filename = Some(filename_str);
-                        // Reset selection to first place if available
-                        if let Some(first_place) = app.breadboard.places.first() {
-                            app.state.selection = Some(Selection::Place(first_place.id));
+        Mode::ImportFile => match app.state.import_path.take() {
+            // Second step: the group for the new places, empty for none
+            Some(filename) => {
+                let group = app.state.save_filename.trim().to_string();
+                let group = Some(group.as_str()).filter(|g| !g.is_empty());
+                app.state.status_message = Some(match import_into_board(app, &filename, group) {
+                    Ok(summary) => locale::fill(s.imported, &[&summary.describe(s), &filename]),
+                    Err(e) => locale::fill(s.import_failed, &[&filename, &format!("{:#}", e)]),
+                });
+                app.state.mode = Mode::Navigate;
+            }
+            None => {
+                let filename = app.state.save_filename.clone();
+                match import::ImportFormat::from_path(&filename) {
+                    // Notes are rough, so they're previewed rather than given a group
+                    Ok(import::ImportFormat::Notes) => match std::fs::read_to_string(&filename) {
+                        Ok(text) => preview_notes(app, &text),
+                        Err(e) => {
+                            app.state.status_message = Some(locale::fill(s.import_failed, &[&filename, &e.to_string()]));
+                            app.state.mode = Mode::Navigate;
                         }
+                    },
+                    Ok(_) => {
+                        app.state.import_path = Some(filename);
+                        app.state.save_filename.clear();
                     }
                     Err(e) => {
-                        // In a real app, you'd show an error message in the UI
-                        eprintln!("Failed to load {}: {}", filename_str, e);
+                        app.state.status_message = Some(locale::fill(s.import_failed, &[&filename, &format!("{:#}", e)]));
+                        app.state.mode = Mode::Navigate;
                     }
                 }
             }
-            // Exit file opening mode
+        },
+        Mode::Incoming => {
+            app.jump_to_selected_incoming();
+            app.state.mode = Mode::Navigate;
+        }
+        Mode::Cycles => {
+            app.clear_cycle_list(true);
+            app.state.mode = Mode::Navigate;
+        }
+        Mode::Trash => match app.restore_selected_trash() {
+            Ok(name) => {
+                app.state.mode = Mode::Navigate;
+                app.state.selected_trash = None;
+                app.state.status_message = Some(locale::fill(s.restored_from_trash, &[&name]));
+            }
+            Err(e) => app.state.status_message = Some(format!("{:#}", e)),
+        },
+        Mode::Views => {
+            if let Some(name) = app.apply_selected_view() {
+                app.state.mode = Mode::Navigate;
+                app.state.status_message = Some(locale::fill(s.showing_view, &[&name]));
+            }
+        }
+        // A name the board doesn't take stays in the prompt to be fixed
+        Mode::ViewName => match app.save_view(&app.state.edit_buffer.clone()) {
+            Ok(message) => {
+                app.state.edit_buffer.clear();
+                app.state.mode = Mode::Views;
+                app.state.status_message = Some(message);
+            }
+            Err(e) => app.state.status_message = Some(e.to_string()),
+        },
+        Mode::Branches => {
+            let dest_id = app.get_selected_branch().and_then(|b| b.connects_to);
+            match dest_id {
+                Some(dest_id) => {
+                    app.clear_branch_list();
+                    app.state.mode = Mode::Navigate;
+                    app.navigate_to_place(dest_id);
+                }
+                None if app.state.selected_branch.is_some() => {
+                    app.state.status_message = Some(s.outcome_not_connected.to_string());
+                }
+                None => {}
+            }
+        }
+        Mode::Snippets => {
+            app.state.mode = Mode::Navigate;
+            let snippet = app.state.selected_snippet.and_then(|i| app.config.snippets.get(i)).cloned();
+            if let (Some(snippet), Some(place_id)) = (snippet, app.get_selected_place().map(|p| p.id)) {
+                let (added, skipped) = app.insert_snippet(place_id, &snippet);
+                let mut message = locale::fill(s.snippet_added, &[&added.to_string(), &snippet.name]);
+                if skipped > 0 {
+                    message.push_str(&locale::fill(s.snippet_skipped, &[&skipped.to_string()]));
+                }
+                app.state.status_message = Some(message);
+            }
+        }
+        Mode::Spelling => {
+            // Fixed, the typo's other corrections go; the rest stay offered
+            let fix = app.state.selected_fix.and_then(|i| app.state.spelling_fixes.get(i)).cloned();
+            if let Some(fix) = fix {
+                app.apply_fix(&fix);
+                app.state.status_message = Some(locale::fill(s.typo_fixed, &[&fix.word, &fix.replacement]));
+            }
+            app.state.spelling_fixes = app.selection_fixes();
+            if app.state.spelling_fixes.is_empty() {
+                app.state.mode = Mode::Navigate;
+                app.state.selected_fix = None;
+            } else {
+                app.state.selected_fix = Some(0);
+            }
+        }
+        Mode::Commands => {
+            app.state.mode = Mode::Navigate;
+            let command = app.state.selected_command.and_then(|i| app.config.commands.get(i)).cloned();
+            if let Some(command) = command {
+                let context = hooks::CommandContext::from_app(app, "command");
+                run_user_command(app, &command.name, &command.run, context);
+            }
+        }
+        Mode::Filter => {
+            // Keep the filter applied and return to navigation
+            if !app.is_filter_active() {
+                app.clear_filter();
+            }
+            app.state.mode = Mode::Navigate;
+        }
+        Mode::OpenFile => {
+            // Exit file opening mode first: a workspace switches to its board list
+            let filename = app.get_selected_file().cloned();
             app.state.mode = Mode::Navigate;
             app.clear_file_selection();
+
+            // Open selected file; the open board stays if it isn't one
+            if let Some(filename) = filename {
+                if let Err(e) = open_board(app, file_manager, &filename) {
+                    app.state.status_message = Some(locale::fill(s.load_failed_kept, &[&filename, &format!("{:#}", e)]));
+                }
+            }
+        }
+    }
+}
+
+// Open the board a cross-board link leads into, at its destination place
+fn follow_link(app: &mut App, file_manager: &FileManager, link: &models::BoardLink) {
+    let Some(path) = app.workspace.as_ref()
+        .and_then(|workspace| Some(workspace.path_of(workspace.find_board(&link.board)?)))
+    else {
+        let s = app.config.language.strings();
+        app.state.status_message = Some(locale::fill(s.not_in_workspace, &[&link.board]));
+        return;
+    };
+
+    match switch_board(app, file_manager, &path.to_string_lossy()) {
+        Ok(()) => {
+            // The trail belongs to the board we left
+            app.state.navigation_trail.clear();
+            if app.breadboard.find_place(&link.place_id).is_some() {
+                app.state.selection = Some(Selection::Place(link.place_id));
+                app.ensure_selection_visible();
+            }
+        }
+        Err(e) => app.state.status_message = Some(format!("{:#}", e)),
+    }
+}
+
+// Connect to the place chosen in connection mode, or with `remove`, take
+// the connection away instead
+fn complete_connection(app: &mut App, add_back: bool, remove: bool) {
+    let s = app.config.language.strings();
+    // Leading back to where it is is rarely meant, so it takes a second Enter
+    let own = app.connecting_from().filter(|_| !remove);
+    if own.is_some() && own == app.get_selected_connection_place().map(|p| p.id) && !app.state.self_connection_warned {
+        app.state.self_connection_warned = true;
+        let message = if app.state.editing_branch { s.own_place_outcome } else { s.own_place };
+        let name = app.get_selected_place().map(|p| p.name.clone()).unwrap_or_default();
+        app.state.status_message = Some(locale::fill(message, &[&name]));
+        return;
+    }
+    // Outcomes connect within the board only
+    if app.state.editing_branch {
+        if remove {
+            app.connect_selected_branch(None);
+        } else if let Some(dest_id) = app.get_selected_connection_place().map(|p| p.id) {
+            app.connect_selected_branch(Some(dest_id));
+            warn_of_broken_rule(app);
+        } else if app.get_selected_external_place().is_some() {
+            app.state.status_message = Some(s.outcomes_stay_on_board.to_string());
+        }
+        app.state.editing_branch = false;
+        app.state.mode = Mode::Branches;
+        app.clear_connection_search();
+        return;
+    }
+
+    // Marked places and affordances all connect to the one place
+    if !app.state.marked.is_empty() {
+        if app.get_selected_external_place().is_some() && !remove {
+            app.state.status_message = Some(s.marked_stay_on_board.to_string());
+            return;
+        }
+        let dest = app.get_selected_connection_place().filter(|_| !remove).map(|p| (p.id, p.name.clone()));
+        if dest.is_none() && !remove {
+            return;
+        }
+        app.take_snapshot(locale::fill(app.config.language.strings().snapshot_connecting, &[&app.state.marked.len().to_string()]));
+        let (connected, linked) = app.connect_marked(dest.as_ref().map(|(id, _)| *id));
+        app.state.status_message = Some(match dest {
+            Some((_, name)) => {
+                let mut parts = Vec::new();
+                match connected {
+                    0 => {}
+                    1 => parts.push(locale::fill(s.connected_affordance, &[&name])),
+                    _ => parts.push(locale::fill(s.connected_affordances, &[&connected.to_string(), &name])),
+                }
+                match linked {
+                    0 => {}
+                    1 => parts.push(locale::fill(s.linked_place, &[&name])),
+                    _ => parts.push(locale::fill(s.linked_places, &[&name, &linked.to_string()])),
+                }
+                if parts.is_empty() {
+                    locale::fill(s.already_leads_to, &[&name])
+                } else {
+                    let message = parts.join(", ");
+                    let mut chars = message.chars();
+                    chars.next().map_or(String::new(), |first| first.to_uppercase().chain(chars).collect())
+                }
+            }
+            None if connected == 1 => s.connection_removed.to_string(),
+            None => locale::fill(s.connections_removed, &[&connected.to_string()]),
+        });
+        app.state.marked.clear();
+        app.state.mode = Mode::Navigate;
+        app.clear_connection_search();
+        return;
+    }
+
+    // A place in another board of the workspace becomes a cross-board link
+    let external = app.get_selected_external_place()
+        .filter(|_| !remove)
+        .map(|place| models::BoardLink { board: place.board.clone(), place_id: place.place_id });
+    if let (Some(link), Some(Selection::Affordance { place_id, affordance_id })) = (external, app.state.selection.clone()) {
+        app.link_affordance(place_id, affordance_id, link);
+        app.state.mode = Mode::Navigate;
+        app.clear_connection_search();
+        return;
+    }
+
+    // Check what action to take before borrowing mutably
+    let should_remove = remove;
+    let selected_place_id = if !should_remove {
+        app.get_selected_connection_place().map(|p| p.id)
+    } else {
+        None
+    };
+
+    if let Some(Selection::Affordance { place_id, affordance_id }) = app.state.selection.clone() {
+        if should_remove {
+            // Remove connection
+            if let Some(place) = app.breadboard.find_place_mut(&place_id) {
+                if let Some(affordance) = place.affordances.iter_mut().find(|a| a.id == affordance_id) {
+                    affordance.connects_to = None;
+                    affordance.link = None;
+                }
+            }
+        } else if let Some(selected_place_id) = selected_place_id {
+            // Create connection with selected place
+            app.connect_affordance(place_id, affordance_id, selected_place_id, add_back);
+            warn_of_broken_rule(app);
         }
     }
+    // Exit connection mode
+    app.state.mode = Mode::Navigate;
+    app.clear_connection_search();
+}
+
+// The connection is kept, but the status bar says which of the board's
+// rules the selected affordance now goes against
+fn warn_of_broken_rule(app: &mut App) {
+    let Some(selection) = app.state.selection.clone() else {
+        return;
+    };
+    if let Some((_, message)) = app.rule_breaks().into_iter().find(|(item, _)| *item == selection) {
+        app.state.status_message = Some(message);
+    }
 }
 
 fn handle_back(app: &mut App) {
+    let s = app.config.language.strings();
     match app.state.mode {
         Mode::Edit => {
-            app.state.mode = Mode::Navigate;
+            app.state.mode = if app.state.editing_branch { Mode::Branches } else { Mode::Navigate };
+            app.state.editing_branch = false;
             app.state.edit_buffer.clear();
         }
         Mode::Connect => {
-            app.state.mode = Mode::Navigate;
+            app.state.mode = if app.state.editing_branch { Mode::Branches } else { Mode::Navigate };
+            app.state.editing_branch = false;
             app.clear_connection_search();
         }
+        Mode::Branches => {
+            app.state.mode = Mode::Navigate;
+            app.clear_branch_list();
+        }
         Mode::SaveFile => {
             // Cancel save
             app.state.mode = Mode::Navigate;
+            app.state.save_target = SaveTarget::Board;
+            app.state.export_scope = ExportScope::Board;
+        }
+        Mode::ImportFile => {
+            app.state.mode = Mode::Navigate;
+            app.state.import_path = None;
         }
         Mode::OpenFile => {
             app.state.mode = Mode::Navigate;
             app.clear_file_selection();
         }
+        Mode::ConfirmReopen => {
+            app.state.mode = Mode::Start;
+            app.state.reopen_candidate = None;
+        }
+        Mode::ConfirmRecover => {
+            app.state.mode = if app.state.current_filename.is_some() { Mode::Navigate } else { Mode::Start };
+            if let Some(recovered) = app.state.recovery_candidate.take() {
+                recovered.remove();
+            }
+        }
+        Mode::ConfirmLock => {
+            // Edit anyway, taking the lock over
+            app.state.mode = Mode::Navigate;
+            if let (Some(holder), Some(filename)) = (app.state.locked_by.take(), app.state.current_filename.clone()) {
+                take_lock(app, &filename);
+                app.state.status_message = Some(locale::fill(s.editing_anyway, &[&holder]));
+            }
+        }
+        Mode::Library => {
+            // On startup, go on to the start screen
+            app.state.mode = if app.state.current_filename.is_none() && app.breadboard.places.is_empty() {
+                Mode::Start
+            } else {
+                Mode::Navigate
+            };
+            app.clear_library();
+        }
+        Mode::Start => {
+            // Leave the board blank; Enter brings the start screen back
+            app.state.mode = Mode::Navigate;
+        }
+        Mode::Compare => {
+            app.state.mode = Mode::Navigate;
+            app.state.comparison = None;
+        }
         Mode::ConfirmDelete => {
             // Cancel deletion
             app.state.mode = Mode::Navigate;
             app.state.pending_deletion = None;
         }
+        Mode::Incoming => {
+            app.state.mode = Mode::Navigate;
+            app.clear_incoming_list();
+        }
+        Mode::Cycles => {
+            app.state.mode = Mode::Navigate;
+            app.clear_cycle_list(false);
+        }
+        Mode::Commands | Mode::Snippets => {
+            app.state.mode = Mode::Navigate;
+        }
+        Mode::Trash => {
+            app.state.mode = Mode::Navigate;
+            app.state.selected_trash = None;
+        }
+        Mode::Spelling => {
+            app.state.mode = Mode::Navigate;
+            app.state.spelling_fixes.clear();
+            app.state.selected_fix = None;
+        }
+        Mode::Views => {
+            app.state.mode = Mode::Navigate;
+            app.state.selected_view = None;
+        }
+        Mode::ViewName => {
+            app.state.mode = Mode::Views;
+            app.state.edit_buffer.clear();
+        }
+        Mode::ConfirmNotes => {
+            app.state.mode = Mode::Navigate;
+            app.state.output_panel = None;
+            app.state.pending_notes = None;
+        }
+        Mode::Capture => {
+            app.state.mode = Mode::Navigate;
+            app.state.edit_buffer.clear();
+            app.state.capture_place = None;
+        }
+        Mode::Comment | Mode::Estimate => {
+            app.state.mode = Mode::Navigate;
+            app.state.edit_buffer.clear();
+        }
+        Mode::Filter => {
+            // Clear the filter entirely
+            app.state.mode = Mode::Navigate;
+            app.clear_filter();
+        }
         Mode::Navigate => {
             if app.state.is_searching_places {
                 // Exit place search mode
                 app.clear_place_search();
+            } else if app.state.output_panel.is_some() {
+                // Dismiss command output first
+                app.state.output_panel = None;
+            } else if !app.state.marked.is_empty() {
+                app.state.marked.clear();
+                app.state.status_message = Some(s.marks_cleared.to_string());
+            } else if app.state.filter.is_some() {
+                // Esc clears an active filter before walking the trail
+                app.clear_filter();
+            } else if app.state.navigation_trail.is_empty() && app.state.tutorial.is_some() {
+                // With nowhere left to go back to, Esc ends the tutorial
+                app.state.tutorial = None;
+                app.state.status_message = Some(s.tutorial_closed.to_string());
             } else {
                 // Navigate back in trail
                 app.navigate_back();
@@ -530,6 +1582,30 @@ fn handle_new_affordance(app: &mut App) {
 }
 
 
+fn handle_new_branch(app: &mut App) {
+    if let Some(label) = app.add_branch() {
+        app.state.editing_branch = true;
+        app.state.mode = Mode::Edit;
+        app.state.edit_buffer = label;
+    }
+}
+
+fn handle_edit_branch(app: &mut App) {
+    if let Some(branch) = app.get_selected_branch() {
+        app.state.edit_buffer = branch.label.clone();
+        app.state.editing_branch = true;
+        app.state.mode = Mode::Edit;
+    }
+}
+
+fn handle_connect_branch(app: &mut App) {
+    if app.state.selected_branch.is_some() {
+        app.state.editing_branch = true;
+        app.state.mode = Mode::Connect;
+        app.start_connection_search();
+    }
+}
+
 fn handle_remove_connection(app: &mut App) {
     // Remove connection from selected affordance ONLY
     // Safety check: Only proceed if we're definitely on an affordance
@@ -547,32 +1623,469 @@ fn handle_remove_connection(app: &mut App) {
         if let Some(affordance) = place.affordances.iter_mut().find(|a| a.id == affordance_id) {
             // Only modify this specific affordance's connection
             affordance.connects_to = None;
+            affordance.link = None;
         }
         // If affordance not found, do nothing (shouldn't happen with valid selection)
     }
 }
 
+fn handle_reverse_connection(app: &mut App, remove_original: bool) {
+    let s = app.config.language.strings();
+    // Only connected affordances can be reversed
+    let (place_id, affordance_id) = match &app.state.selection {
+        Some(Selection::Affordance { place_id, affordance_id }) => (*place_id, *affordance_id),
+        _ => return,
+    };
+
+    let dest_id = app.breadboard.find_place(&place_id)
+        .and_then(|p| p.affordances.iter().find(|a| a.id == affordance_id))
+        .and_then(|a| a.connects_to);
+
+    match (dest_id, app.reverse_connection(place_id, affordance_id, remove_original)) {
+        (Some(dest_id), Some(new_id)) => {
+            // Select the new affordance so it can be renamed right away
+            app.state.selection = Some(Selection::Affordance { place_id: dest_id, affordance_id: new_id });
+            let message = if remove_original { s.connection_swapped } else { s.return_connection_added };
+            app.state.status_message = Some(message.to_string());
+        }
+        _ => {
+            app.state.status_message = Some(s.nothing_to_reverse.to_string());
+        }
+    }
+}
+
 fn handle_save(app: &mut App, file_manager: &FileManager) -> Result<()> {
     // If we have a current filename, save directly
-    if let Some(filename) = &app.state.current_filename {
-        let _ = file_manager.save_to_file(&app.breadboard, filename);
+    if let Some(filename) = app.state.current_filename.clone() {
+        if save_board(app, file_manager, &filename).is_ok() {
+            run_hook(app, "save", None);
+            warn_broken_happy_path(app);
+        }
     } else {
         // No current filename, prompt for one
         app.state.mode = Mode::SaveFile;
-        app.state.save_filename = String::from("breadboard.toml");
+        app.state.save_filename = default_board_filename(app);
     }
     Ok(())
 }
 
+// Edits can quietly cut the main journey, so saving points out where
+fn warn_broken_happy_path(app: &mut App) {
+    let name = |id: u32| app.breadboard.find_place(&id).map_or("?", |p| p.name.as_str());
+    let breaks: Vec<String> = app.breadboard.happy_path_breaks().into_iter()
+        .map(|(from, to)| format!("{} ↛ {}", name(from), name(to)))
+        .collect();
+    if !breaks.is_empty() {
+        let s = app.config.language.strings();
+        app.state.status_message = Some(locale::fill(s.happy_path_broken, &[&breaks.join(", ")]));
+    }
+}
+
 fn handle_save_as(app: &mut App, _file_manager: &FileManager) -> Result<()> {
     // Always prompt for filename (Save As)
     app.state.mode = Mode::SaveFile;
     // Pre-fill with current filename if available, otherwise default
     app.state.save_filename = app.state.current_filename.clone()
-        .unwrap_or_else(|| String::from("breadboard.toml"));
+        .unwrap_or_else(|| default_board_filename(app));
+    Ok(())
+}
+
+// New boards go into the boards directory when there is one
+fn default_board_filename(app: &App) -> String {
+    match app.config.boards_dir() {
+        Some(dir) => dir.join("breadboard.toml").to_string_lossy().to_string(),
+        None => String::from("breadboard.toml"),
+    }
+}
+
+fn handle_export_view(app: &mut App) {
+    // Suggest a text file next to the board
+    let stem = app.state.current_filename.as_deref()
+        .map(|f| f.strip_suffix(".toml").unwrap_or(f))
+        .unwrap_or("breadboard");
+    app.state.save_filename = format!("{}.txt", stem);
+    app.state.save_target = SaveTarget::ViewSnapshot;
+    app.state.mode = Mode::SaveFile;
+}
+
+fn handle_export_document(app: &mut App, document: export::Document) {
+    let stem = app.state.current_filename.as_deref()
+        .map(|f| f.strip_suffix(".toml").unwrap_or(f))
+        .unwrap_or("breadboard");
+    app.state.save_filename = format!("{}{}", stem, document.file_suffix());
+    app.state.save_target = SaveTarget::Document(document);
+    app.state.mode = Mode::SaveFile;
+}
+
+fn handle_extract(app: &mut App, replace: bool) {
+    let Some(place) = app.get_selected_place() else {
+        app.state.status_message = Some(app.config.language.strings().select_place_to_extract.to_string());
+        return;
+    };
+    // Suggest a board named after the place, next to this one
+    let stem = place.name.trim().to_lowercase().split_whitespace().collect::<Vec<_>>().join("-");
+    let dir = app.state.current_filename.as_deref().and_then(|f| Path::new(f).parent());
+    app.state.save_filename = dir.unwrap_or(Path::new("")).join(format!("{}.toml", stem)).to_string_lossy().to_string();
+    app.state.save_target = SaveTarget::Extract { replace };
+    app.state.mode = Mode::SaveFile;
+}
+
+// Write the selected place and everything reachable from it to `filename`.
+// With `replace` they're also taken off the open board, and connections into
+// them become cross-board links.
+fn extract_to_board(app: &mut App, file_manager: &FileManager, filename: &str, replace: bool) -> Result<String> {
+    let s = app.config.language.strings();
+    let place = app.get_selected_place().ok_or_else(|| anyhow::anyhow!("no place selected"))?;
+    let extracted = extract::extract(&app.breadboard, place.id, place.name.clone());
+    file_manager.save_to_file(&extracted, filename)?;
+    let count = extracted.places.len();
+    if !replace {
+        return Ok(locale::fill(s.places_copied, &[&count.to_string(), filename]));
+    }
+
+    // Links name the board as the workspace lists it, relative to this board
+    let dir = app.state.current_filename.as_deref().and_then(|f| Path::new(f).parent());
+    let board = dir.and_then(|dir| Path::new(filename).strip_prefix(dir).ok())
+        .map(|path| path.to_string_lossy().to_string())
+        .unwrap_or_else(|| filename.to_string());
+    app.take_snapshot(locale::fill(s.snapshot_moving, &[filename]));
+    let relinked = extract::replace_with_link(&mut app.breadboard, &extracted, &board);
+    app.state.selection = app.breadboard.places.first().map(|p| Selection::Place(p.id));
+    Ok(locale::fill(s.places_moved, &[&count.to_string(), filename, &relinked.to_string()]))
+}
+
+// Merge a CSV, DOT or board file into the open board, putting new places in
+// `group`. It's applied to a copy, so a file that fails halfway leaves the
+// board untouched.
+fn import_into_board(app: &mut App, filename: &str, group: Option<&str>) -> Result<import::ImportSummary> {
+    let format = import::ImportFormat::from_path(filename)?;
+    let text = std::fs::read_to_string(filename)?;
+    let mut breadboard = app.breadboard.clone();
+    let summary = import::import(&mut breadboard, format, &text, group)?;
+    app.take_snapshot(locale::fill(app.config.language.strings().snapshot_importing, &[filename]));
+    app.breadboard = breadboard;
+    if app.state.selection.is_none() {
+        app.state.selection = app.breadboard.places.first().map(|p| Selection::Place(p.id));
+    }
+    Ok(summary)
+}
+
+fn handle_paste_notes(app: &mut App) {
+    match arboard::Clipboard::new().and_then(|mut clipboard| clipboard.get_text()) {
+        Ok(text) => preview_notes(app, &text),
+        Err(e) => app.state.status_message = Some(locale::fill(app.config.language.strings().clipboard_failed, &[&e.to_string()])),
+    }
+}
+
+// Show what rough notes would add to the board, to be confirmed before
+// anything changes
+fn preview_notes(app: &mut App, text: &str) {
+    let s = app.config.language.strings();
+    let (notes, skipped) = import::parse_notes(text);
+    if notes.places.is_empty() {
+        app.state.status_message = Some(s.no_places_in_notes.to_string());
+        app.state.mode = Mode::Navigate;
+        return;
+    }
+
+    let mut preview = import::preview(&app.breadboard, &notes);
+    match skipped {
+        0 => {}
+        1 => preview.push_str(s.skipped_line),
+        _ => preview.push_str(&locale::fill(s.skipped_lines, &[&skipped.to_string()])),
+    }
+    app.state.output_panel = Some((s.notes_title.to_string(), preview));
+    app.state.pending_notes = Some(notes);
+    app.state.mode = Mode::ConfirmNotes;
+}
+
+fn handle_copy_place(app: &mut App) {
+    let s = app.config.language.strings();
+    let copied = app.get_selected_place().map(|place| place.id).and_then(|id| app.copy_place(id));
+    app.state.status_message = Some(match &copied {
+        Some(copied) => locale::fill(s.place_copied, &[&copied.places[0].name]),
+        None => s.select_place_to_copy.to_string(),
+    });
+    if copied.is_some() {
+        app.state.copied_place = copied;
+    }
+}
+
+fn handle_paste_place(app: &mut App) {
+    let s = app.config.language.strings();
+    let Some(copied) = app.state.copied_place.clone() else {
+        app.state.status_message = Some(s.nothing_copied.to_string());
+        return;
+    };
+    let Some(report) = app.paste_place(&copied) else {
+        return;
+    };
+    app.state.selection = Some(Selection::Place(report.place_id));
+
+    let mut message = locale::fill(s.pasted, &[&copied.places[0].name]);
+    if !report.remapped.is_empty() {
+        message.push_str(&locale::fill(s.pasted_remapped, &[&report.remapped.join(", ")]));
+    }
+    if !report.dropped.is_empty() {
+        message.push_str(&locale::fill(s.pasted_dropped, &[&report.dropped.join(", ")]));
+    }
+    app.state.status_message = Some(message);
+}
+
+fn handle_copy_view(app: &mut App) {
+    let snapshot = ui::view_snapshot(app, view_width());
+    let result = arboard::Clipboard::new().and_then(|mut clipboard| clipboard.set_text(snapshot));
+    let s = app.config.language.strings();
+    app.state.status_message = Some(match result {
+        Ok(()) => s.view_copied.to_string(),
+        Err(e) => locale::fill(s.view_copy_failed, &[&e.to_string()]),
+    });
+}
+
+// Suspend the TUI, open the selection's notes in the user's editor and keep
+// whatever they save
+fn handle_edit_notes(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, app: &mut App) -> Result<()> {
+    app.state.status_message = None;
+    if app.state.mode != Mode::Navigate || app.state.selection.is_none() {
+        return Ok(());
+    }
+
+    let editor = editor::preferred_editor();
+    let text = app.selected_notes().unwrap_or_default().to_string();
+
+    suspend_terminal(terminal)?;
+    let edited = editor::edit_text(&editor, &text);
+    resume_terminal(terminal)?;
+
+    match edited {
+        Ok(edited) => {
+            app.set_selected_notes(&edited);
+            let s = app.config.language.strings();
+            app.state.status_message = Some(match app.selected_notes() {
+                Some(_) => s.notes_saved.to_string(),
+                None => s.no_notes.to_string(),
+            });
+        }
+        Err(e) => app.state.status_message = Some(format!("{:#}", e)),
+    }
+    Ok(())
+}
+
+// Stop like any job on Ctrl+Z: restore the terminal for the shell, then
+// take it over again once resumed with `fg`
+#[cfg(unix)]
+fn suspend_to_shell(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()> {
+    suspend_terminal(terminal)?;
+    // Stops the process here; returns on SIGCONT
+    signal_hook::low_level::emulate_default_handler(signal_hook::consts::SIGTSTP)?;
+    resume_terminal(terminal)
+}
+
+// No job control to return to
+#[cfg(not(unix))]
+fn suspend_to_shell(_terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()> {
+    Ok(())
+}
+
+// Hand the terminal back to the shell or a child process
+fn suspend_terminal(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()> {
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen, DisableMouseCapture)?;
+    terminal.show_cursor()?;
+    Ok(())
+}
+
+fn resume_terminal(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> Result<()> {
+    enable_raw_mode()?;
+    execute!(terminal.backend_mut(), EnterAlternateScreen, EnableMouseCapture)?;
+    terminal.clear()?;
+    Ok(())
+}
+
+// Replace the current board with the one in `filename`, picking up where
+// the user left off in it
+fn open_board(app: &mut App, file_manager: &FileManager, filename: &str) -> Result<()> {
+    // A bundle is unpacked next to itself and its board opened
+    if bundle::is_bundle(Path::new(filename)) {
+        let board = bundle::unpack(Path::new(filename))?;
+        return open_board(app, file_manager, &board.to_string_lossy());
+    }
+
+    // A workspace opens its board switcher instead
+    if workspace::Workspace::is_workspace_file(Path::new(filename)) {
+        app.workspace = Some(workspace::Workspace::load(Path::new(filename))?);
+        if app.start_workspace_switcher(file_manager) {
+            app.state.mode = Mode::Library;
+        }
+        return Ok(());
+    }
+
+    let mut breadboard = file_manager.load_from_file(filename)?;
+    remember_board_state(app);
+    breadboard.sync_id_counters();
+    app.breadboard = breadboard;
+    app.state.drill_stack.clear();
+    app.state.snapshots.clear();
+    app.state.trash.clear();
+    app.mark_saved();
+    app.state.marked.clear();
+    app.state.current_filename = Some(filename.to_string());
+    app.state.selection = app.breadboard.places.first().map(|p| Selection::Place(p.id));
+    restore_board_state(app);
+    app.state.external_places = match &app.workspace {
+        Some(workspace) => workspace.places_outside(file_manager, Some(filename)),
+        None => Vec::new(),
+    };
+    run_hook(app, "load", None);
+    claim_board(app, filename);
+    Ok(())
+}
+
+// `bboard new <file>`: an empty board, named after the file and saved to it
+// with Ctrl+S, starting on its first place's name
+fn start_new_board(app: &mut App, filename: &str) {
+    let name = Path::new(filename).file_stem().map_or("New Breadboard".to_string(), |stem| stem.to_string_lossy().to_string());
+    app.breadboard = models::Breadboard::new(name);
+    app.state.current_filename = Some(filename.to_string());
+    handle_new_place(app);
+    claim_board(app, filename);
+}
+
+// Bring back edits a bboard that didn't exit cleanly left. Offered on the
+// start screen, their board is opened first; it stays unsaved either way,
+// with Undo going back to the board as saved.
+fn recover_board(app: &mut App, file_manager: &FileManager, recovered: recovery::Recovered) -> Result<()> {
+    if let (None, Some(file)) = (&app.state.current_filename, &recovered.board_file) {
+        if Path::new(file).exists() {
+            open_board(app, file_manager, file)?;
+        } else {
+            app.state.current_filename = Some(file.clone());
+            claim_board(app, file);
+        }
+    }
+    let saved_at = recovered.saved_at.format("%H:%M");
+    let mut board = recovered.board;
+    board.sync_id_counters();
+    app.take_snapshot(app.config.language.strings().snapshot_recovering.to_string());
+    app.breadboard = board;
+    app.state.drill_stack.clear();
+    app.state.selection = app.breadboard.places.first().map(|p| Selection::Place(p.id));
+    app.state.status_message = Some(locale::fill(app.config.language.strings().recovered, &[&saved_at.to_string()]));
     Ok(())
 }
 
+// Set the open board beside the one in `filename`, which goes on the left
+// as the older version. Given the board's own file, that's the board as
+// last saved.
+fn open_comparison(app: &mut App, file_manager: &FileManager, filename: &str) -> Result<()> {
+    let s = app.config.language.strings();
+    let other = file_manager.load_from_file(filename)?;
+    let current = app.state.current_filename.clone().unwrap_or_else(|| s.unsaved_board.to_string());
+    let old_title = if filename == current { locale::fill(s.as_saved, &[filename]) } else { filename.to_string() };
+    let comparison = diff::Comparison::new(old_title, &other, current, &app.root_board());
+    app.state.status_message = Some(match diff::summary(&comparison.rows) {
+        Some(summary) => locale::fill(s.compared_with, &[filename, &summary]),
+        None => locale::fill(s.no_differences_from, &[filename]),
+    });
+    app.state.comparison = Some(comparison);
+    app.state.mode = Mode::Compare;
+    Ok(())
+}
+
+// Take the lock on the board just opened, or, when someone else holds it,
+// ask whether to look without editing
+fn claim_board(app: &mut App, filename: &str) {
+    app.lock = None;
+    app.state.locked_by = None;
+    if app.state.read_only {
+        return;
+    }
+    match lock::holder(Path::new(filename)) {
+        Some(holder) => {
+            app.state.locked_by = Some(holder);
+            app.state.mode = Mode::ConfirmLock;
+        }
+        None => take_lock(app, filename),
+    }
+}
+
+fn take_lock(app: &mut App, filename: &str) {
+    match lock::BoardLock::acquire(Path::new(filename), &app.config.author()) {
+        Ok(lock) => app.lock = Some(Arc::new(lock)),
+        Err(e) => app.state.status_message = Some(locale::fill(app.config.language.strings().lock_failed, &[&format!("{:#}", e)])),
+    }
+}
+
+// Move to another board of the workspace, saving the current one first so
+// hopping between boards doesn't lose edits
+fn switch_board(app: &mut App, file_manager: &FileManager, filename: &str) -> Result<()> {
+    if let Some(current) = app.state.current_filename.clone().filter(|_| !app.is_read_only()) {
+        save_board(app, file_manager, &current)?;
+    }
+    open_board(app, file_manager, filename)
+}
+
+// Write the board to `filename`, adding what changed since the last save to
+// its change log. The entry is only kept if the write succeeds.
+fn save_board(app: &mut App, file_manager: &FileManager, filename: &str) -> Result<()> {
+    let mut breadboard = app.root_board();
+    changelog::record(app.saved_version().as_ref(), &mut breadboard, app.config.author());
+    file_manager.save_to_file(&breadboard, filename)?;
+    app.set_root_board(breadboard);
+    app.mark_saved();
+    Ok(())
+}
+
+// The last board used, if it's still there to reopen
+fn recent_board() -> Option<String> {
+    state::SavedState::load().reopenable_board().map(str::to_string)
+}
+
+// Save where the user is in the current board for next time. Boards that
+// were never saved have nothing to key the state by.
+fn remember_board_state(app: &App) {
+    if let Some(file) = &app.state.current_filename {
+        let mut saved = state::SavedState::load();
+        saved.remember(file, state::BoardState::from_app(app));
+        let _ = saved.save();
+    }
+}
+
+fn restore_board_state(app: &mut App) {
+    let saved = state::SavedState::load();
+    if let Some(board_state) = app.state.current_filename.as_deref().and_then(|file| saved.board(file)) {
+        board_state.restore(app);
+    }
+}
+
+// Run the config's hook for `event` ("save", "load" or "export"), if any
+fn run_hook(app: &mut App, event: &'static str, export: Option<String>) {
+    let command = match event {
+        "save" => app.config.hooks.on_save.clone(),
+        "load" => app.config.hooks.on_load.clone(),
+        "export" => app.config.hooks.on_export.clone(),
+        _ => None,
+    };
+    if let Some(command) = command {
+        let mut context = hooks::CommandContext::from_app(app, event);
+        context.export = export;
+        run_user_command(app, &locale::fill(app.config.language.strings().hook_title, &[event]), &command, context);
+    }
+}
+
+// Start `command` in the background; `App::poll_commands` reports how it
+// went once it finishes
+fn run_user_command(app: &mut App, title: &str, command: &str, context: hooks::CommandContext) {
+    app.state.status_message = Some(locale::fill(app.config.language.strings().command_running, &[title]));
+    app.state.running_commands.push(hooks::Running::start(title.to_string(), command.to_string(), context));
+}
+
+// Width of the outline as rendered: the terminal minus the list's borders
+fn view_width() -> usize {
+    crossterm::terminal::size()
+        .map(|(width, _)| width.saturating_sub(2) as usize)
+        .unwrap_or(80)
+}
 
 fn handle_enter_edit_mode(app: &mut App) {
     // Enter edit mode for the currently selected item
@@ -599,19 +2112,21 @@ fn handle_enter_edit_mode(app: &mut App) {
 
 fn handle_delete(app: &mut App) {
     // Delete the currently selected place or affordance
-    match &app.state.selection {
+    match app.state.selection.clone() {
         Some(Selection::Place(_)) => {
             // For places, show confirmation dialog first
             app.state.pending_deletion = app.state.selection.clone();
             app.state.mode = Mode::ConfirmDelete;
         }
-        Some(Selection::Affordance { place_id, affordance_id }) => {
-            // Affordances can be deleted immediately without confirmation
-            if let Some(place) = app.breadboard.find_place_mut(place_id) {
-                place.affordances.retain(|a| &a.id != affordance_id);
+        Some(item @ Selection::Affordance { place_id, .. }) => {
+            // Affordances can be deleted immediately without confirmation,
+            // as the snapshot and the trash bring them back
+            if let Some(name) = app.get_selected_affordance().map(|a| a.name.clone()) {
+                app.take_snapshot(locale::fill(app.config.language.strings().snapshot_deleting, &[&name]));
+                app.delete(&item);
             }
             // Move selection back to the place
-            app.state.selection = Some(Selection::Place(*place_id));
+            app.state.selection = Some(Selection::Place(place_id));
         }
         None => {
             // Nothing to delete
@@ -621,8 +2136,13 @@ fn handle_delete(app: &mut App) {
 
 fn handle_edit(app: &mut App, text_change: String) {
     match app.state.mode {
-        Mode::Edit => {
+        Mode::Edit | Mode::Capture | Mode::Comment | Mode::Estimate | Mode::ViewName => {
             // Handle text editing for regular edit mode
+            if text_change == "tab" {
+                app.pick_icon();
+                return;
+            }
+            app.state.selected_icon = 0;
             if text_change == "backspace" {
                 app.state.edit_buffer.pop();
             } else if text_change == "delete" {
@@ -638,6 +2158,16 @@ fn handle_edit(app: &mut App, text_change: String) {
             }
         }
         Mode::Connect => {
+            // A rewire's prefilled destination goes as soon as anything is typed
+            if std::mem::take(&mut app.state.connection_search_prefilled)
+                && !matches!(text_change.as_str(), "left" | "right" | "home" | "end")
+            {
+                app.state.connection_search_buffer.clear();
+                if text_change == "backspace" || text_change == "delete" {
+                    app.update_connection_search();
+                    return;
+                }
+            }
             // Handle connection search text editing
             if text_change == "backspace" {
                 app.state.connection_search_buffer.pop();
@@ -656,7 +2186,7 @@ fn handle_edit(app: &mut App, text_change: String) {
                 app.update_connection_search();
             }
         }
-        Mode::SaveFile => {
+        Mode::SaveFile | Mode::ImportFile => {
             // Handle filename editing
             if text_change == "backspace" {
                 app.state.save_filename.pop();
@@ -673,10 +2203,29 @@ fn handle_edit(app: &mut App, text_change: String) {
             }
         }
         Mode::OpenFile => {
-            // No text editing in file opening mode
+            // Typing narrows the file list by name
+            let mut filter = app.state.file_filter.clone();
+            if text_change == "backspace" || text_change == "delete" {
+                filter.pop();
+            } else if !text_change.is_empty() {
+                filter.push_str(&text_change);
+            }
+            app.set_file_filter(filter);
         }
-        Mode::ConfirmDelete => {
-            // No text editing in confirmation mode
+        Mode::ConfirmDelete | Mode::ConfirmReopen | Mode::ConfirmRecover | Mode::ConfirmLock | Mode::ConfirmNotes | Mode::Library | Mode::Incoming | Mode::Cycles | Mode::Branches | Mode::Commands | Mode::Snippets | Mode::Trash | Mode::Spelling | Mode::Views | Mode::Start | Mode::Compare => {
+            // No text editing in confirmation or list modes
+        }
+        Mode::Filter => {
+            // Handle filter query editing; the board narrows as you type
+            let query = app.state.filter.get_or_insert_with(String::new);
+            if text_change == "backspace" || text_change == "delete" {
+                query.pop();
+            } else if text_change == "left" || text_change == "right" || text_change == "home" || text_change == "end" {
+                // Cursor movement - simplified for now
+            } else if !text_change.is_empty() {
+                query.push_str(&text_change);
+            }
+            app.ensure_selection_visible();
         }
         Mode::Navigate => {
             if app.state.is_searching_places {
@@ -696,6 +2245,8 @@ fn handle_edit(app: &mut App, text_change: String) {
                     app.state.place_search_buffer.push_str(&text_change);
                     app.update_place_search();
                 }
+            } else if app.type_count(&text_change) {
+                // Taken as a count, or searched for after one
             } else {
                 // Start place search with first character
                 if !text_change.is_empty() && text_change != "backspace" && text_change != "delete"
@@ -710,15 +2261,51 @@ fn handle_edit(app: &mut App, text_change: String) {
 }
 
 fn handle_enter_connect_mode(app: &mut App) {
-    // Only allow connection mode when on an affordance
-    if let Some(Selection::Affordance { .. }) = &app.state.selection {
+    // Only allow connection mode when on an affordance, or with marks to
+    // connect at once
+    if matches!(app.state.selection, Some(Selection::Affordance { .. })) || !app.state.marked.is_empty() {
         app.state.mode = Mode::Connect;
         app.start_connection_search();
     }
 }
 
-fn handle_enter_open_mode(app: &mut App, file_manager: &FileManager) -> Result<()> {
+fn handle_rewire(app: &mut App) {
+    if !matches!(app.state.selection, Some(Selection::Affordance { .. })) {
+        return;
+    }
+    if app.start_rewire() {
+        app.state.mode = Mode::Connect;
+    } else {
+        app.state.status_message = Some(app.config.language.strings().not_connected_yet.to_string());
+    }
+}
+
+fn handle_show_incoming(app: &mut App) {
+    if app.state.is_searching_places {
+        return;
+    }
+
+    if app.start_incoming_list() {
+        app.state.mode = Mode::Incoming;
+    } else if let Some(place) = app.get_selected_place() {
+        app.state.status_message = Some(locale::fill(app.config.language.strings().no_incoming, &[&place.name]));
+    }
+}
+
+fn handle_show_cycles(app: &mut App) {
+    if app.state.is_searching_places {
+        return;
+    }
+
+    if app.start_cycle_list() {
+        app.state.mode = Mode::Cycles;
+    } else {
+        app.state.status_message = Some(app.config.language.strings().no_cycles.to_string());
+    }
+}
+
+fn handle_enter_open_mode(app: &mut App) -> Result<()> {
     app.state.mode = Mode::OpenFile;
-    app.start_file_opening(file_manager)?;
+    app.start_file_opening()?;
     Ok(())
 }
\ No newline at end of file
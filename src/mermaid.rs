@@ -0,0 +1,76 @@
+use crate::models::Breadboard;
+
+// A board as a Mermaid flowchart, for docs and wikis that render Mermaid:
+// each place is a node and each connection an arrow labelled with the
+// affordance, or with the affordance and outcome for a branch. Links into
+// other boards end in a node named after the board. Affordances that lead
+// nowhere are left out, as a flowchart only shows the flow.
+
+pub fn flowchart(breadboard: &Breadboard) -> String {
+    let mut out = String::from("flowchart LR\n");
+    for place in &breadboard.places {
+        out.push_str(&format!("    {}[\"{}\"]\n", node(place.id), label(&place.name)));
+    }
+    let mut boards: Vec<&str> = Vec::new();
+    for place in &breadboard.places {
+        for affordance in &place.affordances {
+            if let Some(dest) = affordance.connects_to {
+                out.push_str(&format!("    {} -->|\"{}\"| {}\n", node(place.id), label(&affordance.name), node(dest)));
+            }
+            for branch in &affordance.branches {
+                if let Some(dest) = branch.connects_to {
+                    let text = format!("{}: {}", affordance.name, branch.label);
+                    out.push_str(&format!("    {} -->|\"{}\"| {}\n", node(place.id), label(&text), node(dest)));
+                }
+            }
+            if let Some(link) = &affordance.link {
+                let index = boards.iter().position(|board| *board == link.board).unwrap_or_else(|| {
+                    boards.push(&link.board);
+                    boards.len() - 1
+                });
+                out.push_str(&format!("    {} -.->|\"{}\"| board{}\n", node(place.id), label(&affordance.name), index));
+            }
+        }
+    }
+    for (index, board) in boards.iter().enumerate() {
+        out.push_str(&format!("    board{}[[\"{}\"]]\n", index, label(board)));
+    }
+    out
+}
+
+fn node(place_id: u32) -> String {
+    format!("p{}", place_id)
+}
+
+// Text inside a quoted Mermaid label, where quotes and angle brackets
+// need entity codes
+fn label(text: &str) -> String {
+    text.replace('"', "#quot;").replace('<', "#lt;").replace('>', "#gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::testing::board;
+    use crate::models::{BoardLink, Branch};
+
+    #[test]
+    fn test_flowchart() {
+        let mut breadboard = board(&["Invoice", "Setup \"Autopay\"", "Done"], &[(0, 1)]);
+        let pay = &mut breadboard.places[1].affordances;
+        pay.push(crate::models::Affordance::new(10, "Pay".to_string()));
+        pay[0].branches.push(Branch { label: "declined".to_string(), connects_to: Some(1) });
+        pay[0].link = Some(BoardLink { board: "billing.toml".to_string(), place_id: 4 });
+
+        assert_eq!(flowchart(&breadboard), "\
+flowchart LR
+    p1[\"Invoice\"]
+    p2[\"Setup #quot;Autopay#quot;\"]
+    p3[\"Done\"]
+    p1 -->|\"to 1\"| p2
+    p2 -->|\"Pay: declined\"| p1
+    p2 -.->|\"Pay\"| board0
+    board0[[\"billing.toml\"]]
+");
+    }
+}
@@ -1,21 +1,437 @@
 use serde::{Deserialize, Serialize};
+use crate::locale::{self, Strings};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Affordance {
     pub id: u32,
     pub name: String,
     pub connects_to: Option<u32>, // Place ID
+    pub notes: Option<String>,
+    pub link: Option<BoardLink>, // Connection into another board instead
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub cut: bool, // Scoped out: kept on the board but not built or exported
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub rabbit_hole: bool, // A risk to settle while shaping
+    #[serde(default, skip_serializing_if = "Status::is_planned")]
+    pub status: Status,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimate: Option<Estimate>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub branches: Vec<Branch>, // Labeled outcomes when the action can lead to different places
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub comments: Vec<Comment>,
+    #[serde(flatten)]
+    pub attribution: Attribution,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+// Who added an item and who last changed it, and when; stamped on save
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Attribution {
+    pub created_by: Option<String>,
+    pub created_at: Option<String>, // RFC 3339 timestamp
+    pub modified_by: Option<String>,
+    pub modified_at: Option<String>,
+}
+
+impl Attribution {
+    pub fn created(author: String) -> Self {
+        Self { created_by: Some(author), created_at: Some(now()), ..Self::default() }
+    }
+
+    pub fn modify(&mut self, author: String) {
+        self.modified_by = Some(author);
+        self.modified_at = Some(now());
+    }
+
+    // (author, timestamp) of the creation and of the last change, where known
+    pub fn stamps(&self) -> impl Iterator<Item = (&str, &str)> {
+        [(&self.created_by, &self.created_at), (&self.modified_by, &self.modified_at)].into_iter()
+            .filter_map(|(by, at)| Some((by.as_deref()?, at.as_deref().unwrap_or_default())))
+    }
+
+    // "Added by alice, 2026-10-16 14:03" and "Changed by bob, ..." lines
+    pub fn describe(&self, s: &Strings) -> Vec<String> {
+        let mut lines = Vec::new();
+        if let Some(by) = &self.created_by {
+            lines.push(locale::fill(s.added_by, &[by, &self.created_at.as_deref().map(local_time).unwrap_or_default()]));
+        }
+        if let Some(by) = &self.modified_by {
+            lines.push(locale::fill(s.changed_by, &[by, &self.modified_at.as_deref().map(local_time).unwrap_or_default()]));
+        }
+        lines
+    }
+}
+
+// Feedback left on a place or affordance, oldest first
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Comment {
+    pub author: String,
+    pub at: String, // RFC 3339 timestamp
+    pub text: String,
+}
+
+impl Comment {
+    pub fn new(author: String, text: String) -> Self {
+        Self { author, at: now(), text }
+    }
+
+    // "alice, 2026-10-16 14:03: Looks good", the time in local time
+    pub fn describe(&self, s: &Strings) -> String {
+        locale::fill(s.comment_line, &[&self.author, &local_time(&self.at), &self.text])
+    }
+}
+
+// One save in the board's change log
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChangeEntry {
+    pub author: String,
+    pub at: String, // RFC 3339 timestamp
+    pub summary: String, // "added Receipt, 2 affordances changed"
+}
+
+impl ChangeEntry {
+    pub fn new(author: String, summary: String) -> Self {
+        Self { author, at: now(), summary }
+    }
+
+    // "2026-10-16 14:03 alice: added Receipt"
+    pub fn describe(&self) -> String {
+        format!("{} {}: {}", local_time(&self.at), self.author, self.summary)
+    }
+}
+
+// A convention the board's navigation keeps to. In the file:
+//
+//   [[rules]]
+//   rule = "no_connection"
+//   from = "email"
+//   to = "admin"
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "rule", rename_all = "snake_case")]
+pub enum Rule {
+    NoConnection { from: String, to: String }, // Places in group `from` may not lead to group `to`
+    WayBack { to: String }, // Every place has a path back to the place named `to`
+}
+
+// How the board's names are written, checked live and by `bboard lint`. In
+// the file:
+//
+//   [naming]
+//   places = "title"
+//   affordances = "sentence"
+//   verb_first = true
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Naming {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub places: Option<Case>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub affordances: Option<Case>,
+    pub verb_first: bool, // Affordances start with a verb, like "Pay now"
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub verbs: Vec<String>, // Verbs besides the usual ones they may start with
+    pub fix: bool, // Put names in their case as their edit is finished
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Case {
+    Sentence, // "Turn on autopay"
+    Title,    // "Turn On Autopay"
+}
+
+impl Case {
+    pub fn label(self) -> &'static str {
+        match self {
+            Case::Sentence => "sentence case",
+            Case::Title => "title case",
+        }
+    }
+}
+
+// How the board is shown: the outline or the graph
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ViewMode {
+    #[default]
+    Outline, // Expanded or collapsed list of places
+    Graph,   // 2D diagram with boxes and connection lines
+}
+
+// How much of each place the diagram shows, from closest to furthest zoom
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Detail {
+    #[default]
+    Full,   // Boxes listing every affordance
+    Titles, // One row per place with just its name
+    Dots,   // One cell per place
+}
+
+impl Detail {
+    pub fn zoom_in(self) -> Self {
+        match self {
+            Detail::Dots => Detail::Titles,
+            _ => Detail::Full,
+        }
+    }
+
+    pub fn zoom_out(self) -> Self {
+        match self {
+            Detail::Full => Detail::Titles,
+            _ => Detail::Dots,
+        }
+    }
+}
+
+// A way of looking at the board kept with it, like "Exec summary" for the
+// graph zoomed out to titles or "V1 only" for a filter
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NamedView {
+    pub name: String,
+    #[serde(default)]
+    pub view: ViewMode,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub collapsed: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+    #[serde(default)]
+    pub zoom: Detail,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub swimlanes: bool,
+}
+
+impl NamedView {
+    // "graph, titles, swimlanes" or "outline, collapsed, filter \"v1\""
+    pub fn describe(&self, s: &Strings) -> String {
+        let mut parts = Vec::new();
+        match self.view {
+            ViewMode::Outline => {
+                parts.push(s.saved_view_outline.to_string());
+                if self.collapsed {
+                    parts.push(s.saved_view_collapsed.to_string());
+                }
+            }
+            ViewMode::Graph => {
+                parts.push(s.saved_view_graph.to_string());
+                parts.push(match self.zoom {
+                    Detail::Full => s.saved_view_full,
+                    Detail::Titles => s.saved_view_titles,
+                    Detail::Dots => s.saved_view_dots,
+                }.to_string());
+                if self.swimlanes {
+                    parts.push(s.saved_view_swimlanes.to_string());
+                }
+            }
+        }
+        if let Some(filter) = &self.filter {
+            parts.push(locale::fill(s.saved_view_filter, &[filter]));
+        }
+        parts.join(", ")
+    }
+}
+
+fn now() -> String {
+    chrono::Local::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, false)
+}
+
+// An RFC 3339 timestamp to the minute in local time, or as written if it
+// doesn't parse
+fn local_time(at: &str) -> String {
+    chrono::DateTime::parse_from_rfc3339(at)
+        .map(|at| at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|_| at.to_string())
+}
+
+// The note in brackets at the end of a name, brackets and all: "[needs API
+// change]" in "Submit [needs API change]". A name that's all brackets has none.
+pub fn annotation(name: &str) -> Option<&str> {
+    let name = name.trim_end();
+    if !name.ends_with(']') {
+        return None;
+    }
+    let start = name.rfind('[')?;
+    if name[..start].trim().is_empty() {
+        return None;
+    }
+    Some(&name[start..])
+}
+
+// One outcome of an affordance, like "failure → Error"
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Branch {
+    pub label: String,
+    pub connects_to: Option<u32>, // Place ID
+}
+
+// How far an affordance is from being shipped
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    #[default]
+    Planned,
+    Building,
+    Built,
+}
+
+impl Status {
+    pub fn next(self) -> Self {
+        match self {
+            Status::Planned => Status::Building,
+            Status::Building => Status::Built,
+            Status::Built => Status::Planned,
+        }
+    }
+
+    pub fn is_planned(&self) -> bool {
+        *self == Status::Planned
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Status::Planned => "planned",
+            Status::Building => "building",
+            Status::Built => "built",
+        }
+    }
+}
+
+// A rough weight for an affordance, to check a board against its appetite:
+// points, written as a number, or a t-shirt size, which counts as points too
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Estimate {
+    Points(f64),
+    Size(Size),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Size {
+    #[serde(rename = "XS")]
+    ExtraSmall,
+    #[serde(rename = "S")]
+    Small,
+    #[serde(rename = "M")]
+    Medium,
+    #[serde(rename = "L")]
+    Large,
+    #[serde(rename = "XL")]
+    ExtraLarge,
+}
+
+const SIZES: [(Size, &str, f64); 5] = [
+    (Size::ExtraSmall, "XS", 0.5),
+    (Size::Small, "S", 1.0),
+    (Size::Medium, "M", 2.0),
+    (Size::Large, "L", 4.0),
+    (Size::ExtraLarge, "XL", 8.0),
+];
+
+impl Estimate {
+    // "3", "1.5" or a size like "m", as typed
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        if let Some((size, ..)) = SIZES.iter().find(|(_, label, _)| label.eq_ignore_ascii_case(text)) {
+            return Some(Estimate::Size(*size));
+        }
+        text.parse().ok()
+            .filter(|points: &f64| points.is_finite() && *points >= 0.0)
+            .map(Estimate::Points)
+    }
+
+    pub fn points(self) -> f64 {
+        match self {
+            Estimate::Points(points) => points,
+            Estimate::Size(size) => SIZES.iter().find(|(s, ..)| *s == size).map_or(0.0, |(.., points)| *points),
+        }
+    }
+}
+
+impl std::fmt::Display for Estimate {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Estimate::Points(points) => write!(f, "{}", points),
+            Estimate::Size(size) => write!(f, "{}", SIZES.iter().find(|(s, ..)| s == size).map_or("?", |(_, label, _)| label)),
+        }
+    }
+}
+
+// Connection to a place in another board of the workspace
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BoardLink {
+    pub board: String, // Board file as listed in the workspace
+    pub place_id: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Place {
     pub id: u32,
     pub name: String,
     pub group: Option<String>,
+    pub notes: Option<String>,
+    pub kind: Option<PlaceKind>, // What sort of place it is; untyped places get no icon
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub rabbit_hole: bool, // A risk to settle while shaping
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub comments: Vec<Comment>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<String>, // Sketches and other files, relative to the board file
+    #[serde(flatten)]
+    pub attribution: Attribution,
     pub affordances: Vec<Affordance>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub board: Option<Box<Breadboard>>, // The places inside this one, drilled into with Alt+Down
+}
+
+// The sorts of places a flow goes through besides ordinary pages
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PlaceKind {
+    Screen,
+    Modal,
+    Email,
+    Job, // Background job
+    External, // Someone else's system
+}
+
+impl PlaceKind {
+    pub const ALL: [PlaceKind; 5] = [PlaceKind::Screen, PlaceKind::Modal, PlaceKind::Email, PlaceKind::Job, PlaceKind::External];
+
+    // Single-column glyph shown before the place name
+    pub fn icon(self) -> char {
+        match self {
+            PlaceKind::Screen => '▭',
+            PlaceKind::Modal => '◫',
+            PlaceKind::Email => '✉',
+            PlaceKind::Job => '⚙',
+            PlaceKind::External => '☁',
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            PlaceKind::Screen => "screen",
+            PlaceKind::Modal => "modal",
+            PlaceKind::Email => "email",
+            PlaceKind::Job => "background job",
+            PlaceKind::External => "external system",
+        }
+    }
+
+    // Untyped, then each kind in turn, then back to untyped
+    pub fn cycle(kind: Option<PlaceKind>) -> Option<PlaceKind> {
+        match kind {
+            None => Some(Self::ALL[0]),
+            Some(kind) => {
+                let index = Self::ALL.iter().position(|k| *k == kind).unwrap_or(0);
+                Self::ALL.get(index + 1).copied()
+            }
+        }
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Breadboard {
     pub name: String,
     pub created: String,
@@ -24,6 +440,18 @@ pub struct Breadboard {
     pub next_place_id: u32,
     #[serde(default = "default_next_affordance_id")]
     pub next_affordance_id: u32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub happy_path: Vec<u32>, // Places of the main journey through the board, in order
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub glossary: BTreeMap<String, String>, // Term → definition
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<Rule>, // Navigation conventions `bboard lint` and the views check
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub naming: Option<Naming>, // How names are written, checked along with the rules
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub views: Vec<NamedView>, // Ways of looking at the board, picked from the views menu
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changelog: Vec<ChangeEntry>, // One entry per save that changed something, oldest first
 }
 
 fn default_next_place_id() -> u32 {
@@ -42,6 +470,12 @@ impl Breadboard {
             places: Vec::new(),
             next_place_id: 1,
             next_affordance_id: 1,
+            happy_path: Vec::new(),
+            glossary: BTreeMap::new(),
+            rules: Vec::new(),
+            naming: None,
+            views: Vec::new(),
+            changelog: Vec::new(),
         }
     }
 
@@ -61,15 +495,9 @@ impl Breadboard {
         self.places
             .iter()
             .flat_map(|place| {
-                place.affordances.iter().filter_map(move |affordance| {
-                    affordance.connects_to.as_ref().and_then(|dest| {
-                        if dest == place_id {
-                            Some((place, affordance))
-                        } else {
-                            None
-                        }
-                    })
-                })
+                place.affordances.iter()
+                    .filter(move |affordance| affordance.destinations().contains(place_id))
+                    .map(move |affordance| (place, affordance))
             })
             .collect()
     }
@@ -86,6 +514,90 @@ impl Breadboard {
         id
     }
 
+    // Places reachable by following connections from `start`, including it
+    pub fn reachable_from(&self, start: u32) -> HashSet<u32> {
+        let mut reachable = HashSet::new();
+        let mut stack = vec![start];
+
+        while let Some(id) = stack.pop() {
+            if !reachable.insert(id) {
+                continue;
+            }
+            if let Some(place) = self.find_place(&id) {
+                stack.extend(place.affordances.iter().flat_map(|a| a.destinations()));
+            }
+        }
+
+        reachable
+    }
+
+    // Places and affordances flagged as rabbit holes, in board order, as
+    // "Invoice" or "Invoice › Pay now" with their notes
+    pub fn rabbit_holes(&self) -> Vec<(String, Option<&str>)> {
+        fn notes(notes: &Option<String>) -> Option<&str> {
+            notes.as_deref().map(str::trim).filter(|n| !n.is_empty())
+        }
+        let mut holes = Vec::new();
+        for place in &self.places {
+            if place.rabbit_hole {
+                holes.push((place.name.clone(), notes(&place.notes)));
+            }
+            for affordance in place.affordances.iter().filter(|a| a.rabbit_hole) {
+                holes.push((format!("{} › {}", place.name, affordance.name), notes(&affordance.notes)));
+            }
+        }
+        holes
+    }
+
+    // Steps of the happy path that no longer hold, as (from, to) places:
+    // the first has no affordance leading to the second, or is gone
+    pub fn happy_path_breaks(&self) -> Vec<(u32, u32)> {
+        self.happy_path.windows(2)
+            .filter(|step| {
+                !self.find_place(&step[0])
+                    .is_some_and(|place| place.affordances.iter().any(|a| a.destinations().contains(&step[1])))
+            })
+            .map(|step| (step[0], step[1]))
+            .collect()
+    }
+
+    // The board with every nested board inlined: a place's inner places come
+    // right after it, grouped under its name, called "Checkout › Cart" and
+    // given fresh IDs. Connections between inner places are kept.
+    pub fn flattened(&self) -> Breadboard {
+        let mut flat = self.clone();
+        flat.sync_id_counters();
+        let mut index = 0;
+        while index < flat.places.len() {
+            if let Some(nested) = flat.places[index].board.take() {
+                let nested = nested.flattened();
+                let container = flat.places[index].name.clone();
+                let ids: HashMap<u32, u32> = nested.places.iter().map(|p| (p.id, flat.generate_place_id())).collect();
+                let remap = |id: Option<u32>| id.and_then(|id| ids.get(&id).copied());
+                let inner: Vec<Place> = nested.places.into_iter()
+                    .map(|mut place| {
+                        place.id = ids[&place.id];
+                        place.name = format!("{} › {}", container, place.name);
+                        place.group = Some(container.clone());
+                        for affordance in &mut place.affordances {
+                            affordance.id = flat.generate_affordance_id();
+                            affordance.connects_to = remap(affordance.connects_to);
+                            for branch in &mut affordance.branches {
+                                branch.connects_to = remap(branch.connects_to);
+                            }
+                        }
+                        place
+                    })
+                    .collect();
+                let count = inner.len();
+                flat.places.splice(index + 1..index + 1, inner);
+                index += count;
+            }
+            index += 1;
+        }
+        flat
+    }
+
     // Sync ID counters after loading from file to ensure new IDs don't conflict
     pub fn sync_id_counters(&mut self) {
         let max_place_id = self.places.iter()
@@ -110,10 +622,40 @@ impl Place {
             id,
             name,
             group: None,
+            notes: None,
+            kind: None,
+            rabbit_hole: false,
+            comments: Vec::new(),
+            attachments: Vec::new(),
+            attribution: Attribution::default(),
             affordances: Vec::new(),
+            board: None,
+        }
+    }
+
+    // Outside the product: shown apart and exempt from reachability checks
+    pub fn is_external(&self) -> bool {
+        self.kind == Some(PlaceKind::External)
+    }
+
+    // Name with the kind's icon in front, as boards and exports show it, and
+    // ⊞ after it when there are places inside
+    pub fn title(&self) -> String {
+        let name = match self.kind {
+            Some(kind) => format!("{} {}", kind.icon(), self.name),
+            None => self.name.clone(),
+        };
+        match self.inner_places() {
+            0 => name,
+            _ => format!("{} ⊞", name),
         }
     }
 
+    // How many places are nested inside this one
+    pub fn inner_places(&self) -> usize {
+        self.board.as_ref().map_or(0, |board| board.places.len())
+    }
+
     #[allow(dead_code)]
     pub fn with_group(mut self, group: String) -> Self {
         self.group = Some(group);
@@ -131,20 +673,124 @@ impl Affordance {
             id,
             name,
             connects_to: None,
+            notes: None,
+            link: None,
+            cut: false,
+            rabbit_hole: false,
+            status: Status::Planned,
+            estimate: None,
+            branches: Vec::new(),
+            comments: Vec::new(),
+            attribution: Attribution::default(),
         }
     }
 
-    #[allow(dead_code)]
+    // Every place this affordance leads to, directly or through one of its
+    // outcomes, each once
+    pub fn destinations(&self) -> Vec<u32> {
+        let mut destinations = Vec::new();
+        for dest in self.connects_to.into_iter().chain(self.branches.iter().filter_map(|b| b.connects_to)) {
+            if !destinations.contains(&dest) {
+                destinations.push(dest);
+            }
+        }
+        destinations
+    }
+
     pub fn with_connection(mut self, destination_place_id: u32) -> Self {
         self.connects_to = Some(destination_place_id);
         self
     }
 }
 
+// Boards for the tests of the modules that work on them
+#[cfg(test)]
+pub mod testing {
+    use super::{Affordance, Breadboard, Place};
+
+    // Places named in order, with an affordance "to N" for each (from, to)
+    // link between their indices
+    pub fn board(places: &[&str], links: &[(usize, usize)]) -> Breadboard {
+        let mut breadboard = Breadboard::new("Test".to_string());
+        for name in places {
+            let id = breadboard.generate_place_id();
+            breadboard.add_place(Place::new(id, name.to_string()));
+        }
+        for (from, to) in links {
+            let id = breadboard.generate_affordance_id();
+            let dest = breadboard.places[*to].id;
+            breadboard.places[*from].add_affordance(Affordance::new(id, format!("to {}", to)).with_connection(dest));
+        }
+        breadboard
+    }
+
+    // Renames the affordances of `breadboard`, in order across its places
+    pub fn name_affordances(breadboard: &mut Breadboard, names: &[&str]) {
+        for (affordance, name) in breadboard.places.iter_mut().flat_map(|p| &mut p.affordances).zip(names) {
+            affordance.name = name.to_string();
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_estimates() {
+        assert_eq!(Estimate::parse(" m "), Some(Estimate::Size(Size::Medium)));
+        assert_eq!(Estimate::parse("1.5"), Some(Estimate::Points(1.5)));
+        assert_eq!(Estimate::parse("-2"), None);
+        assert_eq!(Estimate::parse("huge"), None);
+        assert_eq!(Estimate::Size(Size::ExtraLarge).points(), 8.0);
+        assert_eq!(Estimate::Size(Size::ExtraSmall).to_string(), "XS");
+        assert_eq!(Estimate::Points(3.0).to_string(), "3");
+
+        let mut pay = Affordance::new(1, "Pay".to_string());
+        pay.estimate = Some(Estimate::Size(Size::Large));
+        let text = toml::to_string(&pay).unwrap();
+        assert!(text.contains("estimate = \"L\""));
+        assert_eq!(toml::from_str::<Affordance>(&text).unwrap().estimate, pay.estimate);
+        let pay: Affordance = toml::from_str("id = 1\nname = \"Pay\"\nestimate = 3").unwrap();
+        assert_eq!(pay.estimate, Some(Estimate::Points(3.0)));
+    }
+
+    #[test]
+    fn test_annotation() {
+        assert_eq!(annotation("Submit [needs API change]"), Some("[needs API change]"));
+        assert_eq!(annotation("Pay [v2] "), Some("[v2]"));
+        assert_eq!(annotation("[Beta] Settings"), None);
+        assert_eq!(annotation("[wip]"), None);
+        assert_eq!(annotation("Pay ]"), None);
+    }
+
+    #[test]
+    fn test_flattened_inlines_nested_boards() {
+        let mut checkout = Breadboard::new("Checkout".to_string());
+        checkout.add_place(Place::new(1, "Cart".to_string()));
+        checkout.add_place(Place::new(2, "Payment".to_string()));
+        checkout.places[0].add_affordance(Affordance::new(1, "Pay".to_string()).with_connection(2));
+
+        let mut board = Breadboard::new("Shop".to_string());
+        board.add_place(Place::new(1, "Home".to_string()));
+        board.add_place(Place::new(2, "Checkout".to_string()));
+        board.add_place(Place::new(3, "Receipt".to_string()));
+        board.places[0].add_affordance(Affordance::new(1, "Buy".to_string()).with_connection(2));
+        board.places[1].board = Some(Box::new(checkout));
+
+        let flat = board.flattened();
+        let names: Vec<&str> = flat.places.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["Home", "Checkout", "Checkout › Cart", "Checkout › Payment", "Receipt"]);
+        assert!(flat.places.iter().all(|p| p.board.is_none()));
+        let (cart, payment) = (&flat.places[2], &flat.places[3]);
+        assert_eq!(cart.group.as_deref(), Some("Checkout"));
+        assert_eq!(cart.affordances[0].connects_to, Some(payment.id));
+        assert_eq!(flat.places[0].affordances[0].connects_to, Some(2));
+        let ids: HashSet<u32> = flat.places.iter().map(|p| p.id).collect();
+        assert_eq!(ids.len(), 5);
+        assert_ne!(cart.affordances[0].id, flat.places[0].affordances[0].id);
+    }
+
     #[test]
     fn test_place_creation() {
         let place = Place::new(1, "Test Place".to_string());
@@ -238,6 +884,24 @@ mod tests {
         assert_eq!(incoming[0].1.name, "Go to Place 2");
     }
 
+    #[test]
+    fn test_place_kind_cycles_and_titles() {
+        let mut place = Place::new(1, "Welcome".to_string());
+        assert_eq!(place.title(), "Welcome");
+
+        let mut kinds = Vec::new();
+        for _ in 0..6 {
+            place.kind = PlaceKind::cycle(place.kind);
+            kinds.push(place.kind);
+        }
+        assert_eq!(kinds[2], Some(PlaceKind::Email));
+        assert_eq!(kinds[5], None);
+
+        place.kind = Some(PlaceKind::Email);
+        assert_eq!(place.title(), "✉ Welcome");
+        assert!(toml::to_string(&place).unwrap().contains("kind = \"email\""));
+    }
+
     #[test]
     fn test_serialization() {
         let breadboard = Breadboard::new("Test Board".to_string());
@@ -264,8 +928,11 @@ name = "Test Action"
         assert_eq!(breadboard.places.len(), 1);
         assert_eq!(breadboard.places[0].name, "Test Place");
         assert_eq!(breadboard.places[0].id, 1);
+        assert_eq!(breadboard.places[0].kind, None);
         assert_eq!(breadboard.places[0].affordances.len(), 1);
         assert_eq!(breadboard.places[0].affordances[0].name, "Test Action");
         assert_eq!(breadboard.places[0].affordances[0].id, 1);
+        assert!(!breadboard.places[0].affordances[0].cut);
+        assert_eq!(breadboard.places[0].affordances[0].status, Status::Planned);
     }
 }
\ No newline at end of file
@@ -0,0 +1,135 @@
+use std::collections::BTreeMap;
+
+use crate::glossary::find_terms;
+use crate::models::{annotation, Case, Naming};
+
+// The board's naming convention: place and affordance names in sentence or
+// title case, and affordances that start with a verb.
+
+// Words title case leaves in lower case unless they start or end the name
+const MINOR_WORDS: &[&str] = &[
+    "a", "an", "and", "as", "at", "but", "by", "for", "from", "in", "into", "nor", "of", "on", "or", "per", "the", "to", "via", "with",
+];
+
+// Verbs affordances usually start with; a board's `verbs` adds its own
+const VERBS: &[&str] = &[
+    "accept", "add", "apply", "approve", "archive", "ask", "assign", "attach", "back", "book", "browse", "buy", "call",
+    "cancel", "change", "check", "checkout", "choose", "clear", "click", "close", "collapse", "comment", "compare",
+    "complete", "confirm", "connect", "contact", "continue", "copy", "create", "decline", "delete", "deny", "disable",
+    "disconnect", "dismiss", "download", "drag", "duplicate", "edit", "email", "enable", "enter", "expand", "export",
+    "fill", "filter", "find", "finish", "follow", "forward", "get", "give", "go", "hide", "import", "invite", "join",
+    "keep", "leave", "like", "link", "load", "log", "lock", "manage", "mark", "merge", "move", "mute", "next", "open",
+    "order", "pause", "pay", "pick", "pin", "play", "post", "preview", "print", "publish", "read", "redeem", "refresh",
+    "refund", "register", "reject", "reload", "remove", "rename", "renew", "reopen", "reply", "report", "request",
+    "resend", "reset", "resolve", "restore", "resume", "retry", "return", "review", "revoke", "run", "save", "scan",
+    "schedule", "search", "see", "select", "send", "set", "share", "show", "sign", "skip", "sort", "start", "stop",
+    "submit", "subscribe", "switch", "sync", "tap", "toggle", "track", "try", "turn", "undo", "unlock", "unmute",
+    "unsubscribe", "update", "upgrade", "upload", "use", "validate", "verify", "view", "visit", "vote", "watch",
+];
+
+// `name` as the convention would have it, or None when it already is
+pub fn suggestion(naming: &Naming, name: &str, place: bool, glossary: &BTreeMap<String, String>) -> Option<String> {
+    let case = if place { naming.places } else { naming.affordances }?;
+    let fixed = in_case(name, case, glossary);
+    (fixed != name).then_some(fixed)
+}
+
+// Whether the affordance `name` starts with a verb the board knows
+pub fn starts_with_verb(naming: &Naming, name: &str) -> bool {
+    let Some(first) = name.split_whitespace().next() else {
+        return true;
+    };
+    let first = first.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase();
+    VERBS.contains(&first.as_str()) || naming.verbs.iter().any(|verb| verb.trim().eq_ignore_ascii_case(&first))
+}
+
+// `name` put in `case`. Glossary terms are written as the glossary has
+// them, and a note in brackets at the end, acronyms like "PDF" and words
+// with capitals inside like "iPhone" are left as they are.
+pub fn in_case(name: &str, case: Case, glossary: &BTreeMap<String, String>) -> String {
+    let text = match annotation(name) {
+        Some(note) => &name[..name.trim_end().len() - note.len()],
+        None => name,
+    };
+    let terms = find_terms(text, glossary);
+    let last = text.split(' ').filter(|word| !word.is_empty()).count().saturating_sub(1);
+    let mut cased = String::new();
+    let mut copied = 0; // How much of `text` is in `cased`
+    let mut start = 0;
+    for (index, word) in text.split(' ').filter(|word| !word.is_empty()).enumerate() {
+        start += text[start..].find(word).unwrap_or(0);
+        let word_start = start;
+        start += word.len();
+        if word_start < copied {
+            continue; // Inside a glossary term already written
+        }
+        let (end, written) = match terms.iter().find(|term| term.start == word_start) {
+            Some(term) => {
+                let found = &text[term.clone()];
+                let key = glossary.keys().map(|key| key.trim()).find(|key| key.eq_ignore_ascii_case(found));
+                (term.end, key.unwrap_or(found).to_string())
+            }
+            None if keeps_its_case(word) => (start, word.to_string()),
+            None => {
+                let lower = word.to_lowercase();
+                let capital = match case {
+                    Case::Sentence => index == 0,
+                    Case::Title => index == 0 || index == last || !MINOR_WORDS.contains(&lower.as_str()),
+                };
+                (start, if capital { capitalized(&lower) } else { lower })
+            }
+        };
+        cased.push_str(&text[copied..word_start]);
+        cased.push_str(&written);
+        copied = end;
+    }
+    cased.push_str(&text[copied..]);
+    format!("{}{}", cased, &name[text.len()..])
+}
+
+// Acronyms and words like "iPhone" and "GitHub": any capital after the first
+// letter
+fn keeps_its_case(word: &str) -> bool {
+    word.chars().skip(1).any(char::is_uppercase)
+}
+
+fn capitalized(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_in_case() {
+        let glossary = BTreeMap::from([("Autopay".to_string(), String::new())]);
+        let none = BTreeMap::new();
+        assert_eq!(in_case("turn On autopay", Case::Sentence, &none), "Turn on autopay");
+        assert_eq!(in_case("turn On autopay", Case::Sentence, &glossary), "Turn on Autopay");
+        let glossary = BTreeMap::from([("Saved Card".to_string(), String::new())]);
+        assert_eq!(in_case("PAY  with saved card now", Case::Sentence, &glossary), "PAY  with Saved Card now");
+        assert_eq!(in_case("download the PDF receipt", Case::Title, &none), "Download the PDF Receipt");
+        assert_eq!(in_case("sign in with GitHub", Case::Sentence, &none), "Sign in with GitHub");
+        assert_eq!(in_case("what to pay for", Case::Title, &none), "What to Pay For");
+        // The annotation is left alone
+        assert_eq!(in_case("submit form [Needs API change]", Case::Title, &none), "Submit Form [Needs API change]");
+    }
+
+    #[test]
+    fn test_suggestion_and_verbs() {
+        let naming = Naming { places: Some(Case::Title), affordances: Some(Case::Sentence), verbs: vec!["Upvote".to_string()], ..Naming::default() };
+        let glossary = BTreeMap::new();
+        assert_eq!(suggestion(&naming, "setup autopay", true, &glossary).as_deref(), Some("Setup Autopay"));
+        assert_eq!(suggestion(&naming, "Pay now", false, &glossary), None);
+        assert_eq!(suggestion(&Naming::default(), "setup autopay", true, &glossary), None);
+
+        assert!(starts_with_verb(&naming, "Download PDF"));
+        assert!(starts_with_verb(&naming, "upvote"));
+        assert!(!starts_with_verb(&naming, "Invoice total"));
+    }
+}
@@ -0,0 +1,233 @@
+use crate::config::Paper;
+use crate::html::escape;
+use crate::models::{Affordance, Breadboard, Place};
+use std::collections::HashMap;
+
+// A board laid out on paper, as an HTML file to open in a browser and
+// print. Each page holds the places of one group, or as many of them as
+// fit when the group runs longer; every connection names the page its
+// destination is on, and the last pages index the places with where they
+// lead and what leads to them, so the printout can be followed like the board.
+
+const PLACE_LINES: usize = 3; // Lines a place takes besides its affordances: its title and the gap after it
+const INDEX_HEADER_LINES: usize = 2;
+
+// Rough lines of text a page holds at the stylesheet's size
+fn page_lines(paper: Paper) -> usize {
+    match paper {
+        Paper::A4 => 48,
+        Paper::Letter => 44,
+    }
+}
+
+fn page_size(paper: Paper) -> &'static str {
+    match paper {
+        Paper::A4 => "A4",
+        Paper::Letter => "letter",
+    }
+}
+
+// One printed page of places
+#[derive(Debug, PartialEq)]
+pub struct Page<'a> {
+    pub group: Option<&'a str>,
+    pub places: Vec<&'a Place>,
+}
+
+// The places split into pages of `lines` lines, group by group in the order
+// each group first appears. A place longer than a page gets one to itself.
+pub fn paginate(breadboard: &Breadboard, lines: usize) -> Vec<Page<'_>> {
+    let mut groups: Vec<Option<&str>> = Vec::new();
+    for place in &breadboard.places {
+        if !groups.contains(&place.group.as_deref()) {
+            groups.push(place.group.as_deref());
+        }
+    }
+
+    let mut pages = Vec::new();
+    for group in groups {
+        let mut page = Page { group, places: Vec::new() };
+        let mut used = 0;
+        for place in breadboard.places.iter().filter(|p| p.group.as_deref() == group) {
+            let height = PLACE_LINES + place.affordances.iter().map(|a| 1 + a.branches.len()).sum::<usize>();
+            if !page.places.is_empty() && used + height > lines {
+                pages.push(std::mem::replace(&mut page, Page { group, places: Vec::new() }));
+                used = 0;
+            }
+            page.places.push(place);
+            used += height;
+        }
+        pages.push(page);
+    }
+    pages
+}
+
+pub fn html(breadboard: &Breadboard, paper: Paper) -> String {
+    let lines = page_lines(paper);
+    let pages = paginate(breadboard, lines);
+    let page_of: HashMap<u32, usize> = pages.iter()
+        .enumerate()
+        .flat_map(|(index, page)| page.places.iter().map(move |place| (place.id, index + 1)))
+        .collect();
+    let index_rows = lines - INDEX_HEADER_LINES;
+    let index_pages = breadboard.places.len().div_ceil(index_rows).max(1);
+    let total = pages.len() + index_pages;
+
+    let name = |id: u32| breadboard.find_place(&id).map_or("[Unknown]".to_string(), |p| p.name.clone());
+    let reference = |id: u32| match page_of.get(&id) {
+        Some(page) => format!("{} <span class=\"page-ref\">p.&nbsp;{}</span>", escape(&name(id)), page),
+        None => escape(&name(id)),
+    };
+
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str(&format!("<title>{}</title>\n", escape(&breadboard.name)));
+    out.push_str(&format!("<style>\n@page {{ size: {}; margin: 15mm; }}\n{}</style>\n</head>\n<body>\n", page_size(paper), STYLE));
+
+    for (index, page) in pages.iter().enumerate() {
+        let title = match page.group {
+            Some(group) => format!("{} › {}", breadboard.name, group),
+            None => breadboard.name.clone(),
+        };
+        out.push_str(&page_header(&title, index + 1, total));
+        for place in &page.places {
+            out.push_str(&format!("<section class=\"place\">\n<h2>{}</h2>\n", escape(&place.title())));
+            if let Some(notes) = place.notes.as_deref().map(str::trim).filter(|n| !n.is_empty()) {
+                out.push_str(&format!("<p class=\"notes\">{}</p>\n", escape(notes)));
+            }
+            if !place.affordances.is_empty() {
+                out.push_str("<ul>\n");
+                for affordance in &place.affordances {
+                    out.push_str(&format!("<li>{}", affordance_text(affordance, &reference)));
+                    if !affordance.branches.is_empty() {
+                        out.push_str("\n<ul>\n");
+                        for branch in &affordance.branches {
+                            match branch.connects_to {
+                                Some(dest) => out.push_str(&format!("<li>If {} → {}</li>\n", escape(&branch.label), reference(dest))),
+                                None => out.push_str(&format!("<li>If {}</li>\n", escape(&branch.label))),
+                            }
+                        }
+                        out.push_str("</ul>\n");
+                    }
+                    out.push_str("</li>\n");
+                }
+                out.push_str("</ul>\n");
+            }
+            out.push_str("</section>\n");
+        }
+        out.push_str("</div>\n");
+    }
+
+    // Index of places, by name, with where each leads and what leads to it
+    let mut sorted: Vec<&Place> = breadboard.places.iter().collect();
+    sorted.sort_by_key(|place| place.name.to_lowercase());
+    let mut sources: HashMap<u32, Vec<u32>> = HashMap::new();
+    for place in &breadboard.places {
+        for dest in place.affordances.iter().flat_map(|a| a.destinations()) {
+            let from = sources.entry(dest).or_default();
+            if !from.contains(&place.id) && dest != place.id {
+                from.push(place.id);
+            }
+        }
+    }
+    let list = |ids: Vec<u32>| ids.into_iter().map(reference).collect::<Vec<_>>().join(", ");
+    for (chunk_index, chunk) in sorted.chunks(index_rows.max(1)).enumerate() {
+        out.push_str(&page_header("Connection index", pages.len() + chunk_index + 1, total));
+        out.push_str("<table>\n<tr><th>Place</th><th>Page</th><th>Leads to</th><th>Linked from</th></tr>\n");
+        for place in chunk {
+            let mut leads_to: Vec<u32> = Vec::new();
+            for dest in place.affordances.iter().flat_map(|a| a.destinations()) {
+                if !leads_to.contains(&dest) && dest != place.id {
+                    leads_to.push(dest);
+                }
+            }
+            out.push_str(&format!(
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                escape(&place.name),
+                page_of.get(&place.id).copied().unwrap_or_default(),
+                list(leads_to),
+                list(sources.remove(&place.id).unwrap_or_default()),
+            ));
+        }
+        out.push_str("</table>\n</div>\n");
+    }
+    if breadboard.places.is_empty() {
+        out.push_str(&page_header("Connection index", total, total));
+        out.push_str("<p>The board has no places yet.</p>\n</div>\n");
+    }
+
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+const STYLE: &str = "\
+body { font: 11pt/1.35 system-ui, sans-serif; margin: 0; }
+.page { break-after: page; }
+.page:last-child { break-after: auto; }
+header { display: flex; justify-content: space-between; border-bottom: 1px solid #888; margin-bottom: 8pt; font-size: 9pt; color: #444; }
+.place { break-inside: avoid; margin-bottom: 10pt; }
+h2 { font-size: 12pt; margin: 0 0 2pt; }
+ul { margin: 0; padding-left: 16pt; }
+.notes { margin: 0 0 2pt; font-style: italic; white-space: pre-line; }
+.page-ref { color: #666; font-size: 9pt; }
+table { border-collapse: collapse; width: 100%; font-size: 10pt; }
+th, td { text-align: left; vertical-align: top; border-bottom: 1px solid #ccc; padding: 2pt 4pt; }
+";
+
+fn page_header(title: &str, page: usize, total: usize) -> String {
+    format!("<div class=\"page\">\n<header><span>{}</span><span>Page {} of {}</span></header>\n", escape(title), page, total)
+}
+
+fn affordance_text(affordance: &Affordance, reference: &impl Fn(u32) -> String) -> String {
+    let name = escape(&affordance.name);
+    match (&affordance.connects_to, &affordance.link) {
+        (Some(dest), _) => format!("{} → {}", name, reference(*dest)),
+        (None, Some(link)) => format!("{} ⇒ {}", name, escape(&link.board)),
+        (None, None) => name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::testing;
+
+    fn board() -> Breadboard {
+        let mut breadboard = testing::board(&["Cart", "Help", "Pay", "Receipt"], &[(0, 2), (0, 1), (2, 3), (3, 0)]);
+        breadboard.name = "Checkout".to_string();
+        testing::name_affordances(&mut breadboard, &["Check out", "Get <help>", "Pay", "Done"]);
+        for (place, group) in breadboard.places.iter_mut().zip(["Buying", "Support", "Buying", "Buying"]) {
+            place.group = Some(group.to_string());
+        }
+        breadboard
+    }
+
+    #[test]
+    fn test_paginate_by_group_and_length() {
+        let breadboard = board();
+        let names = |pages: Vec<Page>| -> Vec<(Option<String>, Vec<String>)> {
+            pages.into_iter()
+                .map(|page| (page.group.map(str::to_string), page.places.iter().map(|p| p.name.clone()).collect()))
+                .collect()
+        };
+        assert_eq!(names(paginate(&breadboard, 48)), vec![
+            (Some("Buying".to_string()), vec!["Cart".to_string(), "Pay".to_string(), "Receipt".to_string()]),
+            (Some("Support".to_string()), vec!["Help".to_string()]),
+        ]);
+        // Cart takes five lines and Pay four, so they can't share eight
+        assert_eq!(paginate(&breadboard, 8).iter().map(|p| p.places.len()).collect::<Vec<_>>(), vec![1, 2, 1]);
+    }
+
+    #[test]
+    fn test_html_pages_and_index() {
+        let html = html(&board(), Paper::Letter);
+        assert!(html.contains("@page { size: letter; margin: 15mm; }"));
+        assert!(html.contains("<header><span>Checkout › Buying</span><span>Page 1 of 3</span></header>"));
+        assert!(html.contains("<li>Get &lt;help&gt; → Help <span class=\"page-ref\">p.&nbsp;2</span></li>"));
+        assert!(html.contains("<header><span>Connection index</span><span>Page 3 of 3</span></header>"));
+        assert!(html.contains(
+            "<tr><td>Cart</td><td>1</td><td>Pay <span class=\"page-ref\">p.&nbsp;1</span>, Help <span class=\"page-ref\">p.&nbsp;2</span></td>\
+             <td>Receipt <span class=\"page-ref\">p.&nbsp;1</span></td></tr>"
+        ));
+    }
+}
@@ -0,0 +1,253 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::app::App;
+use crate::locale::Strings;
+use crate::models::Breadboard;
+
+// Unsaved edits, kept in `$XDG_STATE_HOME/bboard/recovery/` while bboard
+// runs so a closed terminal or a crash doesn't lose them. A clean exit
+// removes the file; one still there on the next start is offered back.
+// Each board has its own file, named after its path, and a board that was
+// never saved uses "unsaved.toml".
+
+const WRITE_INTERVAL: Duration = Duration::from_secs(2); // At most this often while editing
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Recovered {
+    pub board_file: Option<String>, // Absolute path of the board's file, if it had one
+    pub saved_at: chrono::DateTime<chrono::Local>,
+    pub pid: u32, // Of the bboard that wrote it, to leave a running one's alone
+    pub board: Breadboard,
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+impl Recovered {
+    pub fn remove(&self) {
+        let _ = fs::remove_file(&self.path);
+    }
+
+    // Whether the board's lock, held by `holder`, is the one the bboard that
+    // wrote this left behind
+    pub fn left_lock(&self, holder: &str) -> bool {
+        holder.contains(&format!("(pid {})", self.pid))
+    }
+
+    // "billing.toml" or "an unsaved board", for the prompt
+    pub fn describe(&self, s: &Strings) -> String {
+        match &self.board_file {
+            Some(file) => Path::new(file).file_name().map_or(file.clone(), |name| name.to_string_lossy().to_string()),
+            None => s.an_unsaved_board.to_string(),
+        }
+    }
+}
+
+pub fn default_dir() -> Option<PathBuf> {
+    crate::state::SavedState::default_path().map(|state| state.with_file_name("recovery"))
+}
+
+// Where `board_file`'s edits are kept in `dir`
+fn path_for(dir: &Path, board_file: Option<&str>) -> PathBuf {
+    let name = match board_file {
+        Some(file) => file.chars().map(|c| if c.is_alphanumeric() || c == '.' || c == '-' { c } else { '_' }).collect(),
+        None => "unsaved".to_string(),
+    };
+    dir.join(format!("{}.toml", name.trim_start_matches('_')))
+}
+
+// The board file as recovery files name it
+fn absolute(file: &str) -> String {
+    fs::canonicalize(file)
+        .or_else(|_| std::env::current_dir().map(|dir| dir.join(file)))
+        .map_or(file.to_string(), |path| path.to_string_lossy().to_string())
+}
+
+// What's left in `dir` from a bboard that didn't exit cleanly: the edits to
+// `board_file`, or without one the most recent edits to any board. File
+// names can collide, so edits kept for another board are left alone.
+pub fn find(dir: &Path, board_file: Option<&str>) -> Option<Recovered> {
+    let board_file = board_file.map(absolute);
+    let paths: Vec<PathBuf> = match &board_file {
+        Some(file) => vec![path_for(dir, Some(file))],
+        None => fs::read_dir(dir).ok()?.filter_map(|entry| entry.ok().map(|e| e.path())).collect(),
+    };
+    paths.into_iter()
+        .filter_map(|path| {
+            let mut recovered: Recovered = toml::from_str(&fs::read_to_string(&path).ok()?).ok()?;
+            recovered.path = path;
+            Some(recovered)
+        })
+        .filter(|recovered| board_file.is_none() || recovered.board_file == board_file)
+        .filter(|recovered| !running(recovered.pid))
+        .max_by_key(|recovered| recovered.saved_at)
+}
+
+// Whether another bboard with this pid may still be running. Where that
+// can't be told, it's taken to be, so its edits and lock stay its own.
+#[cfg(unix)]
+fn running(pid: u32) -> bool {
+    if pid == std::process::id() {
+        return false;
+    }
+    let Ok(pid) = libc::pid_t::try_from(pid) else {
+        return true;
+    };
+    // Signal 0 only checks the process is there; EPERM means it is, but
+    // belongs to someone else
+    // SAFETY: kill with signal 0 sends nothing and touches no memory
+    let result = unsafe { libc::kill(pid, 0) };
+    result == 0 || std::io::Error::last_os_error().raw_os_error() != Some(libc::ESRCH)
+}
+
+#[cfg(not(unix))]
+fn running(pid: u32) -> bool {
+    pid != std::process::id()
+}
+
+// Writes the open board's unsaved edits as they happen
+pub struct Journal {
+    dir: PathBuf,
+    written: Option<PathBuf>, // The file this session last wrote
+    last_write: Option<Instant>,
+    pending: bool, // Something happened since the last write
+}
+
+impl Journal {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir, written: None, last_write: None, pending: false }
+    }
+
+    // An action was handled, so the board may have changed
+    pub fn note_change(&mut self) {
+        self.pending = true;
+    }
+
+    // Write the board's edits if it has any, or remove the file once it's
+    // saved. Unless `now`, waits for WRITE_INTERVAL after the last write.
+    pub fn flush(&mut self, app: &App, now: bool) -> Result<()> {
+        if !self.pending || (!now && self.last_write.is_some_and(|at| at.elapsed() < WRITE_INTERVAL)) {
+            return Ok(());
+        }
+        self.pending = false;
+        self.last_write = Some(Instant::now());
+
+        let board_file = app.state.current_filename.as_deref().map(absolute);
+        let path = path_for(&self.dir, board_file.as_deref());
+        // Another board was opened: what was kept for the last one goes
+        if self.written.as_ref().is_some_and(|written| *written != path) {
+            self.discard();
+        }
+        if !app.is_dirty() {
+            if self.written.take().is_some() {
+                let _ = fs::remove_file(&path);
+            }
+            return Ok(());
+        }
+
+        let recovered = Recovered {
+            board_file,
+            saved_at: chrono::Local::now(),
+            pid: std::process::id(),
+            board: app.root_board(),
+            path: path.clone(),
+        };
+        fs::create_dir_all(&self.dir).with_context(|| format!("Failed to create {}", self.dir.display()))?;
+        fs::write(&path, toml::to_string(&recovered)?).with_context(|| format!("Failed to write {}", path.display()))?;
+        self.written = Some(path);
+        Ok(())
+    }
+
+    // Remove what this session wrote, on a clean exit
+    pub fn discard(&mut self) {
+        if let Some(path) = self.written.take() {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::locale::ENGLISH;
+
+    #[test]
+    fn test_journal_keeps_unsaved_edits() {
+        let dir = tempfile::tempdir().unwrap();
+        let journal_dir = dir.path().join("recovery");
+        let board_file = dir.path().join("billing.toml").to_string_lossy().to_string();
+        let mut app = App::new();
+        app.state.current_filename = Some(board_file.clone());
+        app.new_place("Invoice".to_string());
+        crate::file::FileManager::new().save_to_file(&app.breadboard, &board_file).unwrap();
+        app.mark_saved();
+
+        let mut journal = Journal::new(journal_dir.clone());
+        journal.note_change();
+        journal.flush(&app, true).unwrap();
+        assert!(find(&journal_dir, Some(&board_file)).is_none()); // Nothing unsaved yet
+
+        app.new_place("Receipt".to_string());
+        journal.note_change();
+        journal.flush(&app, true).unwrap();
+        let recovered = find(&journal_dir, Some(&board_file)).unwrap();
+        assert_eq!(recovered.board.places.len(), 2);
+        assert_eq!(recovered.describe(&ENGLISH), "billing.toml");
+        // Without a board named, the newest edits are found
+        assert_eq!(find(&journal_dir, None).unwrap().board_file, recovered.board_file);
+
+        // Saving removes it, as does a clean exit
+        app.mark_saved();
+        journal.note_change();
+        journal.flush(&app, true).unwrap();
+        assert!(find(&journal_dir, None).is_none());
+        app.new_place("Help".to_string());
+        journal.note_change();
+        journal.flush(&app, true).unwrap();
+        journal.discard();
+        assert!(find(&journal_dir, None).is_none());
+    }
+
+    // Above any pid the system hands out
+    const GONE: u32 = 0x3fff_ffff;
+
+    #[test]
+    fn test_find_skips_live_sessions_and_other_boards() {
+        let dir = tempfile::tempdir().unwrap();
+        let board_file = dir.path().join("a_b.toml").to_string_lossy().to_string();
+        let other_file = dir.path().join("a b.toml").to_string_lossy().to_string();
+        let write = |board_file: &str, pid: u32| {
+            let recovered = Recovered {
+                board_file: Some(board_file.to_string()),
+                saved_at: chrono::Local::now(),
+                pid,
+                board: Breadboard::new("Billing".to_string()),
+                path: PathBuf::new(),
+            };
+            fs::write(path_for(dir.path(), Some(board_file)), toml::to_string(&recovered).unwrap()).unwrap();
+        };
+
+        // Both names land in the same file; only the board's own edits count
+        write(&other_file, GONE);
+        assert!(find(dir.path(), Some(&board_file)).is_none());
+        write(&board_file, GONE);
+        assert!(find(dir.path(), Some(&board_file)).is_some());
+
+        // The parent process is alive, so its edits stay its own
+        #[cfg(unix)]
+        {
+            write(&board_file, std::os::unix::process::parent_id());
+            assert!(find(dir.path(), Some(&board_file)).is_none());
+        }
+    }
+
+    #[test]
+    fn test_path_for_board_files() {
+        let dir = Path::new("/state/recovery");
+        assert_eq!(path_for(dir, Some("/home/ana/boards/billing.toml")), dir.join("home_ana_boards_billing.toml.toml"));
+        assert_eq!(path_for(dir, None), dir.join("unsaved.toml"));
+    }
+}
@@ -0,0 +1,279 @@
+use crate::app::App;
+use crate::models::ViewMode;
+use crate::file::FileManager;
+use crate::ui::view_snapshot;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::io::{BufRead, Write};
+
+// JSON-RPC 2.0 over stdio: one request per line on stdin, one response per
+// line on stdout. The session holds a single board, either loaded from a
+// file or built up from scratch, and edits it through the same `App`
+// methods the TUI uses.
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const BOARD_ERROR: i64 = -32000; // Operation failed: unknown place, unreadable file, ...
+
+#[derive(Debug)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self { code, message: message.into() }
+    }
+}
+
+#[derive(Deserialize)]
+struct LoadParams {
+    path: String,
+}
+
+#[derive(Deserialize)]
+struct SaveParams {
+    path: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct AddPlaceParams {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct AddAffordanceParams {
+    place_id: u32,
+    name: String,
+    connects_to: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct ConnectParams {
+    place_id: u32,
+    affordance_id: u32,
+    dest_id: Option<u32>, // null removes the connection
+    #[serde(default)]
+    add_back: bool,
+}
+
+#[derive(Deserialize)]
+struct ExportParams {
+    #[serde(default = "default_export_format")]
+    format: String, // "toml", "outline", "collapsed", "graph" or "swimlanes"
+    filter: Option<String>, // Applies to the text formats
+    #[serde(default)]
+    width: usize,
+    #[serde(default)]
+    include_cut: bool, // Keep cut affordances in the text formats
+}
+
+fn default_export_format() -> String {
+    "toml".to_string()
+}
+
+#[derive(Deserialize)]
+struct QueryParams {
+    filter: Option<String>,
+}
+
+pub struct Session {
+    app: App,
+    file_manager: FileManager,
+}
+
+impl Session {
+    pub fn new() -> Self {
+        Self { app: App::new(), file_manager: FileManager::new() }
+    }
+
+    pub fn load(&mut self, path: &str) -> anyhow::Result<()> {
+        let mut breadboard = self.file_manager.load_from_file(path)?;
+        breadboard.sync_id_counters();
+        self.app.breadboard = breadboard;
+        self.app.state.current_filename = Some(path.to_string());
+        Ok(())
+    }
+
+    // Answer every line of `input` until it closes
+    pub fn serve(&mut self, input: impl BufRead, mut output: impl Write) -> anyhow::Result<()> {
+        for line in input.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(response) = self.handle_line(&line) {
+                writeln!(output, "{}", response)?;
+                output.flush()?;
+            }
+        }
+        Ok(())
+    }
+
+    // Response to one request line, or None for notifications (no "id")
+    fn handle_line(&mut self, line: &str) -> Option<Value> {
+        let request: Value = match serde_json::from_str(line) {
+            Ok(request) => request,
+            Err(e) => return Some(error_response(Value::Null, RpcError::new(PARSE_ERROR, e.to_string()))),
+        };
+
+        let id = request.get("id").cloned();
+        let Some(method) = request.get("method").and_then(Value::as_str) else {
+            return Some(error_response(id.unwrap_or(Value::Null), RpcError::new(INVALID_REQUEST, "missing method")));
+        };
+        let params = request.get("params").cloned().unwrap_or(json!({}));
+
+        let result = self.call(method, params);
+        let id = id?;
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(error) => error_response(id, error),
+        })
+    }
+
+    fn call(&mut self, method: &str, params: Value) -> Result<Value, RpcError> {
+        match method {
+            "load" => {
+                let params: LoadParams = parse_params(params)?;
+                self.load(&params.path).map_err(board_error)?;
+                Ok(self.board_json())
+            }
+            "save" => {
+                let params: SaveParams = parse_params(params)?;
+                let path = params.path
+                    .or_else(|| self.app.state.current_filename.clone())
+                    .ok_or_else(|| RpcError::new(INVALID_PARAMS, "no path given and no file loaded"))?;
+                self.file_manager.save_to_file(&self.app.breadboard, &path).map_err(board_error)?;
+                self.app.state.current_filename = Some(path.clone());
+                Ok(json!({ "path": path }))
+            }
+            "board" => Ok(self.board_json()),
+            "add_place" => {
+                let params: AddPlaceParams = parse_params(params)?;
+                let id = self.app.new_place(params.name);
+                Ok(json!({ "id": id }))
+            }
+            "add_affordance" => {
+                let params: AddAffordanceParams = parse_params(params)?;
+                let id = self.app.add_affordance(params.place_id, params.name, params.connects_to).map_err(board_error)?;
+                Ok(json!({ "id": id }))
+            }
+            "connect" => {
+                let params: ConnectParams = parse_params(params)?;
+                self.app.set_connection(params.place_id, params.affordance_id, params.dest_id, params.add_back)
+                    .map_err(board_error)?;
+                Ok(self.board_json())
+            }
+            "export" => {
+                let params: ExportParams = parse_params(params)?;
+                let text = match params.format.as_str() {
+                    "toml" => toml::to_string_pretty(&self.app.breadboard).map_err(|e| RpcError::new(BOARD_ERROR, e.to_string()))?,
+                    "outline" | "collapsed" | "graph" | "swimlanes" => {
+                        // Rendered from a copy, so the view settings don't
+                        // carry over to later calls
+                        let mut app = self.app.clone();
+                        app.state.filter = params.filter;
+                        app.state.collapsed = params.format == "collapsed";
+                        app.state.swimlanes = params.format == "swimlanes";
+                        app.state.view = if matches!(params.format.as_str(), "graph" | "swimlanes") { ViewMode::Graph } else { ViewMode::Outline };
+                        app.config.export_cut = params.include_cut;
+                        view_snapshot(&app, params.width)
+                    }
+                    other => return Err(RpcError::new(INVALID_PARAMS, format!("unknown format {}", other))),
+                };
+                Ok(json!({ "text": text }))
+            }
+            "query" => {
+                let params: QueryParams = parse_params(params)?;
+                self.app.state.filter = params.filter;
+                let places: Vec<Value> = self.app.visible_places().into_iter()
+                    .map(|(place, affordances)| json!({
+                        "id": place.id,
+                        "name": place.name,
+                        "affordances": affordances,
+                    }))
+                    .collect();
+                self.app.state.filter = None;
+                Ok(json!(places))
+            }
+            _ => Err(RpcError::new(METHOD_NOT_FOUND, format!("unknown method {}", method))),
+        }
+    }
+
+    fn board_json(&self) -> Value {
+        serde_json::to_value(&self.app.breadboard).unwrap_or(Value::Null)
+    }
+}
+
+fn parse_params<T: DeserializeOwned>(params: Value) -> Result<T, RpcError> {
+    serde_json::from_value(params).map_err(|e| RpcError::new(INVALID_PARAMS, e.to_string()))
+}
+
+fn board_error(e: anyhow::Error) -> RpcError {
+    RpcError::new(BOARD_ERROR, format!("{:#}", e))
+}
+
+fn error_response(id: Value, error: RpcError) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": error.code, "message": error.message } })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(session: &mut Session, line: &str) -> Value {
+        session.handle_line(line).expect("response")
+    }
+
+    #[test]
+    fn test_build_and_query_board() {
+        let mut session = Session::new();
+        let invoice = request(&mut session, r#"{"jsonrpc":"2.0","id":1,"method":"add_place","params":{"name":"Invoice"}}"#);
+        assert_eq!(invoice["result"]["id"], 1);
+        request(&mut session, r#"{"jsonrpc":"2.0","id":2,"method":"add_place","params":{"name":"Setup"}}"#);
+
+        let affordance = request(&mut session, r#"{"jsonrpc":"2.0","id":3,"method":"add_affordance","params":{"place_id":1,"name":"Turn on Autopay"}}"#);
+        let affordance_id = affordance["result"]["id"].as_u64().unwrap();
+        let connect = format!(r#"{{"jsonrpc":"2.0","id":4,"method":"connect","params":{{"place_id":1,"affordance_id":{},"dest_id":2,"add_back":true}}}}"#, affordance_id);
+        request(&mut session, &connect);
+
+        let query = request(&mut session, r#"{"jsonrpc":"2.0","id":5,"method":"query","params":{"filter":"back"}}"#);
+        assert_eq!(query["result"][0]["name"], "Setup");
+        assert_eq!(query["result"][0]["affordances"][0]["connects_to"], 1);
+
+        let export = request(&mut session, r#"{"jsonrpc":"2.0","id":6,"method":"export","params":{"format":"collapsed"}}"#);
+        assert!(export["result"]["text"].as_str().unwrap().contains("Invoice  ≡1  ←1  →1"));
+        // The export's view settings stay with that export
+        assert!(!session.app.state.collapsed);
+        assert_eq!(session.app.state.view, ViewMode::Outline);
+    }
+
+    #[test]
+    fn test_errors_and_notifications() {
+        let mut session = Session::new();
+        assert_eq!(request(&mut session, "not json")["error"]["code"], PARSE_ERROR);
+        assert_eq!(request(&mut session, r#"{"jsonrpc":"2.0","id":1,"method":"fly"}"#)["error"]["code"], METHOD_NOT_FOUND);
+        assert_eq!(request(&mut session, r#"{"jsonrpc":"2.0","id":2,"method":"add_place","params":{}}"#)["error"]["code"], INVALID_PARAMS);
+        assert_eq!(
+            request(&mut session, r#"{"jsonrpc":"2.0","id":3,"method":"add_affordance","params":{"place_id":7,"name":"x"}}"#)["error"]["code"],
+            BOARD_ERROR,
+        );
+
+        // Notifications run but get no response
+        assert!(session.handle_line(r#"{"jsonrpc":"2.0","method":"add_place","params":{"name":"Quiet"}}"#).is_none());
+        assert_eq!(session.app.breadboard.places.len(), 1);
+    }
+
+    #[test]
+    fn test_serve_answers_each_line() {
+        let mut session = Session::new();
+        let input = "{\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"board\"}\n\n{\"jsonrpc\":\"2.0\",\"id\":2,\"method\":\"board\"}\n";
+        let mut output = Vec::new();
+        session.serve(input.as_bytes(), &mut output).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap().lines().count(), 2);
+    }
+}
@@ -0,0 +1,180 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Instant;
+
+use crate::file::{FileEntry, FileManager};
+use ignore::WalkBuilder;
+
+// The open dialog's files, listed on a background thread so a huge
+// directory or a slow network mount doesn't freeze the screen. Each file
+// is sent as soon as it has been looked at, and the dialog fills in as
+// they arrive. Dropping the scan stops the thread at its next file.
+
+const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+const SPINNER_FRAME_MS: u128 = 80;
+
+// A scan in progress. Cloning shares it.
+#[derive(Debug, Clone)]
+pub struct Scan {
+    receiver: Arc<Mutex<Receiver<Result<FileEntry, String>>>>,
+    started: Instant,
+}
+
+// What a scan found since it was last asked
+#[derive(Debug, Default)]
+pub struct Found {
+    pub files: Vec<FileEntry>,
+    pub error: Option<String>,
+    pub done: bool,
+}
+
+impl Scan {
+    // List the boards in `dir`, and with `recursive` in its subdirectories
+    // too, leaving out what .gitignore files name. They are listed by full
+    // path or, for the current directory, by the path inside it.
+    pub fn start(dir: PathBuf, full_paths: bool, recursive: bool) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let failed = |e: &dyn std::fmt::Display| format!("Failed to read directory {}: {}", dir.display(), e);
+            let paths: Box<dyn Iterator<Item = Result<PathBuf, String>>> = if recursive {
+                // Hidden files and folders are skipped too, like .git
+                let walk = WalkBuilder::new(&dir).require_git(false).build();
+                Box::new(walk.map(|entry| entry.map(|entry| entry.into_path()).map_err(|e| failed(&e))))
+            } else {
+                match fs::read_dir(&dir) {
+                    Ok(entries) => Box::new(entries.map(|entry| entry.map(|entry| entry.path()).map_err(|e| failed(&e)))),
+                    Err(e) => Box::new(std::iter::once(Err(failed(&e)))),
+                }
+            };
+            let file_manager = FileManager::new();
+            for path in paths {
+                // An unreadable folder is reported, and the rest still listed
+                let path = match path {
+                    Ok(path) => path,
+                    Err(error) => {
+                        if sender.send(Err(error)).is_err() {
+                            return;
+                        }
+                        continue;
+                    }
+                };
+                if !FileManager::is_listed(&path) {
+                    continue;
+                }
+                let shown = match path.strip_prefix(&dir) {
+                    Ok(inside) if !full_paths => inside.to_string_lossy().to_string(),
+                    _ => path.to_string_lossy().to_string(),
+                };
+                let file = FileEntry { path: shown, ..file_manager.peek(&path.to_string_lossy()) };
+                if sender.send(Ok(file)).is_err() {
+                    return; // The dialog was closed
+                }
+            }
+            tracing::debug!(dir = %dir.display(), recursive, "scanned for boards");
+        });
+        Self { receiver: Arc::new(Mutex::new(receiver)), started: Instant::now() }
+    }
+
+    // Everything that arrived since the last call, without waiting
+    pub fn take(&self) -> Found {
+        let mut found = Found::default();
+        let Ok(receiver) = self.receiver.lock() else {
+            found.done = true;
+            return found;
+        };
+        loop {
+            match receiver.try_recv() {
+                Ok(Ok(file)) => found.files.push(file),
+                Ok(Err(error)) => found.error = Some(error),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    found.done = true;
+                    break;
+                }
+            }
+        }
+        found
+    }
+
+    // The spinner's frame for how long the scan has been running
+    pub fn spinner(&self) -> char {
+        SPINNER[(self.started.elapsed().as_millis() / SPINNER_FRAME_MS) as usize % SPINNER.len()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Breadboard;
+    use std::time::Duration;
+
+    // Everything the scan sends, once it's finished
+    fn finish(scan: &Scan) -> Found {
+        let mut all = Found::default();
+        for _ in 0..500 {
+            let found = scan.take();
+            all.files.extend(found.files);
+            all.error = all.error.or(found.error);
+            if found.done {
+                all.done = true;
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        all
+    }
+
+    #[test]
+    fn test_scan() {
+        let dir = tempfile::tempdir().unwrap();
+        FileManager::new().save_to_file(&Breadboard::new("Checkout".to_string()), dir.path().join("checkout.toml")).unwrap();
+        fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"bboard\"\n").unwrap();
+        fs::write(dir.path().join("notes.md"), "# Notes\n").unwrap();
+
+        let found = finish(&Scan::start(dir.path().to_path_buf(), false, false));
+        assert!(found.done);
+        let mut files: Vec<(String, Option<String>)> = found.files.into_iter()
+            .map(|file| (file.path, file.name))
+            .collect();
+        files.sort();
+        assert_eq!(files, [
+            ("Cargo.toml".to_string(), None),
+            ("checkout.toml".to_string(), Some("Checkout".to_string())),
+        ]);
+
+        let found = finish(&Scan::start(dir.path().join("missing"), true, false));
+        assert!(found.done && found.files.is_empty());
+        assert!(found.error.is_some_and(|error| error.starts_with("Failed to read directory")));
+    }
+
+    #[test]
+    fn test_scan_subdirectories() {
+        let dir = tempfile::tempdir().unwrap();
+        let board = Breadboard::new("Billing".to_string());
+        let file_manager = FileManager::new();
+        for folder in ["billing/invoices", "target", ".drafts"] {
+            fs::create_dir_all(dir.path().join(folder)).unwrap();
+            file_manager.save_to_file(&board, dir.path().join(folder).join("board.toml")).unwrap();
+        }
+        file_manager.save_to_file(&board, dir.path().join("top.toml")).unwrap();
+        fs::write(dir.path().join(".gitignore"), "target/\n").unwrap();
+
+        let found = finish(&Scan::start(dir.path().to_path_buf(), false, true));
+        let mut paths: Vec<String> = found.files.into_iter().map(|file| file.path).collect();
+        paths.sort();
+        let inside = PathBuf::from("billing").join("invoices").join("board.toml");
+        assert_eq!(paths, [inside.to_string_lossy().to_string(), "top.toml".to_string()]);
+
+        // Without subdirectories only the top level is listed
+        let found = finish(&Scan::start(dir.path().to_path_buf(), false, false));
+        assert_eq!(found.files.len(), 1);
+
+        // A missing directory is reported by the walk itself
+        let found = finish(&Scan::start(dir.path().join("missing"), false, true));
+        assert!(found.done && found.files.is_empty());
+        assert!(found.error.is_some_and(|error| error.starts_with("Failed to read directory")));
+    }
+}
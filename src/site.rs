@@ -0,0 +1,152 @@
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::file::FileManager;
+use crate::html::{escape, site_page, SiteLinks};
+use crate::models::Breadboard;
+use crate::workspace::Workspace;
+
+// A directory of boards as a static site: an index page listing them, and
+// each board's HTML export (`html.rs`) as a page of its own, linking back to
+// the index and across to the boards its affordances link into. Any static
+// host can serve the output directory as it is.
+
+const INDEX: &str = "index.html";
+
+// What a build wrote and what it left out
+#[derive(Debug, Default, PartialEq)]
+pub struct SiteReport {
+    pub pages: usize,
+    pub skipped: Vec<(String, String)>, // File and why
+}
+
+// One board's entry in the index
+struct Entry {
+    file: String,
+    page: String,
+    board: Breadboard,
+}
+
+// Write the site for the boards directly inside `dir` to `out`, creating it
+// if needed. Pages already in `out` are overwritten; others are left alone.
+pub fn build(dir: &Path, out: &Path) -> Result<SiteReport> {
+    let file_manager = FileManager::new();
+    let mut report = SiteReport::default();
+    let mut title = dir.canonicalize().ok()
+        .and_then(|dir| dir.file_name().map(|name| name.to_string_lossy().to_string()))
+        .unwrap_or_else(|| "Boards".to_string());
+
+    let mut entries = Vec::new();
+    for file in file_manager.list_toml_files_in(dir)? {
+        let path = dir.join(&file);
+        if Workspace::is_workspace_file(&path) {
+            // The workspace names the site
+            if let Ok(workspace) = Workspace::load(&path) {
+                title = workspace.name;
+            }
+            continue;
+        }
+        if !file.ends_with(".toml") {
+            report.skipped.push((file, "bundles aren't published: unpack it first".to_string()));
+            continue;
+        }
+        match file_manager.load_from_file(&path) {
+            Ok(board) => entries.push(Entry { page: page_file(&file), file, board }),
+            Err(e) => report.skipped.push((file, e.to_string())),
+        }
+    }
+    entries.sort_by_key(|entry| entry.board.name.to_lowercase());
+
+    fs::create_dir_all(out).with_context(|| format!("Failed to create {}", out.display()))?;
+    let pages: HashMap<String, String> = entries.iter().map(|e| (e.file.clone(), e.page.clone())).collect();
+    let links = SiteLinks { index: INDEX, pages: &pages };
+    for entry in &entries {
+        let path = out.join(&entry.page);
+        fs::write(&path, site_page(&entry.board, Some(&links)))
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        report.pages += 1;
+    }
+    let path = out.join(INDEX);
+    fs::write(&path, index(&title, &entries)).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(report)
+}
+
+// checkout.toml's page is checkout.html. A board file named index gets
+// another name so it doesn't take the index's place.
+fn page_file(file: &str) -> String {
+    let stem = file.trim_end_matches(".toml");
+    match stem {
+        "index" => "index-board.html".to_string(),
+        _ => format!("{}.html", stem),
+    }
+}
+
+fn index(title: &str, entries: &[Entry]) -> String {
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    out.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+    out.push_str(&format!("<title>{}</title>\n<style>\n{}</style>\n</head>\n<body>\n", escape(title), STYLE));
+    out.push_str(&format!("<h1>{}</h1>\n", escape(title)));
+    if entries.is_empty() {
+        out.push_str("<p>No boards here yet.</p>\n");
+    } else {
+        out.push_str("<table>\n<tr><th>Board</th><th>Places</th><th>Affordances</th><th>File</th></tr>\n");
+        for entry in entries {
+            out.push_str(&format!(
+                "<tr><td><a href=\"{}\">{}</a></td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                escape(&entry.page),
+                escape(&entry.board.name),
+                entry.board.places.len(),
+                entry.board.places.iter().map(|p| p.affordances.len()).sum::<usize>(),
+                escape(&entry.file),
+            ));
+        }
+        out.push_str("</table>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+const STYLE: &str = "\
+body { font: 15px/1.45 system-ui, sans-serif; margin: 0 auto; max-width: 60rem; padding: 1rem; }
+table { border-collapse: collapse; width: 100%; }
+th, td { text-align: left; border-bottom: 1px solid #ddd; padding: .3rem .5rem; }
+td:nth-child(2), td:nth-child(3) { text-align: right; }
+td:last-child { color: #666; }
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Affordance, BoardLink, Place};
+
+    #[test]
+    fn test_build_site() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let file_manager = FileManager::new();
+        let mut checkout = Breadboard::new("Checkout".to_string());
+        checkout.add_place(Place::new(1, "Cart".to_string()));
+        let mut help = Affordance::new(1, "Get help".to_string());
+        help.link = Some(BoardLink { board: "support.toml".to_string(), place_id: 2 });
+        checkout.places[0].add_affordance(help);
+        file_manager.save_to_file(&checkout, dir.path().join("checkout.toml"))?;
+        file_manager.save_to_file(&Breadboard::new("Support".to_string()), dir.path().join("support.toml"))?;
+        file_manager.save_to_file(&Breadboard::new("Home".to_string()), dir.path().join("index.toml"))?;
+        fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"x\"\n")?;
+
+        let out = dir.path().join("public");
+        let report = build(dir.path(), &out)?;
+        assert_eq!(report.pages, 3);
+        assert_eq!(report.skipped.iter().map(|(file, _)| file.as_str()).collect::<Vec<_>>(), vec!["Cargo.toml"]);
+
+        let index = fs::read_to_string(out.join("index.html"))?;
+        assert!(index.contains("<tr><td><a href=\"checkout.html\">Checkout</a></td><td>1</td><td>1</td><td>checkout.toml</td></tr>"));
+        assert!(index.contains("<a href=\"index-board.html\">Home</a>"));
+        let page = fs::read_to_string(out.join("checkout.html"))?;
+        assert!(page.contains("<a href=\"index.html\">All boards</a>"));
+        assert!(page.contains("Get help ⇒ <a href=\"support.html#place-2\">support.toml</a>"));
+        Ok(())
+    }
+}
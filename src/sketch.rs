@@ -0,0 +1,229 @@
+use anyhow::{Context, Result};
+use image::imageops::FilterType;
+use image::RgbImage;
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use std::path::Path;
+
+use crate::config::ImageProtocol;
+use crate::models::Place;
+
+// Previews of the sketches attached to places. Terminals that can show
+// images get the picture itself through the kitty graphics protocol or
+// sixel; the rest get it downscaled into half-block characters.
+
+const CELL_PIXELS: (u32, u32) = (8, 16); // Assumed size of a terminal cell, for the image protocols
+const KITTY_CHUNK: usize = 4096; // Largest base64 payload per kitty escape
+
+// The first attachment of `place` that's an image we can decode
+pub fn image_attachment(place: &Place) -> Option<&str> {
+    place.attachments.iter()
+        .map(String::as_str)
+        .find(|attachment| {
+            let extension = Path::new(attachment).extension().map(|e| e.to_string_lossy().to_lowercase());
+            matches!(extension.as_deref(), Some("png" | "jpg" | "jpeg"))
+        })
+}
+
+pub fn load(path: &Path) -> Result<RgbImage> {
+    let image = image::open(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok(image.to_rgb8())
+}
+
+// Which way to draw images, from the config or else from what the terminal
+// says it is. `var` looks up an environment variable. Inside tmux, which
+// doesn't pass images through by default, it's always blocks.
+pub fn detect(configured: ImageProtocol, var: impl Fn(&str) -> Option<String>) -> ImageProtocol {
+    if configured != ImageProtocol::Auto {
+        return configured;
+    }
+    if var("TMUX").is_some() {
+        return ImageProtocol::Blocks;
+    }
+    let term = var("TERM").unwrap_or_default();
+    let program = var("TERM_PROGRAM").unwrap_or_default();
+    if var("KITTY_WINDOW_ID").is_some() || term.contains("kitty") || matches!(program.as_str(), "WezTerm" | "ghostty") {
+        ImageProtocol::Kitty
+    } else if term.starts_with("foot") || term.starts_with("mlterm") || term.contains("sixel") || program == "iTerm.app" {
+        ImageProtocol::Sixel
+    } else {
+        ImageProtocol::Blocks
+    }
+}
+
+// `image` scaled to fit `width` × `height` pixels, keeping its shape
+fn fit(image: &RgbImage, width: u32, height: u32) -> RgbImage {
+    let scale = (width as f64 / image.width() as f64).min(height as f64 / image.height() as f64);
+    let (w, h) = ((image.width() as f64 * scale) as u32, (image.height() as f64 * scale) as u32);
+    image::imageops::resize(image, w.max(1), h.max(1), FilterType::Triangle)
+}
+
+// The image drawn in `width` × `height` cells of "▀", each cell showing
+// two pixels: the top one in the foreground, the bottom one behind
+pub fn block_lines(image: &RgbImage, width: u16, height: u16) -> Vec<Line<'static>> {
+    let small = fit(image, width as u32, height as u32 * 2);
+    let color = |x: u32, y: u32| {
+        let [r, g, b] = small.get_pixel(x, y).0;
+        Color::Rgb(r, g, b)
+    };
+    (0..small.height()).step_by(2)
+        .map(|y| {
+            Line::from((0..small.width())
+                .map(|x| {
+                    let mut style = Style::default().fg(color(x, y));
+                    if y + 1 < small.height() {
+                        style = style.bg(color(x, y + 1));
+                    }
+                    Span::styled("▀", style)
+                })
+                .collect::<Vec<_>>())
+        })
+        .collect()
+}
+
+// Escapes drawing the image over `columns` × `rows` cells at the cursor
+// with the kitty graphics protocol, replacing any image drawn before
+pub fn kitty_sequence(image: &RgbImage, columns: u16, rows: u16) -> String {
+    let small = fit(image, columns as u32 * CELL_PIXELS.0, rows as u32 * CELL_PIXELS.1);
+    let data = base64(small.as_raw());
+    let chunks: Vec<&str> = data.as_bytes().chunks(KITTY_CHUNK)
+        .map(|chunk| std::str::from_utf8(chunk).unwrap_or_default())
+        .collect();
+
+    let mut sequence = kitty_clear();
+    for (index, chunk) in chunks.iter().enumerate() {
+        let more = (index + 1 < chunks.len()) as u8;
+        if index == 0 {
+            sequence.push_str(&format!(
+                "\x1b_Ga=T,f=24,s={},v={},c={},r={},C=1,q=2,m={};{}\x1b\\",
+                small.width(), small.height(), columns, rows, more, chunk,
+            ));
+        } else {
+            sequence.push_str(&format!("\x1b_Gm={};{}\x1b\\", more, chunk));
+        }
+    }
+    sequence
+}
+
+// Removes every image kitty is showing
+pub fn kitty_clear() -> String {
+    "\x1b_Ga=d,q=2\x1b\\".to_string()
+}
+
+// The image as sixel graphics over `columns` × `rows` cells, with colors
+// rounded to a 6×6×6 palette
+pub fn sixel_sequence(image: &RgbImage, columns: u16, rows: u16) -> String {
+    let small = fit(image, columns as u32 * CELL_PIXELS.0, rows as u32 * CELL_PIXELS.1);
+    let level = |value: u8| (value as u32 * 5 + 127) / 255;
+    let index = |x: u32, y: u32| {
+        let [r, g, b] = small.get_pixel(x, y).0;
+        (level(r) * 36 + level(g) * 6 + level(b)) as usize
+    };
+
+    let mut sequence = format!("\x1bPq\"1;1;{};{}", small.width(), small.height());
+    for color in 0..216u32 {
+        let percent = |level: u32| level * 20;
+        sequence.push_str(&format!("#{};2;{};{};{}", color, percent(color / 36), percent(color / 6 % 6), percent(color % 6)));
+    }
+    for band in (0..small.height()).step_by(6) {
+        let band_rows = band..(band + 6).min(small.height());
+        let mut colors: Vec<usize> = band_rows.clone()
+            .flat_map(|y| (0..small.width()).map(move |x| (x, y)))
+            .map(|(x, y)| index(x, y))
+            .collect();
+        colors.sort_unstable();
+        colors.dedup();
+        for color in colors {
+            sequence.push_str(&format!("#{}", color));
+            let sixels: Vec<char> = (0..small.width())
+                .map(|x| {
+                    let bits = band_rows.clone()
+                        .filter(|&y| index(x, y) == color)
+                        .fold(0u8, |bits, y| bits | 1 << (y - band));
+                    (63 + bits) as char
+                })
+                .collect();
+            push_runs(&mut sequence, &sixels);
+            sequence.push('$');
+        }
+        sequence.push('-');
+    }
+    sequence.push_str("\x1b\\");
+    sequence
+}
+
+// Sixel run-length encoding: "!5~" for five of "~"
+fn push_runs(sequence: &mut String, sixels: &[char]) {
+    let mut rest = sixels;
+    while let Some(&first) = rest.first() {
+        let run = rest.iter().take_while(|&&c| c == first).count();
+        if run > 3 {
+            sequence.push_str(&format!("!{}{}", run, first));
+        } else {
+            sequence.extend(std::iter::repeat_n(first, run));
+        }
+        rest = &rest[run..];
+    }
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut text = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = (chunk[0] as u32) << 16 | (*chunk.get(1).unwrap_or(&0) as u32) << 8 | *chunk.get(2).unwrap_or(&0) as u32;
+        for (i, shift) in [18, 12, 6, 0].into_iter().enumerate() {
+            text.push(if i <= chunk.len() { ALPHABET[(n >> shift & 63) as usize] as char } else { '=' });
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkers() -> RgbImage {
+        RgbImage::from_fn(4, 4, |x, y| if (x + y) % 2 == 0 { image::Rgb([255, 255, 255]) } else { image::Rgb([0, 0, 0]) })
+    }
+
+    #[test]
+    fn test_image_attachment() {
+        let mut place = Place::new(1, "Invoice".to_string());
+        place.attachments = vec!["notes.pdf".to_string(), "sketches/invoice.PNG".to_string()];
+        assert_eq!(image_attachment(&place), Some("sketches/invoice.PNG"));
+    }
+
+    #[test]
+    fn test_detect() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| vars.iter().find(|(n, _)| *n == name).map(|(_, v)| v.to_string())
+        };
+        assert_eq!(detect(ImageProtocol::Auto, env(&[("TERM", "xterm-kitty")])), ImageProtocol::Kitty);
+        assert_eq!(detect(ImageProtocol::Auto, env(&[("TERM", "foot")])), ImageProtocol::Sixel);
+        assert_eq!(detect(ImageProtocol::Auto, env(&[("TERM", "xterm-kitty"), ("TMUX", "1")])), ImageProtocol::Blocks);
+        assert_eq!(detect(ImageProtocol::Auto, env(&[("TERM", "xterm-256color")])), ImageProtocol::Blocks);
+        assert_eq!(detect(ImageProtocol::Sixel, env(&[])), ImageProtocol::Sixel);
+    }
+
+    #[test]
+    fn test_block_lines_keep_the_shape() {
+        let wide = RgbImage::new(8, 2);
+        let lines = block_lines(&wide, 4, 4);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].width(), 4);
+        assert_eq!(block_lines(&checkers(), 10, 2).len(), 2);
+    }
+
+    #[test]
+    fn test_escape_sequences() {
+        let kitty = kitty_sequence(&checkers(), 2, 1);
+        assert!(kitty.starts_with(&kitty_clear()));
+        assert!(kitty.contains("a=T,f=24,s=16,v=16,c=2,r=1,C=1,q=2,m=0;"));
+
+        let sixel = sixel_sequence(&checkers(), 2, 1);
+        assert!(sixel.starts_with("\x1bPq\"1;1;16;16#0;2;0;0;0"));
+        assert!(sixel.ends_with("-\x1b\\"));
+
+        assert_eq!(base64(b"bboard!"), "YmJvYXJkIQ==");
+    }
+}
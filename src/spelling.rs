@@ -0,0 +1,242 @@
+use anyhow::{Context, Result};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::ops::Range;
+use std::path::Path;
+
+use crate::config::Spelling;
+use crate::glossary::find_terms;
+
+// Spell-checking names and notes against a word list: one word per line, as
+// in `/usr/share/dict/words`, or a hunspell `.dic` file, whose count line
+// and affix flags are skipped. Without affix rules, common English endings
+// are taken off unknown words to look up what they're built on.
+
+// Where a dictionary is looked for when the config names none
+const SYSTEM_DICTIONARIES: &[&str] = &[
+    "/usr/share/hunspell/en_US.dic",
+    "/usr/share/myspell/en_US.dic",
+    "/usr/share/myspell/dicts/en_US.dic",
+    "/usr/share/dict/words",
+    "/usr/share/dict/web2",
+];
+
+const SUGGESTIONS: usize = 3; // Offered for each typo
+
+// Endings taken off an unknown word, each with what might have been there
+// instead: "replies" is built on "reply", "saving" on "save"
+const SUFFIXES: &[(&str, &[&str])] = &[
+    ("'s", &[""]),
+    ("ies", &["y"]),
+    ("es", &[""]),
+    ("s", &[""]),
+    ("ied", &["y"]),
+    ("ed", &["", "e"]),
+    ("ing", &["", "e"]),
+    ("er", &["", "e"]),
+    ("est", &["", "e"]),
+    ("ly", &[""]),
+    ("able", &["", "e"]),
+];
+
+#[derive(Debug, Default)]
+pub struct Dictionary {
+    words: HashSet<String>, // Lower case
+}
+
+// One way to fix a typo in the selection: `word` at `range` of its name, or
+// of its notes, replaced with `replacement`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fix {
+    pub in_notes: bool,
+    pub range: Range<usize>,
+    pub word: String,
+    pub replacement: String,
+}
+
+impl Dictionary {
+    // The configured dictionary, or the first system one there is. None when
+    // spell-checking is off or there's no dictionary to use.
+    pub fn load(spelling: &Spelling) -> Result<Option<Self>> {
+        if !spelling.enabled {
+            return Ok(None);
+        }
+        let path = match &spelling.dictionary {
+            Some(path) => Path::new(path).to_path_buf(),
+            None => match SYSTEM_DICTIONARIES.iter().map(Path::new).find(|path| path.exists()) {
+                Some(path) => path.to_path_buf(),
+                None => return Ok(None),
+            },
+        };
+        let text = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read dictionary {}", path.display()))?;
+        Ok(Some(Self::from_words(&text, &spelling.words)))
+    }
+
+    pub fn from_words(text: &str, extra: &[String]) -> Self {
+        let words = text.lines()
+            .map(|line| line.split('/').next().unwrap_or_default().trim())
+            .filter(|word| !word.is_empty() && !word.chars().all(|c| c.is_ascii_digit()))
+            .chain(extra.iter().map(|word| word.trim()))
+            .map(str::to_lowercase)
+            .collect();
+        Self { words }
+    }
+
+    // Whether `word` is spelled right, or is built on a word that is
+    fn knows(&self, word: &str) -> bool {
+        let word = word.to_lowercase();
+        if self.words.contains(&word) {
+            return true;
+        }
+        SUFFIXES.iter().any(|(suffix, replacements)| {
+            let Some(stem) = word.strip_suffix(suffix).filter(|stem| stem.chars().count() >= 2) else {
+                return false;
+            };
+            // "stopped" is built on "stop"
+            let mut last = stem.chars().rev();
+            let doubled = last.next().filter(|c| last.next() == Some(*c));
+            replacements.iter().any(|ending| self.words.contains(&format!("{}{}", stem, ending)))
+                || doubled.is_some_and(|c| self.words.contains(&stem[..stem.len() - c.len_utf8()]))
+        })
+    }
+
+    // Where `text` has words the dictionary doesn't know, as byte ranges.
+    // Glossary terms, acronyms, words like "iPhone", words next to digits
+    // and ones shorter than three letters are left alone.
+    pub fn typos(&self, text: &str, glossary: &BTreeMap<String, String>) -> Vec<Range<usize>> {
+        let terms = find_terms(text, glossary);
+        words(text).into_iter()
+            .filter(|range| {
+                let word = &text[range.clone()];
+                let next_to_digit = text[..range.start].chars().next_back().is_some_and(|c| c.is_ascii_digit())
+                    || text[range.end..].chars().next().is_some_and(|c| c.is_ascii_digit());
+                word.chars().count() >= 3
+                    && !next_to_digit
+                    && !word.chars().skip(1).any(char::is_uppercase)
+                    && !terms.iter().any(|term| term.start <= range.start && range.end <= term.end)
+                    && !self.knows(word)
+            })
+            .collect()
+    }
+
+    // Known words one typing slip away from `word`, in its capitalization
+    pub fn suggestions(&self, word: &str) -> Vec<String> {
+        let lower = word.to_lowercase();
+        let chars: Vec<char> = lower.chars().collect();
+        let mut candidates = Vec::new();
+        for i in 0..chars.len().saturating_sub(1) {
+            let mut swapped = chars.clone();
+            swapped.swap(i, i + 1);
+            candidates.push(swapped.into_iter().collect::<String>());
+        }
+        for i in 0..chars.len() {
+            let mut removed = chars.clone();
+            removed.remove(i);
+            candidates.push(removed.into_iter().collect());
+        }
+        for i in 0..=chars.len() {
+            for c in 'a'..='z' {
+                if i < chars.len() {
+                    let mut replaced = chars.clone();
+                    replaced[i] = c;
+                    candidates.push(replaced.into_iter().collect());
+                }
+                let mut inserted = chars.clone();
+                inserted.insert(i, c);
+                candidates.push(inserted.into_iter().collect());
+            }
+        }
+
+        let capital = word.chars().next().is_some_and(char::is_uppercase);
+        let mut seen = HashSet::new();
+        candidates.into_iter()
+            .filter(|candidate| *candidate != lower && self.words.contains(candidate) && seen.insert(candidate.clone()))
+            .take(SUGGESTIONS)
+            .map(|candidate| if capital { capitalized(&candidate) } else { candidate })
+            .collect()
+    }
+
+    // Every way to fix the typos in a name and its notes
+    pub fn fixes(&self, name: &str, notes: Option<&str>, glossary: &BTreeMap<String, String>) -> Vec<Fix> {
+        let texts = [(false, Some(name)), (true, notes)];
+        texts.into_iter()
+            .filter_map(|(in_notes, text)| Some((in_notes, text?)))
+            .flat_map(|(in_notes, text)| {
+                self.typos(text, glossary).into_iter().flat_map(move |range| {
+                    let word = text[range.clone()].to_string();
+                    self.suggestions(&word).into_iter().map(move |replacement| Fix {
+                        in_notes,
+                        range: range.clone(),
+                        word: word.clone(),
+                        replacement,
+                    })
+                })
+            })
+            .collect()
+    }
+}
+
+// Byte ranges of the words in `text`: letters, with apostrophes inside
+fn words(text: &str) -> Vec<Range<usize>> {
+    let mut start = None;
+    let mut found = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some((at, c)) = chars.next() {
+        let inside = c.is_alphabetic()
+            || (c == '\'' && start.is_some() && chars.peek().is_some_and(|(_, next)| next.is_alphabetic()));
+        match (inside, start) {
+            (true, None) => start = Some(at),
+            (false, Some(from)) => {
+                found.push(from..at);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    found.extend(start.map(|from| from..text.len()));
+    found
+}
+
+fn capitalized(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dictionary() -> Dictionary {
+        Dictionary::from_words("6\nturn/S\non\nautopay\ninvoice/S\nsave\nreply\nstop\nthe\n", &["Stripe".to_string()])
+    }
+
+    #[test]
+    fn test_typos() {
+        let dictionary = dictionary();
+        let glossary = BTreeMap::new();
+        let text = "Trun on Autopay";
+        assert_eq!(dictionary.typos(text, &glossary), vec![0..4]);
+        // Endings, extra words, acronyms, short words and glossary terms
+        assert!(dictionary.typos("Invoices saving replies stopped Stripe's PDF v2beta ok", &glossary).is_empty());
+        let glossary = BTreeMap::from([("Payjoy".to_string(), String::new())]);
+        assert!(dictionary.typos("turn on payjoy", &glossary).is_empty());
+    }
+
+    #[test]
+    fn test_fixes() {
+        let dictionary = dictionary();
+        assert_eq!(dictionary.suggestions("Trun"), vec!["Turn"]);
+        assert_eq!(dictionary.suggestions("invoce"), vec!["invoice"]);
+        assert!(dictionary.suggestions("xyzzy").is_empty());
+
+        let fixes = dictionary.fixes("Save invoce", Some("Stop teh reply"), &BTreeMap::new());
+        assert_eq!(fixes.len(), 2);
+        assert_eq!(fixes[0], Fix { in_notes: false, range: 5..11, word: "invoce".to_string(), replacement: "invoice".to_string() });
+        assert!(fixes[1].in_notes);
+        assert_eq!(fixes[1].replacement, "the");
+    }
+}
@@ -0,0 +1,182 @@
+use crate::app::{App, Selection};
+use crate::models::{Detail, ViewMode};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// UI state remembered between runs, kept in `$XDG_STATE_HOME/bboard/state.toml`
+// (falling back to `~/.local/state/bboard/state.toml`). Unlike the config
+// it's disposable: a missing or broken file just means starting fresh.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SavedState {
+    pub last_board: Option<String>, // Most recently used board file
+    pub boards: BTreeMap<String, BoardState>, // Keyed by the board file's absolute path
+}
+
+// Where the user left off in one board
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BoardState {
+    pub place_id: Option<u32>,
+    pub affordance_id: Option<u32>,
+    pub collapsed: bool,
+    pub view: ViewMode,
+    pub filter: Option<String>,
+    pub graph_offset: (usize, usize),
+    pub graph_zoom: Detail,
+    pub swimlanes: bool,
+}
+
+impl BoardState {
+    pub fn from_app(app: &App) -> Self {
+        let (place_id, affordance_id) = match &app.state.selection {
+            Some(Selection::Place(id)) => (Some(*id), None),
+            Some(Selection::Affordance { place_id, affordance_id }) => (Some(*place_id), Some(*affordance_id)),
+            None => (None, None),
+        };
+
+        Self {
+            place_id,
+            affordance_id,
+            collapsed: app.state.collapsed,
+            view: app.state.view,
+            filter: app.state.filter.clone(),
+            graph_offset: app.state.graph_offset,
+            graph_zoom: app.state.graph_zoom,
+            swimlanes: app.state.swimlanes,
+        }
+    }
+
+    // Apply to a freshly loaded board. The board may have changed since, so
+    // a selection that no longer exists falls back to the first place.
+    pub fn restore(&self, app: &mut App) {
+        app.state.collapsed = self.collapsed;
+        app.state.view = self.view;
+        app.state.filter = self.filter.clone();
+        app.state.graph_offset = self.graph_offset;
+        app.state.graph_zoom = self.graph_zoom;
+        app.state.swimlanes = self.swimlanes;
+
+        app.state.selection = match (self.place_id, self.affordance_id) {
+            (Some(place_id), Some(affordance_id)) => Some(Selection::Affordance { place_id, affordance_id }),
+            (Some(place_id), None) => Some(Selection::Place(place_id)),
+            _ => None,
+        };
+        app.ensure_selection_visible();
+    }
+}
+
+impl SavedState {
+    pub fn default_path() -> Option<PathBuf> {
+        let dir = match std::env::var_os("XDG_STATE_HOME").filter(|d| !d.is_empty()) {
+            Some(dir) => PathBuf::from(dir),
+            None => PathBuf::from(std::env::var_os("HOME")?).join(".local").join("state"),
+        };
+        Some(dir.join("bboard").join("state.toml"))
+    }
+
+    pub fn load() -> Self {
+        Self::default_path()
+            .and_then(|path| Self::load_from(&path).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        toml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        match Self::default_path() {
+            Some(path) => self.save_to(&path),
+            None => Ok(()),
+        }
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let content = toml::to_string_pretty(self)?;
+        fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    pub fn board(&self, file: &str) -> Option<&BoardState> {
+        self.boards.get(&board_key(file))
+    }
+
+    // Also makes `file` the last board
+    pub fn remember(&mut self, file: &str, state: BoardState) {
+        let key = board_key(file);
+        self.last_board = Some(key.clone());
+        self.boards.insert(key, state);
+    }
+
+    // The last board, if it's still there to reopen
+    pub fn reopenable_board(&self) -> Option<&str> {
+        self.last_board.as_deref().filter(|file| Path::new(file).is_file())
+    }
+}
+
+// The same board opened by different relative paths shares one entry
+fn board_key(file: &str) -> String {
+    fs::canonicalize(file)
+        .map(|path| path.to_string_lossy().to_string())
+        .unwrap_or_else(|_| file.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app_with_places() -> App {
+        let mut app = App::new();
+        let invoice = app.new_place("Invoice".to_string());
+        app.add_affordance(invoice, "Pay now".to_string(), None).unwrap();
+        app.new_place("Setup".to_string());
+        app
+    }
+
+    #[test]
+    fn test_board_state_round_trip() {
+        let mut app = app_with_places();
+        app.state.selection = Some(Selection::Affordance { place_id: 1, affordance_id: 1 });
+        app.state.collapsed = true;
+        app.state.view = ViewMode::Graph;
+        app.state.graph_zoom = Detail::Titles;
+        app.state.graph_offset = (16, 6);
+        app.state.swimlanes = true;
+        let state = BoardState::from_app(&app);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state").join("state.toml");
+        let mut saved = SavedState::default();
+        saved.remember("flow.toml", state.clone());
+        saved.save_to(&path).unwrap();
+        let loaded = SavedState::load_from(&path).unwrap();
+        assert_eq!(loaded.board("flow.toml"), Some(&state));
+        assert_eq!(loaded.last_board.as_deref(), Some("flow.toml"));
+        assert_eq!(loaded.reopenable_board(), None); // No such file here
+
+        let mut reopened = app_with_places();
+        state.restore(&mut reopened);
+        assert_eq!(reopened.state.selection, app.state.selection);
+        assert_eq!(reopened.state.view, ViewMode::Graph);
+        assert_eq!(reopened.state.graph_zoom, Detail::Titles);
+        assert_eq!(reopened.state.graph_offset, (16, 6));
+        assert!(reopened.state.swimlanes);
+    }
+
+    #[test]
+    fn test_missing_selection_falls_back_to_first_place() {
+        let state = BoardState { place_id: Some(9), ..Default::default() };
+        let mut app = app_with_places();
+        state.restore(&mut app);
+        assert_eq!(app.state.selection, Some(Selection::Place(1)));
+    }
+}
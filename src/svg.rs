@@ -0,0 +1,155 @@
+use crate::html::escape;
+use crate::layout::layered_layout;
+use crate::models::{Breadboard, Place};
+use std::collections::HashMap;
+use unicode_width::UnicodeWidthStr;
+
+// A board as an SVG image, drawn from the same layered layout as the graph
+// view: one column per layer, a box per place with its title over its
+// affordances, and a curved arrow from each connected affordance to the
+// place it leads to. Outcomes of a branching affordance get dashed arrows
+// labelled with the outcome. Text is sized by estimate, as an SVG can't
+// measure it, so the boxes leave some room.
+
+const CHAR_WIDTH: f32 = 7.5;
+const ROW_HEIGHT: f32 = 20.0;
+const PADDING: f32 = 8.0;
+const COLUMN_GAP: f32 = 90.0;
+const BOX_GAP: f32 = 24.0;
+const MARGIN: f32 = 20.0;
+
+// Where a place's box went
+struct Placed {
+    x: f32,
+    y: f32,
+    width: f32,
+}
+
+pub fn image(breadboard: &Breadboard) -> String {
+    let layout = layered_layout(breadboard);
+    let places: HashMap<u32, &Place> = breadboard.places.iter().map(|p| (p.id, p)).collect();
+
+    let mut boxes: HashMap<u32, Placed> = HashMap::new();
+    let (mut x, mut bottom) = (MARGIN, MARGIN);
+    for layer in &layout.layers {
+        let layer_places: Vec<&Place> = layer.iter().filter_map(|id| places.get(id).copied()).collect();
+        let width = layer_places.iter().map(|place| box_width(place)).fold(0.0, f32::max);
+        let mut y = MARGIN;
+        for place in layer_places {
+            boxes.insert(place.id, Placed { x, y, width });
+            y += box_height(place) + BOX_GAP;
+        }
+        bottom = bottom.max(y);
+        x += width + COLUMN_GAP;
+    }
+    let (width, height) = (x - COLUMN_GAP + MARGIN, bottom - BOX_GAP + MARGIN);
+
+    let mut out = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{:.0}\" height=\"{:.0}\" viewBox=\"0 0 {:.0} {:.0}\" font-family=\"sans-serif\" font-size=\"12\">\n",
+        width.max(MARGIN * 2.0), height.max(MARGIN * 2.0), width.max(MARGIN * 2.0), height.max(MARGIN * 2.0),
+    );
+    out.push_str(&format!("<title>{}</title>\n", escape(&breadboard.name)));
+    out.push_str("<defs><marker id=\"arrow\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" markerWidth=\"8\" markerHeight=\"8\" orient=\"auto\">");
+    out.push_str("<path d=\"M0,0 L10,5 L0,10 z\" fill=\"#555\"/></marker></defs>\n");
+
+    for place in &breadboard.places {
+        let Some(placed) = boxes.get(&place.id) else {
+            continue;
+        };
+        out.push_str(&format!(
+            "<g id=\"place-{}\">\n<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" rx=\"4\" fill=\"#fff\" stroke=\"#333\"/>\n",
+            place.id, placed.x, placed.y, placed.width, box_height(place),
+        ));
+        out.push_str(&format!(
+            "<text x=\"{:.1}\" y=\"{:.1}\" font-weight=\"bold\">{}</text>\n",
+            placed.x + PADDING, row_middle(placed, 0) + 4.0, escape(&place.name),
+        ));
+        if !place.affordances.is_empty() {
+            let line_y = placed.y + ROW_HEIGHT + PADDING / 2.0;
+            out.push_str(&format!(
+                "<line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" stroke=\"#ccc\"/>\n",
+                placed.x, line_y, placed.x + placed.width, line_y,
+            ));
+        }
+        for (row, affordance) in place.affordances.iter().enumerate() {
+            let y = row_middle(placed, row + 1);
+            let text = match &affordance.link {
+                Some(link) => format!("{} ⇒ {}", affordance.name, link.board),
+                None => affordance.name.clone(),
+            };
+            out.push_str(&format!("<text x=\"{:.1}\" y=\"{:.1}\">{}</text>\n", placed.x + PADDING, y + 4.0, escape(&text)));
+            let from = (placed.x + placed.width, y);
+            if let Some(dest) = affordance.connects_to.and_then(|id| boxes.get(&id)) {
+                out.push_str(&arrow(from, dest, None));
+            }
+            for branch in &affordance.branches {
+                if let Some(dest) = branch.connects_to.and_then(|id| boxes.get(&id)) {
+                    out.push_str(&arrow(from, dest, Some(&branch.label)));
+                }
+            }
+        }
+        out.push_str("</g>\n");
+    }
+
+    out.push_str("</svg>\n");
+    out
+}
+
+fn box_width(place: &Place) -> f32 {
+    let widest = place.affordances.iter()
+        .map(|affordance| affordance.name.width() + affordance.link.as_ref().map_or(0, |link| link.board.width() + 3))
+        .chain(std::iter::once(place.name.width()))
+        .max()
+        .unwrap_or(0);
+    widest as f32 * CHAR_WIDTH + PADDING * 2.0
+}
+
+fn box_height(place: &Place) -> f32 {
+    (place.affordances.len() + 1) as f32 * ROW_HEIGHT + PADDING
+}
+
+// Vertical middle of row `row` of a box, the title being row 0
+fn row_middle(placed: &Placed, row: usize) -> f32 {
+    let divider = if row > 0 { PADDING / 2.0 } else { 0.0 };
+    placed.y + PADDING / 2.0 + divider + ROW_HEIGHT * (row as f32 + 0.5)
+}
+
+// A curve from `from` to the left of the destination's title row, dashed
+// and labelled for a branch's outcome
+fn arrow(from: (f32, f32), dest: &Placed, outcome: Option<&str>) -> String {
+    let to = (dest.x, row_middle(dest, 0));
+    // Arrows going back left loop out further so they don't cut the boxes
+    let bend = if to.0 > from.0 { (to.0 - from.0) / 2.0 } else { COLUMN_GAP };
+    let dash = if outcome.is_some() { " stroke-dasharray=\"4 3\"" } else { "" };
+    let mut out = format!(
+        "<path d=\"M{:.1},{:.1} C{:.1},{:.1} {:.1},{:.1} {:.1},{:.1}\" fill=\"none\" stroke=\"#555\"{} marker-end=\"url(#arrow)\"/>\n",
+        from.0, from.1, from.0 + bend, from.1, to.0 - bend, to.1, to.0, to.1, dash,
+    );
+    if let Some(outcome) = outcome {
+        out.push_str(&format!(
+            "<text x=\"{:.1}\" y=\"{:.1}\" font-size=\"10\" fill=\"#555\">{}</text>\n",
+            from.0 + 4.0, from.1 - 4.0, escape(outcome),
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::testing::board;
+
+    #[test]
+    fn test_image() {
+        let breadboard = board(&["Invoice", "Setup <Autopay>"], &[(0, 1), (1, 0)]);
+        let svg = image(&breadboard);
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.ends_with("</svg>\n"));
+        assert!(svg.contains("<g id=\"place-1\">") && svg.contains("<g id=\"place-2\">"));
+        assert!(svg.contains("Setup &lt;Autopay&gt;"));
+        assert_eq!(svg.matches("marker-end=\"url(#arrow)\"").count(), 2);
+
+        let empty = image(&Breadboard::new("Empty".to_string()));
+        assert!(empty.contains("<title>Empty</title>") && !empty.contains("<g id"));
+    }
+}
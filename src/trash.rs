@@ -0,0 +1,193 @@
+use anyhow::{bail, Result};
+
+use crate::locale::{self, Strings};
+use crate::models::{Affordance, Breadboard, Place};
+
+// Places and affordances deleted this session, kept so the trash panel can
+// put them back where they were. Connections into a deleted place are left
+// pointing at it, so they work again once it's back.
+
+#[derive(Debug, Clone)]
+pub enum Trashed {
+    Place {
+        place: Place, // With its affordances and where they led
+        index: usize,
+        happy_path: Option<usize>, // Its step on the happy path, if it had one
+        incoming: usize, // Connections that led to it when it went
+    },
+    Affordance {
+        place_id: u32,
+        place_name: String,
+        affordance: Affordance,
+        index: usize,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct TrashEntry {
+    pub item: Trashed,
+    pub drill_path: Vec<u32>, // Places drilled into to reach the board it was on
+    pub deleted_at: chrono::DateTime<chrono::Local>,
+}
+
+impl Trashed {
+    pub fn name(&self) -> &str {
+        match self {
+            Trashed::Place { place, .. } => &place.name,
+            Trashed::Affordance { affordance, .. } => &affordance.name,
+        }
+    }
+
+    // What the trash panel says about it, after its name
+    pub fn describe(&self, s: &Strings) -> String {
+        match self {
+            Trashed::Place { place, incoming, .. } => {
+                let mut parts = vec![s.trashed_place.to_string()];
+                match place.affordances.len() {
+                    0 => {}
+                    1 => parts.push(s.trashed_affordance_count.to_string()),
+                    n => parts.push(locale::fill(s.trashed_affordances_count, &[&n.to_string()])),
+                }
+                match incoming {
+                    0 => {}
+                    1 => parts.push(s.trashed_connection_in.to_string()),
+                    n => parts.push(locale::fill(s.trashed_connections_in, &[&n.to_string()])),
+                }
+                parts.join(", ")
+            }
+            Trashed::Affordance { place_name, affordance, .. } => match affordance.destinations().len() {
+                0 => locale::fill(s.trashed_affordance, &[place_name]),
+                _ => locale::fill(s.trashed_connected_affordance, &[place_name]),
+            },
+        }
+    }
+}
+
+pub fn delete_place(board: &mut Breadboard, place_id: u32) -> Option<Trashed> {
+    let index = board.places.iter().position(|p| p.id == place_id)?;
+    let place = board.places.remove(index);
+    let happy_path = board.happy_path.iter().position(|id| *id == place_id);
+    // Its neighbours on the happy path now have a gap to warn about
+    board.happy_path.retain(|id| *id != place_id);
+    let incoming = board.places.iter()
+        .flat_map(|p| &p.affordances)
+        .filter(|a| a.destinations().contains(&place_id))
+        .count();
+    Some(Trashed::Place { place, index, happy_path, incoming })
+}
+
+pub fn delete_affordance(board: &mut Breadboard, place_id: u32, affordance_id: u32) -> Option<Trashed> {
+    let place = board.find_place_mut(&place_id)?;
+    let index = place.affordances.iter().position(|a| a.id == affordance_id)?;
+    let affordance = place.affordances.remove(index);
+    Some(Trashed::Affordance { place_id, place_name: place.name.clone(), affordance, index })
+}
+
+// Put `item` back on `board`, returning the place it's on and, for an
+// affordance, its ID. IDs taken since are replaced with new ones.
+pub fn restore(board: &mut Breadboard, item: Trashed) -> Result<(u32, Option<u32>)> {
+    match item {
+        Trashed::Place { mut place, index, happy_path, .. } => {
+            if board.find_place(&place.id).is_some() {
+                place.id = board.generate_place_id();
+            }
+            for affordance in &mut place.affordances {
+                *affordance = fresh_affordance(board, affordance.clone());
+            }
+            let id = place.id;
+            board.places.insert(index.min(board.places.len()), place);
+            if let Some(step) = happy_path {
+                board.happy_path.insert(step.min(board.happy_path.len()), id);
+            }
+            bump_counters(board);
+            Ok((id, None))
+        }
+        Trashed::Affordance { place_id, place_name, affordance, index } => {
+            if board.find_place(&place_id).is_none() {
+                bail!("{} is gone: restore it first", place_name);
+            }
+            let affordance = fresh_affordance(board, affordance);
+            let id = affordance.id;
+            if let Some(place) = board.find_place_mut(&place_id) {
+                place.affordances.insert(index.min(place.affordances.len()), affordance);
+            }
+            bump_counters(board);
+            Ok((place_id, Some(id)))
+        }
+    }
+}
+
+fn fresh_affordance(board: &mut Breadboard, mut affordance: Affordance) -> Affordance {
+    let taken = board.places.iter().flat_map(|p| &p.affordances).any(|a| a.id == affordance.id);
+    if taken {
+        affordance.id = board.generate_affordance_id();
+    }
+    affordance
+}
+
+// Keep new IDs clear of the ones just put back
+fn bump_counters(board: &mut Breadboard) {
+    let (next_place, next_affordance) = (board.next_place_id, board.next_affordance_id);
+    board.sync_id_counters();
+    board.next_place_id = board.next_place_id.max(next_place);
+    board.next_affordance_id = board.next_affordance_id.max(next_affordance);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::locale::ENGLISH;
+    use crate::models::testing;
+
+    fn board() -> Breadboard {
+        let mut board = testing::board(&["Invoice", "Confirmation", "Receipt"], &[(0, 1), (1, 2)]);
+        board.name = "Billing".to_string();
+        testing::name_affordances(&mut board, &["Pay", "Done"]);
+        board.happy_path = vec![1, 2, 3];
+        board
+    }
+
+    #[test]
+    fn test_restore_place_with_its_connections() {
+        let mut board = board();
+        let trashed = delete_place(&mut board, 2).unwrap();
+        assert_eq!(trashed.describe(&ENGLISH), "place, 1 affordance, 1 connection in");
+        assert_eq!(board.happy_path, vec![1, 3]);
+
+        assert_eq!(restore(&mut board, trashed).unwrap(), (2, None));
+        assert_eq!(board.places[1].name, "Confirmation");
+        assert_eq!(board.places[1].affordances[0].connects_to, Some(3));
+        assert_eq!(board.places[0].affordances[0].connects_to, Some(2));
+        assert_eq!(board.happy_path, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_restore_affordance() {
+        let mut board = board();
+        let trashed = delete_affordance(&mut board, 1, 1).unwrap();
+        assert_eq!(trashed.describe(&ENGLISH), "affordance in Invoice, connected");
+        assert!(board.places[0].affordances.is_empty());
+        assert_eq!(restore(&mut board, trashed).unwrap(), (1, Some(1)));
+        assert_eq!(board.places[0].affordances[0].name, "Pay");
+
+        // Its place has to be back first
+        let affordance = delete_affordance(&mut board, 2, 2).unwrap();
+        let place = delete_place(&mut board, 2).unwrap();
+        assert_eq!(restore(&mut board, affordance.clone()).unwrap_err().to_string(), "Confirmation is gone: restore it first");
+        restore(&mut board, place).unwrap();
+        restore(&mut board, affordance).unwrap();
+        assert_eq!(board.places[1].affordances.len(), 1);
+    }
+
+    #[test]
+    fn test_restore_renumbers_taken_ids() {
+        let mut board = board();
+        let trashed = delete_place(&mut board, 3).unwrap();
+        board.next_place_id = 3; // As if the board was reloaded and its counters synced
+        let id = board.generate_place_id();
+        board.add_place(Place::new(id, "Help".to_string()));
+
+        assert_eq!(restore(&mut board, trashed).unwrap(), (4, None));
+        assert_eq!(board.generate_place_id(), 5);
+    }
+}
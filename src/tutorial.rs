@@ -0,0 +1,97 @@
+use crate::app::App;
+use crate::input::Mode;
+use crate::locale::Strings;
+
+// The guided tour of a scratch board: each step says what to do next and
+// is done once the board shows it was done, however the user got there.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Step {
+    CreatePlace,
+    SecondPlace,
+    AddAffordance,
+    Connect,
+    Follow,
+    Save,
+    Done,
+}
+
+pub const STEPS: [Step; 7] = [
+    Step::CreatePlace,
+    Step::SecondPlace,
+    Step::AddAffordance,
+    Step::Connect,
+    Step::Follow,
+    Step::Save,
+    Step::Done,
+];
+
+impl Step {
+    pub fn prompt(&self, s: &'static Strings) -> &'static str {
+        match self {
+            Step::CreatePlace => s.tutorial_create_place,
+            Step::SecondPlace => s.tutorial_second_place,
+            Step::AddAffordance => s.tutorial_add_affordance,
+            Step::Connect => s.tutorial_connect,
+            Step::Follow => s.tutorial_follow,
+            Step::Save => s.tutorial_save,
+            Step::Done => s.tutorial_done_prompt,
+        }
+    }
+
+    // Whether the board shows this step was done. Steps that are typed only
+    // count once the typing is finished.
+    pub fn is_done(&self, app: &App) -> bool {
+        let typing = app.state.mode != Mode::Navigate;
+        let affordances = || app.breadboard.places.iter().flat_map(|p| &p.affordances);
+        match self {
+            Step::CreatePlace => !typing && !app.breadboard.places.is_empty(),
+            Step::SecondPlace => !typing && app.breadboard.places.len() >= 2,
+            Step::AddAffordance => !typing && affordances().next().is_some(),
+            Step::Connect => !typing && affordances().any(|a| a.connects_to.is_some()),
+            Step::Follow => !app.state.navigation_trail.is_empty(),
+            Step::Save => !typing && app.state.current_filename.is_some() && !app.is_dirty(),
+            Step::Done => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::Selection;
+    use crate::models::Affordance;
+
+    #[test]
+    fn test_steps_follow_the_board() {
+        let mut app = App::new();
+        app.start_tutorial();
+        assert_eq!(app.tutorial_step(), Some((0, Step::CreatePlace)));
+
+        // Naming the place is still going on
+        app.new_place("Invoice".to_string());
+        app.state.mode = Mode::Edit;
+        app.advance_tutorial();
+        assert_eq!(app.tutorial_step(), Some((0, Step::CreatePlace)));
+
+        app.state.mode = Mode::Navigate;
+        app.new_place("Setup Autopay".to_string());
+        app.advance_tutorial();
+        assert_eq!(app.tutorial_step(), Some((2, Step::AddAffordance)));
+
+        let (invoice, setup) = (app.breadboard.places[0].id, app.breadboard.places[1].id);
+        let affordance_id = app.breadboard.generate_affordance_id();
+        app.add_affordance_to_place(&invoice, Affordance::new(affordance_id, "Turn on Autopay".to_string()).with_connection(setup));
+        app.advance_tutorial();
+        assert_eq!(app.tutorial_step(), Some((4, Step::Follow)));
+
+        app.state.selection = Some(Selection::Place(invoice));
+        app.navigate_to_place(setup);
+        app.advance_tutorial();
+        assert_eq!(app.tutorial_step(), Some((5, Step::Save)));
+
+        app.state.current_filename = Some("autopay.toml".to_string());
+        app.mark_saved();
+        app.advance_tutorial();
+        assert_eq!(app.tutorial_step(), Some((6, Step::Done)));
+    }
+}
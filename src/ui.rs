@@ -1,27 +1,71 @@
 use ratatui::{
-    backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
     Frame,
 };
 
-use crate::app::{App, Selection};
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::app::{App, Crumb, ExportScope, FileSort, SaveTarget, Selection, StartItem};
+use crate::diagram::{render_diagram, scale, Diagram};
 use crate::input::Mode;
+use crate::layout::{layered_layout, swimlane_layout, GraphLayout};
+use crate::analysis::{group_progress, progress, Progress};
+use crate::glossary::find_terms;
+use crate::lint::duplicate_places;
+use crate::tutorial;
+use crate::bundle;
+use crate::config::ImageProtocol;
+use crate::locale::{self, Strings};
+use crate::sketch;
+use crate::diff::{self, Change, Comparison};
+use crate::models::{annotation, Affordance, Breadboard, Detail, Place, Status, ViewMode};
+
+const MINIMAP_SIZE: (u16, u16) = (24, 8); // Largest minimap, in cells inside its border
+const PROGRESS_BAR_WIDTH: usize = 16;
+const PROGRESS_NAME_WIDTH: usize = 24; // Longer place names are cut short in the progress bars
+const SEARCH_PANEL_WIDTH: u16 = 36; // Jump search results, floating over the board
+const LEADER_POPUP_WIDTH: u16 = 34; // Keys that can follow the leader sequence typed so far
+const ICON_PICKER_WIDTH: u16 = 32; // Icons offered while typing `:name` in a name
+const SEARCH_MATCH: Style = Style::new().fg(Color::Yellow).add_modifier(Modifier::UNDERLINED);
+const SEARCH_CURRENT: Style = Style::new().fg(Color::Black).bg(Color::Yellow).add_modifier(Modifier::BOLD);
 
 pub struct UI {
     list_state: ListState,
+    followed_selection: Option<(Option<Selection>, Detail)>, // Selection and zoom the graph view last scrolled to
+    sketch: Option<(PathBuf, Option<image::RgbImage>)>, // Last sketch loaded, None if it couldn't be read
+    drawn_image: Option<(PathBuf, Rect)>, // Sketch the terminal is showing through an image protocol, and where
+    graphics: Option<Graphics>,
+}
+
+// Escapes for the terminal to draw or remove an image after a frame
+pub struct Graphics {
+    pub sequence: String,
+    pub redraw: bool, // Clear and draw the frame first, as sixel images stay until drawn over
 }
 
 impl UI {
     pub fn new() -> Self {
         Self {
             list_state: ListState::default(),
+            followed_selection: None,
+            sketch: None,
+            drawn_image: None,
+            graphics: None,
         }
     }
 
-    pub fn render<B: Backend>(&mut self, frame: &mut Frame, app: &mut App) {
+    // Image escapes the last frame needs written, if any
+    pub fn take_graphics(&mut self) -> Option<Graphics> {
+        self.graphics.take()
+    }
+
+    pub fn render(&mut self, frame: &mut Frame, app: &mut App) {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
@@ -31,45 +75,249 @@ impl UI {
             ])
             .split(frame.area());
 
-        self.render_status_bar::<B>(frame, app, chunks[0]);
-        self.render_main_content::<B>(frame, app, chunks[1]);
-        self.render_mode_line::<B>(frame, app, chunks[2]);
+        self.render_status_bar(frame, app, chunks[0]);
+        self.render_main_content(frame, app, chunks[1]);
+        self.render_mode_line(frame, app, chunks[2]);
     }
 
-    fn render_status_bar<B: Backend>(&self, frame: &mut Frame, app: &App, area: Rect) {
+    fn render_status_bar(&self, frame: &mut Frame, app: &App, area: Rect) {
+        let s = app.config.language.strings();
         let status_text = if app.state.is_searching_places {
             vec![
-                Span::styled("Jump to: ", Style::default().fg(Color::Green)),
+                Span::styled(s.jump_prompt, Style::default().fg(Color::Green)),
                 Span::styled(&app.state.place_search_buffer, Style::default().fg(Color::White)),
-                Span::raw(" (type to filter, ↑/↓ to select, Enter to jump, Esc to cancel)"),
+                Span::raw(s.jump_hint),
             ]
         } else {
             match app.state.mode {
                 Mode::Edit => {
                     vec![
-                        Span::styled("Editing: ", Style::default().fg(Color::Yellow)),
+                        Span::styled(s.edit_prompt, Style::default().fg(Color::Yellow)),
                         Span::styled(&app.state.edit_buffer, Style::default().fg(Color::White)),
-                        Span::raw(" (Enter to save, Esc to cancel)"),
+                        Span::raw(match app.state.selection {
+                            Some(Selection::Affordance { .. }) if !app.state.editing_branch => s.edit_affordance_hint,
+                            _ => s.edit_hint,
+                        }),
                     ]
                 }
                 Mode::Connect => {
                     vec![
-                        Span::styled("Connect to: ", Style::default().fg(Color::Cyan)),
-                        Span::styled(&app.state.connection_search_buffer, Style::default().fg(Color::White)),
-                        Span::raw(" (↑/↓ to select, Enter to connect, Esc to cancel)"),
+                        Span::styled(match app.state.marked.len() {
+                            0 => s.connect_prompt.to_string(),
+                            count => locale::fill(s.connect_marked_prompt, &[&count.to_string()]),
+                        }, Style::default().fg(Color::Cyan)),
+                        Span::styled(&app.state.connection_search_buffer, if app.state.connection_search_prefilled {
+                            Style::default().bg(Color::Blue).fg(Color::White)
+                        } else {
+                            Style::default().fg(Color::White)
+                        }),
+                        match (app.state.self_connection_warned, &app.state.status_message) {
+                            (true, Some(warning)) => Span::styled(format!("  {}", warning), Style::default().fg(Color::Yellow)),
+                            _ => Span::raw(if app.state.connection_search_prefilled {
+                                s.connect_prefilled_hint
+                            } else {
+                                s.connect_hint
+                            }),
+                        },
                     ]
                 }
                 Mode::SaveFile => {
+                    // Exports name their scope unless it's the whole board
+                    let scope = match app.state.export_scope {
+                        ExportScope::Board => String::new(),
+                        scope => format!(" ({})", scope.label()),
+                    };
+                    let (prompt, hint) = match app.state.save_target {
+                        SaveTarget::Board => (s.save_prompt.to_string(), s.save_hint),
+                        SaveTarget::ViewSnapshot => (locale::fill(s.export_view_prompt, &[&scope]), s.export_hint),
+                        SaveTarget::Document(document) => (
+                            locale::fill(s.export_document_prompt, &[&document.label().to_lowercase(), &scope]),
+                            s.export_hint,
+                        ),
+                        SaveTarget::Extract { replace: false } => (s.copy_subtree_prompt.to_string(), s.save_hint),
+                        SaveTarget::Extract { replace: true } => (s.move_subtree_prompt.to_string(), s.save_hint),
+                        SaveTarget::Bundle => (s.bundle_prompt.to_string(), s.save_hint),
+                        SaveTarget::Attachment => (s.attach_prompt.to_string(), s.attach_hint),
+                        SaveTarget::Compare => (s.compare_prompt.to_string(), s.compare_hint),
+                    };
+                    vec![
+                        Span::styled(prompt, Style::default().fg(Color::Green)),
+                        Span::styled(&app.state.save_filename, Style::default().fg(Color::White)),
+                        Span::raw(hint),
+                    ]
+                }
+                Mode::ImportFile => {
+                    let (prompt, hint) = match app.state.import_path {
+                        Some(_) => (s.import_group_prompt, s.import_group_hint),
+                        None => (s.import_prompt, s.import_hint),
+                    };
                     vec![
-                        Span::styled("Save as: ", Style::default().fg(Color::Green)),
+                        Span::styled(prompt, Style::default().fg(Color::Magenta)),
                         Span::styled(&app.state.save_filename, Style::default().fg(Color::White)),
-                        Span::raw(" (Enter to save, Esc to cancel)"),
+                        Span::raw(hint),
+                    ]
+                }
+                Mode::Incoming => {
+                    vec![
+                        Span::styled(s.incoming_prompt, Style::default().fg(Color::Cyan)),
+                        Span::styled(
+                            app.get_selected_place().map(|p| p.name.as_str()).unwrap_or(""),
+                            Style::default().fg(Color::White),
+                        ),
+                        Span::raw(s.incoming_hint),
+                    ]
+                }
+                Mode::Branches => {
+                    vec![
+                        Span::styled(s.outcomes_prompt, Style::default().fg(Color::LightMagenta)),
+                        Span::styled(
+                            app.get_selected_affordance().map(|a| a.name.as_str()).unwrap_or(""),
+                            Style::default().fg(Color::White),
+                        ),
+                        Span::raw(s.outcomes_hint),
+                    ]
+                }
+                Mode::Cycles => {
+                    let count = match app.state.cycles.len() {
+                        1 => s.cycle_count.to_string(),
+                        count => locale::fill(s.cycles_count, &[&count.to_string()]),
+                    };
+                    vec![
+                        Span::styled(count, Style::default().fg(Color::Yellow)),
+                        Span::raw(s.cycles_hint),
+                    ]
+                }
+                Mode::ConfirmNotes => {
+                    vec![
+                        Span::styled(s.notes_prompt, Style::default().fg(Color::Magenta)),
+                        Span::raw(s.notes_hint),
+                    ]
+                }
+                Mode::Capture => {
+                    vec![
+                        Span::styled(s.capture_prompt, Style::default().fg(Color::LightGreen)),
+                        Span::styled(&app.state.edit_buffer, Style::default().fg(Color::White)),
+                        Span::raw(s.capture_hint),
+                    ]
+                }
+                Mode::Comment => {
+                    vec![
+                        Span::styled(locale::fill(s.comment_prompt, &[&app.config.author()]), Style::default().fg(Color::LightYellow)),
+                        Span::styled(&app.state.edit_buffer, Style::default().fg(Color::White)),
+                        Span::raw(s.comment_hint),
+                    ]
+                }
+                Mode::Compare => {
+                    let summary = app.state.comparison.as_ref()
+                        .and_then(|c| diff::summary(&c.rows))
+                        .unwrap_or_else(|| s.no_differences.to_string());
+                    vec![
+                        Span::styled(locale::fill(s.compared, &[&summary]), Style::default().fg(Color::LightCyan)),
+                        Span::raw(s.compared_hint),
+                    ]
+                }
+                Mode::Estimate => {
+                    vec![
+                        Span::styled(s.estimate_prompt, Style::default().fg(Color::LightMagenta)),
+                        Span::styled(&app.state.edit_buffer, Style::default().fg(Color::White)),
+                        Span::raw(s.estimate_hint),
+                    ]
+                }
+                Mode::Commands => {
+                    vec![
+                        Span::styled(s.commands_prompt, Style::default().fg(Color::Magenta)),
+                        Span::raw(s.commands_hint),
+                    ]
+                }
+                Mode::Start => {
+                    vec![
+                        Span::styled(s.welcome, Style::default().fg(Color::Green)),
+                        Span::raw(s.welcome_hint),
+                    ]
+                }
+                Mode::Snippets => {
+                    vec![
+                        Span::styled(s.snippets_prompt, Style::default().fg(Color::LightMagenta)),
+                        Span::raw(s.snippets_hint),
+                    ]
+                }
+                Mode::Trash => {
+                    vec![
+                        Span::styled(s.trash_prompt, Style::default().fg(Color::LightRed)),
+                        Span::raw(s.trash_hint),
+                    ]
+                }
+                Mode::Spelling => {
+                    vec![
+                        Span::styled(s.spelling_prompt, Style::default().fg(Color::LightRed)),
+                        Span::raw(s.spelling_hint),
+                    ]
+                }
+                Mode::Views => {
+                    vec![
+                        Span::styled(s.views_prompt, Style::default().fg(Color::LightCyan)),
+                        Span::raw(s.views_hint),
+                    ]
+                }
+                Mode::ViewName => {
+                    vec![
+                        Span::styled(s.view_name_prompt, Style::default().fg(Color::LightCyan)),
+                        Span::styled(&app.state.edit_buffer, Style::default().fg(Color::White)),
+                        Span::raw(s.view_name_hint),
+                    ]
+                }
+                Mode::Filter => {
+                    vec![
+                        Span::styled(s.filter_prompt, Style::default().fg(Color::LightBlue)),
+                        Span::styled(app.state.filter.as_deref().unwrap_or(""), Style::default().fg(Color::White)),
+                        Span::raw(s.filter_hint),
                     ]
                 }
                 Mode::OpenFile => {
                     vec![
-                        Span::styled("Select file to open: ", Style::default().fg(Color::Magenta)),
-                        Span::raw(" (↑/↓ to select, Enter to open, Esc to cancel)"),
+                        Span::styled(s.open_file_prompt, Style::default().fg(Color::Magenta)),
+                        Span::styled(&app.state.file_filter, Style::default().fg(Color::White)),
+                        Span::raw(s.open_file_hint),
+                    ]
+                }
+                Mode::Library => {
+                    let hint = if app.state.current_filename.is_some() || app.breadboard.places.is_empty() {
+                        s.library_hint
+                    } else {
+                        s.library_new_board_hint
+                    };
+                    vec![
+                        Span::styled(s.library_prompt, Style::default().fg(Color::Green)),
+                        Span::raw(hint),
+                    ]
+                }
+                Mode::ConfirmReopen => {
+                    let (before, after) = locale::around(s.reopen_prompt);
+                    vec![
+                        Span::styled(before, Style::default().fg(Color::Green)),
+                        Span::styled(app.state.reopen_candidate.as_deref().unwrap_or(""), Style::default().fg(Color::White)),
+                        Span::styled(after, Style::default().fg(Color::Green)),
+                        Span::styled(s.reopen_hint, Style::default().fg(Color::Gray)),
+                    ]
+                }
+                Mode::ConfirmRecover => {
+                    let (board, time) = app.state.recovery_candidate.as_ref()
+                        .map_or((String::new(), String::new()), |r| (r.describe(s), r.saved_at.format("%b %-d %H:%M").to_string()));
+                    let (before, after) = locale::around(s.recover_prompt);
+                    vec![
+                        Span::styled(before, Style::default().fg(Color::Yellow)),
+                        Span::styled(board, Style::default().fg(Color::White)),
+                        Span::styled(locale::fill(after, &[&time]), Style::default().fg(Color::Yellow)),
+                        Span::styled(s.recover_hint, Style::default().fg(Color::Gray)),
+                    ]
+                }
+                Mode::ConfirmLock => {
+                    let (before, after) = locale::around(s.locked_prompt);
+                    vec![
+                        Span::styled(before, Style::default().fg(Color::Red)),
+                        Span::styled(app.state.locked_by.as_deref().unwrap_or(""), Style::default().fg(Color::White)),
+                        Span::styled(after, Style::default().fg(Color::Red)),
+                        Span::styled(s.locked_hint, Style::default().fg(Color::Gray)),
                     ]
                 }
                 Mode::ConfirmDelete => {
@@ -77,9 +325,9 @@ impl UI {
                     let place_name = if let Some(Selection::Place(place_id)) = &app.state.pending_deletion {
                         app.breadboard.find_place(place_id)
                             .map(|p| p.name.as_str())
-                            .unwrap_or("Unknown")
+                            .unwrap_or(s.unknown)
                     } else {
-                        "Unknown"
+                        s.unknown
                     };
 
                     // Count affordances and connections
@@ -87,7 +335,7 @@ impl UI {
                         if let Some(place) = app.breadboard.find_place(place_id) {
                             let aff_count = place.affordances.len();
                             let conn_count = place.affordances.iter()
-                                .filter(|a| a.connects_to.is_some())
+                                .filter(|a| !a.destinations().is_empty())
                                 .count();
                             (aff_count, conn_count)
                         } else {
@@ -97,363 +345,1315 @@ impl UI {
                         (0, 0)
                     };
 
+                    let (before, after) = locale::around(s.delete_prompt);
                     vec![
-                        Span::styled("Delete '", Style::default().fg(Color::Red)),
+                        Span::styled(before, Style::default().fg(Color::Red)),
                         Span::styled(place_name, Style::default().fg(Color::White)),
-                        Span::styled(format!("' with {} affordance(s) and {} connection(s)? ", affordance_count, connection_count), Style::default().fg(Color::Red)),
-                        Span::styled("(Y/Enter to confirm, N/Esc to cancel)", Style::default().fg(Color::Gray)),
+                        Span::styled(
+                            locale::fill(after, &[&affordance_count.to_string(), &connection_count.to_string()]),
+                            Style::default().fg(Color::Red),
+                        ),
+                        Span::styled(s.delete_hint, Style::default().fg(Color::Gray)),
                     ]
                 }
-                _ => {
+                _ if app.state.status_message.is_some() => {
                     vec![
                         Span::styled(
-                            format!("Board: {} ", app.breadboard.name),
+                            app.state.status_message.as_deref().unwrap_or_default(),
                             Style::default().fg(Color::Yellow),
                         ),
+                    ]
+                }
+                _ if app.config.status_bar.is_some() => {
+                    let template = app.config.status_bar.as_deref().unwrap_or_default();
+                    vec![Span::styled(status_template(app, template), Style::default().fg(Color::Yellow))]
+                }
+                _ => {
+                    let mut spans = breadcrumb_spans(&app.breadcrumb());
+                    spans.extend([
+                        Span::raw("  "),
                         Span::styled(
-                            format!("Places: {} ", app.breadboard.places.len()),
+                            locale::fill(s.places_count, &[&app.breadboard.places.len().to_string()]),
                             Style::default().fg(Color::Green),
                         ),
                         Span::styled(
-                            "(type to search) ",
+                            s.type_to_search,
                             Style::default().fg(Color::Gray),
                         ),
-                    ]
+                    ]);
+                    let comments = app.selected_comments().len();
+                    if comments > 0 {
+                        spans.push(Span::styled(locale::fill(s.comments_count, &[&comments.to_string()]), Style::default().fg(Color::LightYellow)));
+                    }
+                    // First line of the selection's notes
+                    if let Some(notes) = app.selected_notes() {
+                        spans.push(Span::styled(
+                            locale::fill(s.notes_line, &[notes.lines().next().unwrap_or_default()]),
+                            Style::default().fg(Color::Cyan),
+                        ));
+                    }
+                    spans
                 }
             }
         };
 
-        let status_line = Line::from(status_text);
+        let width = area.width.saturating_sub(2) as usize;
+        let typing = app.state.is_searching_places || matches!(
+            app.state.mode,
+            Mode::Edit | Mode::Connect | Mode::SaveFile | Mode::ImportFile | Mode::Capture
+                | Mode::Comment | Mode::Estimate | Mode::ViewName | Mode::Filter | Mode::OpenFile
+        );
+        let status_line = if typing { fitted_input(status_text, width) } else { fitted(status_text, width) };
         let status_bar = Paragraph::new(status_line)
             .block(Block::default().borders(Borders::ALL));
 
         frame.render_widget(status_bar, area);
     }
 
-    fn render_main_content<B: Backend>(&mut self, frame: &mut Frame, app: &mut App, area: Rect) {
+    fn render_main_content(&mut self, frame: &mut Frame, app: &mut App, area: Rect) {
+        if app.state.mode == Mode::Compare {
+            if let Some(comparison) = &app.state.comparison {
+                self.render_comparison(frame, comparison, area);
+                return;
+            }
+        }
+
+        // Opening a workspace lists its boards before any board is loaded
+        if app.state.mode == Mode::Library {
+            self.render_library(frame, app, area);
+            return;
+        }
+
         if app.breadboard.places.is_empty() {
-            self.render_empty_state::<B>(frame, area);
+            match app.state.mode {
+                Mode::Start => self.render_start_screen(frame, app, area),
+                Mode::OpenFile => self.render_file_selection(frame, app, area),
+                _ if !app.state.drill_stack.is_empty() => self.render_empty_inner_board(frame, app, area),
+                _ => self.render_empty_state(frame, app, area),
+            }
+            self.render_tutorial(frame, app, area);
+            // Pasted notes are previewed over an empty board too
+            self.render_output_panel(frame, app, area);
             return;
         }
 
         if app.state.mode == Mode::Connect {
-            self.render_connection_search::<B>(frame, app, area);
+            self.render_connection_search(frame, app, area);
         } else if app.state.mode == Mode::OpenFile {
-            self.render_file_selection::<B>(frame, app, area);
-        } else if app.state.is_searching_places {
-            self.render_place_search::<B>(frame, app, area);
+            self.render_file_selection(frame, app, area);
+        } else if app.state.mode == Mode::Incoming {
+            self.render_incoming_list(frame, app, area);
+        } else if app.state.mode == Mode::Branches {
+            self.render_branch_list(frame, app, area);
+        } else if app.state.mode == Mode::Cycles {
+            self.render_cycles_view(frame, app, area);
+        } else if app.state.mode == Mode::Commands {
+            self.render_command_list(frame, app, area);
+        } else if app.state.mode == Mode::Snippets {
+            self.render_snippet_list(frame, app, area);
+        } else if app.state.mode == Mode::Trash {
+            self.render_trash_list(frame, app, area);
+        } else if app.state.mode == Mode::Spelling {
+            self.render_spelling_list(frame, app, area);
+        } else if matches!(app.state.mode, Mode::Views | Mode::ViewName) {
+            self.render_view_list(frame, app, area);
+        } else if app.state.view == ViewMode::Graph {
+            self.render_graph_view(frame, app, area);
         } else if app.state.collapsed {
-            self.render_collapsed_view::<B>(frame, app, area);
+            self.render_collapsed_view(frame, app, area);
         } else {
-            self.render_expanded_view::<B>(frame, app, area);
+            self.render_expanded_view(frame, app, area);
         }
-    }
-
-    fn render_empty_state<B: Backend>(&self, frame: &mut Frame, area: Rect) {
-        let text = vec![
-            Line::from("No places yet. Press Ctrl+N to create a place."),
-            Line::from(""),
-            Line::from("Controls:"),
-            Line::from("  Ctrl+N - New place"),
-            Line::from("  Ctrl+O - Open file"),
-            Line::from("  Ctrl+Q - Quit"),
-        ];
-
-        let paragraph = Paragraph::new(text);
-        frame.render_widget(paragraph, area);
-    }
 
-    fn render_expanded_view<B: Backend>(&mut self, frame: &mut Frame, app: &App, area: Rect) {
-        let mut items = Vec::new();
+        // Jump search results float over the board, which highlights them
+        if app.state.is_searching_places {
+            self.render_place_search(frame, app, area);
+        } else if app.state.show_progress && app.state.mode == Mode::Navigate {
+            self.render_progress_panel(frame, app, area);
+        }
 
-        // Precompute all incoming connections once for performance
-        let mut incoming_sources: std::collections::HashMap<u32, Vec<String>> = std::collections::HashMap::new();
-        for place in &app.breadboard.places {
-            for affordance in &place.affordances {
-                if let Some(dest_id) = &affordance.connects_to {
-                    incoming_sources.entry(*dest_id)
-                        .or_insert_with(Vec::new)
-                        .push(place.name.clone());
-                }
-            }
+        if app.state.show_preview && app.state.mode == Mode::Navigate && !app.state.is_searching_places {
+            self.render_preview(frame, app, area);
         }
 
-        for (place_index, place) in app.breadboard.places.iter().enumerate() {
-            let incoming_names = incoming_sources.get(&place.id);
+        self.render_icon_picker(frame, app, area);
 
-            // Place header with incoming connections indicator
-            let place_style = if app.state.selection == Some(Selection::Place(place.id)) {
-                Style::default().bg(Color::Blue).fg(Color::Black)
-            } else {
-                Style::default().fg(Color::Cyan)
-            };
+        self.render_leader_popup(frame, app, area);
 
-            let place_header = if let Some(names) = incoming_names {
-                if names.is_empty() {
-                    format!("┌─ {}", place.name)
-                } else {
-                    format!("┌─ {} (← {})", place.name, names.join(", "))
-                }
-            } else {
-                format!("┌─ {}", place.name)
-            };
+        self.render_sketch(frame, app, area);
 
-            items.push(ListItem::new(Line::from(Span::styled(place_header, place_style))));
+        self.render_tutorial(frame, app, area);
 
-            // Affordances
-            for affordance in &place.affordances {
-                let affordance_style = if app.state.selection == Some(Selection::Affordance {
-                    place_id: place.id,
-                    affordance_id: affordance.id
-                }) {
-                    Style::default().bg(Color::Blue).fg(Color::Black)
-                } else {
-                    Style::default().fg(Color::White)
-                };
+        self.render_output_panel(frame, app, area);
+    }
 
-                let affordance_text = if let Some(dest_id) = &affordance.connects_to {
-                    if let Some(dest_place) = app.breadboard.find_place(dest_id) {
-                        format!("├─ {} → {}", affordance.name, dest_place.name)
-                    } else {
-                        format!("├─ {} → [Unknown]", affordance.name)
-                    }
-                } else {
-                    format!("├─ {}", affordance.name)
-                };
+    // The tutorial's current step, along the bottom of the main area
+    fn render_tutorial(&self, frame: &mut Frame, app: &App, area: Rect) {
+        let Some((index, step)) = app.tutorial_step() else {
+            return;
+        };
+        let s = app.config.language.strings();
+        let width = area.width.saturating_sub(2) as usize;
+        let lines = wrapped_lines(step.prompt(s), "", width.max(1)).len() as u16;
+        let height = (lines + 2).min(area.height);
+        let panel_area = Rect::new(area.x, area.y + area.height - height, area.width, height);
 
-                items.push(ListItem::new(Line::from(Span::styled(affordance_text, affordance_style))));
-            }
+        let title = if index + 1 < tutorial::STEPS.len() {
+            locale::fill(s.tutorial_title, &[&(index + 1).to_string(), &(tutorial::STEPS.len() - 1).to_string()])
+        } else {
+            s.tutorial_done.to_string()
+        };
+        let panel = Paragraph::new(step.prompt(s))
+            .wrap(Wrap { trim: false })
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::LightGreen))
+                .title(title));
 
-            // Add spacing between places
-            if place_index < app.breadboard.places.len() - 1 {
-                items.push(ListItem::new(""));
-            }
-        }
+        frame.render_widget(Clear, panel_area);
+        frame.render_widget(panel, panel_area);
+    }
 
-        let list = List::new(items)
-            .block(Block::default().borders(Borders::ALL).title("Breadboard"))
-            .highlight_style(Style::default());
+    // Output of a hook or custom command across the bottom of the main
+    // area, until dismissed with Esc
+    fn render_output_panel(&self, frame: &mut Frame, app: &App, area: Rect) {
+        let Some((title, text)) = &app.state.output_panel else {
+            return;
+        };
+        let s = app.config.language.strings();
+        let height = (text.lines().count() as u16 + 2).clamp(3, (area.height / 2).max(3)).min(area.height);
+        let panel_area = Rect {
+            x: area.x,
+            y: area.y + area.height - height,
+            width: area.width,
+            height,
+        };
 
-        // Update list state for scrolling
-        if let Some(selected_index) = app.get_selected_item_index() {
-            self.list_state.select(Some(selected_index));
-        }
+        let panel = Paragraph::new(text.to_string())
+            .wrap(Wrap { trim: false })
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Magenta))
+                .title(locale::fill(s.panel_title, &[title])));
 
-        frame.render_stateful_widget(list, area, &mut self.list_state);
+        frame.render_widget(Clear, panel_area);
+        frame.render_widget(panel, panel_area);
     }
 
-    fn render_collapsed_view<B: Backend>(&self, frame: &mut Frame, app: &App, area: Rect) {
-        let mut items = Vec::new();
-
-        // Determine which places to show based on filter
-        let places_to_show: Vec<_> = if let Some("connected") = app.state.filter.as_deref() {
-            // Show only places connected to the currently selected place
-            // Get the place ID whether we're on a place or an affordance
-            let selected_id = match app.state.selection {
-                Some(Selection::Place(id)) => Some(id),
-                Some(Selection::Affordance { place_id, .. }) => Some(place_id),
-                None => None,
-            };
+    // Floating panel listing the destination place's affordances, anchored to
+    // the right edge of the main area so the selection stays visible
+    fn render_preview(&self, frame: &mut Frame, app: &App, area: Rect) {
+        let Some(place) = app.get_preview_place() else {
+            return;
+        };
 
-            if let Some(selected_id) = selected_id {
-                let mut connected_places = std::collections::HashSet::new();
+        let mut lines: Vec<Line> = place.affordances.iter()
+            .map(|affordance| {
+                let text = match affordance.connects_to.and_then(|id| app.breadboard.find_place(&id)) {
+                    Some(dest) => format!("├─ {} → {}", affordance.name, dest.name),
+                    None => format!("├─ {}", affordance.name),
+                };
+                Line::from(text)
+            })
+            .collect();
+        if lines.is_empty() {
+            lines.push(Line::from(Span::styled("(no affordances)", Style::default().fg(Color::Gray))));
+        }
 
-                // Add outgoing connections
-                if let Some(selected_place) = app.breadboard.find_place(&selected_id) {
-                    for affordance in &selected_place.affordances {
-                        if let Some(dest_id) = &affordance.connects_to {
-                            connected_places.insert(*dest_id);
-                        }
-                    }
-                }
+        let width = (area.width * 2 / 5).max(20).min(area.width);
+        let height = (lines.len() as u16 + 2).min(area.height);
+        let popup = Rect::new(area.x + area.width - width, area.y + 1, width, height.min(area.height.saturating_sub(1)));
 
-                // Add incoming connections
-                for incoming in app.breadboard.get_incoming_connections(&selected_id) {
-                    connected_places.insert(incoming.0.id);
-                }
+        let paragraph = Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan))
+                .title(format!("→ {}", place.title())));
 
-                connected_places.insert(selected_id); // Include the selected place itself
+        frame.render_widget(Clear, popup);
+        frame.render_widget(paragraph, popup);
+    }
 
-                app.breadboard.places.iter()
-                    .filter(|p| connected_places.contains(&p.id))
-                    .collect()
-            } else {
-                app.breadboard.places.iter().collect()
-            }
-        } else {
-            app.breadboard.places.iter().collect()
+    // The selected place's sketch in the bottom-right corner of the main
+    // area, drawn with whichever image protocol the terminal has
+    fn render_sketch(&mut self, frame: &mut Frame, app: &App, area: Rect) {
+        let protocol = sketch::detect(app.config.image_protocol, |name| std::env::var(name).ok());
+        let shown = app.state.show_sketch && app.state.mode == Mode::Navigate && !app.state.is_searching_places;
+        let Some(attachment) = app.get_selected_place().and_then(sketch::image_attachment).filter(|_| shown) else {
+            self.show_image(None, protocol);
+            return;
         };
+        let path = bundle::resolve(&app.board_dir(), attachment);
+        if self.sketch.as_ref().is_none_or(|(loaded, _)| *loaded != path) {
+            self.sketch = Some((path.clone(), sketch::load(&path).ok()));
+        }
 
-        // Precompute incoming connection sources for performance
-        let mut incoming_sources: std::collections::HashMap<u32, Vec<String>> = std::collections::HashMap::new();
-        for place in &app.breadboard.places {
-            for affordance in &place.affordances {
-                if let Some(dest_id) = &affordance.connects_to {
-                    incoming_sources.entry(*dest_id)
-                        .or_insert_with(Vec::new)
-                        .push(place.name.clone());
-                }
+        let width = (area.width * 2 / 5).max(20).min(area.width);
+        let height = (area.height / 2).max(6).min(area.height);
+        let pane = Rect::new(area.x + area.width - width, area.y + area.height - height, width, height);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Magenta))
+            .title(locale::fill(app.config.language.strings().sketch_title, &[attachment]));
+        let inner = block.inner(pane);
+        frame.render_widget(Clear, pane);
+        frame.render_widget(block, pane);
+
+        match self.sketch.as_ref().and_then(|(_, image)| image.as_ref()) {
+            None => {
+                self.show_image(None, protocol);
+                frame.render_widget(Paragraph::new(Span::styled(app.config.language.strings().unreadable_image, Style::default().fg(Color::Gray))), inner);
             }
+            Some(image) if protocol == ImageProtocol::Blocks => {
+                frame.render_widget(Paragraph::new(sketch::block_lines(image, inner.width, inner.height)), inner);
+            }
+            Some(_) => self.show_image(Some((path, inner)), protocol),
         }
+    }
 
-        for place in places_to_show {
-            let incoming_names = incoming_sources.get(&place.id);
-            let outgoing_connections: Vec<_> = place.affordances.iter()
-                .filter_map(|a| a.connects_to.as_ref())
-                .filter_map(|dest_id| app.breadboard.find_place(dest_id))
-                .collect();
-
-            let place_style = if app.state.selection == Some(Selection::Place(place.id)) {
-                Style::default().bg(Color::Blue).fg(Color::Black)
-            } else {
-                Style::default().fg(Color::Cyan)
-            };
+    // Have the terminal show the loaded sketch at `target` through `protocol`,
+    // or nothing, unless it already does
+    fn show_image(&mut self, target: Option<(PathBuf, Rect)>, protocol: ImageProtocol) {
+        if self.drawn_image == target {
+            return;
+        }
+        let redraw = protocol == ImageProtocol::Sixel && self.drawn_image.is_some();
+        let image = self.sketch.as_ref().and_then(|(_, image)| image.as_ref());
+        let sequence = match (&target, image) {
+            (Some((_, rect)), Some(image)) => {
+                let drawing = match protocol {
+                    ImageProtocol::Sixel => sketch::sixel_sequence(image, rect.width, rect.height),
+                    _ => sketch::kitty_sequence(image, rect.width, rect.height),
+                };
+                // Saved and restored around the move to where the image goes
+                format!("\x1b7\x1b[{};{}H{}\x1b8", rect.y + 1, rect.x + 1, drawing)
+            }
+            _ if protocol == ImageProtocol::Kitty => sketch::kitty_clear(),
+            _ => String::new(),
+        };
+        self.drawn_image = target;
+        self.graphics = Some(Graphics { sequence, redraw });
+    }
 
-            let mut place_info = format!("{} ({})", place.name, place.affordances.len());
+    // The two versions of the board side by side, older on the left, with
+    // one scroll position so their rows stay lined up
+    fn render_comparison(&self, frame: &mut Frame, comparison: &Comparison, area: Rect) {
+        let halves = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(area);
+        let height = halves[0].height.saturating_sub(2) as usize;
+        let rows = &comparison.rows[comparison.scroll.min(comparison.rows.len())..];
 
-            if let Some(names) = incoming_names {
-                if !names.is_empty() {
-                    place_info.push_str(&format!(" ← {}", names.join(", ")));
-                }
-            }
+        for (half, title, old) in [(halves[0], &comparison.old_title, true), (halves[1], &comparison.new_title, false)] {
+            let width = half.width.saturating_sub(2) as usize;
+            let lines: Vec<Line> = rows.iter().take(height)
+                .map(|row| {
+                    let text = if old { &row.old } else { &row.new };
+                    let (mark, style) = match row.change {
+                        _ if text.is_empty() => (" ", Style::default()),
+                        Change::Same => (" ", Style::default()),
+                        Change::Added => ("+", Style::default().fg(Color::LightGreen)),
+                        Change::Removed => ("-", Style::default().fg(Color::LightRed)),
+                        Change::Renamed | Change::Rewired => ("~", Style::default().fg(Color::Yellow)),
+                    };
+                    Line::from(Span::styled(fit_to_width(&format!("{} {}", mark, text), width), style))
+                })
+                .collect();
+            let panel = Paragraph::new(lines)
+                .block(Block::default().borders(Borders::ALL).title(title.as_str()));
+            frame.render_widget(panel, half);
+        }
+    }
 
-            if !outgoing_connections.is_empty() {
-                let dest_names: Vec<_> = outgoing_connections.iter()
-                    .map(|p| p.name.as_str())
-                    .collect();
-                place_info.push_str(&format!(" → {}", dest_names.join(", ")));
+    // Built affordances per place, in the top-right corner of the main area
+    fn render_progress_panel(&self, frame: &mut Frame, app: &App, area: Rect) {
+        let s = app.config.language.strings();
+        let mut lines = progress_lines(&app.breadboard, s);
+        let total = progress(&app.breadboard).1;
+        // What the undo history holds, and what it costs
+        if let Some(history) = app.state.snapshots.describe(s) {
+            lines.push(String::new());
+            lines.push(locale::fill(s.history_line, &[&history]));
+            if let Some(label) = app.state.snapshots.labels().last() {
+                lines.push(locale::fill(s.undo_line, &[label]));
             }
-
-            items.push(ListItem::new(Line::from(Span::styled(place_info, place_style))));
         }
 
-        let title = if app.state.filter.is_some() {
-            "Breadboard (Filtered)"
-        } else {
-            "Breadboard (Collapsed)"
-        };
+        let widest = lines.iter().map(|line| line.width()).max().unwrap_or(0) as u16;
+        let width = (widest + 2).min(area.width);
+        let height = (lines.len() as u16 + 2).min(area.height.saturating_sub(1));
+        let panel_area = Rect::new(area.x + area.width - width, area.y + 1, width, height);
 
-        let list = List::new(items)
-            .block(Block::default().borders(Borders::ALL).title(title));
+        let text: Vec<Line> = lines.into_iter().map(Line::from).collect();
+        let panel = Paragraph::new(text)
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Green))
+                .title(match total.points {
+                    0.0 => locale::fill(s.progress_title, &[&total.built.to_string(), &total.total().to_string()]),
+                    points => locale::fill(s.progress_points_title, &[&total.built.to_string(), &total.total().to_string(), &points_label(points, s)]),
+                }));
 
-        frame.render_widget(list, area);
+        frame.render_widget(Clear, panel_area);
+        frame.render_widget(panel, panel_area);
     }
 
-    fn render_mode_line<B: Backend>(&self, frame: &mut Frame, app: &App, area: Rect) {
-        let mode_text = match app.state.mode {
-            Mode::Navigate => "NAVIGATE",
-            Mode::Edit => "EDIT",
-            Mode::Connect => "CONNECT",
-            Mode::SaveFile => "SAVE FILE",
-            Mode::OpenFile => "OPEN FILE",
-            Mode::ConfirmDelete => "CONFIRM DELETE",
-        };
+    fn render_empty_state(&self, frame: &mut Frame, app: &App, area: Rect) {
+        let s = app.config.language.strings();
+        let text = vec![
+            Line::from(s.empty_board),
+            Line::from(""),
+            Line::from(Span::styled(s.breadboarding_intro, Style::default().fg(Color::Gray))),
+            Line::from(""),
+            Line::from(s.controls),
+            Line::from(s.control_new_place),
+            Line::from(s.control_open),
+            Line::from(s.control_start),
+            Line::from(s.control_quit),
+        ];
 
-        let mode_style = match app.state.mode {
-            Mode::Navigate => Style::default().fg(Color::Green),
-            Mode::Edit => Style::default().fg(Color::Yellow),
-            Mode::Connect => Style::default().fg(Color::Cyan),
-            Mode::SaveFile => Style::default().fg(Color::Green),
-            Mode::OpenFile => Style::default().fg(Color::Magenta),
-            Mode::ConfirmDelete => Style::default().fg(Color::Red),
-        };
+        let paragraph = Paragraph::new(text).wrap(Wrap { trim: false });
+        frame.render_widget(paragraph, area);
+    }
 
+    // A place drilled into that has nothing inside yet
+    fn render_empty_inner_board(&self, frame: &mut Frame, app: &App, area: Rect) {
+        let s = app.config.language.strings();
         let text = vec![
-            Span::styled("Mode: ", Style::default().fg(Color::Gray)),
-            Span::styled(mode_text, mode_style),
-            Span::raw(" | "),
-            Span::styled(
-                if app.state.collapsed { "Collapsed" } else { "Expanded" },
-                Style::default().fg(Color::Cyan),
-            ),
+            Line::from(locale::fill(s.empty_inner_board, &[&app.breadboard.name])),
+            Line::from(""),
+            Line::from(s.control_back_out),
         ];
+        frame.render_widget(Paragraph::new(text).wrap(Wrap { trim: false }), area);
+    }
 
-        let mode_line = Line::from(text);
-        let paragraph = Paragraph::new(mode_line);
+    // What breadboarding is, then the ways to begin: a new board, the last
+    // one, a file or one of the bundled examples
+    fn render_start_screen(&self, frame: &mut Frame, app: &App, area: Rect) {
+        let s = app.config.language.strings();
+        let mut text = vec![
+            Line::from(Span::styled(s.breadboarding, Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))),
+            Line::from(s.breadboarding_intro),
+            Line::from(""),
+        ];
+        for (index, item) in app.state.start_items.iter().enumerate() {
+            let style = if Some(index) == app.state.selected_start_index {
+                Style::default().bg(Color::Blue).fg(Color::White)
+            } else {
+                Style::default()
+            };
+            let shortcut = match item {
+                StartItem::NewBoard => s.start_new_board_key,
+                StartItem::Open => s.start_open_key,
+                _ => "",
+            };
+            text.push(Line::from(vec![
+                Span::styled(format!("  {}", item.label(s)), style),
+                Span::styled(shortcut, Style::default().fg(Color::Gray)),
+            ]));
+        }
+
+        let paragraph = Paragraph::new(text)
+            .wrap(Wrap { trim: false })
+            .block(Block::default().borders(Borders::ALL).title(s.start_title));
         frame.render_widget(paragraph, area);
     }
 
-    fn render_connection_search<B: Backend>(&self, frame: &mut Frame, app: &App, area: Rect) {
-        let mut items = Vec::new();
+    fn render_expanded_view(&mut self, frame: &mut Frame, app: &App, area: Rect) {
+        let inner_width = area.width.saturating_sub(2) as usize;
+        let rows = expanded_rows(app, &app.visible_places());
+        let mut items: Vec<ListItem> = rows.iter()
+            .map(|row| outline_item(app, row, inner_width))
+            .collect();
 
-        if app.state.connection_search_results.is_empty() {
-            items.push(ListItem::new(Line::from(Span::styled(
-                "No places found",
-                Style::default().fg(Color::Gray),
-            ))));
-        } else {
-            for (index, place_id) in app.state.connection_search_results.iter().enumerate() {
-                let is_selected = Some(index) == app.state.selected_connection_result;
-                let style = if is_selected {
-                    Style::default().bg(Color::Blue).fg(Color::White)
-                } else {
-                    Style::default()
-                };
+        if items.is_empty() {
+            items.push(no_matches_item(app));
+        }
 
-                // Check if this is the remove connection option (using ID 0)
-                if *place_id == 0 {
-                    items.push(ListItem::new(Line::from(Span::styled(
-                        "Remove connection",
-                        style.fg(if is_selected { Color::White } else { Color::Red }),
-                    ))));
-                } else if let Some(place) = app.breadboard.find_place(place_id) {
-                    items.push(ListItem::new(Line::from(Span::styled(
-                        &place.name,
-                        style,
-                    ))));
-                }
-            }
+        // The padding keeps the selection off the top row, where a pinned
+        // header would cover it
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(board_title(app, "Breadboard")))
+            .highlight_style(Style::default())
+            .scroll_padding(1);
+
+        // Update list state for scrolling
+        if let Some(selected_index) = app.get_selected_item_index() {
+            self.list_state.select(Some(selected_index));
+        }
+
+        frame.render_stateful_widget(list, area, &mut self.list_state);
+
+        if let Some(header) = sticky_header(app, &rows, self.list_state.offset()) {
+            let line = fitted(vec![Span::styled(header.text.clone(), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))], inner_width);
+            let top = Rect::new(area.x + 1, area.y + 1, inner_width as u16, 1.min(area.height.saturating_sub(2)));
+            frame.render_widget(Clear, top);
+            frame.render_widget(Paragraph::new(line), top);
+        }
+    }
+
+    fn render_collapsed_view(&self, frame: &mut Frame, app: &App, area: Rect) {
+        let s = app.config.language.strings();
+        let inner_width = area.width.saturating_sub(2) as usize;
+        let mut items: Vec<ListItem> = collapsed_rows(app, &app.visible_places()).iter()
+            .map(|row| outline_item(app, row, inner_width))
+            .collect();
+
+        if items.is_empty() {
+            items.push(no_matches_item(app));
         }
 
+        let title = board_title(app, "Breadboard (Collapsed)");
+
         let list = List::new(items)
             .block(Block::default()
                 .borders(Borders::ALL)
-                .title("Select place to connect to"));
+                .title(title)
+                .title_bottom(Line::from(format!(" {} ", s.collapsed_legend)).right_aligned()));
 
         frame.render_widget(list, area);
     }
 
-    fn render_file_selection<B: Backend>(&self, frame: &mut Frame, app: &App, area: Rect) {
+    fn render_graph_view(&mut self, frame: &mut Frame, app: &mut App, area: Rect) {
+        let board = app.visible_board();
+        let zoom = app.state.graph_zoom;
+        let diagram = render_diagram(&board, &graph_layout(app, &board), zoom);
+
+        // Dots carry no names, so say which place is selected
+        let mut title = board_title(app, graph_title(app));
+        if zoom == Detail::Dots {
+            if let Some(place) = app.get_selected_place() {
+                title = format!("{} - {}", title, place.name);
+            }
+        }
+        let block = Block::default().borders(Borders::ALL).title(title);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        if board.places.is_empty() {
+            frame.render_widget(Paragraph::new(vec![Line::from(Span::styled(
+                app.config.language.strings().no_matches,
+                Style::default().fg(Color::Gray),
+            ))]), inner);
+            return;
+        }
+
+        let (view_width, view_height) = (inner.width as usize, inner.height as usize);
+
+        // Highlighted region: the whole box for a place, one row for an
+        // affordance (or its place when zoomed out past affordance rows)
+        let highlight = match &app.state.selection {
+            Some(Selection::Place(place_id)) | Some(Selection::Affordance { place_id, .. }) if zoom != Detail::Full => {
+                diagram.boxes.get(place_id).map(|b| (b.x, b.y, b.width, b.height))
+            }
+            Some(Selection::Place(id)) => diagram.boxes.get(id)
+                .map(|b| (b.x, b.y, b.width, b.height)),
+            Some(Selection::Affordance { place_id, affordance_id }) => diagram.boxes.get(place_id)
+                .zip(board.find_place(place_id))
+                .and_then(|(b, place)| {
+                    let index = place.affordances.iter().position(|a| &a.id == affordance_id)?;
+                    Some((b.x, b.y + 1 + index, b.width, 1))
+                }),
+            None => None,
+        };
+
+        // When the selection or zoom changes, scroll just enough to keep the
+        // selection on screen; otherwise leave the view where the user panned it
+        let (mut off_x, mut off_y) = app.state.graph_offset;
+        let followed = Some((app.state.selection.clone(), zoom));
+        if self.followed_selection != followed {
+            self.followed_selection = followed;
+            if let Some((x, y, width, height)) = highlight {
+                if x + width > off_x + view_width {
+                    off_x = (x + width).saturating_sub(view_width);
+                }
+                if x < off_x {
+                    off_x = x;
+                }
+                if y + height > off_y + view_height {
+                    off_y = (y + height).saturating_sub(view_height);
+                }
+                if y < off_y {
+                    off_y = y;
+                }
+            }
+        }
+        off_x = off_x.min(diagram.width().saturating_sub(view_width));
+        off_y = off_y.min(diagram.height().saturating_sub(view_height));
+        app.state.graph_offset = (off_x, off_y);
+
+        // Cut affordance rows are struck through inside their box's border
+        let mut regions: Vec<(usize, usize, usize, usize, Style)> = Vec::new();
+        if zoom == Detail::Full {
+            let cut_style = Style::default().fg(Color::DarkGray).add_modifier(Modifier::CROSSED_OUT);
+            for place in &board.places {
+                let Some(b) = diagram.boxes.get(&place.id) else {
+                    continue;
+                };
+                for (index, affordance) in place.affordances.iter().enumerate() {
+                    if affordance.cut {
+                        regions.push((b.x + 1, b.y + 1 + index, b.width.saturating_sub(2), 1, cut_style));
+                    }
+                }
+            }
+        }
+        // Happy path places and jump search results have their title row picked out
+        let happy_style = Style::default().fg(Color::LightGreen).add_modifier(Modifier::BOLD);
+        for place_id in &board.happy_path {
+            if let Some(b) = diagram.boxes.get(place_id) {
+                regions.push((b.x, b.y, b.width, 1, happy_style));
+            }
+        }
+        for place in &board.places {
+            let style = match app.search_match(&Selection::Place(place.id)) {
+                Some(true) => SEARCH_CURRENT,
+                Some(false) => SEARCH_MATCH,
+                None => continue,
+            };
+            if let Some(b) = diagram.boxes.get(&place.id) {
+                regions.retain(|&(x, y, _, _, _)| (x, y) != (b.x, b.y));
+                regions.push((b.x, b.y, b.width, 1, style));
+            }
+        }
+        if let Some((hx, hy, hw, hh)) = highlight {
+            // The selection wins over any cut row it covers
+            regions.retain(|&(x, y, _, _, _)| !(x >= hx && x < hx + hw && y >= hy && y < hy + hh));
+            regions.push((hx, hy, hw, hh, Style::default().bg(Color::Blue).fg(Color::Black)));
+        }
+        regions.sort_by_key(|&(x, ..)| x);
+
+        let lines: Vec<Line> = (off_y..(off_y + view_height).min(diagram.height()))
+            .map(|y| {
+                let end = off_x + view_width;
+                let mut spans = Vec::new();
+                let mut at = off_x;
+                for &(rx, ry, rw, rh, style) in &regions {
+                    if y < ry || y >= ry + rh || rx + rw <= at || rx >= end {
+                        continue;
+                    }
+                    spans.push(Span::raw(diagram.segment(y, at, rx.max(at))));
+                    spans.push(Span::styled(diagram.segment(y, rx.max(at), (rx + rw).min(end)), style));
+                    at = (rx + rw).min(end);
+                }
+                spans.push(Span::raw(diagram.segment(y, at, end)));
+                Line::from(spans)
+            })
+            .collect();
+
+        frame.render_widget(Paragraph::new(lines), inner);
+
+        let fits = diagram.width() <= view_width && diagram.height() <= view_height;
+        if app.state.show_minimap && !fits {
+            self.render_minimap(frame, app, &diagram, inner);
+        }
+    }
+
+    // Overview of the whole diagram in the bottom-right corner, with the
+    // visible part shaded and the selected place highlighted
+    fn render_minimap(&self, frame: &mut Frame, app: &App, diagram: &Diagram, area: Rect) {
+        let width = (MINIMAP_SIZE.0 as usize).min(diagram.width()).min(area.width as usize / 3);
+        let height = (MINIMAP_SIZE.1 as usize).min(diagram.height()).min(area.height as usize / 3);
+        if width == 0 || height == 0 {
+            return;
+        }
+
+        let minimap_area = Rect {
+            x: area.x + area.width - width as u16 - 2,
+            y: area.y + area.height - height as u16 - 2,
+            width: width as u16 + 2,
+            height: height as u16 + 2,
+        };
+
+        let (off_x, off_y) = app.state.graph_offset;
+        let view_x = scale(off_x, diagram.width(), width)
+            ..=scale(off_x + area.width as usize - 1, diagram.width(), width);
+        let view_y = scale(off_y, diagram.height(), height)
+            ..=scale(off_y + area.height as usize - 1, diagram.height(), height);
+        let selected_place = match &app.state.selection {
+            Some(Selection::Place(id)) => Some(*id),
+            Some(Selection::Affordance { place_id, .. }) => Some(*place_id),
+            None => None,
+        };
+
+        let lines: Vec<Line> = diagram.overview(width, height).into_iter()
+            .enumerate()
+            .map(|(y, row)| {
+                Line::from(row.into_iter()
+                    .enumerate()
+                    .map(|(x, place)| {
+                        let mut style = match place {
+                            Some(id) if Some(id) == selected_place => Style::default().fg(Color::Blue),
+                            _ => Style::default().fg(Color::Gray),
+                        };
+                        if view_x.contains(&x) && view_y.contains(&y) {
+                            style = style.bg(Color::DarkGray);
+                        }
+                        Span::styled(if place.is_some() { "█" } else { " " }, style)
+                    })
+                    .collect::<Vec<_>>())
+            })
+            .collect();
+
+        frame.render_widget(Clear, minimap_area);
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Map")),
+            minimap_area,
+        );
+    }
+
+    fn render_mode_line(&self, frame: &mut Frame, app: &App, area: Rect) {
+        let s = app.config.language.strings();
+        let mode_text = match app.state.mode {
+            Mode::Navigate => s.mode_navigate,
+            Mode::Edit => s.mode_edit,
+            Mode::Connect => s.mode_connect,
+            Mode::SaveFile => s.mode_save_file,
+            Mode::OpenFile => s.mode_open_file,
+            Mode::ImportFile => s.mode_import,
+            Mode::ConfirmDelete => s.mode_confirm_delete,
+            Mode::ConfirmReopen => s.mode_reopen,
+            Mode::ConfirmRecover => s.mode_recover,
+            Mode::ConfirmLock => s.mode_locked,
+            Mode::Library => s.mode_library,
+            Mode::Filter => s.mode_filter,
+            Mode::Incoming => s.mode_incoming,
+            Mode::Cycles => s.mode_cycles,
+            Mode::Branches => s.mode_outcomes,
+            Mode::Commands => s.mode_commands,
+            Mode::Snippets => s.mode_snippets,
+            Mode::Trash => s.mode_trash,
+            Mode::Spelling => s.mode_spelling,
+            Mode::Views | Mode::ViewName => s.mode_views,
+            Mode::Start => s.mode_start,
+            Mode::Capture => s.mode_capture,
+            Mode::Comment => s.mode_comment,
+            Mode::Estimate => s.mode_estimate,
+            Mode::Compare => s.mode_compare,
+            Mode::ConfirmNotes => s.mode_import,
+        };
+
+        let mode_style = match app.state.mode {
+            Mode::Navigate => Style::default().fg(Color::Green),
+            Mode::Edit => Style::default().fg(Color::Yellow),
+            Mode::Connect => Style::default().fg(Color::Cyan),
+            Mode::SaveFile => Style::default().fg(Color::Green),
+            Mode::OpenFile => Style::default().fg(Color::Magenta),
+            Mode::ImportFile => Style::default().fg(Color::Magenta),
+            Mode::ConfirmDelete => Style::default().fg(Color::Red),
+            Mode::ConfirmReopen => Style::default().fg(Color::Green),
+            Mode::ConfirmRecover => Style::default().fg(Color::Yellow),
+            Mode::ConfirmLock => Style::default().fg(Color::Red),
+            Mode::Library => Style::default().fg(Color::Green),
+            Mode::Filter => Style::default().fg(Color::LightBlue),
+            Mode::Incoming => Style::default().fg(Color::Cyan),
+            Mode::Cycles => Style::default().fg(Color::Yellow),
+            Mode::Branches => Style::default().fg(Color::LightMagenta),
+            Mode::Commands => Style::default().fg(Color::Magenta),
+            Mode::Snippets => Style::default().fg(Color::LightMagenta),
+            Mode::Trash => Style::default().fg(Color::LightRed),
+            Mode::Spelling => Style::default().fg(Color::LightRed),
+            Mode::Views | Mode::ViewName => Style::default().fg(Color::LightCyan),
+            Mode::Start => Style::default().fg(Color::Green),
+            Mode::Capture => Style::default().fg(Color::LightGreen),
+            Mode::Comment => Style::default().fg(Color::LightYellow),
+            Mode::Estimate => Style::default().fg(Color::LightMagenta),
+            Mode::Compare => Style::default().fg(Color::LightCyan),
+            Mode::ConfirmNotes => Style::default().fg(Color::Magenta),
+        };
+
+        let mut text = vec![
+            Span::styled(s.mode, Style::default().fg(Color::Gray)),
+            Span::styled(mode_text, mode_style),
+            Span::raw(" | "),
+        ];
+        if app.is_read_only() {
+            text.push(Span::styled(s.read_only, Style::default().fg(Color::Red)));
+            text.push(Span::raw(" | "));
+        }
+        text.push(Span::styled(
+            match app.state.view {
+                ViewMode::Graph => {
+                    let name = if app.state.swimlanes { s.view_swimlanes } else { s.view_graph };
+                    match app.state.graph_zoom {
+                        Detail::Full => name.to_string(),
+                        Detail::Titles => locale::fill(s.view_titles, &[name]),
+                        Detail::Dots => locale::fill(s.view_dots, &[name]),
+                    }
+                }
+                ViewMode::Outline if app.state.collapsed => s.view_collapsed.to_string(),
+                ViewMode::Outline => s.view_expanded.to_string(),
+            },
+            Style::default().fg(Color::Cyan),
+        ));
+        if let Some(count) = &app.state.count {
+            text.push(Span::raw(" | "));
+            text.push(Span::styled(format!("{}×", count), Style::default().fg(Color::Yellow)));
+        }
+        if !app.state.marked.is_empty() {
+            text.push(Span::raw(" | "));
+            text.push(Span::styled(locale::fill(s.marked, &[&app.state.marked.len().to_string()]), Style::default().fg(Color::Magenta)));
+        }
+
+        let mode_line = Line::from(text);
+        let paragraph = Paragraph::new(mode_line);
+        frame.render_widget(paragraph, area);
+    }
+
+    fn render_connection_search(&self, frame: &mut Frame, app: &App, area: Rect) {
+        let s = app.config.language.strings();
+        let width = area.width.saturating_sub(2) as usize;
         let mut items = Vec::new();
 
-        if app.state.file_list.is_empty() {
-            items.push(ListItem::new(Line::from(Span::styled(
-                "No TOML files found in current directory",
+        if app.connection_result_count() == 0 {
+            items.push(ListItem::new(fitted(Span::styled(
+                s.no_places_found,
                 Style::default().fg(Color::Gray),
-            ))));
+            ), width)));
         } else {
-            for (index, filename) in app.state.file_list.iter().enumerate() {
-                let is_selected = Some(index) == app.state.selected_file_index;
+            let duplicates = duplicate_places(&app.breadboard);
+            // Where the affordance leads now, marked when changing it
+            let current = app.get_selected_affordance()
+                .filter(|_| !app.state.editing_branch && app.state.marked.is_empty())
+                .and_then(|a| a.connects_to);
+            for (index, place_id) in app.state.connection_search_results.iter().enumerate() {
+                let is_selected = Some(index) == app.state.selected_connection_result;
                 let style = if is_selected {
                     Style::default().bg(Color::Blue).fg(Color::White)
                 } else {
                     Style::default()
                 };
 
-                items.push(ListItem::new(Line::from(Span::styled(
-                    filename,
-                    style,
-                ))));
+                if let Some(place) = app.breadboard.find_place(place_id) {
+                    let mut spans = vec![Span::styled(disambiguated_name(place, &duplicates), style)];
+                    if current == Some(*place_id) {
+                        spans.push(Span::styled(s.current_connection, style.fg(Color::Gray)));
+                    }
+                    if app.connecting_from() == Some(*place_id) {
+                        spans.push(Span::styled(s.this_place, style.fg(Color::Yellow)));
+                    }
+                    items.push(ListItem::new(fitted(spans, width)));
+                }
+            }
+
+            // Places in the workspace's other boards
+            let offset = app.state.connection_search_results.len();
+            for (index, external_index) in app.state.connection_search_external.iter().enumerate() {
+                let Some(place) = app.state.external_places.get(*external_index) else {
+                    continue;
+                };
+                let style = if Some(offset + index) == app.state.selected_connection_result {
+                    Style::default().bg(Color::Blue).fg(Color::White)
+                } else {
+                    Style::default()
+                };
+                items.push(ListItem::new(fitted(vec![
+                    Span::styled(format!("{}: ", place.title), style.fg(Color::Magenta)),
+                    Span::styled(place.name.clone(), style),
+                ], width)));
+            }
+        }
+
+        let list = List::new(items)
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .title(s.connect_title));
+
+        // Keep the highlighted entry in view as the list pages
+        let mut state = ListState::default().with_selected(app.state.selected_connection_result);
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    fn render_file_selection(&self, frame: &mut Frame, app: &App, area: Rect) {
+        let s = app.config.language.strings();
+        let width = area.width.saturating_sub(2) as usize;
+        let mut items = Vec::new();
+
+        if app.state.file_list.is_empty() && app.is_scanning_files() {
+            items.push(ListItem::new(fitted(Span::styled(
+                s.looking_for_boards,
+                Style::default().fg(Color::Gray),
+            ), width)));
+        } else if app.state.file_list.is_empty() {
+            let message = match app.config.boards_dir() {
+                Some(dir) => locale::fill(s.no_files_in, &[&dir.display().to_string()]),
+                None => s.no_files.to_string(),
+            };
+            items.push(ListItem::new(fitted(Span::styled(
+                message,
+                Style::default().fg(Color::Gray),
+            ), width)));
+        } else {
+            let files = app.visible_files();
+            if files.is_empty() {
+                items.push(ListItem::new(fitted(Span::styled(
+                    locale::fill(s.no_files_match, &[&app.state.file_filter]),
+                    Style::default().fg(Color::Gray),
+                ), width)));
+            }
+            let name_width = files.iter().map(|file| app.file_label(file).width()).max().unwrap_or(0);
+            for (index, file) in files.into_iter().enumerate() {
+                let filename = app.file_label(file);
+                let is_selected = Some(index) == app.state.selected_file_index;
+                // Files that aren't boards stay listed, greyed out
+                let style = match (is_selected, file.error.is_some()) {
+                    (true, false) => Style::default().bg(Color::Blue).fg(Color::White),
+                    (true, true) => Style::default().bg(Color::Blue).fg(Color::Gray),
+                    (false, false) => Style::default(),
+                    (false, true) => Style::default().fg(Color::DarkGray),
+                };
+                let modified = file.modified
+                    .map(|time| time.format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_default();
+                let padding = " ".repeat(name_width - filename.width());
+
+                let mut spans = vec![
+                    Span::styled(format!("{}{}", filename, padding), style),
+                    Span::styled(format!("  {}", modified), style.fg(Color::Gray)),
+                ];
+                if let Some(name) = &file.name {
+                    spans.push(Span::styled(format!("  {}", name), style.add_modifier(Modifier::BOLD)));
+                }
+                if let Some(places) = file.places {
+                    spans.push(Span::styled(locale::fill(s.file_places, &[&places.to_string()]), style.fg(Color::Gray)));
+                }
+                if let Some(error) = &file.error {
+                    spans.push(Span::styled(format!("  {}", error.describe(s)), style.add_modifier(Modifier::ITALIC)));
+                }
+                items.push(ListItem::new(fitted(spans, width)));
+            }
+        }
+
+        let mut title = match app.state.file_sort {
+            FileSort::Name => s.files_by_name_title.to_string(),
+            FileSort::Modified => s.files_by_date_title.to_string(),
+        };
+        // A spinner while the directory is still being listed
+        if let Some(scan) = &app.state.file_scan {
+            title.push_str(&locale::fill(s.files_found, &[&scan.spinner().to_string(), &app.state.file_list.len().to_string()]));
+        }
+        let list = List::new(items)
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .title(title));
+
+        let mut state = ListState::default().with_selected(app.state.selected_file_index);
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    // Boards in the boards directory: name, size, age and file
+    fn render_library(&self, frame: &mut Frame, app: &App, area: Rect) {
+        let s = app.config.language.strings();
+        let width = area.width.saturating_sub(2) as usize;
+        let name_width = app.state.library.iter().map(|b| b.name.width()).max().unwrap_or(0);
+        let items: Vec<ListItem> = app.state.library.iter()
+            .enumerate()
+            .map(|(index, board)| {
+                let style = if Some(index) == app.state.selected_library_index {
+                    Style::default().bg(Color::Blue).fg(Color::White)
+                } else {
+                    Style::default()
+                };
+                let modified = board.modified
+                    .map(|time| time.format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_default();
+                let file = Path::new(&board.path).file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                let padding = " ".repeat(name_width - board.name.width());
+
+                ListItem::new(fitted(vec![
+                    Span::styled(format!("{}{}", board.name, padding), style.add_modifier(Modifier::BOLD)),
+                    Span::styled(locale::fill(s.library_places, &[&format!("{:>3}", board.places), &modified, &file]), style.fg(Color::Gray)),
+                ], width))
+            })
+            .collect();
+
+        let title = match (&app.workspace, app.config.boards_dir()) {
+            (Some(workspace), _) => locale::fill(s.workspace_title, &[&workspace.name]),
+            (None, Some(dir)) => locale::fill(s.boards_in_title, &[&dir.display().to_string()]),
+            (None, None) => s.boards_title.to_string(),
+        };
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(title));
+
+        let mut state = ListState::default().with_selected(app.state.selected_library_index);
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    fn render_incoming_list(&self, frame: &mut Frame, app: &App, area: Rect) {
+        let s = app.config.language.strings();
+        let width = area.width.saturating_sub(2) as usize;
+        let mut items = Vec::new();
+
+        for (index, (place_id, affordance_id)) in app.state.incoming_results.iter().enumerate() {
+            let is_selected = Some(index) == app.state.selected_incoming_result;
+            let style = if is_selected {
+                Style::default().bg(Color::Blue).fg(Color::White)
+            } else {
+                Style::default()
+            };
+
+            if let Some(place) = app.breadboard.find_place(place_id) {
+                let affordance_name = place.affordances.iter()
+                    .find(|a| &a.id == affordance_id)
+                    .map(|a| a.name.as_str())
+                    .unwrap_or("");
+                let mut spans = vec![
+                    Span::styled(format!("← {}", place.name), style),
+                    Span::styled(locale::fill(s.via, &[affordance_name]), style.fg(Color::Gray)),
+                ];
+                match app.state.incoming_steps.as_ref().and_then(|steps| steps.get(index)) {
+                    Some(steps) if *steps > 1 => spans.push(Span::styled(locale::fill(s.steps_away, &[&steps.to_string()]), style.fg(Color::DarkGray))),
+                    _ => {}
+                }
+                items.push(ListItem::new(fitted(spans, width)));
             }
         }
 
+        let place_name = app.get_selected_place().map(|p| p.name.as_str()).unwrap_or("");
+        let title = match app.state.incoming_steps {
+            Some(_) => locale::fill(s.reach_title, &[place_name]),
+            None => locale::fill(s.linking_title, &[place_name]),
+        };
+        let list = List::new(items)
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .title(title));
+
+        let mut state = ListState::default().with_selected(app.state.selected_incoming_result);
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    fn render_branch_list(&self, frame: &mut Frame, app: &App, area: Rect) {
+        let s = app.config.language.strings();
+        let width = area.width.saturating_sub(2) as usize;
+        let branches = app.get_selected_affordance().map(|a| a.branches.as_slice()).unwrap_or_default();
+        let mut items: Vec<ListItem> = branches.iter()
+            .enumerate()
+            .map(|(index, branch)| {
+                let style = if Some(index) == app.state.selected_branch {
+                    Style::default().bg(Color::Blue).fg(Color::White)
+                } else {
+                    Style::default()
+                };
+                let dest = branch.connects_to
+                    .map(|id| app.breadboard.find_place(&id).map_or("[Unknown]".to_string(), |p| p.name.clone()));
+                ListItem::new(fitted(vec![
+                    Span::styled(branch.label.clone(), style),
+                    match dest {
+                        Some(dest) => Span::styled(format!(" → {}", dest), style.fg(Color::Gray)),
+                        None => Span::styled(s.not_connected, style.fg(Color::DarkGray)),
+                    },
+                ], width))
+            })
+            .collect();
+        if items.is_empty() {
+            items.push(ListItem::new(fitted(Span::styled(
+                s.no_outcomes,
+                Style::default().fg(Color::Gray),
+            ), width)));
+        }
+
+        let title = locale::fill(
+            s.outcomes_title,
+            &[app.get_selected_affordance().map(|a| a.name.as_str()).unwrap_or("")],
+        );
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(title));
+
+        let mut state = ListState::default().with_selected(app.state.selected_branch);
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    // The list of cycles above the outline, with the selected cycle's places
+    // and affordances highlighted in the outline
+    fn render_cycles_view(&mut self, frame: &mut Frame, app: &App, area: Rect) {
+        let s = app.config.language.strings();
+        let width = area.width.saturating_sub(2) as usize;
+        let list_height = (app.state.cycles.len() as u16 + 2).min(area.height / 3).max(3);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(list_height), Constraint::Min(0)])
+            .split(area);
+
+        let items: Vec<ListItem> = app.state.cycles.iter()
+            .enumerate()
+            .map(|(index, cycle)| {
+                let style = if Some(index) == app.state.selected_cycle {
+                    Style::default().bg(Color::Blue).fg(Color::White)
+                } else {
+                    Style::default()
+                };
+                let names: Vec<&str> = cycle.places()
+                    .filter_map(|id| app.breadboard.find_place(&id))
+                    .map(|place| place.name.as_str())
+                    .collect();
+                // A cycle ends where it starts; a self-link gets a loop arrow
+                let path = match names.as_slice() {
+                    [only] => format!("{} ↺", only),
+                    _ => format!("{} → {}", names.join(" → "), names.first().unwrap_or(&"")),
+                };
+                let affordances: Vec<&str> = cycle.steps.iter()
+                    .filter_map(|(place_id, affordance_id)| {
+                        app.breadboard.find_place(place_id)?.affordances.iter().find(|a| &a.id == affordance_id)
+                    })
+                    .map(|affordance| affordance.name.as_str())
+                    .collect();
+
+                let mut spans = vec![
+                    Span::styled(path, style),
+                    Span::styled(locale::fill(s.via, &[&affordances.join(", ")]), style.fg(Color::Gray)),
+                ];
+                if cycle.is_back_and_forth() {
+                    spans.push(Span::styled(s.back_and_forth, style.fg(Color::DarkGray)));
+                }
+                ListItem::new(fitted(spans, width))
+            })
+            .collect();
+
+        let mut list_state = ListState::default();
+        list_state.select(app.state.selected_cycle);
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(s.cycles_title));
+        frame.render_stateful_widget(list, chunks[0], &mut list_state);
+
+        self.render_expanded_view(frame, app, chunks[1]);
+    }
+
+    fn render_command_list(&self, frame: &mut Frame, app: &App, area: Rect) {
+        let width = area.width.saturating_sub(2) as usize;
+        let items: Vec<ListItem> = app.config.commands.iter()
+            .enumerate()
+            .map(|(index, command)| {
+                let style = if Some(index) == app.state.selected_command {
+                    Style::default().bg(Color::Blue).fg(Color::White)
+                } else {
+                    Style::default()
+                };
+                ListItem::new(fitted(vec![
+                    Span::styled(command.name.clone(), style),
+                    Span::styled(format!("  {}", command.run), style.fg(Color::Gray)),
+                ], width))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .title(app.config.language.strings().commands_title));
+
+        let mut state = ListState::default().with_selected(app.state.selected_command);
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    fn render_snippet_list(&self, frame: &mut Frame, app: &App, area: Rect) {
+        let s = app.config.language.strings();
+        let width = area.width.saturating_sub(2) as usize;
+        let items: Vec<ListItem> = app.config.snippets.iter()
+            .enumerate()
+            .map(|(index, snippet)| {
+                let style = if Some(index) == app.state.selected_snippet {
+                    Style::default().bg(Color::Blue).fg(Color::White)
+                } else {
+                    Style::default()
+                };
+                ListItem::new(fitted(vec![
+                    Span::styled(snippet.name.clone(), style),
+                    Span::styled(format!("  {}", snippet.affordances.join(", ")), style.fg(Color::Gray)),
+                ], width))
+            })
+            .collect();
+
+        let title = match app.get_selected_place() {
+            Some(place) => locale::fill(s.snippets_for_title, &[&place.name]),
+            None => s.snippets_title.to_string(),
+        };
+        let list = List::new(items)
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .title(title));
+
+        let mut state = ListState::default().with_selected(app.state.selected_snippet);
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    fn render_spelling_list(&self, frame: &mut Frame, app: &App, area: Rect) {
+        let s = app.config.language.strings();
+        let width = area.width.saturating_sub(2) as usize;
+        let items: Vec<ListItem> = app.state.spelling_fixes.iter()
+            .enumerate()
+            .map(|(index, fix)| {
+                let style = if Some(index) == app.state.selected_fix {
+                    Style::default().bg(Color::Blue).fg(Color::White)
+                } else {
+                    Style::default()
+                };
+                ListItem::new(fitted(vec![
+                    Span::styled(fix.word.clone(), style.fg(Color::LightRed).add_modifier(Modifier::UNDERLINED)),
+                    Span::styled(" → ", style),
+                    Span::styled(fix.replacement.clone(), style),
+                    Span::styled(if fix.in_notes { s.in_the_notes } else { "" }, style.fg(Color::Gray)),
+                ], width))
+            })
+            .collect();
+
+        let name = match &app.state.selection {
+            Some(Selection::Affordance { .. }) => app.get_selected_affordance().map(|a| a.name.as_str()),
+            _ => app.get_selected_place().map(|p| p.name.as_str()),
+        };
+        let list = List::new(items)
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .title(locale::fill(s.typos_title, &[name.unwrap_or_default()])));
+
+        let mut state = ListState::default().with_selected(app.state.selected_fix);
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    fn render_trash_list(&self, frame: &mut Frame, app: &App, area: Rect) {
+        let s = app.config.language.strings();
+        let width = area.width.saturating_sub(2) as usize;
+        let items: Vec<ListItem> = app.trash_entries()
+            .enumerate()
+            .map(|(index, entry)| {
+                let style = if Some(index) == app.state.selected_trash {
+                    Style::default().bg(Color::Blue).fg(Color::White)
+                } else {
+                    Style::default()
+                };
+                ListItem::new(fitted(vec![
+                    Span::styled(entry.item.name().to_string(), style),
+                    Span::styled(format!("  {}", entry.item.describe(s)), style.fg(Color::Gray)),
+                    Span::styled(format!("  {}", entry.deleted_at.format("%H:%M")), style.fg(Color::DarkGray)),
+                ], width))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .title(locale::fill(app.config.language.strings().trash_title, &[&app.state.trash.len().to_string()])));
+
+        let mut state = ListState::default().with_selected(app.state.selected_trash);
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    // The jump search results in the top-right corner, leaving the board
+    // around them in view
+    fn render_view_list(&self, frame: &mut Frame, app: &App, area: Rect) {
+        let s = app.config.language.strings();
+        let width = area.width.saturating_sub(2) as usize;
+        let mut items: Vec<ListItem> = app.breadboard.views.iter()
+            .enumerate()
+            .map(|(index, view)| {
+                let style = if Some(index) == app.state.selected_view {
+                    Style::default().bg(Color::Blue).fg(Color::White)
+                } else {
+                    Style::default()
+                };
+                ListItem::new(fitted(vec![
+                    Span::styled(view.name.clone(), style),
+                    Span::styled(format!("  {}", view.describe(s)), style.fg(Color::Gray)),
+                ], width))
+            })
+            .collect();
+        if items.is_empty() {
+            items.push(ListItem::new(Span::styled(s.no_views, Style::default().fg(Color::DarkGray))));
+        }
+
+        let list = List::new(items)
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .title(locale::fill(s.views_title, &[&app.breadboard.views.len().to_string()])));
+
+        let mut state = ListState::default().with_selected(app.state.selected_view);
+        frame.render_stateful_widget(list, area, &mut state);
+    }
+
+    // Icons for the `:name` being typed in a name, under the status bar
+    fn render_icon_picker(&self, frame: &mut Frame, app: &App, area: Rect) {
+        let icons = app.icon_matches();
+        if icons.is_empty() {
+            return;
+        }
+        let height = (icons.len() as u16 + 2).min(area.height.saturating_sub(1) / 2).max(3.min(area.height));
+        let area = Rect::new(area.x + 1, area.y, ICON_PICKER_WIDTH.min(area.width.saturating_sub(1)), height);
+        let width = area.width.saturating_sub(2) as usize;
+        let items: Vec<ListItem> = icons.iter()
+            .enumerate()
+            .map(|(index, icon)| {
+                let style = if index == app.state.selected_icon {
+                    Style::default().bg(Color::Blue).fg(Color::White)
+                } else {
+                    Style::default()
+                };
+                // Emoji take two columns, so the names line up after either
+                let gap = " ".repeat(3usize.saturating_sub(icon.glyph.width()));
+                ListItem::new(fitted(Span::styled(format!("{}{}{}", icon.glyph, gap, icon.name), style), width))
+            })
+            .collect();
         let list = List::new(items)
             .block(Block::default()
                 .borders(Borders::ALL)
-                .title("Select file to open"));
+                .border_style(Style::default().fg(Color::Yellow))
+                .title(app.config.language.strings().icons_title));
+
+        let mut state = ListState::default().with_selected(Some(app.state.selected_icon));
+        frame.render_widget(Clear, area);
+        frame.render_stateful_widget(list, area, &mut state);
+    }
 
+    // The keys that can follow the leader sequence typed so far, in the
+    // bottom right corner
+    fn render_leader_popup(&self, frame: &mut Frame, app: &App, area: Rect) {
+        let Some(pending) = &app.state.leader else {
+            return;
+        };
+        let s = app.config.language.strings();
+        let key_width = pending.next.iter().map(|(key, _)| key.width()).max().unwrap_or(0);
+        let height = (pending.next.len().max(1) as u16 + 2).min(area.height);
+        let width = LEADER_POPUP_WIDTH.min(area.width);
+        let area = Rect::new(area.x + area.width - width, area.y + area.height - height, width, height);
+        let inner = area.width.saturating_sub(2) as usize;
+        let items: Vec<ListItem> = if pending.next.is_empty() {
+            vec![ListItem::new(fitted(Span::styled(s.nothing_bound_yet, Style::default().fg(Color::Gray)), inner))]
+        } else {
+            pending.next.iter()
+                .map(|(key, what)| {
+                    let style = if what.starts_with('+') { Style::default().fg(Color::Magenta) } else { Style::default() };
+                    ListItem::new(fitted(Line::from(vec![
+                        Span::styled(format!("{:<width$}  ", key, width = key_width), Style::default().fg(Color::Yellow)),
+                        Span::styled(what.as_str(), style),
+                    ]), inner))
+                })
+                .collect()
+        };
+        let list = List::new(items)
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow))
+                .title(locale::fill(s.leader_title, &[&pending.typed])));
+        frame.render_widget(Clear, area);
         frame.render_widget(list, area);
     }
 
-    fn render_place_search<B: Backend>(&self, frame: &mut Frame, app: &App, area: Rect) {
+    fn render_place_search(&self, frame: &mut Frame, app: &App, area: Rect) {
+        let s = app.config.language.strings();
+        let panel_width = SEARCH_PANEL_WIDTH.min(area.width);
+        let rows = app.state.place_search_results.len().max(1) as u16;
+        let height = (rows + 2).min(area.height.saturating_sub(1) / 2).max(3.min(area.height));
+        let area = Rect::new(area.x + area.width - panel_width, area.y + 1, panel_width, height);
+        let width = area.width.saturating_sub(2) as usize;
         let mut items = Vec::new();
 
         if app.state.place_search_results.is_empty() {
-            items.push(ListItem::new(Line::from(Span::styled(
-                "No places found",
+            items.push(ListItem::new(fitted(Span::styled(
+                s.no_places_found,
                 Style::default().fg(Color::Gray),
-            ))));
+            ), width)));
         } else {
+            let duplicates = duplicate_places(&app.breadboard);
             for (index, place_id) in app.state.place_search_results.iter().enumerate() {
                 let is_selected = Some(index) == app.state.selected_place_result;
                 let style = if is_selected {
@@ -463,20 +1663,1088 @@ impl UI {
                 };
 
                 if let Some(place) = app.breadboard.find_place(place_id) {
-                    items.push(ListItem::new(Line::from(Span::styled(
-                        &place.name,
+                    items.push(ListItem::new(fitted(Span::styled(
+                        disambiguated_name(place, &duplicates),
                         style,
-                    ))));
+                    ), width)));
                 }
             }
         }
 
-        let title = format!("Jump to place: {}", app.state.place_search_buffer);
+        let title = locale::fill(s.jump_title, &[&app.state.place_search_buffer]);
         let list = List::new(items)
             .block(Block::default()
                 .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Green))
                 .title(title));
 
-        frame.render_widget(list, area);
+        let mut state = ListState::default().with_selected(app.state.selected_place_result);
+        frame.render_widget(Clear, area);
+        frame.render_stateful_widget(list, area, &mut state);
     }
-}
\ No newline at end of file
+}
+
+// One entry of the expanded or collapsed outline, before wrapping
+struct OutlineRow {
+    text: String,
+    continuation: &'static str, // Prefix for wrapped lines
+    item: Option<Selection>, // None for spacing rows
+    outcomes: Vec<String>, // Lines for an affordance's outcomes, shown under it
+    links: Vec<String>, // Places linking here, listed after the text
+}
+
+// Less room than this after a header and its list of linking places goes
+// on a line of its own
+const MIN_LINKS_ROOM: usize = 12;
+
+impl OutlineRow {
+    // The row wrapped to `width`, then its outcome lines wrapped the same way.
+    // The list of places linking here doesn't wrap: it's cut short with an
+    // ellipsis, so a well-connected place still takes one or two lines.
+    fn lines(&self, width: usize) -> Vec<String> {
+        let mut lines = wrapped_lines(&self.text, self.continuation, width);
+        if !self.links.is_empty() {
+            let list = self.links.join(", ");
+            let last = lines.last().map_or(0, |line| line.width());
+            let room = width.saturating_sub(last + " (← )".width());
+            if width == 0 || list.width() <= room || room >= MIN_LINKS_ROOM {
+                let list = if width == 0 { list } else { ellipsized(&list, room) };
+                if let Some(line) = lines.last_mut() {
+                    line.push_str(&format!(" (← {})", list));
+                }
+            } else {
+                let room = width.saturating_sub(self.continuation.width() + "(← )".width());
+                lines.push(format!("{}(← {})", self.continuation, ellipsized(&list, room)));
+            }
+        }
+        for outcome in &self.outcomes {
+            lines.extend(wrapped_lines(outcome, "│    ", width));
+        }
+        lines
+    }
+}
+
+// Names of the places linking into each place
+fn incoming_sources(app: &App) -> HashMap<u32, Vec<String>> {
+    let mut incoming: HashMap<u32, Vec<String>> = HashMap::new();
+    for place in &app.breadboard.places {
+        for affordance in &place.affordances {
+            for dest_id in affordance.destinations() {
+                incoming.entry(dest_id)
+                    .or_default()
+                    .push(place.name.clone());
+            }
+        }
+    }
+    incoming
+}
+
+// Planned affordances carry no mark, so untracked boards look as before
+fn status_mark(status: Status) -> &'static str {
+    match status {
+        Status::Planned => "",
+        Status::Building => "◐ ",
+        Status::Built => "✓ ",
+    }
+}
+
+// One line per place, then one per group and a total: a bar of built (█)
+// and in-progress (▒) affordances, then the built count, any cut ones and
+// the estimated points once anything on the board is estimated
+pub fn progress_lines(breadboard: &Breadboard, s: &Strings) -> Vec<String> {
+    let (places, total) = progress(breadboard);
+    let mut rows: Vec<(String, Progress)> = places.into_iter()
+        .filter_map(|(id, progress)| breadboard.find_place(&id).map(|place| (place.name.clone(), progress)))
+        .collect();
+    rows.extend(group_progress(breadboard).into_iter().map(|(group, progress)| (format!("▸ {}", group), progress)));
+    rows.push((s.progress_total.to_string(), total));
+    let estimated = breadboard.places.iter().flat_map(|p| &p.affordances).any(|a| a.estimate.is_some());
+
+    let name_width = rows.iter().map(|(name, _)| name.width()).max().unwrap_or(0).min(PROGRESS_NAME_WIDTH);
+    rows.iter()
+        .map(|(name, progress)| {
+            let mut line = format!(
+                "{} {} {}/{}",
+                fit_to_width(name, name_width),
+                progress_bar(progress, PROGRESS_BAR_WIDTH),
+                progress.built,
+                progress.total(),
+            );
+            if progress.cut > 0 {
+                line.push_str(&locale::fill(s.progress_cut, &[&progress.cut.to_string()]));
+            }
+            if estimated {
+                line.push_str(&format!(" {}", points_label(progress.points, s)));
+            }
+            line
+        })
+        .collect()
+}
+
+// "~4.5 pts", to a tenth of a point
+pub fn points_label(points: f64, s: &Strings) -> String {
+    let points = (points * 10.0).round() / 10.0;
+    locale::fill(if points == 1.0 { s.point } else { s.points }, &[&points.to_string()])
+}
+
+fn progress_bar(progress: &Progress, width: usize) -> String {
+    let total = progress.total();
+    if total == 0 {
+        return "·".repeat(width);
+    }
+    let built = progress.built * width / total;
+    let started = (progress.built + progress.building) * width / total;
+    format!("{}{}{}", "█".repeat(built), "▒".repeat(started - built), "░".repeat(width - started))
+}
+
+// Text padded to exactly `width` columns, or cut short with an ellipsis
+fn fit_to_width(text: &str, width: usize) -> String {
+    let fitted = ellipsized(text, width);
+    format!("{}{}", fitted, " ".repeat(width.saturating_sub(fitted.width())))
+}
+
+// Text cut short with an ellipsis if it's wider than `width` columns
+fn ellipsized(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+    let mut fitted = String::new();
+    for c in text.chars() {
+        if fitted.width() + c.width().unwrap_or(0) + 1 > width {
+            break;
+        }
+        fitted.push(c);
+    }
+    fitted.push('…');
+    fitted
+}
+
+// One row of a list or bar, cut short with an ellipsis where it would run
+// past `width` columns; Alt+Z shows the selection's full text
+fn fitted<'a>(line: impl Into<Line<'a>>, width: usize) -> Line<'a> {
+    let line = line.into();
+    if line.width() <= width {
+        return line;
+    }
+    let mut spans = Vec::new();
+    let mut used = 0;
+    for span in line.spans {
+        let span_width = span.content.width();
+        if used + span_width < width {
+            used += span_width;
+            spans.push(span);
+            continue;
+        }
+        // Ending the line here, so the ellipsis goes in even if this span fits
+        spans.push(Span::styled(ellipsized(&format!("{}…", span.content), width - used), span.style));
+        break;
+    }
+    Line::from(spans).style(line.style)
+}
+
+// A prompt row whose second span is the text being typed. When that runs
+// past `width` columns its start scrolls out of sight behind `…`, keeping
+// the end being typed in view, and the hint after it is cut short instead.
+fn fitted_input<'a>(mut spans: Vec<Span<'a>>, width: usize) -> Line<'a> {
+    if spans.len() >= 2 {
+        let room = width.saturating_sub(spans[0].width() + 1);
+        if spans[1].width() > room {
+            spans[1] = Span::styled(tail_ellipsized(&spans[1].content, room), spans[1].style);
+        }
+    }
+    fitted(spans, width)
+}
+
+// Text with its start cut off behind an ellipsis if it's wider than `width`
+// columns
+fn tail_ellipsized(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+    if width == 0 {
+        return String::new();
+    }
+    let mut tail = Vec::new();
+    let mut used = 1;
+    for c in text.chars().rev() {
+        let char_width = c.width().unwrap_or(0);
+        if used + char_width > width {
+            break;
+        }
+        used += char_width;
+        tail.push(c);
+    }
+    std::iter::once('…').chain(tail.into_iter().rev()).collect()
+}
+
+fn is_cut(app: &App, place_id: u32, affordance_id: u32) -> bool {
+    app.breadboard.find_place(&place_id)
+        .and_then(|p| p.affordances.iter().find(|a| a.id == affordance_id))
+        .is_some_and(|a| a.cut)
+}
+
+// Place title with a warning badge when another place has the same name,
+// ⊘ when it goes against one of the board's rules, and a flag when it's a
+// rabbit hole
+fn badged_name(place: &Place, duplicates: &HashMap<u32, usize>, breaks: &[(Selection, String)]) -> String {
+    let mut name = place.title();
+    if duplicates.contains_key(&place.id) {
+        name.push_str(" ⚠");
+    }
+    if breaks_rule(breaks, &Selection::Place(place.id)) {
+        name.push_str(" ⊘");
+    }
+    if place.rabbit_hole {
+        name.push_str(" ⚑");
+    }
+    name
+}
+
+fn breaks_rule(breaks: &[(Selection, String)], item: &Selection) -> bool {
+    breaks.iter().any(|(broken, _)| broken == item)
+}
+
+// Same-named places told apart by their group, or by position when ungrouped
+fn disambiguated_name(place: &Place, duplicates: &HashMap<u32, usize>) -> String {
+    match (duplicates.get(&place.id), &place.group) {
+        (Some(_), Some(group)) => format!("{} ({})", place.name, group),
+        (Some(index), None) => format!("{} #{}", place.name, index),
+        (None, _) => place.name.clone(),
+    }
+}
+
+fn expanded_rows(app: &App, visible_places: &[(&Place, Vec<&Affordance>)]) -> Vec<OutlineRow> {
+    let mut rows = Vec::new();
+    let incoming = incoming_sources(app);
+    let duplicates = duplicate_places(&app.breadboard);
+    let breaks = app.rule_breaks();
+
+    for (place_index, (place, affordances)) in visible_places.iter().enumerate() {
+        // Place header with incoming connections indicator
+        let mut name = badged_name(place, &duplicates, &breaks);
+        if app.state.folded.contains(&place.id) {
+            name.push_str(&format!(" ▸ {}", place.affordances.len())); // Folded away, with how many
+        }
+        let corner = if place.is_external() { "┌╌" } else { "┌─" };
+        rows.push(OutlineRow {
+            text: format!("{} {}", corner, name),
+            continuation: "   ",
+            item: Some(Selection::Place(place.id)),
+            outcomes: Vec::new(),
+            links: incoming.get(&place.id).cloned().unwrap_or_default(),
+        });
+
+        for affordance in affordances {
+            let mut name = format!("{}{}", status_mark(affordance.status), affordance.name);
+            if let Some(estimate) = affordance.estimate {
+                name.push_str(&format!(" ~{}", estimate));
+            }
+            if affordance.rabbit_hole {
+                name.push_str(" ⚑");
+            }
+            if breaks_rule(&breaks, &Selection::Affordance { place_id: place.id, affordance_id: affordance.id }) {
+                name.push_str(" ⊘");
+            }
+            let text = if let Some(dest_id) = &affordance.connects_to {
+                if let Some(dest_place) = app.breadboard.find_place(dest_id) {
+                    format!("├─ {} → {}", name, dest_place.name)
+                } else {
+                    format!("├─ {} → [Unknown]", name)
+                }
+            } else if let Some(link) = &affordance.link {
+                format!("├─ {} ⇒ {}", name, app.link_label(link))
+            } else {
+                format!("├─ {}", name)
+            };
+            let outcomes = affordance.branches.iter()
+                .enumerate()
+                .map(|(index, branch)| {
+                    let fork = if index + 1 == affordance.branches.len() { "└" } else { "├" };
+                    match branch.connects_to {
+                        Some(dest_id) => {
+                            let dest = app.breadboard.find_place(&dest_id).map_or("[Unknown]", |p| p.name.as_str());
+                            format!("│  {} {} → {}", fork, branch.label, dest)
+                        }
+                        None => format!("│  {} {}", fork, branch.label),
+                    }
+                })
+                .collect();
+            rows.push(OutlineRow {
+                text,
+                continuation: "│  ",
+                item: Some(Selection::Affordance { place_id: place.id, affordance_id: affordance.id }),
+                outcomes,
+                links: Vec::new(),
+            });
+        }
+
+        // Add spacing between places
+        if place_index < visible_places.len() - 1 {
+            rows.push(OutlineRow { text: String::new(), continuation: "", item: None, outcomes: Vec::new(), links: Vec::new() });
+        }
+    }
+
+    rows
+}
+
+// Names longer than this push their badges out of line rather than pushing
+// every row's badges across
+const MAX_BADGE_COLUMN: usize = 32;
+
+// One row per place: its name, then badges lined up in columns counting its
+// affordances, the places linking to it and the places (or other boards'
+// places) it leads to, and ✎ if it has notes
+fn collapsed_rows(app: &App, visible_places: &[(&Place, Vec<&Affordance>)]) -> Vec<OutlineRow> {
+    let incoming = incoming_sources(app);
+    let duplicates = duplicate_places(&app.breadboard);
+    let breaks = app.rule_breaks();
+
+    let counts: Vec<(String, [usize; 3], bool)> = visible_places.iter()
+        .map(|(place, _)| {
+            let mut sources = incoming.get(&place.id).cloned().unwrap_or_default();
+            sources.sort();
+            sources.dedup();
+            let mut destinations: Vec<String> = place.affordances.iter()
+                .flat_map(|a| {
+                    let places = a.destinations().into_iter().map(|id| id.to_string());
+                    let link = a.link.as_ref().filter(|_| a.connects_to.is_none()).map(|link| app.link_label(link));
+                    places.chain(link).collect::<Vec<_>>()
+                })
+                .collect();
+            destinations.sort();
+            destinations.dedup();
+            let has_notes = place.notes.as_deref().is_some_and(|notes| !notes.trim().is_empty());
+            (badged_name(place, &duplicates, &breaks), [place.affordances.len(), sources.len(), destinations.len()], has_notes)
+        })
+        .collect();
+
+    let name_width = counts.iter().map(|(name, ..)| name.width()).max().unwrap_or(0).min(MAX_BADGE_COLUMN);
+    let count_width = |column: usize| counts.iter().map(|(_, c, _)| c[column].to_string().len()).max().unwrap_or(1);
+    let widths = [count_width(0), count_width(1), count_width(2)];
+
+    visible_places.iter().zip(counts)
+        .map(|((place, _), (name, [affordances, sources, destinations], has_notes))| {
+            let padding = " ".repeat(name_width.saturating_sub(name.width()));
+            let mut text = format!(
+                "{}{}  ≡{:<w0$}  ←{:<w1$}  →{:<w2$}",
+                name, padding, affordances, sources, destinations,
+                w0 = widths[0], w1 = widths[1], w2 = widths[2],
+            );
+            if has_notes {
+                text.push_str("  ✎");
+            }
+            OutlineRow { text: text.trim_end().to_string(), continuation: "  ", item: Some(Selection::Place(place.id)), outcomes: Vec::new(), links: Vec::new() }
+        })
+        .collect()
+}
+
+// The selected place's header row, when the outline has scrolled past it
+// to `offset` while the selection is still among its affordances
+fn sticky_header<'a>(app: &App, rows: &'a [OutlineRow], offset: usize) -> Option<&'a OutlineRow> {
+    let Some(Selection::Affordance { place_id, .. }) = &app.state.selection else {
+        return None;
+    };
+    let place = Selection::Place(*place_id);
+    rows[..offset.min(rows.len())].iter().find(|row| row.item.as_ref() == Some(&place))
+}
+
+fn outline_item(app: &App, row: &OutlineRow, width: usize) -> ListItem<'static> {
+    let mut style = match &row.item {
+        None => return ListItem::new(""),
+        Some(item) if app.state.selection.as_ref() == Some(item) => Style::default().bg(Color::Blue).fg(Color::Black),
+        Some(item) if app.search_match(item) == Some(true) => SEARCH_CURRENT,
+        Some(item) if app.search_match(item).is_some() => SEARCH_MATCH,
+        Some(item) if app.state.marked.contains(item) => Style::default().fg(Color::Black).bg(Color::Magenta),
+        Some(item) if app.in_selected_cycle(item) => Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+        Some(item) if app.on_happy_path(item) => Style::default().fg(Color::LightGreen).add_modifier(Modifier::BOLD),
+        Some(Selection::Place(_)) => Style::default().fg(Color::Cyan),
+        Some(Selection::Affordance { .. }) => Style::default().fg(Color::White),
+    };
+    if let Some(Selection::Affordance { place_id, affordance_id }) = &row.item {
+        if is_cut(app, *place_id, *affordance_id) {
+            style = style.add_modifier(Modifier::CROSSED_OUT);
+            if app.state.selection.as_ref() != row.item.as_ref() {
+                style = style.fg(Color::DarkGray);
+            }
+        }
+    }
+    let names = match &row.item {
+        Some(Selection::Place(id)) => app.breadboard.find_place(id).map(|p| p.name.as_str()).into_iter().collect(),
+        Some(Selection::Affordance { place_id, affordance_id }) => {
+            let affordance = app.breadboard.find_place(place_id)
+                .and_then(|p| p.affordances.iter().find(|a| a.id == *affordance_id));
+            let destination = affordance.and_then(|a| a.connects_to).and_then(|id| app.breadboard.find_place(&id));
+            affordance.map(|a| a.name.as_str()).into_iter().chain(destination.map(|p| p.name.as_str())).collect()
+        }
+        None => Vec::new(),
+    };
+    let annotations: Vec<&str> = names.into_iter().filter_map(annotation).collect();
+    let lines = row.lines(width);
+    let dimmed = annotation_ranges(&lines, row.continuation, &annotations);
+    let typos: Vec<Vec<Range<usize>>> = match &app.dictionary {
+        Some(dictionary) => lines.iter().map(|line| dictionary.typos(line, &app.breadboard.glossary)).collect(),
+        None => Vec::new(),
+    };
+    glossary_item(lines, style, &app.breadboard.glossary, &dimmed, &typos)
+}
+
+// Where `annotations` show in a row's wrapped `lines`, as byte ranges for
+// each line. One the wrap cut in two carries on after the next line's
+// `continuation` prefix.
+fn annotation_ranges(lines: &[String], continuation: &str, annotations: &[&str]) -> Vec<Vec<Range<usize>>> {
+    let mut ranges = vec![Vec::new(); lines.len()];
+    let mut wrapped: Option<&str> = None; // What's left of a cut annotation
+    for (index, line) in lines.iter().enumerate() {
+        let mut from = 0;
+        if let Some(rest) = wrapped.take() {
+            let start = if line.starts_with(continuation) { continuation.len() } else { 0 };
+            let text = &line[start..];
+            if text.starts_with(rest) {
+                ranges[index].push(start..start + rest.len());
+                from = start + rest.len();
+            } else if let Some(left) = rest.strip_prefix(text) {
+                ranges[index].push(start..line.len());
+                wrapped = Some(left.trim_start());
+                continue;
+            }
+        }
+        for (at, _) in line[from..].match_indices('[').map(|(at, bracket)| (at + from, bracket)) {
+            let text = &line[at..];
+            if let Some(annotation) = annotations.iter().find(|a| text.starts_with(**a)) {
+                ranges[index].push(at..at + annotation.len());
+            } else if let Some(annotation) = annotations.iter().find(|a| a.starts_with(text)) {
+                ranges[index].push(at..line.len());
+                wrapped = Some(annotation[text.len()..].trim_start());
+                break;
+            }
+        }
+    }
+    ranges
+}
+
+// The one-paragraph introduction on the start screen and the empty board
+const BREADCRUMB_SEPARATOR: &str = " ▸ ";
+const STATUS_TEXT_AT: (u16, u16) = (1, 1); // Column and row where the status bar's text starts
+
+// The breadcrumb as the status bar draws it, the selection's own segment in bold
+fn breadcrumb_spans(crumbs: &[Crumb]) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    for (index, crumb) in crumbs.iter().enumerate() {
+        if index > 0 {
+            spans.push(Span::styled(BREADCRUMB_SEPARATOR, Style::default().fg(Color::DarkGray)));
+        }
+        let style = Style::default().fg(Color::Yellow);
+        let style = if index + 1 == crumbs.len() { style.add_modifier(Modifier::BOLD) } else { style };
+        spans.push(Span::styled(crumb.label.clone(), style));
+    }
+    spans
+}
+
+// The breadcrumb segment drawn at a screen position, if the status bar is
+// showing the breadcrumb there
+pub fn breadcrumb_at(app: &App, column: u16, row: u16) -> Option<Crumb> {
+    let shows_breadcrumb = app.state.mode == Mode::Navigate
+        && !app.state.is_searching_places
+        && app.state.status_message.is_none()
+        && app.config.status_bar.is_none();
+    if !shows_breadcrumb || row != STATUS_TEXT_AT.1 {
+        return None;
+    }
+    let mut start = STATUS_TEXT_AT.0 as usize;
+    for crumb in app.breadcrumb() {
+        let end = start + crumb.label.width();
+        if (start..end).contains(&(column as usize)) {
+            return Some(crumb);
+        }
+        start = end + BREADCRUMB_SEPARATOR.width();
+    }
+    None
+}
+
+// The config's status bar template filled in. Tokens are {board}, {file},
+// {dirty} ("*" with unsaved edits), {places}, {selection} (place › affordance),
+// {breadcrumb} (board ▸ group ▸ place ▸ affordance), {filter} and {notes} (first line of the selection's notes); ones the
+// selection or filter don't give are left empty.
+pub fn status_template(app: &App, template: &str) -> String {
+    let tokens = [
+        ("{board}", app.breadboard.name.clone()),
+        ("{file}", app.state.current_filename.clone().unwrap_or_default()),
+        ("{dirty}", if app.is_dirty() { "*".to_string() } else { String::new() }),
+        ("{places}", app.breadboard.places.len().to_string()),
+        ("{selection}", app.selection_path().unwrap_or_default()),
+        ("{breadcrumb}", app.breadcrumb().iter().map(|c| c.label.as_str()).collect::<Vec<_>>().join(BREADCRUMB_SEPARATOR)),
+        ("{filter}", app.state.filter.clone().unwrap_or_default()),
+        ("{notes}", app.selected_notes().and_then(|n| n.lines().next()).unwrap_or_default().to_string()),
+    ];
+    tokens.iter().fold(template.to_string(), |text, (token, value)| text.replace(token, value))
+}
+
+// Everything about the selection that the views may have cut short: the
+// whole name, the places linking to it or where it leads, and its notes.
+// `status` is the last status message, in case that was cut short too.
+pub fn full_text(app: &App, status: Option<&str>) -> String {
+    let s = app.config.language.strings();
+    let mut text = Vec::new();
+    if let Some(status) = status {
+        text.push(status.to_string());
+        text.push(String::new());
+    }
+    let name = |id: &u32| app.breadboard.find_place(id).map_or(format!("[{}]", s.unknown), |p| p.name.clone());
+    match &app.state.selection {
+        Some(Selection::Place(place_id)) => {
+            if let Some(place) = app.breadboard.find_place(place_id) {
+                text.push(place.title());
+                if let Some(group) = &place.group {
+                    text.push(locale::fill(s.group_line, &[group]));
+                }
+                let mut sources: Vec<String> = incoming_sources(app).remove(place_id).unwrap_or_default();
+                sources.dedup();
+                if !sources.is_empty() {
+                    text.push(locale::fill(s.linked_from_line, &[&sources.join(", ")]));
+                }
+            }
+        }
+        Some(Selection::Affordance { place_id, .. }) => {
+            if let Some(affordance) = app.get_selected_affordance() {
+                text.push(format!("{}: {}", name(place_id), affordance.name));
+                if let Some(dest) = &affordance.connects_to {
+                    text.push(locale::fill(s.leads_to_line, &[&name(dest)]));
+                } else if let Some(link) = &affordance.link {
+                    text.push(locale::fill(s.leads_to_line, &[&app.link_label(link)]));
+                }
+                for branch in &affordance.branches {
+                    let dest = branch.connects_to.as_ref().map_or(s.nowhere_yet.to_string(), name);
+                    text.push(locale::fill(s.outcome_line, &[&branch.label, &dest]));
+                }
+                if let Some(estimate) = affordance.estimate {
+                    text.push(locale::fill(s.estimate_line, &[&estimate.to_string(), &points_label(estimate.points(), s)]));
+                }
+            }
+        }
+        None => {}
+    }
+    if let Some(selection) = &app.state.selection {
+        text.extend(app.rule_breaks().into_iter().filter(|(item, _)| item == selection).map(|(_, message)| message));
+    }
+    if let Some(notes) = app.selected_notes() {
+        text.push(String::new());
+        text.push(notes.trim_end().to_string());
+    }
+    if let Some(attribution) = app.selected_attribution() {
+        text.extend(attribution.describe(s));
+    }
+    if let Some(Selection::Place(_)) = &app.state.selection {
+        let attachments = app.get_selected_place().map_or(&[][..], |p| &p.attachments);
+        if !attachments.is_empty() {
+            text.push(locale::fill(s.attachments_line, &[&attachments.join(", ")]));
+        }
+    }
+    let comments = app.selected_comments();
+    if !comments.is_empty() {
+        text.push(String::new());
+        text.push(locale::fill(s.comments_line, &[&comments.len().to_string()]));
+        text.extend(comments.iter().map(|comment| format!("- {}", comment.describe(s))));
+    }
+    text.join("\n")
+}
+
+// Plain-text copy of the current view as rendered: the outline wrapped to
+// `width` (0 for no wrapping), or the whole graph diagram at the current
+// zoom, with filters, export scopes and collapsing applied.
+pub fn view_snapshot(app: &App, width: usize) -> String {
+    // Cut affordances stay out of exports unless the config keeps them
+    let has_cut = app.breadboard.places.iter().any(|p| p.affordances.iter().any(|a| a.cut));
+    if has_cut && !app.config.export_cut {
+        return view_snapshot(&app.without_cut(), width);
+    }
+    let s = app.config.language.strings();
+
+    let visible = app.visible_places();
+
+    let (title, lines) = match app.state.view {
+        ViewMode::Graph => {
+            let board = app.visible_board();
+            let diagram = render_diagram(&board, &graph_layout(app, &board), app.state.graph_zoom);
+            (board_title(app, graph_title(app)), diagram.lines())
+        }
+        ViewMode::Outline => {
+            let outline_rows = |places: &[(&Place, Vec<&Affordance>)]| if app.state.collapsed {
+                collapsed_rows(app, places)
+            } else {
+                expanded_rows(app, places)
+            };
+            let title = if app.state.collapsed {
+                board_title(app, "Breadboard (Collapsed)")
+            } else {
+                board_title(app, "Breadboard")
+            };
+
+            // External systems are grouped after the places we build
+            let (external, own): (Vec<_>, Vec<_>) = visible.into_iter().partition(|(place, _)| place.is_external());
+            let mut rows = outline_rows(&own);
+            if !external.is_empty() {
+                let blank = || OutlineRow { text: String::new(), continuation: "", item: None, outcomes: Vec::new(), links: Vec::new() };
+                if !own.is_empty() {
+                    rows.push(blank());
+                }
+                rows.push(OutlineRow { text: s.external_systems.to_string(), continuation: "", item: None, outcomes: Vec::new(), links: Vec::new() });
+                if !app.state.collapsed {
+                    rows.push(blank());
+                }
+                rows.extend(outline_rows(&external));
+            }
+
+            let lines = rows.iter()
+                .flat_map(|row| row.lines(width))
+                .collect();
+            (title, lines)
+        }
+    };
+
+    let mut text = format!("{}\n\n", title);
+    for line in lines {
+        text.push_str(line.trim_end());
+        text.push('\n');
+    }
+    if app.state.collapsed && app.state.view == ViewMode::Outline {
+        text.push_str(&format!("\n{}\n", s.collapsed_legend));
+    }
+    text
+}
+
+// The graph view's layout, split into lanes by group when swimlanes are on
+fn graph_layout(app: &App, board: &Breadboard) -> GraphLayout {
+    if app.state.swimlanes {
+        swimlane_layout(board)
+    } else {
+        layered_layout(board)
+    }
+}
+
+fn graph_title(app: &App) -> &'static str {
+    if app.state.swimlanes {
+        "Breadboard (Swimlanes)"
+    } else {
+        "Breadboard (Graph)"
+    }
+}
+
+fn board_title(app: &App, base: &str) -> String {
+    match app.state.filter.as_deref() {
+        Some(query) if !query.is_empty() => format!("{} (Filtered: {})", base, query),
+        _ => base.to_string(),
+    }
+}
+
+fn no_matches_item(app: &App) -> ListItem<'static> {
+    ListItem::new(Line::from(Span::styled(
+        app.config.language.strings().no_matches,
+        Style::default().fg(Color::Gray),
+    )))
+}
+
+// Build a list item from already wrapped lines (see `wrapped_lines`).
+// Like `styled_item`, with glossary terms underlined and the byte ranges in
+// `dimmed`, one list for each line, dimmed as annotations. Those in `typos`
+// get a red underline.
+fn glossary_item(
+    lines: Vec<String>,
+    style: Style,
+    glossary: &BTreeMap<String, String>,
+    dimmed: &[Vec<Range<usize>>],
+    typos: &[Vec<Range<usize>>],
+) -> ListItem<'static> {
+    if glossary.is_empty() && dimmed.iter().chain(typos).all(Vec::is_empty) {
+        return styled_item(lines, style);
+    }
+    let lines: Vec<Line> = lines.into_iter()
+        .enumerate()
+        .map(|(index, line)| {
+            let terms = find_terms(&line, glossary);
+            let dim = dimmed.get(index).map_or(&[][..], Vec::as_slice);
+            let typos = typos.get(index).map_or(&[][..], Vec::as_slice);
+            let mut cuts: Vec<usize> = terms.iter().chain(dim).chain(typos)
+                .flat_map(|range| [range.start, range.end])
+                .chain([0, line.len()])
+                .collect();
+            cuts.sort_unstable();
+            cuts.dedup();
+            let spans: Vec<Span> = cuts.windows(2)
+                .map(|cut| {
+                    let within = |ranges: &[Range<usize>]| ranges.iter().any(|r| r.start <= cut[0] && cut[1] <= r.end);
+                    let mut span_style = style;
+                    if within(&terms) {
+                        span_style = span_style.add_modifier(Modifier::UNDERLINED);
+                    }
+                    if within(dim) {
+                        span_style = span_style.add_modifier(Modifier::DIM | Modifier::ITALIC);
+                    }
+                    if within(typos) {
+                        span_style = span_style.add_modifier(Modifier::UNDERLINED).underline_color(Color::Red);
+                    }
+                    Span::styled(line[cut[0]..cut[1]].to_string(), span_style)
+                })
+                .collect();
+            Line::from(spans)
+        })
+        .collect();
+    ListItem::new(lines)
+}
+
+// Because the whole item shares one style, the selection highlight covers
+// every line.
+fn styled_item(lines: Vec<String>, style: Style) -> ListItem<'static> {
+    let lines: Vec<Line> = lines.into_iter()
+        .map(|line| Line::from(Span::styled(line, style)))
+        .collect();
+    ListItem::new(lines)
+}
+
+// Text word-wrapped to `width`. The first line keeps the text as-is;
+// continuation lines get the `continuation` prefix so wrapped names stay
+// visually attached to their tree branch.
+fn wrapped_lines(text: &str, continuation: &str, width: usize) -> Vec<String> {
+    wrap_text(text, width, continuation.width()).into_iter()
+        .enumerate()
+        .map(|(index, line)| if index == 0 { line } else { format!("{}{}", continuation, line) })
+        .collect()
+}
+
+// Greedy word wrap measured in terminal columns. Continuation lines are
+// `indent` columns narrower to leave room for their prefix. Words longer than
+// a full line are broken mid-word so nothing runs off the edge.
+pub fn wrap_text(text: &str, width: usize, indent: usize) -> Vec<String> {
+    if width == 0 || text.width() <= width {
+        return vec![text.to_string()];
+    }
+
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    let mut current_width = 0;
+
+    for word in text.split(' ') {
+        let line_width = if lines.is_empty() { width } else { width.saturating_sub(indent).max(1) };
+        let word_width = word.width();
+        let needed = if current.is_empty() { word_width } else { current_width + 1 + word_width };
+
+        if needed <= line_width {
+            if !current.is_empty() {
+                current.push(' ');
+                current_width += 1;
+            }
+            current.push_str(word);
+            current_width += word_width;
+            continue;
+        }
+
+        if !current.is_empty() {
+            lines.push(std::mem::take(&mut current));
+            current_width = 0;
+        }
+
+        // Break words that don't fit on a line of their own
+        for c in word.chars() {
+            let line_width = if lines.is_empty() { width } else { width.saturating_sub(indent).max(1) };
+            let char_width = c.width().unwrap_or(0);
+            if current_width + char_width > line_width && !current.is_empty() {
+                lines.push(std::mem::take(&mut current));
+                current_width = 0;
+            }
+            current.push(c);
+            current_width += char_width;
+        }
+    }
+
+    if !current.is_empty() || lines.is_empty() {
+        lines.push(current);
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Rule;
+    use crate::locale::{Language, ENGLISH};
+
+    #[test]
+    fn test_wrap_text_short_line_unchanged() {
+        assert_eq!(wrap_text("├─ Pay now", 40, 3), vec!["├─ Pay now"]);
+    }
+
+    #[test]
+    fn test_wrap_text_breaks_on_words() {
+        let lines = wrap_text("├─ Send reminder email to customers with overdue invoices", 24, 3);
+        assert_eq!(lines, vec![
+            "├─ Send reminder email",
+            "to customers with",
+            "overdue invoices",
+        ]);
+        assert!(lines.iter().skip(1).all(|l| l.width() <= 21));
+    }
+
+    #[test]
+    fn test_wrap_text_breaks_long_words() {
+        let lines = wrap_text("abcdefghij", 4, 0);
+        assert_eq!(lines, vec!["abcd", "efgh", "ij"]);
+    }
+
+    #[test]
+    fn test_annotation_ranges_follow_the_wrap() {
+        let lines = wrapped_lines("├─ Submit [needs API change] → Review [v2]", "│  ", 22);
+        assert_eq!(lines, vec!["├─ Submit [needs API", "│  change] → Review", "│  [v2]"]);
+        let ranges = annotation_ranges(&lines, "│  ", &["[needs API change]", "[v2]"]);
+        let dimmed: Vec<Vec<&str>> = ranges.iter().zip(&lines)
+            .map(|(ranges, line)| ranges.iter().map(|r| &line[r.clone()]).collect())
+            .collect();
+        assert_eq!(dimmed, vec![vec!["[needs API"], vec!["change]"], vec!["[v2]"]]);
+    }
+
+    fn snapshot_test_app() -> App {
+        let mut app = App::new();
+        app.new_place("Invoice".to_string());
+        app.new_place("Setup Autopay".to_string());
+        let (invoice_id, setup_id) = (app.breadboard.places[0].id, app.breadboard.places[1].id);
+        let affordance_id = app.breadboard.generate_affordance_id();
+        app.add_affordance_to_place(&invoice_id, crate::models::Affordance::new(affordance_id, "Turn on Autopay".to_string())
+            .with_connection(setup_id));
+        app
+    }
+
+    #[test]
+    fn test_view_snapshot_expanded_wraps_like_the_view() {
+        let app = snapshot_test_app();
+        let snapshot = view_snapshot(&app, 16);
+        assert_eq!(snapshot, "Breadboard\n\n┌─ Invoice\n├─ Turn on\n│  Autopay →\n│  Setup Autopay\n\n┌─ Setup Autopay\n   (← Invoice)\n");
+    }
+
+    #[test]
+    fn test_view_snapshot_follows_collapse_and_filter() {
+        let mut app = snapshot_test_app();
+        app.state.collapsed = true;
+        app.state.filter = Some("setup".to_string());
+        let snapshot = view_snapshot(&app, 80);
+        assert!(snapshot.starts_with("Breadboard (Collapsed) (Filtered: setup)\n\n"));
+        assert!(snapshot.contains("\nSetup Autopay  ≡0  ←1  →0\n"));
+        assert!(!snapshot.contains("Invoice  "));
+        assert!(snapshot.ends_with(&format!("\n\n{}\n", ENGLISH.collapsed_legend)));
+    }
+
+    #[test]
+    fn test_view_snapshot_graph_is_whole_diagram() {
+        let mut app = snapshot_test_app();
+        app.state.view = ViewMode::Graph;
+        let snapshot = view_snapshot(&app, 10);
+        assert!(snapshot.contains("┌ Invoice"));
+        assert!(snapshot.contains("▶┌ Setup Autopay"));
+
+        app.breadboard.places[1].kind = Some(crate::models::PlaceKind::Job);
+        assert!(view_snapshot(&app, 10).contains("▶┌ ⚙ Setup Autopay ──┐"));
+    }
+
+    #[test]
+    fn test_view_snapshot_leaves_out_cut_affordances() {
+        let mut app = snapshot_test_app();
+        app.state.selection = Some(Selection::Affordance {
+            place_id: app.breadboard.places[0].id,
+            affordance_id: app.breadboard.places[0].affordances[0].id,
+        });
+        assert_eq!(app.toggle_selected_cut(), Some(("Turn on Autopay".to_string(), true)));
+
+        let snapshot = view_snapshot(&app, 80);
+        assert!(!snapshot.contains("Turn on Autopay"));
+        assert!(!snapshot.contains("(← Invoice)"));
+
+        app.config.export_cut = true;
+        assert!(view_snapshot(&app, 80).contains("├─ Turn on Autopay → Setup Autopay"));
+    }
+
+    #[test]
+    fn test_progress_lines() {
+        let mut app = snapshot_test_app();
+        let invoice_id = app.breadboard.places[0].id;
+        app.breadboard.places[0].affordances[0].status = Status::Built;
+        let affordance_id = app.breadboard.generate_affordance_id();
+        app.add_affordance_to_place(&invoice_id, crate::models::Affordance::new(affordance_id, "Pay now".to_string()));
+        app.breadboard.places[0].affordances[1].status = Status::Building;
+
+        assert_eq!(progress_lines(&app.breadboard, &ENGLISH), vec![
+            "Invoice       ████████▒▒▒▒▒▒▒▒ 1/2",
+            "Setup Autopay ················ 0/0",
+            "Total         ████████▒▒▒▒▒▒▒▒ 1/2",
+        ]);
+        assert!(view_snapshot(&app, 80).contains("├─ ✓ Turn on Autopay → Setup Autopay\n├─ ◐ Pay now\n"));
+    }
+
+    #[test]
+    fn test_view_snapshot_groups_external_systems_last() {
+        let mut app = snapshot_test_app();
+        app.breadboard.places[0].kind = Some(crate::models::PlaceKind::External);
+        assert_eq!(
+            view_snapshot(&app, 80),
+            "Breadboard\n\n┌─ Setup Autopay (← Invoice)\n\n── External systems ──\n\n┌╌ ☁ Invoice\n├─ Turn on Autopay → Setup Autopay\n",
+        );
+
+        app.state.view = ViewMode::Graph;
+        assert!(view_snapshot(&app, 80).contains("┌ ☁ Invoice ╌╌╌╌╌╌┐"));
+    }
+
+    #[test]
+    fn test_view_snapshot_shows_outcomes_under_their_affordance() {
+        let mut app = snapshot_test_app();
+        let (invoice_id, setup_id) = (app.breadboard.places[0].id, app.breadboard.places[1].id);
+        let affordance = &mut app.breadboard.places[0].affordances[0];
+        affordance.connects_to = None;
+        affordance.branches = vec![
+            crate::models::Branch { label: "card on file".to_string(), connects_to: Some(setup_id) },
+            crate::models::Branch { label: "no card".to_string(), connects_to: Some(invoice_id) },
+        ];
+
+        assert_eq!(
+            view_snapshot(&app, 80),
+            "Breadboard\n\n┌─ Invoice (← Invoice)\n├─ Turn on Autopay\n│  ├ card on file → Setup Autopay\n│  └ no card → Invoice\n\n┌─ Setup Autopay (← Invoice)\n",
+        );
+
+        app.state.collapsed = true;
+        assert!(view_snapshot(&app, 80).contains("Invoice        ≡1  ←1  →2\n"));
+
+        app.breadboard.places[0].notes = Some("Monthly".to_string());
+        assert!(view_snapshot(&app, 80).contains("Invoice        ≡1  ←1  →2  ✎\n"));
+
+        app.state.view = ViewMode::Graph;
+        let graph = view_snapshot(&app, 80);
+        assert!(graph.contains("Turn on Autopay ◇"));
+        assert!(graph.contains("▶┌ Setup Autopay"));
+    }
+
+    #[test]
+    fn test_long_lines_cut_with_ellipsis() {
+        let line = fitted(vec![Span::raw("Connect to: "), Span::raw("Setup Autopay")], 16);
+        assert_eq!(line.to_string(), "Connect to: Set…");
+        assert_eq!(fitted(vec![Span::raw("Connect"), Span::raw(" to")], 7).to_string(), "Connec…");
+        assert_eq!(fitted("Short", 16).to_string(), "Short");
+
+        // Typing past the bar keeps the end of the text in view
+        let line = fitted_input(vec![Span::raw("Editing: "), Span::raw("Turn on Autopay"), Span::raw(" (Enter to save)")], 20);
+        assert_eq!(line.to_string(), "Editing: …n Autopay…");
+        let line = fitted_input(vec![Span::raw("Editing: "), Span::raw("Pay"), Span::raw(" (Enter to save)")], 20);
+        assert_eq!(line.to_string(), "Editing: Pay (Enter…");
+
+        // A well-connected place's sources are cut short rather than wrapped
+        let mut app = snapshot_test_app();
+        for name in ["Home", "Billing", "Account settings", "Help center"] {
+            let id = app.new_place(name.to_string());
+            app.add_affordance(id, "Set up".to_string(), Some(app.breadboard.places[1].id)).unwrap();
+        }
+        let snapshot = view_snapshot(&app, 40);
+        assert!(snapshot.contains("┌─ Setup Autopay (← Invoice, Home, Bil…)\n"), "{}", snapshot);
+        assert!(view_snapshot(&app, 0).contains("(← Invoice, Home, Billing, Account settings, Help center)"));
+    }
+
+    #[test]
+    fn test_full_text_of_selection() {
+        let mut app = snapshot_test_app();
+        app.state.selection = Some(Selection::Place(app.breadboard.places[1].id));
+        app.breadboard.places[1].notes = Some("Monthly only".to_string());
+        assert_eq!(full_text(&app, Some("Saved")), "Saved\n\nSetup Autopay\nLinked from: Invoice\n\nMonthly only");
+
+        let invoice = &app.breadboard.places[0];
+        app.state.selection = Some(Selection::Affordance { place_id: invoice.id, affordance_id: invoice.affordances[0].id });
+        assert_eq!(full_text(&app, None), "Invoice: Turn on Autopay\nLeads to: Setup Autopay");
+
+        app.config.language = Language::German;
+        assert_eq!(full_text(&app, None), "Invoice: Turn on Autopay\nFührt zu: Setup Autopay");
+    }
+
+    #[test]
+    fn test_sticky_header_once_scrolled_past() {
+        let mut app = snapshot_test_app();
+        let setup_id = app.breadboard.places[1].id;
+        for index in 0..10 {
+            let affordance_id = app.breadboard.generate_affordance_id();
+            app.add_affordance_to_place(&setup_id, crate::models::Affordance::new(affordance_id, format!("Field {}", index)));
+        }
+        let rows = expanded_rows(&app, &app.visible_places());
+        let header = rows.iter().position(|row| row.item == Some(Selection::Place(setup_id))).unwrap();
+
+        let last = app.breadboard.places[1].affordances[9].id;
+        app.state.selection = Some(Selection::Affordance { place_id: setup_id, affordance_id: last });
+        assert!(sticky_header(&app, &rows, header).is_none());
+        assert_eq!(sticky_header(&app, &rows, header + 5).unwrap().text, "┌─ Setup Autopay");
+
+        // A selected place is its own header
+        app.state.selection = Some(Selection::Place(setup_id));
+        assert!(sticky_header(&app, &rows, header + 5).is_none());
+    }
+
+    #[test]
+    fn test_breadcrumb_at() {
+        let mut app = snapshot_test_app();
+        let invoice = &app.breadboard.places[0];
+        app.state.selection = Some(Selection::Affordance { place_id: invoice.id, affordance_id: invoice.affordances[0].id });
+        // " New Breadboard ▸ Invoice ▸ Turn on Autopay"
+        assert_eq!(breadcrumb_at(&app, 1, 1).unwrap().label, "New Breadboard");
+        assert_eq!(breadcrumb_at(&app, 16, 1), None);
+        assert_eq!(breadcrumb_at(&app, 18, 1).unwrap().label, "Invoice");
+        assert_eq!(breadcrumb_at(&app, 28, 1).unwrap().label, "Turn on Autopay");
+        assert_eq!(breadcrumb_at(&app, 18, 2), None);
+
+        app.state.status_message = Some("Saved".to_string());
+        assert_eq!(breadcrumb_at(&app, 18, 1), None);
+    }
+
+    #[test]
+    fn test_status_template() {
+        let mut app = snapshot_test_app();
+        let template = "{dirty}{board} ({places}) {selection} {filter}|{file}";
+        app.state.selection = None;
+        assert_eq!(status_template(&app, template), format!("*{} (2)  |", app.breadboard.name));
+
+        app.mark_saved();
+        app.state.current_filename = Some("billing.toml".to_string());
+        let invoice = &app.breadboard.places[0];
+        app.state.selection = Some(Selection::Affordance { place_id: invoice.id, affordance_id: invoice.affordances[0].id });
+        app.state.filter = Some("auto".to_string());
+        assert_eq!(status_template(&app, template), format!("{} (2) Invoice › Turn on Autopay auto|billing.toml", app.breadboard.name));
+
+        app.breadboard.places[0].name = "Bill".to_string();
+        assert!(status_template(&app, "{dirty}{unknown}").starts_with("*{unknown}"));
+    }
+
+    #[test]
+    fn test_duplicate_places_are_badged_and_told_apart() {
+        let mut app = App::new();
+        app.new_place("Settings".to_string());
+        app.new_place("settings".to_string());
+        app.breadboard.places[1].group = Some("admin".to_string());
+        app.new_place("Settings".to_string());
+
+        assert!(view_snapshot(&app, 80).contains("┌─ Settings ⚠\n"));
+        let duplicates = duplicate_places(&app.breadboard);
+        let names: Vec<String> = app.breadboard.places.iter().map(|p| disambiguated_name(p, &duplicates)).collect();
+        assert_eq!(names, vec!["Settings #1", "settings (admin)", "Settings #3"]);
+    }
+
+    #[test]
+    fn test_rule_breaks_are_badged() {
+        let mut app = App::new();
+        let home = app.new_place("Home".to_string());
+        let email = app.new_place("Welcome email".to_string());
+        let admin = app.new_place("Admin".to_string());
+        app.breadboard.find_place_mut(&email).unwrap().group = Some("email".to_string());
+        app.breadboard.find_place_mut(&admin).unwrap().group = Some("admin".to_string());
+        app.add_affordance(home, "Mail".to_string(), Some(email)).unwrap();
+        let open = app.add_affordance(email, "Open".to_string(), Some(admin)).unwrap();
+        app.add_affordance(admin, "Done".to_string(), Some(home)).unwrap();
+        assert!(!view_snapshot(&app, 80).contains('⊘'));
+
+        app.breadboard.rules = vec![Rule::NoConnection { from: "email".to_string(), to: "admin".to_string() }];
+        let view = view_snapshot(&app, 80);
+        assert!(view.contains("├─ Open ⊘ → Admin\n"));
+        assert!(view.contains("┌─ Welcome email (← Home)\n"));
+
+        app.state.selection = Some(Selection::Affordance { place_id: email, affordance_id: open });
+        assert!(full_text(&app, None).contains("Rule: \"Open\" in \"Welcome email\" leads to \"Admin\""));
+    }
+}
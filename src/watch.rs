@@ -0,0 +1,141 @@
+use anyhow::{anyhow, Context, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::app::App;
+use crate::export::{render_document, Document};
+use crate::file::FileManager;
+use crate::ui::view_snapshot;
+
+// Files kept up to date from a board by `bboard watch`: whenever the board
+// is saved, each target is written again. The board's modification time is
+// polled, which works the same on every platform and for editors that save
+// by replacing the file.
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Artifact {
+    View, // The board as `bboard render` prints it
+    Document(Document),
+}
+
+// One --export: what to write and where
+#[derive(Debug, Clone, PartialEq)]
+pub struct Target {
+    pub artifact: Artifact,
+    pub path: PathBuf,
+}
+
+impl Target {
+    // "walkthrough:docs/flow.md", with any `bboard export` format or "text"
+    pub fn parse(spec: &str) -> Result<Self> {
+        let (format, path) = spec.split_once(':')
+            .filter(|(_, path)| !path.is_empty())
+            .ok_or_else(|| anyhow!("--export takes <format>:<file>, not {}", spec))?;
+        let artifact = match format {
+            "text" => Artifact::View,
+            "tasks-per-affordance" => Artifact::Document(Document::Tasks { per_affordance: true }),
+            _ => Artifact::Document(Document::parse(format).ok_or_else(|| anyhow!("unknown format {}", format))?),
+        };
+        Ok(Self { artifact, path: PathBuf::from(path) })
+    }
+}
+
+pub struct Watcher {
+    file: PathBuf,
+    targets: Vec<Target>,
+    modified: Option<SystemTime>, // As of the last check
+    written: HashMap<PathBuf, String>, // What each target got last, so unchanged ones aren't rewritten
+}
+
+impl Watcher {
+    pub fn new(file: PathBuf, targets: Vec<Target>) -> Self {
+        Self { file, targets, modified: None, written: HashMap::new() }
+    }
+
+    // Whether the board was saved since the last check. The first check
+    // counts as a change, so the targets are written on start.
+    pub fn changed(&mut self) -> Result<bool> {
+        let modified = fs::metadata(&self.file)
+            .and_then(|m| m.modified())
+            .with_context(|| format!("Failed to read {}", self.file.display()))?;
+        Ok(self.modified.replace(modified) != Some(modified))
+    }
+
+    // Write every target whose content changed, returning their paths
+    pub fn export(&mut self) -> Result<Vec<PathBuf>> {
+        let mut app = App::new();
+        app.breadboard = FileManager::new().load_from_file(&self.file)?;
+
+        let mut written = Vec::new();
+        for target in &self.targets {
+            let content = match target.artifact {
+                Artifact::View => view_snapshot(&app, 0),
+                Artifact::Document(document) => render_document(&app, document),
+            };
+            if self.written.get(&target.path) == Some(&content) {
+                continue;
+            }
+            if let Some(dir) = target.path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+                fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+            }
+            fs::write(&target.path, &content).with_context(|| format!("Failed to write {}", target.path.display()))?;
+            self.written.insert(target.path.clone(), content);
+            written.push(target.path.clone());
+        }
+        Ok(written)
+    }
+
+    pub fn file(&self) -> &Path {
+        &self.file
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Breadboard, Place};
+
+    #[test]
+    fn test_parse_target() {
+        assert_eq!(Target::parse("print:out/board.html").unwrap(), Target {
+            artifact: Artifact::Document(Document::Print),
+            path: PathBuf::from("out/board.html"),
+        });
+        assert_eq!(Target::parse("text:board.txt").unwrap().artifact, Artifact::View);
+        assert_eq!(Target::parse("mermaid:docs/flow.mmd").unwrap().artifact, Artifact::Document(Document::Mermaid));
+        assert_eq!(Target::parse("svg:docs/flow.svg").unwrap().artifact, Artifact::Document(Document::Svg));
+        assert_eq!(Target::parse("dot:flow.dot").unwrap_err().to_string(), "unknown format dot");
+        assert!(Target::parse("board.txt").is_err());
+        assert!(Target::parse("html:").is_err());
+    }
+
+    #[test]
+    fn test_export_writes_what_changed() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let file = dir.path().join("board.toml");
+        let file_manager = FileManager::new();
+        let mut board = Breadboard::new("Billing".to_string());
+        board.add_place(Place::new(1, "Invoice".to_string()));
+        file_manager.save_to_file(&board, &file)?;
+
+        let text = dir.path().join("docs/board.txt");
+        let tasks = dir.path().join("docs/tasks.md");
+        let mut watcher = Watcher::new(file.clone(), vec![
+            Target { artifact: Artifact::View, path: text.clone() },
+            Target::parse(&format!("tasks:{}", tasks.display()))?,
+        ]);
+        assert!(watcher.changed()?);
+        assert!(!watcher.changed()?);
+        assert_eq!(watcher.export()?, vec![text.clone(), tasks.clone()]);
+        assert!(fs::read_to_string(&text)?.contains("Invoice"));
+        assert!(watcher.export()?.is_empty());
+
+        board.places[0].name = "Bill".to_string();
+        file_manager.save_to_file(&board, &file)?;
+        assert_eq!(watcher.export()?.len(), 2);
+        assert!(fs::read_to_string(&tasks)?.contains("Bill"));
+        Ok(())
+    }
+}
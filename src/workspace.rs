@@ -0,0 +1,144 @@
+use crate::file::FileManager;
+use crate::models::Breadboard;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// A product area split over several boards, listed in a workspace file:
+//
+//   name = "Billing"
+//
+//   [[boards]]
+//   title = "Invoices"
+//   file = "invoices.toml"    # Relative to the workspace file
+//
+// Affordances can link into the workspace's other boards (`models::BoardLink`).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Workspace {
+    #[serde(default)]
+    pub name: String,
+    pub boards: Vec<WorkspaceBoard>,
+    #[serde(skip)]
+    pub dir: PathBuf, // Directory the board files are relative to
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkspaceBoard {
+    pub title: String,
+    pub file: String,
+}
+
+// A place in another board of the workspace, offered as a link target
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExternalPlace {
+    pub board: String, // Board file as listed in the workspace
+    pub title: String,
+    pub place_id: u32,
+    pub name: String,
+}
+
+impl Workspace {
+    // A workspace lists boards and, unlike a board, has no places
+    pub fn is_workspace_file(path: &Path) -> bool {
+        fs::read_to_string(path).ok()
+            .and_then(|content| content.parse::<toml::Table>().ok())
+            .is_some_and(|table| table.contains_key("boards") && !table.contains_key("places"))
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let mut workspace: Workspace = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse {} as a workspace", path.display()))?;
+        workspace.dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        if workspace.name.is_empty() {
+            workspace.name = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+        }
+        Ok(workspace)
+    }
+
+    pub fn path_of(&self, board: &WorkspaceBoard) -> PathBuf {
+        self.dir.join(&board.file)
+    }
+
+    pub fn find_board(&self, file: &str) -> Option<&WorkspaceBoard> {
+        self.boards.iter().find(|board| board.file == file)
+    }
+
+    // The workspace entry for an open board file, however its path was spelled
+    pub fn board_at(&self, path: &str) -> Option<&WorkspaceBoard> {
+        let path = fs::canonicalize(path).ok()?;
+        self.boards.iter().find(|board| fs::canonicalize(self.path_of(board)).ok().as_ref() == Some(&path))
+    }
+
+    pub fn load_boards(&self, file_manager: &FileManager) -> Vec<(&WorkspaceBoard, Result<Breadboard>)> {
+        self.boards.iter()
+            .map(|board| (board, file_manager.load_from_file(self.path_of(board))))
+            .collect()
+    }
+
+    // Places of every board except the one open at `current`. Boards that
+    // can't be read are skipped.
+    pub fn places_outside(&self, file_manager: &FileManager, current: Option<&str>) -> Vec<ExternalPlace> {
+        let current = current.and_then(|path| self.board_at(path)).map(|board| board.file.clone());
+        self.load_boards(file_manager).into_iter()
+            .filter(|(board, _)| Some(&board.file) != current.as_ref())
+            .filter_map(|(board, breadboard)| Some((board, breadboard.ok()?)))
+            .flat_map(|(board, breadboard)| {
+                breadboard.places.into_iter().map(|place| ExternalPlace {
+                    board: board.file.clone(),
+                    title: board.title.clone(),
+                    place_id: place.id,
+                    name: place.name,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Place;
+
+    fn write_workspace(dir: &Path) -> PathBuf {
+        let file_manager = FileManager::new();
+        for (file, place) in [("invoices.toml", "Invoice"), ("setup.toml", "Setup Autopay")] {
+            let mut breadboard = Breadboard::new(file.to_string());
+            breadboard.add_place(Place::new(1, place.to_string()));
+            file_manager.save_to_file(&breadboard, dir.join(file)).unwrap();
+        }
+        let path = dir.join("workspace.toml");
+        fs::write(&path, "name = \"Billing\"\n\n[[boards]]\ntitle = \"Invoices\"\nfile = \"invoices.toml\"\n\n[[boards]]\ntitle = \"Autopay\"\nfile = \"setup.toml\"\n").unwrap();
+        path
+    }
+
+    #[test]
+    fn test_load_workspace() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_workspace(dir.path());
+        assert!(Workspace::is_workspace_file(&path));
+        assert!(!Workspace::is_workspace_file(&dir.path().join("invoices.toml")));
+
+        let workspace = Workspace::load(&path).unwrap();
+        assert_eq!(workspace.name, "Billing");
+        assert_eq!(workspace.boards.len(), 2);
+        assert_eq!(workspace.path_of(&workspace.boards[1]), dir.path().join("setup.toml"));
+    }
+
+    #[test]
+    fn test_places_outside_current_board() {
+        let dir = tempfile::tempdir().unwrap();
+        let workspace = Workspace::load(&write_workspace(dir.path())).unwrap();
+        let current = dir.path().join("invoices.toml").to_string_lossy().to_string();
+
+        let places = workspace.places_outside(&FileManager::new(), Some(&current));
+        assert_eq!(places, vec![ExternalPlace {
+            board: "setup.toml".to_string(),
+            title: "Autopay".to_string(),
+            place_id: 1,
+            name: "Setup Autopay".to_string(),
+        }]);
+    }
+}